@@ -0,0 +1,26 @@
+//! A smoke benchmark comparing `sync::Interner::intern_all` with `par_intern_all`.
+//! Run it with `cargo bench --features rayon`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use str_intern::sync::Interner;
+
+const ROWS: usize = 1_000_000;
+const DISTINCT: usize = 50_000;
+
+fn time(name: &str, mut f: impl FnMut() -> usize) {
+  let mut best = Duration::MAX;
+  for _ in 0..5 {
+    let start = Instant::now();
+    black_box(f());
+    best = best.min(start.elapsed());
+  }
+  println!("{name:>16}: {best:?}");
+}
+
+fn main() {
+  let strings = (0..ROWS).map(|i| format!("row-{}", i * 7919 % DISTINCT)).collect::<Vec<_>>();
+  time("intern_all", || Interner::new().intern_all(&strings).len());
+  time("par_intern_all", || Interner::new().par_intern_all(&strings).len());
+}
@@ -0,0 +1,232 @@
+/*!
+ * An interner which copies its strings into an arena instead of reference-counting them.
+ * 
+ * This is useful when every interned string lives for about as long as everything else, e.g., for a single pass of a compiler,
+ * since it makes each handle a plain `&str`, and stores many strings in each allocation.
+ * 
+ * The arena is a [`Bump`] from the [`bumpalo`] crate.
+ */
+
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::Iter as SetIter;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::iter::{Copied, FusedIterator};
+
+pub use bumpalo::Bump;
+
+/**
+ * An interner which copies each distinct string into an arena, and returns references into it.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::arena::{ArenaInterner, Bump};
+ * let arena = Bump::new();
+ * let mut interner = ArenaInterner::new(&arena);
+ * let foo0 = interner.intern(&String::from("foo"));
+ * let foo1 = interner.intern("foo");
+ * assert!(std::ptr::eq(foo0, foo1));
+ * drop(interner);
+ * // The strings are still usable, since they belong to the arena.
+ * assert_eq!(foo0, "foo");
+ * ```
+ * 
+ * Strings can never be removed from an `ArenaInterner`; they are all freed at once when the arena is dropped (or [reset](Bump::reset)).
+ * Since the returned references borrow the arena, not the `ArenaInterner`, they can outlive it,
+ * and several `ArenaInterner`s can share one arena (although they do not deduplicate strings between each other).
+ */
+pub struct ArenaInterner<'arena, S = RandomState> {
+  
+  arena: &'arena Bump,
+  strings: HashSet<&'arena str, S>
+  
+}
+
+impl<'arena> ArenaInterner<'arena> {
+  
+  /**
+   * Constructs a new `ArenaInterner` which saves its strings in the given arena.
+   */
+  pub fn new(arena: &'arena Bump) -> Self {
+    Self::with_hasher(arena, RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `ArenaInterner` with the given capacity, which saves its strings in the given arena. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(arena: &'arena Bump, capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(arena, capacity, RandomState::new())
+  }
+  
+}
+
+impl<'arena, S> ArenaInterner<'arena, S> {
+  
+  /**
+   * Constructs a new `ArenaInterner` with the given hasher, which saves its strings in the given arena. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(arena: &'arena Bump, hasher: S) -> Self {
+    Self { arena, strings: HashSet::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `ArenaInterner` with the given capacity and hasher, which saves its strings in the given arena.
+   * See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(arena: &'arena Bump, capacity: usize, hasher: S) -> Self {
+    Self { arena, strings: HashSet::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the arena that this `ArenaInterner` saves its strings in.
+   */
+  pub fn arena(&self) -> &'arena Bump {
+    self.arena
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * An iterator over all of the interned strings.
+   */
+  pub fn iter(&self) -> Iter<'_, 'arena> {
+    Iter::new(self.strings.iter().copied())
+  }
+  
+}
+
+impl<'arena, S: BuildHasher> ArenaInterner<'arena, S> {
+  
+  /**
+   * Copies the given string into the arena if it is not already saved, and returns a reference to the saved copy.
+   */
+  pub fn intern(&mut self, string: &str) -> &'arena str {
+    if let Some(&saved) = self.strings.get(string) {
+      return saved;
+    }
+    let saved = &*self.arena.alloc_str(string);
+    self.strings.insert(saved);
+    saved
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: &str) -> bool {
+    self.strings.contains(string)
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved copy, or `None` otherwise.
+   */
+  pub fn get(&self, string: &str) -> Option<&'arena str> {
+    self.strings.get(string).copied()
+  }
+  
+}
+
+impl<'arena, S: Clone> Clone for ArenaInterner<'arena, S> {
+  
+  /**
+   * Returns a new `ArenaInterner` which shares the same copies of the strings in the same arena.
+   */
+  fn clone(&self) -> Self {
+    Self { arena: self.arena, strings: self.strings.clone() }
+  }
+  
+}
+
+impl<'arena, S> Debug for ArenaInterner<'arena, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("ArenaInterner").field(&self.strings).finish()
+  }
+  
+}
+
+impl<'a, 'arena, S> IntoIterator for &'a ArenaInterner<'arena, S> {
+  
+  type Item = &'arena str;
+  type IntoIter = Iter<'a, 'arena>;
+  
+  fn into_iter(self) -> Iter<'a, 'arena> {
+    self.iter()
+  }
+  
+}
+
+impl<'arena, T: AsRef<str>, S: BuildHasher> Extend<T> for ArenaInterner<'arena, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string.as_ref());
+    }
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an [`ArenaInterner`].
+ * 
+ * This `struct` is created by the [`iter`](ArenaInterner::iter) method on [`ArenaInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Iter<'a, 'arena> {
+  
+  iter: Copied<SetIter<'a, &'arena str>>
+  
+}
+
+impl<'a, 'arena> Iter<'a, 'arena> {
+  
+  fn new(iter: Copied<SetIter<'a, &'arena str>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a, 'arena> Iterator for Iter<'a, 'arena> {
+  
+  type Item = &'arena str;
+  
+  fn next(&mut self) -> Option<&'arena str> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, 'arena> ExactSizeIterator for Iter<'a, 'arena> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a, 'arena> FusedIterator for Iter<'a, 'arena> {}
+
+impl<'a, 'arena> Debug for Iter<'a, 'arena> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
@@ -0,0 +1,50 @@
+/*!
+ * Interners for byte strings, which need not be valid UTF-8 (e.g., hashes or binary tokens).
+ * 
+ * These mirror the [`str`] interners: [`Interner`] is not thread-safe, and the [`sync`] module provides a thread-safe variant.
+ * They are just the [`generic`] interners for `[u8]`.
+ */
+
+pub mod sync;
+
+use std::collections::hash_map::RandomState;
+use std::rc::Rc;
+
+use crate::generic;
+
+/**
+ * The type of byte strings that have been interned.
+ * 
+ * Like [`InternedStr`](crate::InternedStr), this is just a type alias.
+ */
+pub type InternedBytes = Rc<[u8]>;
+
+/**
+ * An interner will keep track of byte strings and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::bytes::{Interner, InternedBytes};
+ * let mut interner = Interner::new();
+ * let tag0 = interner.intern(vec![0x08, 0x96, 0x01]);
+ * let tag1 = interner.intern([0x08, 0x96, 0x01]);
+ * assert!(InternedBytes::ptr_eq(&tag0, &tag1));
+ * ```
+ * 
+ * This `Interner` is not thread-safe (which is to say, it is implements neither [`Send`] nor [`Sync`]). For a thread-safe variant, see the [`sync`] module.
+ */
+pub type Interner<S = RandomState> = generic::Interner<[u8], S>;
+
+/**
+ * An iterator over the byte strings in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](generic::Interner::iter) method on [`Interner`].
+ */
+pub type Iter<'a> = generic::Iter<'a, [u8]>;
+
+/**
+ * An owning iterator over the byte strings that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+pub type IntoIter = generic::IntoIter<[u8]>;
@@ -0,0 +1,50 @@
+/*!
+ * A thread-safe variant of the byte string interner.
+ */
+
+use std::collections::hash_map::RandomState;
+use std::sync::Arc;
+
+use crate::generic;
+
+/**
+ * The type of byte strings that have been interned.
+ * 
+ * Like [`sync::InternedStr`](crate::sync::InternedStr), this is just a type alias.
+ */
+pub type InternedBytes = Arc<[u8]>;
+
+/**
+ * An interner will keep track of byte strings and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::bytes::sync::{Interner, InternedBytes};
+ * let interner = Interner::new();
+ * let tag0 = interner.intern(vec![0x08, 0x96, 0x01]);
+ * let tag1 = interner.intern([0x08, 0x96, 0x01]);
+ * assert!(InternedBytes::ptr_eq(&tag0, &tag1));
+ * ```
+ * 
+ * This `Interner` is thread-safe, meaning that it implements both [`Send`] and [`Sync`] (when S implements [`Send`], which the default does).
+ */
+pub type Interner<S = RandomState> = generic::sync::Interner<[u8], S>;
+
+/**
+ * A locked [`Interner`]. This `struct` is created by [`Interner::lock`]; see its documentation for more details.
+ */
+pub type LockedInterner<'a, S = RandomState> = generic::sync::LockedInterner<'a, [u8], S>;
+
+/**
+ * An iterator over the byte strings in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](generic::sync::LockedInterner::iter) method on [`LockedInterner`].
+ */
+pub type Iter<'a> = generic::sync::Iter<'a, [u8]>;
+
+/**
+ * An owning iterator over the byte strings that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+pub type IntoIter = generic::sync::IntoIter<[u8]>;
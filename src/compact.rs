@@ -0,0 +1,446 @@
+/*!
+ * An interner whose handles store short strings inline, instead of always pointing to a shared allocation.
+ * 
+ * It can also save `&'static str`s without copying them at all (see [`CompactInterner::intern_static`]).
+ */
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::Iter as SetIter;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::Deref;
+use std::ptr;
+use std::rc::Rc;
+use std::str;
+
+/**
+ * The longest string, in bytes, that a [`CompactStr`] stores inline.
+ * 
+ * This is as long as possible without making a `CompactStr` any bigger than it has to be to hold an [`InternedStr`](crate::InternedStr).
+ */
+pub const INLINE_LEN: usize = 22;
+
+/**
+ * A string that has been interned in a [`CompactInterner`].
+ * 
+ * A string of up to [`INLINE_LEN`] bytes is stored inside the `CompactStr` itself, so it needs no allocation, and using it does not need to follow a pointer.
+ * A longer string points to an allocation shared with every other `CompactStr` for it from the same `CompactInterner`, just like an [`InternedStr`](crate::InternedStr).
+ * 
+ * A string saved with [`intern_static`](CompactInterner::intern_static) instead points to the `&'static str` it was saved from, whatever its length.
+ * 
+ * Each string is only ever saved one way in a `CompactInterner`, so comparing two `CompactStr`s from the same `CompactInterner` for equality
+ * never has to look at a shared allocation: short strings are compared inline, and long or static strings are equal exactly when they point to the same data.
+ * (Strings from different `CompactInterner`s are still compared by their contents, so `CompactStr` is consistent with [`str`](prim@str)'s [`Eq`] and [`Hash`].)
+ */
+#[derive(Clone)]
+pub struct CompactStr(Repr);
+
+#[derive(Clone)]
+enum Repr {
+  
+  Inline {
+    len: u8,
+    bytes: [u8; INLINE_LEN]
+  },
+  
+  Shared(Rc<str>),
+  
+  Static(&'static str)
+  
+}
+
+impl CompactStr {
+  
+  /**
+   * Returns a `CompactStr` which stores the given string inline, if it is short enough.
+   */
+  fn inline(string: &str) -> Option<Self> {
+    let mut bytes = [0; INLINE_LEN];
+    bytes.get_mut(..string.len())?.copy_from_slice(string.as_bytes());
+    Some(Self(Repr::Inline { len: string.len() as u8, bytes }))
+  }
+  
+  /**
+   * Returns whether this `CompactStr` stores its string inline, which is true exactly when it is no longer than [`INLINE_LEN`].
+   */
+  pub fn is_inline(&self) -> bool {
+    matches!(self.0, Repr::Inline { .. })
+  }
+  
+  /**
+   * Returns whether this `CompactStr` points to a `&'static str`, which is true exactly when it was saved with [`intern_static`](CompactInterner::intern_static).
+   */
+  pub fn is_static(&self) -> bool {
+    matches!(self.0, Repr::Static(_))
+  }
+  
+  /**
+   * Returns whether two `CompactStr`s point to the same shared allocation, or the same `&'static str`.
+   * This is always `false` if either of them stores its string inline.
+   */
+  pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+    match (&this.0, &other.0) {
+      (Repr::Shared(this), Repr::Shared(other)) => Rc::ptr_eq(this, other),
+      (Repr::Static(this), Repr::Static(other)) => ptr::eq(*this, *other),
+      _ => false
+    }
+  }
+  
+}
+
+impl Deref for CompactStr {
+  
+  type Target = str;
+  
+  fn deref(&self) -> &str {
+    match &self.0 {
+      // This only ever holds a whole `str`, so it can't fail, but checking is cheap for so few bytes.
+      Repr::Inline { len, bytes } => str::from_utf8(&bytes[..usize::from(*len)]).expect("inline string should be valid UTF-8"),
+      Repr::Shared(string) => string,
+      Repr::Static(string) => string
+    }
+  }
+  
+}
+
+impl AsRef<str> for CompactStr {
+  
+  fn as_ref(&self) -> &str {
+    self
+  }
+  
+}
+
+impl Borrow<str> for CompactStr {
+  
+  fn borrow(&self) -> &str {
+    self
+  }
+  
+}
+
+impl PartialEq for CompactStr {
+  
+  fn eq(&self, other: &Self) -> bool {
+    match (&self.0, &other.0) {
+      (Repr::Inline { len, bytes }, Repr::Inline { len: other_len, bytes: other_bytes }) => len == other_len && bytes == other_bytes,
+      (Repr::Shared(this), Repr::Shared(other)) => Rc::ptr_eq(this, other) || this == other,
+      (Repr::Static(this), Repr::Static(other)) => ptr::eq(*this, *other) || this == other,
+      // A static string may have the same contents as a string stored another way in a different `CompactInterner`.
+      (Repr::Static(this), _) => *this == &**other,
+      (_, Repr::Static(other)) => &**self == *other,
+      // Every other string is stored the same way, based on its length, so these can't be equal.
+      _ => false
+    }
+  }
+  
+}
+
+impl Eq for CompactStr {}
+
+impl PartialOrd for CompactStr {
+  
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+  
+}
+
+impl Ord for CompactStr {
+  
+  fn cmp(&self, other: &Self) -> Ordering {
+    str::cmp(self, other)
+  }
+  
+}
+
+impl Hash for CompactStr {
+  
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    str::hash(self, state)
+  }
+  
+}
+
+impl Debug for CompactStr {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    Debug::fmt(&**self, f)
+  }
+  
+}
+
+impl Display for CompactStr {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    Display::fmt(&**self, f)
+  }
+  
+}
+
+/**
+ * An interner which hands out [`CompactStr`]s, which store short strings inline.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::compact::CompactInterner;
+ * let mut interner = CompactInterner::new();
+ * let id = interner.intern("id");
+ * assert!(id.is_inline());
+ * let long0 = interner.intern("a string that is too long to store inline");
+ * let long1 = interner.intern(String::from("a string that is too long to store inline"));
+ * assert!(!long0.is_inline());
+ * assert!(CompactStr::ptr_eq(&long0, &long1));
+ * # use str_intern::compact::CompactStr;
+ * ```
+ * 
+ * Every string is saved, whether or not it is stored inline, so [`len`](Self::len), [`contains`](Self::contains), and [`iter`](Self::iter)
+ * work the same way as they do for an [`Interner`](crate::Interner).
+ * 
+ * Strings that are known ahead of time (e.g., keywords) can be saved with [`intern_static`](Self::intern_static), which never copies them,
+ * and every later [`intern`](Self::intern) of the same contents returns a handle to the same `&'static str`.
+ */
+pub struct CompactInterner<S = RandomState> {
+  
+  strings: HashSet<CompactStr, S>
+  
+}
+
+impl CompactInterner {
+  
+  /**
+   * Constructs a new `CompactInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `CompactInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> CompactInterner<S> {
+  
+  /**
+   * Constructs a new `CompactInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { strings: HashSet::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `CompactInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { strings: HashSet::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   * 
+   * Any long string that is interned again afterwards is saved in a new allocation, so it is no longer [`ptr_eq`](CompactStr::ptr_eq) to the old one,
+   * although they still compare equal.
+   */
+  pub fn clear(&mut self) {
+    self.strings.clear()
+  }
+  
+  /**
+   * Consume this `CompactInterner` and return a set containing all of the strings that were interned.
+   * 
+   * Strings saved with [`intern_static`](CompactInterner::intern_static) are included, and still [point to](CompactStr::is_static) their `&'static str`s.
+   */
+  pub fn into_set(self) -> HashSet<CompactStr, S> {
+    self.strings
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings.
+   * 
+   * Strings saved with [`intern_static`](CompactInterner::intern_static) are included, and still [point to](CompactStr::is_static) their `&'static str`s.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.strings.iter())
+  }
+  
+}
+
+impl<S: BuildHasher> CompactInterner<S> {
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a handle to it,
+   * which stores it inline if it is short enough, or points to the saved allocation otherwise.
+   * If it was saved with [`intern_static`](Self::intern_static), the handle points to that `&'static str` instead.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> CompactStr {
+    let string = string.as_ref();
+    if let Some(saved) = self.strings.get(string) {
+      return saved.clone();
+    }
+    let saved = CompactStr::inline(string).unwrap_or_else(|| CompactStr(Repr::Shared(Rc::from(string))));
+    self.strings.insert(saved.clone());
+    saved
+  }
+  
+  /**
+   * Saves the given `&'static str` if the same contents are not already saved, and returns a handle to it which points to it directly,
+   * without copying it (even if it is short enough to store inline).
+   * Every later [`intern`](Self::intern) of the same contents returns a handle which is [`ptr_eq`](CompactStr::ptr_eq) to this one.
+   * 
+   * If the same contents have already been saved (e.g., by [`intern`](Self::intern)), this returns a handle to that string instead,
+   * so that all of the handles for any given contents still agree.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::compact::{CompactInterner, CompactStr};
+   * let mut interner = CompactInterner::new();
+   * let keyword = interner.intern_static("fn");
+   * assert!(keyword.is_static());
+   * assert!(CompactStr::ptr_eq(&interner.intern(String::from("fn")), &keyword));
+   * ```
+   */
+  pub fn intern_static(&mut self, string: &'static str) -> CompactStr {
+    if let Some(saved) = self.strings.get(string) {
+      return saved.clone();
+    }
+    let saved = CompactStr(Repr::Static(string));
+    self.strings.insert(saved.clone());
+    saved
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a handle to it, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<CompactStr> {
+    self.strings.get(string.as_ref()).cloned()
+  }
+  
+}
+
+impl<S: Clone> Clone for CompactInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone() }
+  }
+  
+}
+
+impl<S> Debug for CompactInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("CompactInterner").field(&self.strings).finish()
+  }
+  
+}
+
+impl<S: Default> Default for CompactInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<'a, S> IntoIterator for &'a CompactInterner<S> {
+  
+  type Item = &'a CompactStr;
+  type IntoIter = Iter<'a>;
+  
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for CompactInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+/**
+ * An iterator over the strings in a [`CompactInterner`].
+ * 
+ * This `struct` is created by the [`iter`](CompactInterner::iter) method on [`CompactInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, CompactStr>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, CompactStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item = &'a CompactStr;
+  
+  fn next(&mut self) -> Option<&'a CompactStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+impl<'a> Debug for Iter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
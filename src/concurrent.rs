@@ -0,0 +1,258 @@
+/*!
+ * A thread-safe interner which is backed by a concurrent map instead of a single lock.
+ * 
+ * The map is a [`DashMap`] from the [`dashmap`] crate.
+ */
+
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::iter::FusedIterator;
+
+use dashmap::DashMap;
+use dashmap::iter::Iter as MapIter;
+use dashmap::mapref::entry::Entry;
+
+use crate::sync::InternedStr;
+
+/**
+ * An interner which any number of threads can use at once, without a single lock.
+ * 
+ * Like a [`sync::Interner`](crate::sync::Interner), a `ConcurrentInterner` ensures there is only one allocation for any given string contents,
+ * but its strings are split across many internally locked shards, each of which is only locked for as long as it takes to look up or save a single string.
+ * Those locks are never poisoned, so a thread panicking while using a `ConcurrentInterner` does not prevent other threads from using it.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::concurrent::ConcurrentInterner;
+ * # use str_intern::sync::InternedStr;
+ * let interner = ConcurrentInterner::new();
+ * let foo0 = interner.intern(String::from("foo"));
+ * let foo1 = interner.intern(String::from("foo"));
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * ```
+ * 
+ * If several threads intern the same new string at the same time, only one of their allocations is saved, and all of them return that one.
+ * 
+ * Since each string is only locked on its own, methods which involve every string (e.g., [`len`](Self::len) and [`iter`](Self::iter))
+ * may or may not observe strings being interned or removed by other threads at the same time.
+ */
+pub struct ConcurrentInterner<S = RandomState> {
+  
+  /**
+   * The values are unused; this is a map because only maps have an entry API, which is needed to save strings without racing.
+   */
+  strings: DashMap<InternedStr, (), S>
+  
+}
+
+impl ConcurrentInterner {
+  
+  /**
+   * Constructs a new `ConcurrentInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `ConcurrentInterner` with the given capacity. See [`DashMap`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S: BuildHasher + Clone> ConcurrentInterner<S> {
+  
+  /**
+   * Constructs a new `ConcurrentInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { strings: DashMap::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `ConcurrentInterner` with the given capacity and hasher. See [`DashMap`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { strings: DashMap::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of strings that are currently interned.
+   * 
+   * This may not include strings being interned or removed by other threads at the same time.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings are currently interned.
+   * 
+   * This may not include strings being interned or removed by other threads at the same time.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   * 
+   * Strings interned by other threads at the same time may or may not be removed.
+   */
+  pub fn clear(&self) {
+    self.strings.clear()
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This only locks the part of this `ConcurrentInterner` that the string belongs to, and only briefly.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    if let Some(saved) = self.strings.get(string) {
+      return InternedStr::clone(saved.key());
+    }
+    // Another thread may have saved the string since it was looked up, so the entry decides which allocation is saved.
+    match self.strings.entry(InternedStr::from(string)) {
+      Entry::Occupied(entry) => InternedStr::clone(entry.key()),
+      Entry::Vacant(entry) => {
+        let saved = InternedStr::clone(entry.key());
+        entry.insert(());
+        saved
+      }
+    }
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains_key(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.get(string.as_ref()).map(|saved| InternedStr::clone(saved.key()))
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.remove(string.as_ref()).map(|(saved, ())| saved)
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings.
+   * 
+   * The iterator only locks one part of this `ConcurrentInterner` at a time (for reading), so it is only weakly consistent:
+   * it returns every string that is interned for the whole time it is iterating exactly once,
+   * but strings interned or removed by other threads while it is iterating may or may not be returned.
+   * 
+   * Interning or removing strings on the same thread while iterating may deadlock, since the part being iterated over is locked.
+   */
+  pub fn iter(&self) -> Iter<'_, S> {
+    Iter::new(self.strings.iter())
+  }
+  
+}
+
+impl<S: BuildHasher + Clone> Clone for ConcurrentInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone() }
+  }
+  
+}
+
+impl<S: BuildHasher + Clone> Debug for ConcurrentInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("ConcurrentInterner").field(&DebugSet(self)).finish()
+  }
+  
+}
+
+struct DebugSet<'a, S>(&'a ConcurrentInterner<S>);
+
+impl<'a, S: BuildHasher + Clone> Debug for DebugSet<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_set().entries(self.0.iter()).finish()
+  }
+  
+}
+
+impl<S: BuildHasher + Clone + Default> Default for ConcurrentInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Clone> Extend<T> for ConcurrentInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Clone + Default> FromIterator<T> for ConcurrentInterner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An iterator over the strings in a [`ConcurrentInterner`]. See [`ConcurrentInterner::iter`] for more information.
+ * 
+ * This `struct` is created by the [`iter`](ConcurrentInterner::iter) method on [`ConcurrentInterner`].
+ */
+pub struct Iter<'a, S = RandomState> {
+  
+  iter: MapIter<'a, InternedStr, (), S>
+  
+}
+
+impl<'a, S: BuildHasher + Clone> Iter<'a, S> {
+  
+  fn new(iter: MapIter<'a, InternedStr, (), S>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a, S: BuildHasher + Clone> Iterator for Iter<'a, S> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<InternedStr> {
+    self.iter.next().map(|saved| InternedStr::clone(saved.key()))
+  }
+  
+}
+
+impl<'a, S: BuildHasher + Clone> FusedIterator for Iter<'a, S> {}
+
+impl<'a, S> Debug for Iter<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("Iter").finish_non_exhaustive()
+  }
+  
+}
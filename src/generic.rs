@@ -0,0 +1,400 @@
+/*!
+ * Interners for any hashable value, not just [`str`] (e.g., `[u8]`, [`OsStr`], [`Path`], [`CStr`], or `[u32]`).
+ * 
+ * These mirror the [`str`] interners: [`Interner`] is not thread-safe, and the [`sync`] module provides a thread-safe variant.
+ * They only provide the basics, though; the [`str`] interners ([`crate::Interner`] and [`crate::sync::Interner`]) have many more features
+ * (e.g., normalization, validation, and limits) that only make sense for strings.
+ * 
+ * Values are hashed and compared by `T`'s own [`Hash`] and [`Eq`], so (e.g.) an `Interner<Path>` treats `a/b` and `a//b` as the same path.
+ * To intern paths exactly as they are written, see the [`path`](crate::path) module.
+ */
+
+pub mod sync;
+
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
+use std::ffi::{CStr, OsStr};
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/**
+ * A type that can be interned, i.e., one that can be hashed, and copied into a new [`Rc`] or [`Arc`] when it has not been saved yet.
+ * 
+ * This is implemented for [`str`], slices of any [`Clone`] type (e.g., `[u8]` or `[u32]`), [`OsStr`], [`Path`], and [`CStr`].
+ */
+pub trait InternTarget: Eq + Hash {
+  
+  /**
+   * Copies this value into a new [`Rc`].
+   */
+  fn to_rc(&self) -> Rc<Self>;
+  
+  /**
+   * Copies this value into a new [`Arc`].
+   */
+  fn to_arc(&self) -> Arc<Self>;
+  
+}
+
+macro_rules! impl_intern_target {
+  ($($T:ty),*) => {
+    $(
+      impl InternTarget for $T {
+        
+        fn to_rc(&self) -> Rc<Self> {
+          Rc::from(self)
+        }
+        
+        fn to_arc(&self) -> Arc<Self> {
+          Arc::from(self)
+        }
+        
+      }
+    )*
+  };
+}
+
+impl_intern_target!(str, OsStr, Path, CStr);
+
+impl<T: Clone + Eq + Hash> InternTarget for [T] {
+  
+  fn to_rc(&self) -> Rc<Self> {
+    Rc::from(self)
+  }
+  
+  fn to_arc(&self) -> Arc<Self> {
+    Arc::from(self)
+  }
+  
+}
+
+/**
+ * An interner will keep track of values and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use std::rc::Rc;
+ * # use str_intern::generic::Interner;
+ * let mut interner = Interner::<[u32]>::new();
+ * let code_points0 = interner.intern(vec![0x66, 0x6f, 0x6f]);
+ * let code_points1 = interner.intern([0x66, 0x6f, 0x6f]);
+ * assert!(Rc::ptr_eq(&code_points0, &code_points1));
+ * ```
+ * 
+ * This `Interner` is not thread-safe (which is to say, it is implements neither [`Send`] nor [`Sync`]). For a thread-safe variant, see the [`sync`] module.
+ */
+#[repr(transparent)]
+pub struct Interner<T: ?Sized, S = RandomState> {
+  
+  values: HashSet<Rc<T>, S>
+  
+}
+
+impl<T: ?Sized> Interner<T> {
+  
+  /**
+   * Constructs a new `Interner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<T: ?Sized, S> Interner<T, S> {
+  
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
+  }
+  
+  /**
+   * Constructs an `Interner` which already has all of the values in the given set.
+   */
+  pub fn from_set(values: HashSet<Rc<T>, S>) -> Self {
+    Self { values }
+  }
+  
+  /**
+   * Consume this `Interner` and return a set containing all of the values that were interned.
+   */
+  pub fn into_set(self) -> HashSet<Rc<T>, S> {
+    self.values
+  }
+  
+  /**
+   * Returns the number of values that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+  
+  /**
+   * Returns whether no values are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned values.
+   */
+  pub fn clear(&mut self) {
+    self.values.clear()
+  }
+  
+  /**
+   * Removes every interned value which is not referenced anywhere besides this `Interner`, and returns how many were removed.
+   */
+  pub fn gc(&mut self) -> usize {
+    let len = self.values.len();
+    self.values.retain(|value| Rc::strong_count(value) > 1);
+    len - self.values.len()
+  }
+  
+  /**
+   * An iterator over all of the currently interned values.
+   */
+  pub fn iter(&self) -> Iter<'_, T> {
+    Iter::new(self.values.iter())
+  }
+  
+}
+
+impl<T: InternTarget + ?Sized, S: BuildHasher> Interner<T, S> {
+  
+  /**
+   * Saves the given value if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, value: impl AsRef<T>) -> Rc<T> {
+    let value = value.as_ref();
+    if let Some(saved) = self.values.get(value) {
+      return Rc::clone(saved);
+    }
+    let saved = value.to_rc();
+    self.values.insert(Rc::clone(&saved));
+    saved
+  }
+  
+  /**
+   * Returns whether the given value has been saved.
+   */
+  pub fn contains(&self, value: impl AsRef<T>) -> bool {
+    self.values.contains(value.as_ref())
+  }
+  
+  /**
+   * If the given value has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, value: impl AsRef<T>) -> Option<Rc<T>> {
+    self.values.get(value.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given value, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, value: impl AsRef<T>) -> Option<Rc<T>> {
+    self.values.take(value.as_ref())
+  }
+  
+}
+
+impl<T: ?Sized, S: Clone> Clone for Interner<T, S> {
+  
+  fn clone(&self) -> Self {
+    Self::from_set(self.values.clone())
+  }
+  
+}
+
+impl<T: Debug + ?Sized, S> Debug for Interner<T, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&self.values).finish()
+  }
+  
+}
+
+impl<T: ?Sized, S: Default> Default for Interner<T, S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: ?Sized, S> IntoIterator for Interner<T, S> {
+  
+  type Item = Rc<T>;
+  type IntoIter = IntoIter<T>;
+  
+  fn into_iter(self) -> IntoIter<T> {
+    IntoIter::new(self.values.into_iter())
+  }
+  
+}
+
+impl<'a, T: ?Sized, S> IntoIterator for &'a Interner<T, S> {
+  
+  type Item = &'a Rc<T>;
+  type IntoIter = Iter<'a, T>;
+  
+  fn into_iter(self) -> Iter<'a, T> {
+    self.iter()
+  }
+  
+}
+
+impl<A: AsRef<T>, T: InternTarget + ?Sized, S: BuildHasher> Extend<A> for Interner<T, S> {
+  
+  fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+    for value in iter {
+      self.intern(value);
+    }
+  }
+  
+}
+
+impl<A: AsRef<T>, T: InternTarget + ?Sized, S: BuildHasher + Default> FromIterator<A> for Interner<T, S> {
+  
+  fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An iterator over the values in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](Interner::iter) method on [`Interner`].
+ */
+#[repr(transparent)]
+pub struct Iter<'a, T: ?Sized> {
+  
+  iter: SetIter<'a, Rc<T>>
+  
+}
+
+impl<'a, T: ?Sized> Iter<'a, T> {
+  
+  fn new(iter: SetIter<'a, Rc<T>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+// This can't be derived, since that would require `T: Clone`.
+impl<'a, T: ?Sized> Clone for Iter<'a, T> {
+  
+  fn clone(&self) -> Self {
+    Self::new(self.iter.clone())
+  }
+  
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+  
+  type Item = &'a Rc<T>;
+  
+  fn next(&mut self) -> Option<&'a Rc<T>> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, T: ?Sized> ExactSizeIterator for Iter<'a, T> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a, T: ?Sized> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T: Debug + ?Sized> Debug for Iter<'a, T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * An owning iterator over the values that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+pub struct IntoIter<T: ?Sized> {
+  
+  iter: SetIntoIter<Rc<T>>
+  
+}
+
+impl<T: ?Sized> IntoIter<T> {
+  
+  fn new(iter: SetIntoIter<Rc<T>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<T: ?Sized> Iterator for IntoIter<T> {
+  
+  type Item = Rc<T>;
+  
+  fn next(&mut self) -> Option<Rc<T>> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<T: ?Sized> ExactSizeIterator for IntoIter<T> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<T: ?Sized> FusedIterator for IntoIter<T> {}
+
+impl<T: Debug + ?Sized> Debug for IntoIter<T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
@@ -0,0 +1,482 @@
+/*!
+ * A thread-safe variant of the generic interner.
+ */
+
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::iter::FusedIterator;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use super::InternTarget;
+
+/**
+ * An interner will keep track of values and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use str_intern::generic::sync::Interner;
+ * let interner = Interner::<[u32]>::new();
+ * let code_points0 = interner.intern(vec![0x66, 0x6f, 0x6f]);
+ * let code_points1 = interner.intern([0x66, 0x6f, 0x6f]);
+ * assert!(Arc::ptr_eq(&code_points0, &code_points1));
+ * ```
+ * 
+ * This `Interner` is thread-safe, meaning that it implements both [`Send`] and [`Sync`] (when T implements [`Send`] and [`Sync`], and S implements [`Send`], which the default does).
+ */
+pub struct Interner<T: ?Sized, S = RandomState> {
+  
+  values: Mutex<HashSet<Arc<T>, S>>
+  
+}
+
+impl<T: ?Sized> Interner<T> {
+  
+  /**
+   * Constructs a new `Interner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<T: ?Sized, S> Interner<T, S> {
+  
+  const POISON_MESSAGE: &'static str = "Interner mutex was poisoned";
+  
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
+  }
+  
+  /**
+   * Constructs an `Interner` which already has all of the values in the given set.
+   */
+  pub fn from_set(values: HashSet<Arc<T>, S>) -> Self {
+    Self { values: Mutex::new(values) }
+  }
+  
+  /**
+   * Consume this `Interner` and return a set containing all of the values that were interned.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned.
+   */
+  pub fn into_set(self) -> HashSet<Arc<T>, S> {
+    self.values.into_inner().expect(Self::POISON_MESSAGE)
+  }
+  
+  fn values(&self) -> MutexGuard<'_, HashSet<Arc<T>, S>> {
+    self.values.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `Interner` and returns the number of values that are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn len(&self) -> usize {
+    self.values().len()
+  }
+  
+  /**
+   * Locks this `Interner` and returns whether no values are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.values().is_empty()
+  }
+  
+  /**
+   * Locks this `Interner` and removes all of the interned values, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn clear(&self) {
+    self.values().clear()
+  }
+  
+  /**
+   * Locks this `Interner` and removes every interned value which is not referenced anywhere besides this `Interner`,
+   * and returns how many were removed, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn gc(&self) -> usize {
+    self.lock().gc()
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * While it is locked, the current thread has exclusive access to this `Interner`'s methods
+   * (accessible from the [`LockedInterner`]; any methods used directly on `self` may panic).
+   * This enables iterating over the interned values, with [`LockedInterner::iter`].
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn lock(&self) -> LockedInterner<'_, T, S> {
+    LockedInterner::new(self.values())
+  }
+  
+}
+
+impl<T: InternTarget + ?Sized, S: BuildHasher> Interner<T, S> {
+  
+  /**
+   * Locks this `Interner`, saves the given value if it is not already saved,
+   * and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern(&self, value: impl AsRef<T>) -> Arc<T> {
+    self.lock().intern(value)
+  }
+  
+  /**
+   * Locks this `Interner` and returns whether the given value has been saved, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn contains(&self, value: impl AsRef<T>) -> bool {
+    self.values().contains(value.as_ref())
+  }
+  
+  /**
+   * Locks this `Interner`, and if the given value has been saved, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn get(&self, value: impl AsRef<T>) -> Option<Arc<T>> {
+    self.values().get(value.as_ref()).cloned()
+  }
+  
+  /**
+   * Locks this `Interner`, removes the given value, if it has been saved, and returns the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn remove(&self, value: impl AsRef<T>) -> Option<Arc<T>> {
+    self.values().take(value.as_ref())
+  }
+  
+}
+
+impl<T: ?Sized, S: Clone> Clone for Interner<T, S> {
+  
+  fn clone(&self) -> Self {
+    Self::from_set(self.values().clone())
+  }
+  
+}
+
+impl<T: Debug + ?Sized, S> Debug for Interner<T, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&*self.values()).finish()
+  }
+  
+}
+
+impl<T: ?Sized, S: Default> Default for Interner<T, S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: ?Sized, S> IntoIterator for Interner<T, S> {
+  
+  type Item = Arc<T>;
+  type IntoIter = IntoIter<T>;
+  
+  fn into_iter(self) -> IntoIter<T> {
+    IntoIter::new(self.into_set().into_iter())
+  }
+  
+}
+
+impl<A: AsRef<T>, T: InternTarget + ?Sized, S: BuildHasher> Extend<A> for Interner<T, S> {
+  
+  fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+    self.lock().extend(iter)
+  }
+  
+}
+
+impl<A: AsRef<T>, T: InternTarget + ?Sized, S: BuildHasher + Default> FromIterator<A> for Interner<T, S> {
+  
+  fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * A locked [`Interner`]. This `struct` is created by [`Interner::lock`]; see its documentation for more details.
+ */
+#[repr(transparent)]
+pub struct LockedInterner<'a, T: ?Sized, S = RandomState> {
+  
+  values: MutexGuard<'a, HashSet<Arc<T>, S>>
+  
+}
+
+impl<'a, T: ?Sized, S> LockedInterner<'a, T, S> {
+  
+  fn new(values: MutexGuard<'a, HashSet<Arc<T>, S>>) -> Self {
+    Self { values }
+  }
+  
+  /**
+   * Returns the number of values that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+  
+  /**
+   * Returns whether no values are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned values.
+   */
+  pub fn clear(&mut self) {
+    self.values.clear()
+  }
+  
+  /**
+   * Removes every interned value which is not referenced anywhere besides this `Interner`, and returns how many were removed.
+   */
+  pub fn gc(&mut self) -> usize {
+    let len = self.values.len();
+    self.values.retain(|value| Arc::strong_count(value) > 1);
+    len - self.values.len()
+  }
+  
+  /**
+   * An iterator over all of the currently interned values.
+   */
+  pub fn iter(&self) -> Iter<'_, T> {
+    Iter::new(self.values.iter())
+  }
+  
+}
+
+impl<'a, T: InternTarget + ?Sized, S: BuildHasher> LockedInterner<'a, T, S> {
+  
+  /**
+   * Saves the given value if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, value: impl AsRef<T>) -> Arc<T> {
+    let value = value.as_ref();
+    if let Some(saved) = self.values.get(value) {
+      return Arc::clone(saved);
+    }
+    let saved = value.to_arc();
+    self.values.insert(Arc::clone(&saved));
+    saved
+  }
+  
+  /**
+   * Returns whether the given value has been saved.
+   */
+  pub fn contains(&self, value: impl AsRef<T>) -> bool {
+    self.values.contains(value.as_ref())
+  }
+  
+  /**
+   * If the given value has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, value: impl AsRef<T>) -> Option<Arc<T>> {
+    self.values.get(value.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given value, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, value: impl AsRef<T>) -> Option<Arc<T>> {
+    self.values.take(value.as_ref())
+  }
+  
+}
+
+impl<'a, T: Debug + ?Sized, S> Debug for LockedInterner<'a, T, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("LockedInterner").field(&*self.values).finish()
+  }
+  
+}
+
+impl<'a, 'b, T: ?Sized, S> IntoIterator for &'b LockedInterner<'a, T, S> {
+  
+  type Item = &'b Arc<T>;
+  type IntoIter = Iter<'b, T>;
+  
+  fn into_iter(self) -> Iter<'b, T> {
+    self.iter()
+  }
+  
+}
+
+impl<'a, A: AsRef<T>, T: InternTarget + ?Sized, S: BuildHasher> Extend<A> for LockedInterner<'a, T, S> {
+  
+  fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+    for value in iter {
+      self.intern(value);
+    }
+  }
+  
+}
+
+/**
+ * An iterator over the values in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](LockedInterner::iter) method on [`LockedInterner`].
+ */
+#[repr(transparent)]
+pub struct Iter<'a, T: ?Sized> {
+  
+  iter: SetIter<'a, Arc<T>>
+  
+}
+
+impl<'a, T: ?Sized> Iter<'a, T> {
+  
+  fn new(iter: SetIter<'a, Arc<T>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+// This can't be derived, since that would require `T: Clone`.
+impl<'a, T: ?Sized> Clone for Iter<'a, T> {
+  
+  fn clone(&self) -> Self {
+    Self::new(self.iter.clone())
+  }
+  
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+  
+  type Item = &'a Arc<T>;
+  
+  fn next(&mut self) -> Option<&'a Arc<T>> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, T: ?Sized> ExactSizeIterator for Iter<'a, T> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a, T: ?Sized> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T: Debug + ?Sized> Debug for Iter<'a, T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * An owning iterator over the values that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+pub struct IntoIter<T: ?Sized> {
+  
+  iter: SetIntoIter<Arc<T>>
+  
+}
+
+impl<T: ?Sized> IntoIter<T> {
+  
+  fn new(iter: SetIntoIter<Arc<T>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<T: ?Sized> Iterator for IntoIter<T> {
+  
+  type Item = Arc<T>;
+  
+  fn next(&mut self) -> Option<Arc<T>> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<T: ?Sized> ExactSizeIterator for IntoIter<T> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<T: ?Sized> FusedIterator for IntoIter<T> {}
+
+impl<T: Debug + ?Sized> Debug for IntoIter<T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
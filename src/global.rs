@@ -0,0 +1,108 @@
+/*!
+ * A process-wide interner which picks the cheapest implementation the target allows.
+ * 
+ * Normally, this module just forwards to the [`GlobalInterner`](crate::sync::GlobalInterner),
+ * and [`InternedStr`] is [`sync::InternedStr`](crate::sync::InternedStr) (i.e., [`Arc<str>`](std::sync::Arc)).
+ * 
+ * On targets without threads (specifically, `wasm32` without the `atomics` target feature), or when the `global-unsync` feature is enabled,
+ * the lock and the atomic reference counts are pure overhead, so this module instead uses a single [`RefCell<Interner>`](crate::Interner),
+ * and [`InternedStr`] is [`crate::InternedStr`] (i.e., [`Rc<str>`](std::rc::Rc)).
+ * Since a `thread_local` is used to store it, if the target does have threads anyway, each thread gets its own `Interner`, as in the [`local`](crate::local) module.
+ * 
+ * **Note that this means [`InternedStr`] is a different type depending on the target and features.**
+ * Code which needs to compile in either configuration should only rely on what the two types have in common
+ * (e.g., [`Deref<Target = str>`](std::ops::Deref), [`Clone`], `ptr_eq`, and the comparison traits),
+ * and in particular should not assume that it implements [`Send`] or [`Sync`].
+ * Also, unlike most features, `global-unsync` is not purely additive: enabling it changes this type for every crate in the build.
+ * Code which always needs an [`Arc<str>`](std::sync::Arc) should use [`sync::intern`](crate::sync::intern) directly instead.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::global::{self, InternedStr};
+ * let foo = global::intern("foo");
+ * assert!(InternedStr::ptr_eq(&global::intern(String::from("foo")), &foo));
+ * assert!(global::contains("foo"));
+ * ```
+ */
+
+#[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+use std::cell::RefCell;
+
+#[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+use crate::Interner;
+
+/**
+ * The type of strings that have been interned by this module.
+ * 
+ * This is [`crate::InternedStr`] (i.e., [`Rc<str>`](std::rc::Rc)) without threads or with the `global-unsync` feature,
+ * and [`sync::InternedStr`](crate::sync::InternedStr) (i.e., [`Arc<str>`](std::sync::Arc)) otherwise. (See the [module documentation](self).)
+ */
+#[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+pub type InternedStr = crate::InternedStr;
+
+/**
+ * The type of strings that have been interned by this module.
+ * 
+ * This is [`crate::InternedStr`] (i.e., [`Rc<str>`](std::rc::Rc)) without threads or with the `global-unsync` feature,
+ * and [`sync::InternedStr`](crate::sync::InternedStr) (i.e., [`Arc<str>`](std::sync::Arc)) otherwise. (See the [module documentation](self).)
+ */
+#[cfg(not(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics")))))]
+pub type InternedStr = crate::sync::InternedStr;
+
+#[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+thread_local! {
+  
+  static GLOBAL: RefCell<Interner> = RefCell::new(Interner::new());
+  
+}
+
+/**
+ * Saves the given string if it is not already saved, and returns the saved string.
+ * 
+ * With threads, this is equivalent to [`sync::intern`](crate::sync::intern).
+ * 
+ * # Panics
+ * With threads, this function panics if the [`GlobalInterner`](crate::sync::GlobalInterner) has been [poisoned](crate::sync::Interner#poisoning),
+ * and it may panic if the `GlobalInterner` is already locked on this thread.
+ */
+#[inline]
+pub fn intern(string: impl AsRef<str>) -> InternedStr {
+  #[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+  return GLOBAL.with(|interner| interner.borrow_mut().intern(string));
+  #[cfg(not(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics")))))]
+  return crate::sync::intern(string);
+}
+
+/**
+ * Returns whether the given string has been saved.
+ * 
+ * With threads, this is equivalent to `GlobalInterner.contains(string)`. (See [`sync::Interner::contains`](crate::sync::Interner::contains).)
+ * 
+ * # Panics
+ * With threads, this function panics if the [`GlobalInterner`](crate::sync::GlobalInterner) has been [poisoned](crate::sync::Interner#poisoning),
+ * and it may panic if the `GlobalInterner` is already locked on this thread.
+ */
+#[inline]
+pub fn contains(string: impl AsRef<str>) -> bool {
+  #[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+  return GLOBAL.with(|interner| interner.borrow().contains(string));
+  #[cfg(not(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics")))))]
+  return crate::sync::GlobalInterner.contains(string);
+}
+
+/**
+ * If the given string has been saved, returns the saved string, or `None` otherwise.
+ * 
+ * With threads, this is equivalent to `GlobalInterner.get(string)`. (See [`sync::Interner::get`](crate::sync::Interner::get).)
+ * 
+ * # Panics
+ * With threads, this function panics if the [`GlobalInterner`](crate::sync::GlobalInterner) has been [poisoned](crate::sync::Interner#poisoning),
+ * and it may panic if the `GlobalInterner` is already locked on this thread.
+ */
+#[inline]
+pub fn get(string: impl AsRef<str>) -> Option<InternedStr> {
+  #[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+  return GLOBAL.with(|interner| interner.borrow().get(string));
+  #[cfg(not(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics")))))]
+  return crate::sync::GlobalInterner.get(string);
+}
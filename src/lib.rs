@@ -2,16 +2,53 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "bumpalo")]
+pub mod arena;
+pub mod bytes;
+pub mod compact;
+#[cfg(feature = "dashmap")]
+pub mod concurrent;
+pub mod generic;
+#[cfg(any(feature = "global", feature = "global-unsync"))]
+pub mod global;
+#[cfg(feature = "local")]
+pub mod local;
+pub mod path;
+pub mod static_set;
+pub mod substr;
+pub mod symbol;
 pub mod sync;
 
+mod ord;
+mod ordered;
+mod scratch;
+mod table;
+mod weak;
+
+pub use std::collections::TryReserveError;
+
+use std::borrow::Cow;
+use std::cell::{Ref, RefCell, RefMut};
 use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::collections::hash_map::RandomState;
-use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
-use std::fmt::{self, Debug, Formatter};
-use std::hash::BuildHasher;
-use std::iter::{Sum, Product, FusedIterator};
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter, Drain as SetDrain};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{BuildHasher, DefaultHasher, Hasher};
+use std::io::{self, BufRead};
+use std::iter::{Chain, Sum, Product, FusedIterator};
+use std::ops::{Index, RangeBounds, RangeInclusive};
 use std::rc::Rc;
+use std::slice::Iter as SliceIter;
+use std::str::{self, Split, Utf8Error};
+use std::vec::IntoIter as VecIntoIter;
+
+use ord::{OrdTable, BTreeIter};
+use ordered::OrderedTable;
+use scratch::ScratchBuffer;
+use table::Table;
+use weak::WeakTable;
 
 /**
  * The type of strings that have been interned.
@@ -43,7 +80,7 @@ pub type InternedStr = Rc<str>;
 #[repr(transparent)]
 pub struct Interner<S = RandomState> {
   
-  strings: HashSet<InternedStr, S>
+  table: Table<Local, S>
   
 }
 
@@ -56,474 +93,3709 @@ impl Interner {
     Self::from_set(HashSet::new())
   }
   
-}
-
-impl<S> Interner<S> {
-  
   /**
-   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   * Constructs a new `Interner` with space for at least `capacity` strings before it needs to reallocate.
    */
-  pub fn with_hasher(hasher: S) -> Self {
-    Self::from_set(HashSet::with_hasher(hasher))
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::from_set(HashSet::with_capacity(capacity))
   }
   
   /**
-   * Construct a new `Interner` with the given set's contents already interned.
-   * The new `Interner` will also use the given set's hasher.
+   * Returns an [`InternerBuilder`], which can combine any of the options that the other constructors each set on their own, with any hasher.
    */
-  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
-    Self { strings }
+  pub fn builder() -> InternerBuilder {
+    InternerBuilder::new()
   }
   
   /**
-   * Consume this `Interner` and return a set containing all of strings that were interned.
-   * The returned set also uses the same hasher.
+   * Constructs a new `Interner` which only saves strings that the given validator accepts.
+   * 
+   * `Interner::with_validator(validator)` is equivalent to `Interner::builder().validator(validator).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * [`try_intern`](Self::try_intern) returns the validator's error (as [`TryInternError::Invalid`]) instead of saving a rejected string.
+   * Every other method which saves strings (e.g., [`intern`](Self::intern)) panics instead, so they should not be used with untrusted strings.
+   * Strings which have already been saved are not validated again.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{Interner, ValidationError};
+   * let mut interner = Interner::with_validator(|string| {
+   *   if string.chars().any(char::is_control) {
+   *     Err(ValidationError::new("control characters are not allowed"))
+   *   } else {
+   *     Ok(())
+   *   }
+   * });
+   * assert!(interner.try_intern("foo").is_ok());
+   * assert!(interner.try_intern("foo\n").is_err());
+   * assert!(!interner.contains("foo\n"));
+   * ```
    */
-  pub fn into_set(self) -> HashSet<InternedStr, S> {
-    self.strings
+  pub fn with_validator(validator: impl Fn(&str) -> Result<(), ValidationError> + 'static) -> Self {
+    Self::builder().validator(validator).build()
   }
   
   /**
-   * Removes all of the interned strings.
+   * Constructs a new `Interner` which only saves strings that are no longer than `max_len` bytes.
+   * 
+   * `Interner::with_max_len(max_len)` is equivalent to `Interner::builder().max_len(max_len).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * [`try_intern`](Self::try_intern) returns [`TryInternError::TooLong`] instead of saving a string that is too long,
+   * without allocating anything for it.
+   * Every other method which saves strings (e.g., [`intern`](Self::intern)) panics instead, so they should not be used with untrusted strings.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{Interner, TryInternError};
+   * let mut interner = Interner::with_max_len(3);
+   * assert!(interner.try_intern("foo").is_ok());
+   * assert_eq!(interner.try_intern("foobar"), Err(TryInternError::TooLong { len: 6, max: 3 }));
+   * ```
    */
-  pub fn clear(&mut self) {
-    self.strings.clear();
+  pub fn with_max_len(max_len: usize) -> Self {
+    Self::builder().max_len(max_len).build()
   }
   
   /**
-   * An iterator over all of the currently interned strings.
+   * Constructs a new `Interner` which holds at most `max_entries` strings.
+   * 
+   * `Interner::with_max_entries(max_entries)` is equivalent to `Interner::builder().max_entries(max_entries).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * When a new string is saved while the `Interner` is full, the least recently used string is evicted to make room for it.
+   * Interning a string counts as using it, whether or not it was already saved, and so does looking it up with [`get`](Self::get), [`contains`](Self::contains), or [`entry`](Self::entry).
+   * Evicted strings remain valid for anything that still refers to them, but they are no longer canonical:
+   * interning the same contents again saves a new allocation.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{Interner, InternedStr};
+   * let mut interner = Interner::with_max_entries(2);
+   * let foo = interner.intern("foo");
+   * interner.intern("bar");
+   * interner.intern("foo");
+   * interner.intern("baz");
+   * assert!(!interner.contains("bar"));
+   * assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+   * ```
+   * 
+   * # Panics
+   * This method panics if `max_entries` is 0.
    */
-  pub fn iter(&self) -> Iter {
-    Iter::new(self.strings.iter())
+  pub fn with_max_entries(max_entries: usize) -> Self {
+    Self::builder().max_entries(max_entries).build()
   }
   
-}
-
-impl<S: BuildHasher> Interner<S> {
-  
   /**
-   * Saves the given string if it is not already saved, and returns a reference the saved allocation.
+   * Constructs a new `Interner` whose strings are at most `max_bytes` long in total.
+   * 
+   * `Interner::with_max_bytes(max_bytes)` is equivalent to `Interner::builder().max_bytes(max_bytes).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * When a new string is saved, the least recently used strings are evicted until there is room for it, just like [`with_max_entries`](Interner::with_max_entries).
+   * A string which is longer than `max_bytes` by itself is still returned, but it is never saved, so that it doesn't evict everything else.
+   * Only the lengths of the strings themselves count toward the limit, not any other memory that the `Interner` uses (see [`interned_bytes`](Self::interned_bytes)).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::with_max_bytes(8);
+   * interner.intern("foo");
+   * interner.intern("bar");
+   * interner.intern("bazz");
+   * assert!(!interner.contains("foo"));
+   * assert_eq!(interner.interned_bytes(), 7);
+   * interner.intern("too long!");
+   * assert!(!interner.contains("too long!"));
+   * assert!(interner.contains("bar"));
+   * ```
    */
-  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
-    // Sorrow abounds, for behold: HashSet::get_or_insert_with doesn't exist yet.
-    let string = string.as_ref();
-    match self.strings.get(string) {
-      Some(string) => string.clone(),
-      None => {
-        let string = InternedStr::from(string);
-        self.strings.insert(InternedStr::clone(&string));
-        string
-      }
-    }
+  pub fn with_max_bytes(max_bytes: usize) -> Self {
+    Self::builder().max_bytes(max_bytes).build()
   }
   
   /**
-   * Returns whether the given string has already been saved.
+   * Constructs a new `Interner` which counts how many times each string is interned.
+   * 
+   * `Interner::with_counting()` is equivalent to `Interner::builder().counting().build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * Every call that interns a string (e.g., [`intern`](Self::intern)) adds 1 to its count, whether or not it was already saved,
+   * and the counts can be read with [`count_of`](Self::count_of) and [`most_common`](Self::most_common).
+   * Looking a string up (e.g., with [`get`](Self::get)) does not count.
+   * A string's count is discarded along with it when it is removed (including by [`gc`](Self::gc), which ignores counts),
+   * so it starts over from 0 if it is interned again.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::with_counting();
+   * for message in ["disk full", "retrying", "disk full"] {
+   *   interner.intern(message);
+   * }
+   * assert_eq!(interner.count_of("disk full"), Some(2));
+   * assert_eq!(interner.most_common(1)[0].1, 2);
+   * ```
    */
-  pub fn contains(&self, string: impl AsRef<str>) -> bool {
-    self.strings.contains(string.as_ref())
+  pub fn with_counting() -> Self {
+    Self::builder().counting().build()
   }
   
   /**
-   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise.
+   * Constructs a new `Interner` which ignores ASCII case when checking whether a string has already been saved.
+   * 
+   * `Interner::new_ascii_case_insensitive()` is equivalent to `Interner::builder().ascii_case_insensitive().build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * Only the first casing of a string to be interned is saved, and all other casings of it return a reference to that allocation.
+   * [`get`](Self::get), [`contains`](Self::contains), and [`remove`](Self::remove) ignore ASCII case in the same way.
+   * Non-ASCII characters are compared exactly as they are.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::new_ascii_case_insensitive();
+   * let content_type = interner.intern("Content-Type");
+   * assert_eq!(interner.intern("content-type"), content_type);
+   * assert_eq!(&*content_type, "Content-Type");
+   * ```
    */
-  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
-    self.strings.get(string.as_ref()).cloned()
+  pub fn new_ascii_case_insensitive() -> Self {
+    Self::with_hasher_ascii_case_insensitive(RandomState::new())
   }
   
-}
-
-impl<S: Clone> Clone for Interner<S> {
+  /**
+   * Constructs a new `Interner` which normalizes strings to [Unicode Normalization Form C](https://www.unicode.org/reports/tr15/) (NFC)
+   * before checking whether they have already been saved, and before saving them.
+   * 
+   * `Interner::new_nfc()` is equivalent to `Interner::builder().nfc().build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * This means that different representations of the same text (e.g., `"é"` as a single code point, or as `"e"` followed by a combining accent)
+   * all return a reference to the same allocation, which contains the NFC form.
+   * Strings that are already in NFC (including all ASCII strings) are not copied in order to normalize them.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::new_nfc();
+   * let composed = interner.intern("caf\u{e9}");
+   * assert_eq!(interner.intern("cafe\u{301}"), composed);
+   * assert_eq!(&*composed, "caf\u{e9}");
+   * ```
+   */
+  #[cfg(feature = "unicode-normalization")]
+  pub fn new_nfc() -> Self {
+    Self::with_hasher_nfc(RandomState::new())
+  }
   
-  fn clone(&self) -> Self {
-    Interner { strings: self.strings.clone() }
+  /**
+   * Constructs a new `Interner` with each of the given strings already interned,
+   * and returns it along with references to the saved allocations, in the same order (including any duplicates).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{Interner, InternedStr};
+   * let (mut interner, keywords) = Interner::with_seeds(["if", "else", "while"]);
+   * assert!(InternedStr::ptr_eq(&interner.intern("if"), &keywords[0]));
+   * ```
+   */
+  pub fn with_seeds<I: IntoIterator>(seeds: I) -> (Self, Vec<InternedStr>) where I::Item: AsRef<str> {
+    let mut interner = Self::new();
+    let seeds = interner.intern_all(seeds);
+    (interner, seeds)
   }
   
-  fn clone_from(&mut self, source: &Self) {
-    self.strings.clone_from(&source.strings)
+  /**
+   * Constructs a new `Interner` with each line of the given string already interned.
+   * 
+   * Lines are split on `\n`, with any trailing `\r` removed, and empty lines are skipped.
+   */
+  pub fn from_lines(string: &str) -> Self {
+    // Counting the lines ahead of time overestimates if there are duplicates, but that's better than rehashing over and over.
+    let mut interner = Self::with_capacity(string.bytes().filter(|&byte| byte == b'\n').count() + 1);
+    for line in string.lines().filter(|line| !line.is_empty()) {
+      interner.intern_from(line);
+    }
+    interner
   }
   
 }
 
-impl<S: BuildHasher> PartialEq for Interner<S> {
+impl<S> Interner<S> {
   
-  fn eq(&self, other: &Self) -> bool {
-    self.strings.eq(&other.strings)
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
   }
   
-  fn ne(&self, other: &Self) -> bool {
-    self.strings.ne(&other.strings)
+  /**
+   * Constructs a new `Interner` with the given hasher, and with space for at least `capacity` strings before it needs to reallocate.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
   }
   
-}
-
-impl<S: BuildHasher> Eq for Interner<S> {}
-
-impl<S> Debug for Interner<S> {
-  
-  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    f.debug_tuple("Interner").field(&self.strings).finish()
+  /**
+   * Constructs a new `Interner` with the given hasher, which ignores ASCII case when checking whether a string has already been saved.
+   * (See [`new_ascii_case_insensitive`](Interner::new_ascii_case_insensitive).)
+   */
+  pub fn with_hasher_ascii_case_insensitive(hasher: S) -> Self {
+    InternerBuilder::with_hasher(hasher).ascii_case_insensitive().build()
   }
   
-}
-
-impl<S: Default> Default for Interner<S> {
-  
-  fn default() -> Self {
-    Self { strings: HashSet::default() }
+  /**
+   * Constructs a new `Interner` with the given hasher, which applies the given normalizer to every string before checking whether it has already been saved, and before saving it.
+   * 
+   * `Interner::with_normalizer(hasher, normalizer)` is equivalent to `InternerBuilder::with_hasher(hasher).normalizer(normalizer).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * Only the normalized form of a string is ever saved, so [`get`](Self::get), [`contains`](Self::contains), and [`remove`](Self::remove)
+   * also apply the normalizer, and [`iter`](Self::iter) yields normalized strings.
+   * The normalizer should return [`Cow::Borrowed`] if a string is already normalized, so that it does not need to be copied.
+   * 
+   * For example:
+   * ```rust
+   * # use std::borrow::Cow;
+   * # use std::collections::hash_map::RandomState;
+   * # use str_intern::Interner;
+   * let mut interner = Interner::with_normalizer(RandomState::new(), |string| Cow::Borrowed(string.trim()));
+   * let foo = interner.intern(" foo ");
+   * assert_eq!(interner.intern("foo"), foo);
+   * assert_eq!(&*foo, "foo");
+   * ```
+   */
+  pub fn with_normalizer(hasher: S, normalizer: impl Fn(&str) -> Cow<'_, str> + 'static) -> Self {
+    InternerBuilder::with_hasher(hasher).normalizer(normalizer).build()
   }
   
-}
-
-impl<S> IntoIterator for Interner<S> {
-  
-  type Item = InternedStr;
-  type IntoIter = IntoIter;
-  
-  fn into_iter(self) -> IntoIter {
-    IntoIter::new(self.strings.into_iter())
+  /**
+   * Constructs a new `Interner` with the given hasher, which normalizes strings to NFC before checking whether they have already been saved, and before saving them.
+   * (See [`new_nfc`](Interner::new_nfc).)
+   */
+  #[cfg(feature = "unicode-normalization")]
+  pub fn with_hasher_nfc(hasher: S) -> Self {
+    Self::with_normalizer(hasher, table::nfc)
   }
   
-}
-
-impl<'a, S> IntoIterator for &'a Interner<S> {
-  
-  type Item = &'a InternedStr;
-  type IntoIter = Iter<'a>;
-  
-  fn into_iter(self) -> Iter<'a> {
-    Iter::new(self.strings.iter())
+  /**
+   * Construct a new `Interner` with the given set's contents already interned.
+   * The new `Interner` will also use the given set's hasher.
+   */
+  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
+    Self { table: Table::new(strings) }
   }
   
-}
-
-impl<A, S> FromIterator<A> for Interner<S> where HashSet<InternedStr, S>: FromIterator<A> {
-  
-  fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
-    Self::from_set(HashSet::from_iter(iter))
+  /**
+   * Consume this `Interner` and return a set containing all of strings that were interned.
+   * The returned set also uses the same hasher.
+   */
+  pub fn into_set(self) -> HashSet<InternedStr, S> {
+    self.table.into_set()
   }
   
-}
-
-/**
- * An iterator over the strings in an `Interner`.
- * 
- * This `struct` is created by the [`iter`](Interner::iter) method on `Interner`.
- */
-#[repr(transparent)]
-#[derive(Clone, Debug)]
-pub struct Iter<'a> {
-  
-  iter: SetIter<'a, InternedStr>
-  
-}
-
-impl<'a> Iter<'a> {
-  
-  fn new(iter: SetIter<'a, InternedStr>) -> Self {
-    Self { iter }
+  /**
+   * Consume this `Interner` and return all of the strings that were interned, in lexicographic order. (See [`iter_sorted`](Self::iter_sorted).)
+   */
+  pub fn into_sorted_vec(self) -> Vec<InternedStr> {
+    let mut strings: Vec<_> = self.table.into_set().into_iter().collect();
+    strings.sort_unstable();
+    strings
   }
   
-}
-
-impl<'a> Iterator for Iter<'a> {
-  
-  type Item =  &'a InternedStr;
-  
-  fn next(&mut self) -> Option<Self::Item> {
-    self.iter.next()
+  /**
+   * Returns the number of strings that can be interned without reallocating.
+   */
+  pub fn capacity(&self) -> usize {
+    self.table.strings.capacity()
   }
   
-  fn size_hint(&self) -> (usize, Option<usize>) {
-    self.iter.size_hint()
+  /**
+   * Returns the number of strings that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.table.strings.len()
   }
   
-  fn count(self) -> usize {
-    self.iter.count()
+  /**
+   * Returns whether no strings are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.strings.is_empty()
   }
   
-  fn last(self) -> Option<Self::Item> {
-    self.iter.last()
+  /**
+   * Returns the total length in bytes of the strings that are currently interned.
+   * 
+   * This is kept up to date as strings are saved and removed, so it does not need to look at every string.
+   */
+  pub fn interned_bytes(&self) -> usize {
+    self.table.bytes()
   }
   
-  fn nth(&mut self, n: usize) -> Option<Self::Item> {
-    self.iter.nth(n)
+  /**
+   * Returns an estimate of how much memory this `Interner` uses, including the interned strings themselves.
+   * 
+   * Like [`interned_bytes`](Self::interned_bytes), this does not need to look at every string.
+   */
+  pub fn memory_usage(&self) -> MemoryUsage {
+    self.table.memory_usage()
   }
   
-  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
-    self.iter.for_each(f)
+  /**
+   * Returns how many times strings have been interned into this `Interner` and found to be already saved or not,
+   * since it was constructed or since the last [`reset_stats`](Self::reset_stats).
+   */
+  pub fn stats(&self) -> InternStats {
+    self.table.stats()
   }
   
-  fn collect<B: FromIterator<Self::Item>>(self) -> B {
-    self.iter.collect()
+  /**
+   * Resets all of the counters returned by [`stats`](Self::stats) to 0.
+   */
+  pub fn reset_stats(&mut self) {
+    self.table.reset_stats()
   }
   
-  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
-    self.iter.partition(f)
+  /**
+   * Returns a counter which increases every time the set of interned strings changes,
+   * i.e., whenever a string is newly saved or removed, but not when a string is interned which has already been saved.
+   * 
+   * This can be used to tell whether anything built from the interned strings (e.g., with [`iter`](Self::iter)) is out of date.
+   * Two equal versions from the same `Interner` mean that it contains the same strings,
+   * but versions from different `Interner`s (including clones, which start from the same version) cannot be compared.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::new();
+   * interner.intern("foo");
+   * let version = interner.version();
+   * interner.intern("foo");
+   * assert_eq!(interner.version(), version);
+   * interner.intern("bar");
+   * assert!(interner.version() > version);
+   * ```
+   */
+  pub fn version(&self) -> u64 {
+    self.table.version()
   }
   
-  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
-    self.iter.fold(init, f)
+  /**
+   * Returns statistics about the lengths of the interned strings, including a histogram.
+   * 
+   * This has to look at every string, so it takes time proportional to the number of strings.
+   */
+  pub fn len_stats(&self) -> LenStats {
+    LenStats::new(self.table.strings.iter().map(|string| &**string))
   }
   
-  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
-    self.iter.reduce(f)
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.table.clear();
   }
   
-  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.all(f)
+  /**
+   * Removes all of the interned strings, and returns an iterator over them.
+   * 
+   * If the returned iterator is dropped before being fully consumed, the remaining strings are removed anyway.
+   */
+  pub fn drain(&mut self) -> Drain<'_> {
+    Drain::new(self.table.drain())
   }
   
-  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.any(f)
+  /**
+   * Returns an iterator which removes and yields each interned string for which the given predicate returns `true`.
+   * 
+   * Strings are only removed as the returned iterator is advanced; if it is dropped before being fully consumed,
+   * any strings it has not yet visited are kept, whether or not they match the predicate.
+   */
+  pub fn extract_if<F: FnMut(&InternedStr) -> bool>(&mut self, predicate: F) -> ExtractIf<'_, F> {
+    ExtractIf::new(self.table.extract_if(predicate, RemovalCause::Retain))
   }
   
-  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
-    self.iter.find(predicate)
+  /**
+   * Removes every interned string for which the given predicate returns `false`.
+   */
+  pub fn retain(&mut self, predicate: impl FnMut(&InternedStr) -> bool) {
+    self.table.retain(predicate)
   }
   
-  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
-    self.iter.find_map(f)
+  /**
+   * Removes every interned string which is not referenced outside of this `Interner`, and returns how many strings were removed, and how many bytes they contained.
+   * 
+   * Any string that is still referenced elsewhere keeps its saved allocation.
+   * A string that was removed is saved in a new allocation if it is interned again.
+   */
+  pub fn gc(&mut self) -> GcReport {
+    self.table.gc()
   }
   
-  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
-    self.iter.position(predicate)
+  /**
+   * Returns an iterator over the interned strings, along with how many times each of them is referenced outside of this `Interner`,
+   * i.e., how many [`InternedStr`]s there are for it besides the ones this `Interner` holds itself.
+   * 
+   * A count of 0 means the string would be removed by [`gc`](Self::gc).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::new();
+   * let foo = interner.intern("foo");
+   * let foo_again = foo.clone();
+   * interner.intern("bar");
+   * let mut counts: Vec<_> = interner.ref_counts().map(|(string, count)| (&**string, count)).collect();
+   * counts.sort();
+   * assert_eq!(counts, [("bar", 0), ("foo", 2)]);
+   * ```
+   */
+  pub fn ref_counts(&self) -> RefCounts<'_> {
+    RefCounts::new(self.table.ref_counts(0))
   }
   
-  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.max()
+  /**
+   * Returns an iterator over the interned strings which are referenced at least `min_refs` times outside of this `Interner`,
+   * along with how many times each of them is referenced. (See [`ref_counts`](Self::ref_counts).)
+   */
+  pub fn leaked(&self, min_refs: usize) -> RefCounts<'_> {
+    RefCounts::new(self.table.ref_counts(min_refs))
   }
   
-  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.min()
+  /**
+   * Returns how many strings are saved between each automatic [`gc`](Self::gc), or 0 if this `Interner` never sweeps automatically.
+   * (See [`set_gc_threshold`](Self::set_gc_threshold).)
+   */
+  pub fn gc_threshold(&self) -> usize {
+    self.table.gc_threshold()
   }
   
-  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.max_by_key(f)
+  /**
+   * Makes this `Interner` automatically [`gc`](Self::gc) after saving every `threshold` new strings, or never if `threshold` is 0 (the default).
+   * 
+   * Interning a string which has already been saved does not count toward the threshold.
+   * The string that triggers a sweep is never removed by it, since a reference to it is returned.
+   */
+  pub fn set_gc_threshold(&mut self, threshold: usize) {
+    self.table.set_gc_threshold(threshold)
   }
   
-  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.max_by(compare)
+  /**
+   * Makes this `Interner` call the given function with every string that is removed from it, along with why it was removed,
+   * replacing any function that was set before.
+   * 
+   * The function is called exactly once for each removed string, as soon as it is removed
+   * (which, for [`extract_if`](Self::extract_if), is when the returned iterator yields it, and for [`drain`](Self::drain), is right away).
+   * Strings which leave this `Interner` without being removed from it, e.g., through [`into_iter`](IntoIterator::into_iter), are not reported.
+   * A clone of this `Interner` does not keep the function.
+   * 
+   * The function must not use this `Interner` (e.g., through an [`Rc`]`<`[`RefCell`]`<Interner>>`),
+   * since it is called in the middle of removing strings; trying to do so with a `RefCell` panics.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{Interner, RemovalCause};
+   * # use std::cell::RefCell;
+   * # use std::rc::Rc;
+   * let removed = Rc::new(RefCell::new(Vec::new()));
+   * let mut interner = Interner::new();
+   * interner.set_removal_hook({
+   *   let removed = Rc::clone(&removed);
+   *   move |string, cause| removed.borrow_mut().push((string.to_string(), cause))
+   * });
+   * interner.intern("foo");
+   * interner.remove("foo");
+   * assert_eq!(*removed.borrow(), [(String::from("foo"), RemovalCause::Explicit)]);
+   * ```
+   */
+  pub fn set_removal_hook(&mut self, hook: impl FnMut(&InternedStr, RemovalCause) + 'static) {
+    self.table.set_removal_hook(Box::new(hook))
   }
   
-  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.min_by_key(f)
+  /**
+   * Makes this `Interner` call the given function with every string that is newly saved in it, replacing any function that was set before.
+   * 
+   * The function is called exactly once for each saved string, with the saved allocation, no matter which method saved it
+   * (including [`extend`](Extend::extend), [`intern_all`](Self::intern_all), and [`intern_lines`](Self::intern_lines)).
+   * It is not called when a string is interned which has already been saved, or which is rejected or never fits (see [`with_max_bytes`](Interner::with_max_bytes)).
+   * Strings which are already in this `Interner` when the function is set (e.g., from [`from_lines`](Interner::from_lines)) are not reported.
+   * A clone of this `Interner` does not keep the function.
+   * 
+   * Like the removal hook (see [`set_removal_hook`](Self::set_removal_hook)), the function must not use this `Interner`.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * # use std::cell::Cell;
+   * # use std::rc::Rc;
+   * let inserted = Rc::new(Cell::new(0));
+   * let mut interner = Interner::new();
+   * interner.set_insert_hook({
+   *   let inserted = Rc::clone(&inserted);
+   *   move |_| inserted.set(inserted.get() + 1)
+   * });
+   * interner.intern_all(["foo", "bar", "foo"]);
+   * assert_eq!(inserted.get(), 2);
+   * ```
+   */
+  pub fn set_insert_hook(&mut self, hook: impl FnMut(&InternedStr) + 'static) {
+    self.table.set_insert_hook(Box::new(hook))
   }
   
-  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.min_by(compare)
+  /**
+   * An iterator over all of the currently interned strings.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.table.strings.iter())
   }
   
-  fn sum<S: Sum<Self::Item>>(self) -> S {
-    self.iter.sum()
+  /**
+   * An iterator over the currently interned strings which start with the given prefix, in no particular order.
+   * 
+   * An empty prefix matches every string, and the prefix itself is included if it has been interned.
+   * This has to check every string, so it takes time proportional to the number of strings, no matter how many match.
+   * (An [`OrdInterner`](OrdInterner::iter_prefix) can find them without checking the others.)
+   */
+  pub fn iter_prefix<'a>(&'a self, prefix: &'a str) -> IterPrefix<'a> {
+    IterPrefix::new(self.table.strings.iter(), prefix)
   }
   
-  fn product<P: Product<Self::Item>>(self) -> P {
-    self.iter.product()
+  /**
+   * An iterator over all of the currently interned strings, in lexicographic order (i.e., the order of their UTF-8 bytes, like [`str`'s `Ord`](Ord)).
+   * 
+   * This collects a reference to every string into a temporary [`Vec`] and sorts it before yielding anything,
+   * so it takes time proportional to `n log n` for `n` strings, and allocates space for `n` references.
+   */
+  pub fn iter_sorted(&self) -> IterSorted<'_> {
+    IterSorted::new(self.table.strings.iter().collect())
   }
   
-  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
-    self.iter.cmp(other)
-  }
+}
+
+impl<S: BuildHasher> Interner<S> {
   
-  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
-    self.iter.partial_cmp(other)
+  /**
+   * Reserves space for at least `additional` more strings to be interned without reallocating.
+   */
+  pub fn reserve(&mut self, additional: usize) {
+    self.table.strings.reserve(additional)
   }
   
-  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.eq(other)
+  /**
+   * Tries to reserve space for at least `additional` more strings to be interned without reallocating.
+   * 
+   * Unlike [`reserve`](Self::reserve), this returns an error instead of panicking or aborting if the capacity overflows or the allocation fails.
+   */
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.table.strings.try_reserve(additional)
   }
   
-  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.ne(other)
+  /**
+   * Shrinks the capacity as much as possible while still holding all of the currently interned strings.
+   * 
+   * This is particularly useful after a [`clear`](Self::clear), which does not release any memory by itself.
+   */
+  pub fn shrink_to_fit(&mut self) {
+    self.table.shrink_to(0);
   }
   
-  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.lt(other)
+  /**
+   * Shrinks the capacity as much as possible while still holding all of the currently interned strings, but not below `min_capacity`,
+   * and returns how much the capacity decreased.
+   * 
+   * Anything else this `Interner` uses to keep track of its strings (e.g., for [`new_ascii_case_insensitive`](Interner::new_ascii_case_insensitive)
+   * or [`with_max_entries`](Interner::with_max_entries)) is shrunk as well.
+   * This is particularly useful after removing most of the strings, e.g., with [`gc`](Self::gc) or [`extract_if`](Self::extract_if),
+   * since removing strings does not release any memory by itself.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::with_capacity(100);
+   * interner.intern("foo");
+   * assert!(interner.compact(0) > 0);
+   * assert_eq!(interner.compact(0), 0);
+   * ```
+   */
+  pub fn compact(&mut self, min_capacity: usize) -> usize {
+    self.table.shrink_to(min_capacity)
   }
   
-  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.le(other)
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected. (See [`try_intern`](Self::try_intern).)
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.intern_from(string.as_ref())
   }
   
-  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.gt(other)
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * or returns an error if the string is rejected, in which case nothing is saved.
+   * 
+   * # Errors
+   * This method returns [`TryInternError::TooLong`] if the string is longer than this `Interner`'s maximum length (see [`with_max_len`](Interner::with_max_len)),
+   * or [`TryInternError::Invalid`] if it is rejected by this `Interner`'s validator (see [`with_validator`](Interner::with_validator)).
+   */
+  pub fn try_intern(&mut self, string: impl AsRef<str>) -> Result<InternedStr, TryInternError> {
+    self.table.try_intern(string.as_ref()).map(|(string, _)| string)
   }
   
-  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * along with whether it was newly saved (i.e., `true` if it was not already saved).
+   * A string which is too long to ever be saved by an `Interner` [with a maximum number of bytes](Interner::with_max_bytes) is returned with `false`, since it was not saved.
+   */
+  pub fn intern_full(&mut self, string: impl AsRef<str>) -> (InternedStr, bool) {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Saves each of the given strings if they are not already saved, and returns references to the saved allocations, in the same order.
+   */
+  pub fn intern_all<I: IntoIterator>(&mut self, strings: I) -> Vec<InternedStr> where I::Item: AsRef<str> {
+    let strings = strings.into_iter();
+    self.table.strings.reserve(strings.size_hint().0);
+    strings.map(|string| self.intern(string)).collect()
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is like [`intern`](Self::intern), except that it takes ownership of the string, which is simply dropped if it has already been saved.
+   * See [`IntoInterned`] for more information.
+   */
+  pub fn intern_from(&mut self, string: impl IntoInterned) -> InternedStr {
+    self.table.intern(string).0
+  }
+  
+  /**
+   * Makes the given allocation canonical if its contents are not already saved, and returns a reference to the saved allocation.
+   * 
+   * If the contents have already been saved, the given allocation is dropped and the existing one is returned instead;
+   * otherwise, the given allocation itself is saved, without being copied.
+   * This is equivalent to [`intern_from`](Self::intern_from), but is more explicit about what it accepts.
+   */
+  pub fn canonicalize(&mut self, string: InternedStr) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Replaces each of the given references with a reference to the saved allocation for its contents, saving any contents that are not already saved,
+   * and returns how many references were replaced (i.e., how many referred to an allocation other than the saved one).
+   * 
+   * Contents that are not already saved adopt the first given allocation with those contents, so nothing is copied.
+   */
+  pub fn dedupe_in_place(&mut self, strings: &mut [InternedStr]) -> usize {
+    let mut collapsed = 0;
+    for string in strings {
+      let canonical = self.canonicalize(InternedStr::clone(string));
+      if !InternedStr::ptr_eq(&canonical, string) {
+        *string = canonical;
+        collapsed += 1;
+      }
+    }
+    collapsed
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is equivalent to [`intern_from`](Self::intern_from), but is more explicit about what it accepts.
+   * Note that because an [`InternedStr`] keeps its reference counts in the same allocation as its contents,
+   * a [`Cow::Owned`] string cannot reuse its buffer, and is still copied into a new allocation if it has not already been saved.
+   */
+  pub fn intern_cow(&mut self, string: Cow<'_, str>) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Validates that the given bytes are UTF-8, then saves them as a string if they are not already saved, and returns a reference to the saved allocation.
+   * 
+   * If the given bytes are not valid UTF-8, nothing is saved, and the returned [`Utf8Error`] describes where the invalid bytes begin.
+   */
+  pub fn intern_utf8(&mut self, bytes: &[u8]) -> Result<InternedStr, Utf8Error> {
+    str::from_utf8(bytes).map(|string| self.intern_from(string))
+  }
+  
+  /**
+   * Saves the given bytes as a string if they are not already saved, and returns a reference to the saved allocation.
+   * 
+   * Any invalid UTF-8 sequences are replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER), as with [`String::from_utf8_lossy`].
+   * If the given bytes are already valid UTF-8, no intermediate allocation is made.
+   */
+  pub fn intern_utf8_lossy(&mut self, bytes: &[u8]) -> InternedStr {
+    self.intern_from(String::from_utf8_lossy(bytes))
+  }
+  
+  /**
+   * Formats the given arguments, then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is intended to be used with [`format_args!`], e.g., `interner.intern_fmt(format_args!("{}:{}", module, name))`.
+   * Unlike interning the result of [`format!`], short results are formatted into a buffer on the stack,
+   * so no heap allocation is made unless the result has not already been saved.
+   * 
+   * # Panics
+   * This method panics if a formatting trait implementation returns an error, just like [`format!`].
+   */
+  pub fn intern_fmt(&mut self, args: fmt::Arguments<'_>) -> InternedStr {
+    match args.as_str() {
+      Some(string) => self.intern_from(string),
+      None => self.intern_from(&*ScratchBuffer::format(args))
+    }
+  }
+  
+  /**
+   * Formats the given value with its [`Display`] implementation, then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * `interner.intern_display(&value)` is equivalent to `interner.intern_fmt(format_args!("{}", value))`.
+   * (See [`intern_fmt`](Self::intern_fmt).)
+   * If this panics, this `Interner` is left unchanged.
+   * 
+   * # Panics
+   * This method panics if the value's [`Display`] implementation panics or returns an error.
+   */
+  pub fn intern_display<T: Display + ?Sized>(&mut self, value: &T) -> InternedStr {
+    self.intern_fmt(format_args!("{value}"))
+  }
+  
+  /**
+   * Concatenates the given parts, with the given separator between each of them,
+   * then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * Unlike interning the result of [`join`](slice::join), short results are concatenated into a buffer on the stack,
+   * so no heap allocation is made unless the result has not already been saved.
+   * If there are no parts, the empty string is saved.
+   */
+  pub fn intern_join<I: IntoIterator>(&mut self, separator: &str, parts: I) -> InternedStr where I::Item: AsRef<str> {
+    self.intern_from(&*ScratchBuffer::join(separator, parts))
+  }
+  
+  /**
+   * Returns an iterator which splits the given string by the given separator, saves each substring if it is not already saved, and yields references to the saved allocations.
+   * 
+   * Substrings are split exactly like [`str::split`], including any empty substrings, and are only saved as the returned iterator is advanced.
+   */
+  pub fn intern_split<'b>(&'b mut self, string: &'b str, separator: char) -> InternSplit<'b, S> {
+    InternSplit::new(&mut self.table, string.split(separator))
+  }
+  
+  /**
+   * Reads each line from the given reader, saves it if it is not already saved, and returns how many lines were newly saved.
+   * 
+   * Lines are split on `\n`, with any trailing `\r` removed, and empty lines are skipped.
+   * 
+   * # Errors
+   * This method returns any error encountered while reading, in which case any lines that were read before the error remain saved.
+   */
+  pub fn intern_lines<R: BufRead>(&mut self, mut reader: R) -> io::Result<usize> {
+    // Count what was actually saved, since the table can shrink along the way (through eviction or automatic collection).
+    let mut saved = 0;
+    let mut line = String::new();
+    while reader.read_line(&mut line)? != 0 {
+      let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+      let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+      if !trimmed.is_empty() && self.table.intern(trimmed).1 {
+        saved += 1;
+      }
+      line.clear();
+    }
+    Ok(saved)
+  }
+  
+  /**
+   * If the given key has already been saved, returns a reference to the saved allocation;
+   * otherwise, saves the result of `make`, and returns a reference to the new saved allocation.
+   * `make` is only called if the key has not already been saved.
+   * 
+   * `interner.get_or_intern_with(key, make)` is equivalent to `interner.entry(key).or_insert_with(make)`.
+   * (See [`entry`](Self::entry) and [`Entry::or_insert_with`].)
+   * 
+   * # Panics
+   * This method panics if `make` returns a string with different contents than the key.
+   */
+  pub fn get_or_intern_with<T: Into<InternedStr>>(&mut self, key: &str, make: impl FnOnce() -> T) -> InternedStr {
+    self.entry(key).or_insert_with(make)
+  }
+  
+  /**
+   * Gets the entry for the given string, which can be used to save it only if it is not already saved.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::Interner;
+   * let mut interner = Interner::new();
+   * let foo = interner.entry("foo").or_insert_with(|| String::from("foo"));
+   * assert_eq!(interner.entry("foo").or_insert_with(|| -> String { unreachable!() }), foo);
+   * ```
+   */
+  pub fn entry<'a>(&'a mut self, key: &'a str) -> Entry<'a, S> {
+    Entry::new(&mut self.table, key)
+  }
+  
+  /**
+   * Returns whether the given string has already been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.get(string.as_ref()).is_some()
+  }
+  
+  /**
+   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Returns how many times the given string has been interned since it was saved,
+   * or `None` if it has not been saved or this `Interner` does not count (see [`with_counting`](Interner::with_counting)).
+   */
+  pub fn count_of(&self, string: impl AsRef<str>) -> Option<u64> {
+    self.table.count_of(string.as_ref())
+  }
+  
+  /**
+   * Returns up to `n` of the interned strings which have been interned the most times, along with how many times each of them has been interned.
+   * 
+   * They are sorted by count from most to least, and strings with the same count are sorted by their contents.
+   * If this `Interner` does not count (see [`with_counting`](Interner::with_counting)), this returns an empty `Vec`.
+   */
+  pub fn most_common(&self, n: usize) -> Vec<(InternedStr, u64)> {
+    self.table.most_common(n)
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents,
+   * rather than merely to an allocation with the same contents (e.g., one saved by a different interner).
+   */
+  pub fn is_canonical(&self, string: &InternedStr) -> bool {
+    self.table.get(string).is_some_and(|saved| InternedStr::ptr_eq(saved, string))
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents.
+   * 
+   * This is an alias for [`is_canonical`](Self::is_canonical).
+   */
+  pub fn contains_ptr(&self, string: &InternedStr) -> bool {
+    self.is_canonical(string)
+  }
+  
+  /**
+   * Removes the given string if it has been saved, and returns the saved allocation, or `None` if it was not saved.
+   * 
+   * Any outstanding references to the saved allocation remain valid; they are simply no longer canonical,
+   * so interning the same contents again will create a new allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.remove(string.as_ref())
+  }
+  
+}
+
+impl<S: Clone> Clone for Interner<S> {
+  
+  fn clone(&self) -> Self {
+    Interner { table: self.table.clone() }
+  }
+  
+  fn clone_from(&mut self, source: &Self) {
+    self.table.clone_from(&source.table)
+  }
+  
+}
+
+impl<S: BuildHasher> PartialEq for Interner<S> {
+  
+  fn eq(&self, other: &Self) -> bool {
+    self.table.strings.eq(&other.table.strings)
+  }
+  
+}
+
+impl<S: BuildHasher> Eq for Interner<S> {}
+
+impl<S> Debug for Interner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&self.table.strings).finish()
+  }
+  
+}
+
+impl<S: BuildHasher> Index<&str> for Interner<S> {
+  
+  type Output = InternedStr;
+  
+  /**
+   * Returns a reference to the saved allocation for the given string.
+   * 
+   * # Panics
+   * This method panics if the given string has not been saved.
+   */
+  fn index(&self, string: &str) -> &InternedStr {
+    self.table.get(string).unwrap_or_else(|| panic!("{string:?} has not been interned"))
+  }
+  
+}
+
+impl<S: Default> Default for Interner<S> {
+  
+  fn default() -> Self {
+    Self { table: Table::default() }
+  }
+  
+}
+
+impl<S> IntoIterator for Interner<S> {
+  
+  type Item = InternedStr;
+  type IntoIter = IntoIter;
+  
+  fn into_iter(self) -> IntoIter {
+    IntoIter::new(self.table.into_set().into_iter())
+  }
+  
+}
+
+impl<'a, S> IntoIterator for &'a Interner<S> {
+  
+  type Item = &'a InternedStr;
+  type IntoIter = Iter<'a>;
+  
+  fn into_iter(self) -> Iter<'a> {
+    Iter::new(self.table.strings.iter())
+  }
+  
+}
+
+impl<T: IntoInterned, S: BuildHasher> Extend<T> for Interner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.table.strings.reserve(iter.size_hint().0);
+    iter.for_each(|string| {
+      self.intern_from(string);
+    });
+  }
+  
+}
+
+/**
+ * Constructs a new `Interner` with each of the given strings already interned.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::Interner;
+ * let interner = Interner::from(["if", "else", "while", "if"]);
+ * assert_eq!(interner.len(), 3);
+ * ```
+ */
+impl<T: IntoInterned, const N: usize> From<[T; N]> for Interner {
+  
+  fn from(strings: [T; N]) -> Self {
+    let mut interner = Self::with_capacity(N);
+    interner.extend(strings);
+    interner
+  }
+  
+}
+
+/**
+ * Constructs a new `Interner` with each of the given strings already interned.
+ */
+impl<T: AsRef<str>> From<&[T]> for Interner {
+  
+  fn from(strings: &[T]) -> Self {
+    let mut interner = Self::with_capacity(strings.len());
+    interner.extend(strings.iter().map(AsRef::as_ref));
+    interner
+  }
+  
+}
+
+/**
+ * Collects strings into a new `Interner`, saving each of them if they are not already saved.
+ * 
+ * For example:
+ * ```rust
+ * # use std::borrow::Cow;
+ * # use str_intern::{Interner, InternedStr};
+ * let interner: Interner = "foo bar foo".split(' ').collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = vec![String::from("foo"), String::from("bar")].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = [Cow::Borrowed("foo"), Cow::Owned(String::from("bar"))].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = [InternedStr::from("foo"), InternedStr::from("bar")].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * ```
+ */
+impl<A: IntoInterned, S: BuildHasher + Default> FromIterator<A> for Interner<S> {
+  
+  fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * A builder for an [`Interner`], which can combine any of the ways an `Interner` can behave, with any hasher.
+ * 
+ * Each of the specialized constructors on [`Interner`] (e.g., [`with_max_entries`](Interner::with_max_entries)) is a shortcut for a builder with just one option set.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::{Interner, FixedState};
+ * let mut interner = Interner::builder()
+ *   .hasher(FixedState::with_seed(42))
+ *   .ascii_case_insensitive()
+ *   .max_entries(2)
+ *   .build();
+ * let content_type = interner.intern("Content-Type");
+ * assert_eq!(interner.intern("content-type"), content_type);
+ * interner.intern("Accept");
+ * interner.intern("Host");
+ * assert!(!interner.contains("CONTENT-TYPE"));
+ * ```
+ */
+pub struct InternerBuilder<S = RandomState> {
+  
+  hasher: S,
+  capacity: usize,
+  options: table::Options<Local>
+  
+}
+
+impl InternerBuilder {
+  
+  /**
+   * Constructs a new `InternerBuilder`, which builds the same `Interner` as [`Interner::new`] until any options are set.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+}
+
+impl<S> InternerBuilder<S> {
+  
+  /**
+   * Constructs a new `InternerBuilder` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { hasher, capacity: 0, options: table::Options::new() }
+  }
+  
+  /**
+   * Uses the given hasher instead of the current one. See [`BuildHasher`] for more information.
+   */
+  pub fn hasher<T>(self, hasher: T) -> InternerBuilder<T> {
+    InternerBuilder { hasher, capacity: self.capacity, options: self.options }
+  }
+  
+  /**
+   * Makes space for at least `capacity` strings before the `Interner` needs to reallocate. (See [`Interner::with_capacity`].)
+   */
+  pub fn capacity(mut self, capacity: usize) -> Self {
+    self.capacity = capacity;
+    self
+  }
+  
+  /**
+   * Applies the given normalizer to every string before checking whether it has already been saved, and before saving it, replacing any previous normalizer.
+   * (See [`Interner::with_normalizer`].)
+   */
+  pub fn normalizer(mut self, normalizer: impl Fn(&str) -> Cow<'_, str> + 'static) -> Self {
+    self.options.normalizer = Some(Rc::new(normalizer));
+    self
+  }
+  
+  /**
+   * Normalizes strings to NFC before checking whether they have already been saved, and before saving them, replacing any previous normalizer.
+   * (See [`Interner::new_nfc`].)
+   */
+  #[cfg(feature = "unicode-normalization")]
+  pub fn nfc(self) -> Self {
+    self.normalizer(table::nfc)
+  }
+  
+  /**
+   * Only saves strings that the given validator accepts, replacing any previous validator. (See [`Interner::with_validator`].)
+   * 
+   * If there is also a normalizer, the validator is given the normalized string.
+   */
+  pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), ValidationError> + 'static) -> Self {
+    self.options.validator = Some(Rc::new(validator));
+    self
+  }
+  
+  /**
+   * Only saves strings that are no longer than `max_len` bytes (before they are normalized). (See [`Interner::with_max_len`].)
+   */
+  pub fn max_len(mut self, max_len: usize) -> Self {
+    self.options.max_len = Some(max_len);
+    self
+  }
+  
+  /**
+   * Holds at most `max_entries` strings, evicting the least recently used one to make room for a new one. (See [`Interner::with_max_entries`].)
+   * 
+   * This can be combined with [`max_bytes`](Self::max_bytes), in which case strings are evicted until both limits are met.
+   * 
+   * # Panics
+   * This method panics if `max_entries` is 0.
+   */
+  pub fn max_entries(mut self, max_entries: usize) -> Self {
+    assert!(max_entries != 0, "an interner must be able to hold at least one string");
+    self.options.max_entries = Some(max_entries);
+    self
+  }
+  
+  /**
+   * Holds strings which are at most `max_bytes` long in total, evicting the least recently used ones to make room for a new one.
+   * (See [`Interner::with_max_bytes`].)
+   * 
+   * This can be combined with [`max_entries`](Self::max_entries), in which case strings are evicted until both limits are met.
+   */
+  pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+    self.options.max_bytes = Some(max_bytes);
+    self
+  }
+  
+  /**
+   * Ignores ASCII case when checking whether a string has already been saved. (See [`Interner::new_ascii_case_insensitive`].)
+   * 
+   * If there is also a normalizer, ASCII case is ignored after normalizing.
+   */
+  pub fn ascii_case_insensitive(mut self) -> Self {
+    self.options.ascii_case_insensitive = true;
+    self
+  }
+  
+  /**
+   * Counts how many times each string is interned. (See [`Interner::with_counting`].)
+   */
+  pub fn counting(mut self) -> Self {
+    self.options.counting = true;
+    self
+  }
+  
+  /**
+   * Constructs a new, empty `Interner` with all of the options that have been set.
+   */
+  pub fn build(self) -> Interner<S> {
+    Interner { table: Table::with_options(HashSet::with_capacity_and_hasher(self.capacity, self.hasher), self.options) }
+  }
+  
+}
+
+impl<S: Clone> Clone for InternerBuilder<S> {
+  
+  fn clone(&self) -> Self {
+    Self { hasher: self.hasher.clone(), capacity: self.capacity, options: self.options.clone() }
+  }
+  
+}
+
+impl<S: Debug> Debug for InternerBuilder<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("InternerBuilder").field("hasher", &self.hasher).field("capacity", &self.capacity).field("options", &self.options).finish()
+  }
+  
+}
+
+impl<S: Default> Default for InternerBuilder<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+/**
+ * A string that can be passed to [`intern_from`](Interner::intern_from) (and friends), which takes ownership of it.
+ * 
+ * Owned strings are only converted into an [`InternedStr`] if their contents have not already been saved; otherwise, they are simply dropped.
+ * An [`InternedStr`] that has not already been saved becomes the saved allocation itself, without being copied.
+ * Other owned strings must still be copied into a new allocation, since an [`InternedStr`] keeps its reference counts alongside its contents.
+ */
+pub trait IntoInterned {
+  
+  /**
+   * Returns the contents of this string, which are used to check whether it has already been saved.
+   */
+  fn as_str(&self) -> &str;
+  
+  /**
+   * Converts this string into an [`InternedStr`]. This is only called if it has not already been saved.
+   */
+  fn into_interned(self) -> InternedStr;
+  
+}
+
+impl<T: IntoInterned> table::Source<InternedStr> for T {
+  
+  fn as_str(&self) -> &str {
+    IntoInterned::as_str(self)
+  }
+  
+  fn into_saved(self) -> InternedStr {
+    self.into_interned()
+  }
+  
+}
+
+/**
+ * The kind of table behind [`Interner`].
+ */
+enum Local {}
+
+impl table::Kind for Local {
+  
+  type Str = InternedStr;
+  type Normalizer = Rc<dyn Fn(&str) -> Cow<'_, str>>;
+  type Validator = Rc<dyn Fn(&str) -> Result<(), ValidationError>>;
+  type RemovalHook = Box<dyn FnMut(&InternedStr, RemovalCause)>;
+  type InsertHook = Box<dyn FnMut(&InternedStr)>;
+  
+  fn normalize<'s>(normalizer: &Self::Normalizer, string: &'s str) -> Cow<'s, str> {
+    normalizer(string)
+  }
+  
+  fn validate(validator: &Self::Validator, string: &str) -> Result<(), ValidationError> {
+    validator(string)
+  }
+  
+  fn strong_count(string: &InternedStr) -> usize {
+    Rc::strong_count(string)
+  }
+  
+}
+
+/**
+ * The error returned by a validator to reject a string. (See [`Interner::with_validator`].)
+ */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidationError {
+  
+  reason: Cow<'static, str>
+  
+}
+
+impl ValidationError {
+  
+  /**
+   * Constructs a new `ValidationError` with the given reason, which is used as its [`Display`] output.
+   */
+  pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+    Self { reason: reason.into() }
+  }
+  
+  /**
+   * Returns the reason that the string was rejected.
+   */
+  pub fn reason(&self) -> &str {
+    &self.reason
+  }
+  
+}
+
+impl Display for ValidationError {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.write_str(&self.reason)
+  }
+  
+}
+
+impl Error for ValidationError {}
+
+/**
+ * The error returned by [`try_intern`](Interner::try_intern) when a string is rejected.
+ */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TryInternError {
+  
+  /**
+   * The string was longer than the `Interner`'s maximum length. (See [`Interner::with_max_len`].)
+   */
+  TooLong {
+    
+    /**
+     * The length of the string, in bytes.
+     */
+    len: usize,
+    
+    /**
+     * The maximum length, in bytes.
+     */
+    max: usize
+    
+  },
+  
+  /**
+   * The string was rejected by the `Interner`'s validator. (See [`Interner::with_validator`].)
+   */
+  Invalid(ValidationError)
+  
+}
+
+impl Display for TryInternError {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      TryInternError::TooLong { len, max } => write!(f, "string is {len} bytes long, but the maximum is {max}"),
+      TryInternError::Invalid(error) => Display::fmt(error, f)
+    }
+  }
+  
+}
+
+impl Error for TryInternError {
+  
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      TryInternError::TooLong { .. } => None,
+      TryInternError::Invalid(error) => Some(error)
+    }
+  }
+  
+}
+
+impl From<ValidationError> for TryInternError {
+  
+  fn from(error: ValidationError) -> Self {
+    TryInternError::Invalid(error)
+  }
+  
+}
+
+/**
+ * What was removed by [`gc`](Interner::gc).
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct GcReport {
+  
+  /**
+   * How many strings were removed.
+   */
+  pub entries: usize,
+  
+  /**
+   * How many bytes the removed strings contained (not counting the reference counts stored alongside them).
+   */
+  pub bytes: usize
+  
+}
+
+/**
+ * Why a string was removed from an [`Interner`], as reported to its removal hook. (See [`set_removal_hook`](Interner::set_removal_hook).)
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RemovalCause {
+  
+  /**
+   * The string was removed on its own, e.g., by [`remove`](Interner::remove).
+   */
+  Explicit,
+  
+  /**
+   * The string was removed by [`retain`](Interner::retain) or [`extract_if`](Interner::extract_if).
+   */
+  Retain,
+  
+  /**
+   * The string was removed by [`gc`](Interner::gc), either explicitly or automatically.
+   */
+  Gc,
+  
+  /**
+   * The string was evicted to make room for another string. (See [`with_max_entries`](Interner::with_max_entries).)
+   */
+  Evicted,
+  
+  /**
+   * The string was removed along with all of the others, by [`clear`](Interner::clear) or [`drain`](Interner::drain).
+   */
+  Cleared
+  
+}
+
+/**
+ * An estimate of how much memory an [`Interner`] uses, from [`memory_usage`](Interner::memory_usage).
+ * 
+ * This does not account for any padding that the allocator adds, or for the internal nodes of the structure that keeps track of
+ * the order strings were used in (see [`with_max_entries`](Interner::with_max_entries)).
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct MemoryUsage {
+  
+  /**
+   * The total length of the interned strings, which is the same as [`interned_bytes`](Interner::interned_bytes).
+   */
+  pub strings: usize,
+  
+  /**
+   * The reference counts stored alongside the interned strings.
+   */
+  pub headers: usize,
+  
+  /**
+   * The hash table that the strings are stored in, along with anything else that is needed to look them up or evict them.
+   */
+  pub table: usize
+  
+}
+
+impl MemoryUsage {
+  
+  /**
+   * Returns the sum of all of the parts of this estimate.
+   */
+  pub fn total(&self) -> usize {
+    self.strings + self.headers + self.table
+  }
+  
+}
+
+/**
+ * How well an [`Interner`] has been deduplicating strings, from [`stats`](Interner::stats).
+ * 
+ * Only methods which intern strings (e.g., [`intern`](Interner::intern)) are counted, not ones which just look them up (e.g., [`get`](Interner::get)).
+ * A string which is rejected before it is looked up (e.g., because it is longer than the maximum length) is not counted at all.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct InternStats {
+  
+  /**
+   * How many times a string was interned which had already been saved.
+   */
+  pub hits: usize,
+  
+  /**
+   * How many times a string was interned which had not already been saved.
+   */
+  pub misses: usize,
+  
+  /**
+   * How many strings were newly saved.
+   * 
+   * This is usually the same as `misses`, but strings can be rejected (see [`with_validator`](Interner::with_validator))
+   * or be too long to save at all (see [`with_max_bytes`](Interner::with_max_bytes)).
+   */
+  pub inserts: usize,
+  
+  /**
+   * The total length of the strings that were hits, which is roughly how many bytes would have been allocated without interning.
+   */
+  pub bytes_saved_estimate: usize
+  
+}
+
+/**
+ * Statistics about the lengths of the strings in an [`Interner`], from [`len_stats`](Interner::len_stats).
+ * 
+ * Its [`Display`] implementation shows all of the statistics along with the histogram as a table.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct LenStats {
+  
+  /**
+   * How many strings there are.
+   */
+  pub count: usize,
+  
+  /**
+   * The length of the shortest string, or 0 if there are no strings.
+   */
+  pub min: usize,
+  
+  /**
+   * The length of the longest string, or 0 if there are no strings.
+   */
+  pub max: usize,
+  
+  /**
+   * The total length of the strings.
+   */
+  pub total: usize,
+  
+  /**
+   * How many strings there are of each length, grouped by powers of 2.
+   * 
+   * `histogram[0]` counts the empty strings, and `histogram[i]` counts the strings whose lengths are in <code>2<sup>i - 1</sup>..2<sup>i</sup></code>
+   * (see [`bucket_range`](Self::bucket_range)).
+   * It only goes up to the group of the longest string, so it is empty if there are no strings.
+   */
+  pub histogram: Vec<usize>
+  
+}
+
+impl LenStats {
+  
+  fn new<'a>(strings: impl Iterator<Item = &'a str>) -> Self {
+    let mut stats = LenStats { min: usize::MAX, ..LenStats::default() };
+    for string in strings {
+      let len = string.len();
+      stats.count += 1;
+      stats.min = stats.min.min(len);
+      stats.max = stats.max.max(len);
+      stats.total += len;
+      let bucket = (usize::BITS - len.leading_zeros()) as usize;
+      if stats.histogram.len() <= bucket {
+        stats.histogram.resize(bucket + 1, 0);
+      }
+      stats.histogram[bucket] += 1;
+    }
+    if stats.count == 0 {
+      stats.min = 0;
+    }
+    stats
+  }
+  
+  /**
+   * Returns the average length of the strings, or 0 if there are no strings.
+   */
+  pub fn mean(&self) -> f64 {
+    if self.count == 0 {
+      0.0
+    } else {
+      self.total as f64 / self.count as f64
+    }
+  }
+  
+  /**
+   * Returns the lengths that are counted by `histogram[bucket]`.
+   */
+  pub fn bucket_range(bucket: usize) -> RangeInclusive<usize> {
+    match bucket {
+      0 => 0..=0,
+      _ => {
+        let start = 1 << (bucket - 1);
+        start..=start + (start - 1)
+      }
+    }
+  }
+  
+}
+
+impl Display for LenStats {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    writeln!(f, "count: {}, total: {}, min: {}, max: {}, mean: {:.2}", self.count, self.total, self.min, self.max, self.mean())?;
+    write!(f, "{:>24} | count", "length")?;
+    for (bucket, &count) in self.histogram.iter().enumerate() {
+      let range = Self::bucket_range(bucket);
+      let range = if range.start() == range.end() { range.start().to_string() } else { format!("{}..={}", range.start(), range.end()) };
+      write!(f, "\n{range:>24} | {count}")?;
+    }
+    Ok(())
+  }
+  
+}
+
+/**
+ * A [`BuildHasher`] with a fixed seed, which (unlike [`RandomState`]) can be constructed in a `const` context.
+ * 
+ * This makes it possible to have a [`sync::Interner`] as a plain `static`, without lazy initialization (see [`sync::Interner::with_hasher`]).
+ * Every hasher it builds is the standard library's [`DefaultHasher`], seeded by first hashing the seed,
+ * so two `FixedState`s with the same seed always hash strings the same way, even in different runs of the program.
+ * 
+ * # HashDoS
+ * Because the seed is fixed, anyone who knows it (or who can guess it, e.g., because it is the default seed of 0)
+ * can choose many strings with colliding hashes, which makes looking them up (and holding the [`sync::Interner`]'s lock while doing so) much slower.
+ * If an interner might intern strings from untrusted sources, it should use [`RandomState`] instead,
+ * or at least a seed which is hard to predict (e.g., one generated at build time).
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct FixedState {
+  
+  seed: u64
+  
+}
+
+impl FixedState {
+  
+  /**
+   * Constructs a new `FixedState` with the default seed of 0.
+   */
+  pub const fn new() -> Self {
+    Self::with_seed(0)
+  }
+  
+  /**
+   * Constructs a new `FixedState` with the given seed.
+   */
+  pub const fn with_seed(seed: u64) -> Self {
+    Self { seed }
+  }
+  
+  /**
+   * Returns the seed of this `FixedState`.
+   */
+  pub const fn seed(&self) -> u64 {
+    self.seed
+  }
+  
+}
+
+impl BuildHasher for FixedState {
+  
+  type Hasher = DefaultHasher;
+  
+  fn build_hasher(&self) -> DefaultHasher {
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u64(self.seed);
+    hasher
+  }
+  
+}
+
+/**
+ * An "extension trait" to add the [`intern_in`](InternInExt::intern_in) and [`intern_in_local`](InternInExt::intern_in_local) methods to [`str`](prim@str),
+ * which effectively adds them to all types that directly or transitively implement [`Deref<Target = str>`](std::ops::Deref),
+ * e.g., [`String`] and [`Box<str>`].
+ * 
+ * This is like [`sync::InternExt`] (which is only available with the `global` feature), except that it interns strings into the given interner,
+ * rather than the [`GlobalInterner`](sync::GlobalInterner). For example:
+ * ```rust
+ * # use str_intern::{Interner, InternedStr, InternInExt};
+ * let mut interner = Interner::new();
+ * let foo0 = "foo".intern_in_local(&mut interner);
+ * let foo1 = String::from("foo").intern_in_local(&mut interner);
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * ```
+ */
+pub trait InternInExt: AsRef<str> {
+  
+  /**
+   * Equivalent to `interner.intern(self)`.
+   * 
+   * See [`sync::Interner::intern`].
+   */
+  #[inline]
+  fn intern_in<S: BuildHasher>(&self, interner: &sync::Interner<S>) -> sync::InternedStr {
+    interner.intern(self)
+  }
+  
+  /**
+   * Equivalent to `interner.intern(self)`.
+   * 
+   * See [`Interner::intern`].
+   */
+  #[inline]
+  fn intern_in_local<S: BuildHasher>(&self, interner: &mut Interner<S>) -> InternedStr {
+    interner.intern(self)
+  }
+  
+}
+
+impl InternInExt for str {}
+
+impl IntoInterned for &str {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for String {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for Box<str> {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for Cow<'_, str> {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for InternedStr {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    self
+  }
+  
+}
+
+/**
+ * A view into a single string in an `Interner`, which may or may not have been saved.
+ * 
+ * This `enum` is created by the [`entry`](Interner::entry) method on [`Interner`].
+ */
+pub enum Entry<'a, S = RandomState> {
+  
+  /**
+   * The string has already been saved.
+   */
+  Occupied(OccupiedEntry<'a, S>),
+  
+  /**
+   * The string has not been saved yet.
+   */
+  Vacant(VacantEntry<'a, S>)
+  
+}
+
+impl<'a, S: BuildHasher> Entry<'a, S> {
+  
+  fn new(table: &'a mut Table<Local, S>, key: &'a str) -> Self {
+    match table.get(key).cloned() {
+      Some(string) => {
+        table.hit(&string);
+        Entry::Occupied(OccupiedEntry::new(table, string))
+      }
+      None => Entry::Vacant(VacantEntry::new(table, key))
+    }
+  }
+  
+  /**
+   * Returns the string that this entry was created with.
+   */
+  pub fn key(&self) -> &str {
+    match self {
+      Entry::Occupied(entry) => entry.key(),
+      Entry::Vacant(entry) => entry.key()
+    }
+  }
+  
+  /**
+   * Saves a copy of the key if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn or_insert(self) -> InternedStr {
+    match self {
+      Entry::Occupied(entry) => entry.get(),
+      Entry::Vacant(entry) => {
+        let key = entry.key;
+        entry.insert(key)
+      }
+    }
+  }
+  
+  /**
+   * Saves the result of `f` if the key is not already saved, and returns a reference to the saved allocation.
+   * `f` is only called if the key is not already saved.
+   * 
+   * # Panics
+   * This method panics if `f` returns a string with different contents than the key.
+   */
+  pub fn or_insert_with<T: Into<InternedStr>, F: FnOnce() -> T>(self, f: F) -> InternedStr {
+    match self {
+      Entry::Occupied(entry) => entry.get(),
+      Entry::Vacant(entry) => entry.insert(f())
+    }
+  }
+  
+}
+
+impl<'a, S> Debug for Entry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+      Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish()
+    }
+  }
+  
+}
+
+/**
+ * A view into a string in an `Interner` that has already been saved. It is part of the [`Entry`] `enum`.
+ */
+pub struct OccupiedEntry<'a, S = RandomState> {
+  
+  table: &'a mut Table<Local, S>,
+  string: InternedStr
+  
+}
+
+impl<'a, S> OccupiedEntry<'a, S> {
+  
+  fn new(table: &'a mut Table<Local, S>, string: InternedStr) -> Self {
+    Self { table, string }
+  }
+  
+  /**
+   * Returns the saved string.
+   */
+  pub fn key(&self) -> &str {
+    &self.string
+  }
+  
+  /**
+   * Returns a reference to the saved allocation.
+   */
+  pub fn get(&self) -> InternedStr {
+    self.string.clone()
+  }
+  
+}
+
+impl<'a, S: BuildHasher> OccupiedEntry<'a, S> {
+  
+  /**
+   * Removes the saved string, and returns the saved allocation.
+   */
+  pub fn remove(self) -> InternedStr {
+    self.table.remove(&self.string);
+    self.string
+  }
+  
+}
+
+impl<'a, S> Debug for OccupiedEntry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OccupiedEntry").field(&self.string).finish()
+  }
+  
+}
+
+/**
+ * A view into a string in an `Interner` that has not been saved yet. It is part of the [`Entry`] `enum`.
+ */
+pub struct VacantEntry<'a, S = RandomState> {
+  
+  table: &'a mut Table<Local, S>,
+  key: &'a str
+  
+}
+
+impl<'a, S> VacantEntry<'a, S> {
+  
+  fn new(table: &'a mut Table<Local, S>, key: &'a str) -> Self {
+    Self { table, key }
+  }
+  
+  /**
+   * Returns the string that this entry was created with.
+   */
+  pub fn key(&self) -> &str {
+    self.key
+  }
+  
+}
+
+impl<'a, S: BuildHasher> VacantEntry<'a, S> {
+  
+  /**
+   * Saves the given string, and returns a reference to the saved allocation.
+   * 
+   * # Panics
+   * This method panics if the given string has different contents than the key.
+   */
+  pub fn insert(self, string: impl Into<InternedStr>) -> InternedStr {
+    let string = string.into();
+    assert_eq!(&*string, self.key, "interned string must match its entry's key");
+    // The entry already looked the key up, so don't look it up again.
+    self.table.intern_vacant(string).0
+  }
+  
+}
+
+impl<'a, S> Debug for VacantEntry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("VacantEntry").field(&self.key).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an `Interner`.
+ * 
+ * This `struct` is created by the [`iter`](Interner::iter) method on `Interner`.
+ */
+#[repr(transparent)]
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, InternedStr>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item =  &'a InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.ge(other)
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+/**
+ * An iterator over the strings in an [`Interner`], in lexicographic order.
+ * 
+ * This `struct` is created by the [`iter_sorted`](Interner::iter_sorted) method on [`Interner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct IterSorted<'a> {
+  
+  iter: VecIntoIter<&'a InternedStr>
+  
+}
+
+impl<'a> IterSorted<'a> {
+  
+  fn new(mut strings: Vec<&'a InternedStr>) -> Self {
+    // Equal strings are never saved twice, so there's no order among them to preserve.
+    strings.sort_unstable();
+    Self { iter: strings.into_iter() }
+  }
+  
+}
+
+impl<'a> Iterator for IterSorted<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> DoubleEndedIterator for IterSorted<'a> {
+  
+  fn next_back(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for IterSorted<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for IterSorted<'a> {}
+
+impl<'a> Debug for IterSorted<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.iter.as_slice()).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an [`Interner`] which start with a given prefix.
+ * 
+ * This `struct` is created by the [`iter_prefix`](Interner::iter_prefix) method on [`Interner`].
+ */
+#[derive(Clone)]
+pub struct IterPrefix<'a> {
+  
+  iter: SetIter<'a, InternedStr>,
+  prefix: &'a str
+  
+}
+
+impl<'a> IterPrefix<'a> {
+  
+  fn new(iter: SetIter<'a, InternedStr>, prefix: &'a str) -> Self {
+    Self { iter, prefix }
+  }
+  
+}
+
+impl<'a> Iterator for IterPrefix<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    let prefix = self.prefix;
+    self.iter.find(|string| string.starts_with(prefix))
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.iter.size_hint().1)
+  }
+  
+}
+
+impl<'a> FusedIterator for IterPrefix<'a> {}
+
+impl<'a> Debug for IterPrefix<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
+
+/**
+ * An owning iterator over the strings that were in an `Interner`.
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`]
+ * (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct IntoIter {
+  
+  iter: SetIntoIter<InternedStr>
+  
+}
+
+impl IntoIter {
+  
+  fn new(iter: SetIntoIter<InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl Iterator for IntoIter {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.ge(other)
+  }
+  
+}
+
+impl ExactSizeIterator for IntoIter {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl FusedIterator for IntoIter {}
+
+/**
+ * A draining iterator over the strings that were in an `Interner`.
+ * 
+ * This `struct` is created by the [`drain`](Interner::drain) method on [`Interner`].
+ * When it is dropped, any remaining strings are removed, even if it was not fully consumed.
+ */
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct Drain<'a> {
+  
+  iter: SetDrain<'a, InternedStr>
+  
+}
+
+impl<'a> Drain<'a> {
+  
+  fn new(iter: SetDrain<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Drain<'a> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
     self.iter.ge(other)
   }
   
 }
 
-impl<'a> ExactSizeIterator for Iter<'a> {
+impl<'a> ExactSizeIterator for Drain<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Drain<'a> {}
+
+/**
+ * An iterator which saves each substring of a string in an `Interner`, and yields references to the saved allocations.
+ * 
+ * This `struct` is created by the [`intern_split`](Interner::intern_split) method on [`Interner`].
+ */
+pub struct InternSplit<'a, S = RandomState> {
+  
+  table: &'a mut Table<Local, S>,
+  split: Split<'a, char>
+  
+}
+
+impl<'a, S> InternSplit<'a, S> {
+  
+  fn new(table: &'a mut Table<Local, S>, split: Split<'a, char>) -> Self {
+    Self { table, split }
+  }
+  
+}
+
+impl<'a, S: BuildHasher> Iterator for InternSplit<'a, S> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.split.next().map(|string| Entry::new(self.table, string).or_insert())
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.split.size_hint()
+  }
+  
+}
+
+impl<'a, S: BuildHasher> FusedIterator for InternSplit<'a, S> {}
+
+impl<'a, S> Debug for InternSplit<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("InternSplit").field(&self.split).finish()
+  }
+  
+}
+
+/**
+ * An iterator which removes and yields the strings in an `Interner` that match a predicate.
+ * 
+ * This `struct` is created by the [`extract_if`](Interner::extract_if) method on [`Interner`].
+ */
+#[repr(transparent)]
+pub struct ExtractIf<'a, F: FnMut(&InternedStr) -> bool> {
+  
+  iter: table::ExtractIf<'a, Local, F>
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> ExtractIf<'a, F> {
+  
+  fn new(iter: table::ExtractIf<'a, Local, F>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> Iterator for ExtractIf<'a, F> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> FusedIterator for ExtractIf<'a, F> {}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> Debug for ExtractIf<'a, F> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("ExtractIf").field(&self.iter).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an `Interner`, along with how many times each of them is referenced outside of the `Interner`.
+ * 
+ * This `struct` is created by the [`ref_counts`](Interner::ref_counts) and [`leaked`](Interner::leaked) methods on [`Interner`].
+ * Its [`Debug`] implementation shows each string and its count, so it can be printed as is to look for leaks.
+ */
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RefCounts<'a> {
+  
+  iter: table::RefCounts<'a, Local>
+  
+}
+
+impl<'a> RefCounts<'a> {
+  
+  fn new(iter: table::RefCounts<'a, Local>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for RefCounts<'a> {
+  
+  type Item = (&'a InternedStr, usize);
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> FusedIterator for RefCounts<'a> {}
+
+impl<'a> Debug for RefCounts<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_map().entries(self.clone()).finish()
+  }
+  
+}
+
+/**
+ * An interner which does not keep strings alive by itself.
+ * 
+ * Like an [`Interner`], a `WeakInterner` ensures there is only one allocation for any given string contents,
+ * but it only holds weak references to its strings, so a string is freed as soon as every reference to it outside of the `WeakInterner` is dropped.
+ * Interning the same contents after that creates a new allocation.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::{WeakInterner, InternedStr};
+ * let mut interner = WeakInterner::new();
+ * let foo0 = interner.intern("foo");
+ * let foo1 = interner.intern("foo");
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * drop((foo0, foo1));
+ * assert!(!interner.contains("foo"));
+ * ```
+ * 
+ * Entries for strings that have been freed are removed whenever a string with the same hash is interned, or all at once by [`purge`](Self::purge),
+ * which also happens automatically whenever interning a string doubles the number of entries since the last time they were all purged.
+ * Until then, each of them still takes up a little memory: the reference counts of an [`InternedStr`] share an allocation with its contents,
+ * so that allocation is only released when its entry is removed.
+ */
+pub struct WeakInterner<S = RandomState> {
+  
+  table: WeakTable<InternedStr, S>
+  
+}
+
+impl WeakInterner {
+  
+  /**
+   * Constructs a new `WeakInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+}
+
+impl<S> WeakInterner<S> {
+  
+  /**
+   * Constructs a new `WeakInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { table: WeakTable::new(hasher) }
+  }
+  
+  /**
+   * Returns the number of interned strings which have not been freed.
+   * 
+   * This has to check every entry, so it takes time proportional to the number of entries, including any that have not been purged yet.
+   */
+  pub fn len(&self) -> usize {
+    self.table.len()
+  }
+  
+  /**
+   * Returns whether every interned string has been freed.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.is_empty()
+  }
+  
+  /**
+   * Returns the number of entries, including the ones for strings which have been freed but not purged yet (see [`purge`](Self::purge)).
+   * 
+   * Unlike [`len`](Self::len), this doesn't need to check every entry.
+   */
+  pub fn entries(&self) -> usize {
+    self.table.entries()
+  }
+  
+  /**
+   * Removes all of the entries, whether or not their strings have been freed.
+   */
+  pub fn clear(&mut self) {
+    self.table.clear()
+  }
+  
+  /**
+   * Removes the entries for every string that has been freed, and returns how many were removed.
+   */
+  pub fn purge(&mut self) -> usize {
+    self.table.purge()
+  }
+  
+}
+
+impl<S: BuildHasher> WeakInterner<S> {
+  
+  /**
+   * Returns a reference to the saved allocation for the given string if it has not been freed,
+   * or saves the given string in a new allocation and returns a reference to that otherwise.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Returns whether the given string has been saved, and has not been freed.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.get(string.as_ref()).is_some()
+  }
+  
+  /**
+   * If the given string has been saved, and has not been freed, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.get(string.as_ref())
+  }
+  
+}
+
+impl<S> Debug for WeakInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("WeakInterner").field(&self.table.live().collect::<Vec<_>>()).finish()
+  }
+  
+}
+
+impl<S: Default> Default for WeakInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+/**
+ * An interner which remembers the order its strings were first interned in.
+ * 
+ * Like an [`Interner`], an `OrderedInterner` ensures there is only one allocation for any given string contents,
+ * but [`iter`](Self::iter) always yields its strings in the order they were first interned, and each string can be looked up by its position in that order.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::OrderedInterner;
+ * let mut interner = OrderedInterner::new();
+ * for string in ["foo", "bar", "foo", "baz"] {
+ *   interner.intern(string);
+ * }
+ * assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), ["foo", "bar", "baz"]);
+ * assert_eq!(interner.index_of("bar"), Some(1));
+ * assert_eq!(interner.get_index(2).map(|string| &**string), Some("baz"));
+ * ```
+ * 
+ * There are two ways to remove a string, which treat the order of the remaining strings differently:
+ * [`swap_remove`](Self::swap_remove) moves the last string into the removed one's position, which is fast but changes the order,
+ * while [`shift_remove`](Self::shift_remove) moves every later string back by one position, which keeps the order but takes time proportional to the number of later strings.
+ */
+pub struct OrderedInterner<S = RandomState> {
+  
+  table: OrderedTable<InternedStr, S>
+  
+}
+
+impl OrderedInterner {
+  
+  /**
+   * Constructs a new `OrderedInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> OrderedInterner<S> {
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::with_capacity_and_hasher(0, hasher)
+  }
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { table: OrderedTable::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.table.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.table.clear()
+  }
+  
+  /**
+   * Returns the interned string at the given position, if there is one.
+   */
+  pub fn get_index(&self, index: usize) -> Option<&InternedStr> {
+    self.table.get_index(index)
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings, in the order they were first interned.
+   */
+  pub fn iter(&self) -> OrderedIter<'_> {
+    OrderedIter::new(self.table.strings.iter())
+  }
+  
+}
+
+impl<S: BuildHasher> OrderedInterner<S> {
+  
+  /**
+   * Saves the given string if it is not already saved (after all of the strings that are), and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.index_of(string.as_ref()).is_some()
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Returns the position of the given string, if it has been saved.
+   */
+  pub fn index_of(&self, string: impl AsRef<str>) -> Option<usize> {
+    self.table.index_of(string.as_ref())
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, by moving the last string into its position, and returns the saved allocation.
+   * 
+   * This takes constant time, but changes the position of the last string. (See [`shift_remove`](Self::shift_remove).)
+   */
+  pub fn swap_remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.swap_remove(string.as_ref())
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, by moving every later string back by one position, and returns the saved allocation.
+   * 
+   * This keeps the rest of the strings in order, but takes time proportional to the number of later strings. (See [`swap_remove`](Self::swap_remove).)
+   */
+  pub fn shift_remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.shift_remove(string.as_ref())
+  }
+  
+}
+
+impl<S: Clone> Clone for OrderedInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { table: self.table.clone() }
+  }
+  
+}
+
+impl<S> Debug for OrderedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OrderedInterner").field(&self.table.strings).finish()
+  }
+  
+}
+
+impl<S: Default> Default for OrderedInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<'a, S> IntoIterator for &'a OrderedInterner<S> {
+  
+  type Item = &'a InternedStr;
+  type IntoIter = OrderedIter<'a>;
+  
+  fn into_iter(self) -> OrderedIter<'a> {
+    self.iter()
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for OrderedInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Default> FromIterator<T> for OrderedInterner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an [`OrderedInterner`], in the order they were first interned.
+ * 
+ * This `struct` is created by the [`iter`](OrderedInterner::iter) method on [`OrderedInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct OrderedIter<'a> {
+  
+  iter: SliceIter<'a, InternedStr>
+  
+}
+
+impl<'a> OrderedIter<'a> {
+  
+  fn new(iter: SliceIter<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for OrderedIter<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> DoubleEndedIterator for OrderedIter<'a> {
+  
+  fn next_back(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for OrderedIter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for OrderedIter<'a> {}
+
+impl<'a> Debug for OrderedIter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * An interner which keeps its strings sorted, so that they can be queried by range.
+ * 
+ * Like an [`Interner`], an `OrdInterner` ensures there is only one allocation for any given string contents,
+ * but it keeps its strings in a [`BTreeSet`](std::collections::BTreeSet) rather than a [`HashSet`], so it does not need a hasher,
+ * [`iter`](Self::iter) always yields its strings in lexicographic order, and [`range`](Self::range) can yield just the strings between two bounds.
+ * Looking up a string takes time proportional to the logarithm of the number of strings, rather than constant time.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::OrdInterner;
+ * let mut interner = OrdInterner::new();
+ * for string in ["user:1001:name", "user:1000:name", "user:1000:email", "group:7:name"] {
+ *   interner.intern(string);
+ * }
+ * let user = interner.range("user:1000:".."user:1001:").map(|string| &**string).collect::<Vec<_>>();
+ * assert_eq!(user, ["user:1000:email", "user:1000:name"]);
+ * assert_eq!(interner.first().map(|string| &**string), Some("group:7:name"));
+ * ```
+ */
+#[derive(Clone, Default)]
+#[repr(transparent)]
+pub struct OrdInterner {
+  
+  table: OrdTable<InternedStr>
+  
+}
+
+impl OrdInterner {
+  
+  /**
+   * Constructs a new `OrdInterner`.
+   */
+  pub fn new() -> Self {
+    Self { table: OrdTable::default() }
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.table.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.table.strings.clear()
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.strings.contains(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.strings.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.strings.take(string.as_ref())
+  }
+  
+  /**
+   * Returns the lexicographically first interned string, if there is one.
+   */
+  pub fn first(&self) -> Option<&InternedStr> {
+    self.table.strings.first()
+  }
+  
+  /**
+   * Returns the lexicographically last interned string, if there is one.
+   */
+  pub fn last(&self) -> Option<&InternedStr> {
+    self.table.strings.last()
+  }
+  
+  /**
+   * An iterator over the interned strings within the given range, in lexicographic order.
+   * 
+   * Any range of `&str`s can be used, e.g., `"a".."b"`, `"a"..="b"`, `"a"..`, or `..`,
+   * as can a pair of [`Bound`](std::ops::Bound)s (e.g., `(Bound::Excluded("a"), Bound::Unbounded)`).
+   * 
+   * # Panics
+   * This method panics if the start of the range is after its end, or if they are equal and both excluded.
+   */
+  pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> OrdIter<'_> {
+    OrdIter::new(self.table.range(range))
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings, in lexicographic order.
+   */
+  pub fn iter(&self) -> OrdIter<'_> {
+    OrdIter::new(self.table.iter())
+  }
+  
+  /**
+   * An iterator over the interned strings which start with the given prefix, in lexicographic order.
+   * 
+   * An empty prefix matches every string, and the prefix itself is included if it has been interned.
+   * Since every string with the same prefix is next to each other, this is a [`range`](Self::range) query, so it never checks any strings that don't match.
+   */
+  pub fn iter_prefix(&self, prefix: &str) -> OrdIter<'_> {
+    OrdIter::new(self.table.prefix(prefix))
+  }
+  
+}
+
+impl Debug for OrdInterner {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OrdInterner").field(&self.table.strings).finish()
+  }
+  
+}
+
+impl<'a> IntoIterator for &'a OrdInterner {
+  
+  type Item = &'a InternedStr;
+  type IntoIter = OrdIter<'a>;
+  
+  fn into_iter(self) -> OrdIter<'a> {
+    self.iter()
+  }
+  
+}
+
+impl<T: AsRef<str>> Extend<T> for OrdInterner {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+impl<T: AsRef<str>> FromIterator<T> for OrdInterner {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::new();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An iterator over some or all of the strings in an [`OrdInterner`], in lexicographic order.
+ * 
+ * This `struct` is created by the [`iter`](OrdInterner::iter) and [`range`](OrdInterner::range) methods on [`OrdInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct OrdIter<'a> {
+  
+  iter: BTreeIter<'a, InternedStr>
+  
+}
+
+impl<'a> OrdIter<'a> {
+  
+  fn new(iter: BTreeIter<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for OrdIter<'a> {
   
-  fn len(&self) -> usize {
-    self.iter.len()
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
   }
   
 }
 
-impl<'a> FusedIterator for Iter<'a> {}
+impl<'a> DoubleEndedIterator for OrdIter<'a> {
+  
+  fn next_back(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl<'a> FusedIterator for OrdIter<'a> {}
+
+impl<'a> Debug for OrdIter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
 
 /**
- * An owning iterator over the strings that were in an `Interner`.
+ * A shared handle to an [`Interner`], which can intern strings through a shared reference (`&self`).
  * 
- * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`]
- * (provided by the [`IntoIterator`] trait).
+ * Cloning a `SharedInterner` is cheap, and the clone shares the same underlying [`Interner`], so many structs (e.g., in a parser) can each keep one:
+ * ```rust
+ * # use str_intern::{SharedInterner, InternedStr};
+ * let interner = SharedInterner::new();
+ * let clone = interner.clone();
+ * let foo0 = interner.intern("foo");
+ * let foo1 = clone.intern("foo");
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * ```
+ * 
+ * The [`Interner`] is kept in a [`RefCell`], so every method borrows it for its duration.
+ * They only panic if it is already borrowed, which can only happen while a [`borrow`](Self::borrow) or [`borrow_mut`](Self::borrow_mut) is still held,
+ * or if a hook (see [`Interner::set_insert_hook`]) or validator (see [`Interner::with_validator`]) uses the same `SharedInterner` while it is interning a string.
+ * 
+ * Like an [`Interner`], a `SharedInterner` is not thread-safe. For a thread-safe variant, see [`sync::Interner`], which can already be shared through `&self`.
  */
-#[repr(transparent)]
-#[derive(Debug)]
-pub struct IntoIter {
+pub struct SharedInterner<S = RandomState> {
   
-  iter: SetIntoIter<InternedStr>
+  interner: Rc<RefCell<Interner<S>>>
   
 }
 
-impl IntoIter {
+impl SharedInterner {
   
-  fn new(iter: SetIntoIter<InternedStr>) -> Self {
-    Self { iter }
+  /**
+   * Constructs a new `SharedInterner`, with a new [`Interner`].
+   */
+  pub fn new() -> Self {
+    Self::from_interner(Interner::new())
   }
   
 }
 
-impl Iterator for IntoIter {
+impl<S> SharedInterner<S> {
   
-  type Item = InternedStr;
+  /**
+   * Constructs a new `SharedInterner` which shares the given [`Interner`].
+   */
+  pub fn from_interner(interner: Interner<S>) -> Self {
+    Self { interner: Rc::new(RefCell::new(interner)) }
+  }
   
-  fn next(&mut self) -> Option<Self::Item> {
-    self.iter.next()
+  /**
+   * Immutably borrows the underlying [`Interner`] until the returned [`Ref`] is dropped, for access to the rest of its methods.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn borrow(&self) -> Ref<'_, Interner<S>> {
+    self.interner.borrow()
   }
   
-  fn size_hint(&self) -> (usize, Option<usize>) {
-    self.iter.size_hint()
+  /**
+   * Mutably borrows the underlying [`Interner`] until the returned [`RefMut`] is dropped, for access to the rest of its methods.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently borrowed.
+   */
+  pub fn borrow_mut(&self) -> RefMut<'_, Interner<S>> {
+    self.interner.borrow_mut()
   }
   
-  fn count(self) -> usize {
-    self.iter.count()
+  /**
+   * Returns whether both `SharedInterner`s share the same underlying [`Interner`].
+   */
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.interner, &other.interner)
   }
   
-  fn last(self) -> Option<Self::Item> {
-    self.iter.last()
+  /**
+   * Returns the number of strings that are currently interned.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn len(&self) -> usize {
+    self.borrow().len()
   }
   
-  fn nth(&mut self, n: usize) -> Option<Self::Item> {
-    self.iter.nth(n)
+  /**
+   * Returns whether no strings are currently interned.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.borrow().is_empty()
   }
   
-  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
-    self.iter.for_each(f)
+  /**
+   * Removes all of the interned strings.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently borrowed.
+   */
+  pub fn clear(&self) {
+    self.borrow_mut().clear()
   }
   
-  fn collect<B: FromIterator<Self::Item>>(self) -> B {
-    self.iter.collect()
+  /**
+   * Returns all of the currently interned strings, in an arbitrary order.
+   * 
+   * Unlike [`Interner::iter`], this does not keep the [`Interner`] borrowed, so it can still be used while going through the strings.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn iter_snapshot(&self) -> Vec<InternedStr> {
+    self.borrow().iter().cloned().collect()
   }
   
-  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
-    self.iter.partition(f)
+}
+
+impl<S: BuildHasher> SharedInterner<S> {
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * `interner.intern(string)` is equivalent to `interner.borrow_mut().intern(string)`.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, or if the [`Interner`] is currently borrowed.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.borrow_mut().intern(string)
   }
   
-  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
-    self.iter.fold(init, f)
+  /**
+   * Returns whether the given string has been saved.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.borrow().contains(string)
   }
   
-  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
-    self.iter.reduce(f)
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   * 
+   * # Panics
+   * This method panics if the [`Interner`] is currently mutably borrowed.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.borrow().get(string)
   }
   
-  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.all(f)
+}
+
+impl<S> Clone for SharedInterner<S> {
+  
+  /**
+   * Returns a new handle to the same underlying [`Interner`].
+   */
+  fn clone(&self) -> Self {
+    Self { interner: Rc::clone(&self.interner) }
   }
   
-  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.any(f)
+}
+
+impl<S> Debug for SharedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("SharedInterner").field(&self.interner).finish()
   }
   
-  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
-    self.iter.find(predicate)
+}
+
+impl<S: Default> Default for SharedInterner<S> {
+  
+  fn default() -> Self {
+    Self::from_interner(Interner::default())
   }
   
-  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
-    self.iter.find_map(f)
+}
+
+impl<S> From<Interner<S>> for SharedInterner<S> {
+  
+  fn from(interner: Interner<S>) -> Self {
+    Self::from_interner(interner)
   }
   
-  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
-    self.iter.position(predicate)
+}
+
+/**
+ * A short-lived interner in front of a long-lived parent [`Interner`], which saves new strings locally rather than in the parent.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::{Interner, ScopedInterner, InternedStr};
+ * let mut parent = Interner::new();
+ * let id = parent.intern("id");
+ * let mut scoped = ScopedInterner::new(&parent);
+ * assert!(InternedStr::ptr_eq(&scoped.intern("id"), &id));
+ * let local = scoped.intern("some request-local string");
+ * assert!(InternedStr::ptr_eq(&scoped.intern("some request-local string"), &local));
+ * drop(scoped);
+ * assert!(!parent.contains("some request-local string"));
+ * ```
+ * 
+ * [`intern`](Self::intern) first checks the parent, and only saves the string locally if the parent does not have it,
+ * so strings from the parent are shared, while the local strings are freed when the `ScopedInterner` is dropped (unless they are still referenced elsewhere).
+ * The parent is borrowed for as long as the `ScopedInterner` exists, so it cannot change in the meantime, and no string is ever saved in both.
+ * 
+ * The local strings are saved in an ordinary [`Interner`], which does not share the parent's configuration (e.g., [`with_validator`](Interner::with_validator)).
+ */
+pub struct ScopedInterner<'a, S = RandomState> {
+  
+  parent: &'a Interner<S>,
+  local: Interner<S>
+  
+}
+
+impl<'a, S: Default> ScopedInterner<'a, S> {
+  
+  /**
+   * Constructs a new `ScopedInterner` in front of the given parent, with no local strings.
+   */
+  pub fn new(parent: &'a Interner<S>) -> Self {
+    Self::with_hasher(parent, S::default())
   }
   
-  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.max()
+}
+
+impl<'a, S> ScopedInterner<'a, S> {
+  
+  /**
+   * Constructs a new `ScopedInterner` in front of the given parent, with no local strings, which uses the given hasher for them.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(parent: &'a Interner<S>, hasher: S) -> Self {
+    Self { parent, local: Interner::with_hasher(hasher) }
   }
   
-  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.min()
+  /**
+   * Returns the parent [`Interner`].
+   */
+  pub fn parent(&self) -> &'a Interner<S> {
+    self.parent
   }
   
-  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.max_by_key(f)
+  /**
+   * Returns the [`Interner`] which contains the local strings.
+   */
+  pub fn local(&self) -> &Interner<S> {
+    &self.local
   }
   
-  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.max_by(compare)
+  /**
+   * Consume this `ScopedInterner` and return the [`Interner`] which contains the local strings.
+   */
+  pub fn into_local(self) -> Interner<S> {
+    self.local
   }
   
-  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.min_by_key(f)
+  /**
+   * Returns the number of strings that are interned, including the parent's.
+   */
+  pub fn len(&self) -> usize {
+    self.parent.len() + self.local.len()
   }
   
-  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.min_by(compare)
+  /**
+   * Returns whether no strings are interned, including the parent's.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.parent.is_empty() && self.local.is_empty()
   }
   
-  fn sum<S: Sum<Self::Item>>(self) -> S {
-    self.iter.sum()
+  /**
+   * Removes all of the local strings. The parent's strings are unaffected.
+   */
+  pub fn clear_local(&mut self) {
+    self.local.clear()
   }
   
-  fn product<P: Product<Self::Item>>(self) -> P {
-    self.iter.product()
+  /**
+   * An iterator over all of the interned strings, including the parent's, followed by the local ones.
+   */
+  pub fn iter(&self) -> ScopedIter<'_> {
+    ScopedIter::new(self.parent.iter().chain(self.local.iter()))
   }
   
-  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
-    self.iter.cmp(other)
+  /**
+   * An iterator over only the local strings.
+   */
+  pub fn iter_local(&self) -> Iter<'_> {
+    self.local.iter()
   }
   
-  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
-    self.iter.partial_cmp(other)
+}
+
+impl<'a, S: BuildHasher> ScopedInterner<'a, S> {
+  
+  /**
+   * Returns the parent's saved allocation for the given string, if it has one,
+   * or otherwise saves the given string locally if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    match self.parent.get(string) {
+      Some(saved) => saved,
+      None => self.local.intern(string)
+    }
   }
   
-  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.eq(other)
+  /**
+   * Returns whether the given string has been saved, either by the parent or locally.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    let string = string.as_ref();
+    self.parent.contains(string) || self.local.contains(string)
   }
   
-  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.ne(other)
+  /**
+   * If the given string has been saved, either by the parent or locally, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.parent.get(string).or_else(|| self.local.get(string))
   }
   
-  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.lt(other)
+  /**
+   * Returns whether the given string has been saved locally (rather than by the parent).
+   */
+  pub fn contains_local(&self, string: impl AsRef<str>) -> bool {
+    self.local.contains(string)
   }
   
-  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.le(other)
+}
+
+impl<'a, S> Debug for ScopedInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("ScopedInterner").field("parent", &self.parent).field("local", &self.local).finish()
   }
   
-  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.gt(other)
+}
+
+impl<'a, 'b, S> IntoIterator for &'b ScopedInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = ScopedIter<'b>;
+  
+  fn into_iter(self) -> ScopedIter<'b> {
+    self.iter()
   }
   
-  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.ge(other)
+}
+
+impl<'a, T: AsRef<str>, S: BuildHasher> Extend<T> for ScopedInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
   }
   
 }
 
-impl ExactSizeIterator for IntoIter {
+/**
+ * An iterator over the strings in a [`ScopedInterner`], including its parent's.
+ * 
+ * This `struct` is created by the [`iter`](ScopedInterner::iter) method on [`ScopedInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct ScopedIter<'a> {
   
-  fn len(&self) -> usize {
-    self.iter.len()
+  iter: Chain<Iter<'a>, Iter<'a>>
+  
+}
+
+impl<'a> ScopedIter<'a> {
+  
+  fn new(iter: Chain<Iter<'a>, Iter<'a>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for ScopedIter<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
   }
   
 }
 
-impl FusedIterator for IntoIter {}
\ No newline at end of file
+// Both halves know their exact length, so the `Chain` does too.
+impl<'a> ExactSizeIterator for ScopedIter<'a> {}
+
+impl<'a> FusedIterator for ScopedIter<'a> {}
+
+impl<'a> Debug for ScopedIter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
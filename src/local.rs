@@ -0,0 +1,89 @@
+/*!
+ * A thread-local [`Interner`], for code which only ever interns strings on one thread at a time (e.g., a parser per thread).
+ * 
+ * Unlike the [`GlobalInterner`](crate::sync::GlobalInterner), there is no lock to take, not even an uncontended one,
+ * but every thread has its own `Interner`, which is created the first time it is used on that thread, and dropped when that thread exits.
+ * This means that strings interned on different threads are never pointer-equal, even if they have the same contents,
+ * and that the interned strings are [`Rc`](std::rc::Rc)s, which cannot be sent to other threads at all:
+ * ```rust,compile_fail
+ * # use std::thread;
+ * # use str_intern::local;
+ * let foo = local::intern("foo");
+ * thread::spawn(move || println!("{foo}"));
+ * ```
+ * (If strings need to be shared between threads, use the [`sync`](crate::sync) module instead.)
+ * 
+ * For example:
+ * ```rust
+ * # use std::thread;
+ * # use str_intern::{local, InternedStr};
+ * let foo = local::intern("foo");
+ * assert!(InternedStr::ptr_eq(&local::intern(String::from("foo")), &foo));
+ * thread::spawn(|| assert!(!local::contains("foo"))).join().unwrap();
+ * ```
+ */
+
+use std::cell::RefCell;
+
+use crate::{Interner, InternedStr};
+
+thread_local! {
+  
+  static LOCAL: RefCell<Interner> = RefCell::new(Interner::new());
+  
+}
+
+/**
+ * Saves the given string in this thread's [`Interner`] if it is not already saved, and returns the saved string.
+ * 
+ * `local::intern(string)` is equivalent to `local::with(|interner| interner.intern(string))`. (See [`Interner::intern`].)
+ * 
+ * # Panics
+ * This function panics if it is called from within [`with`].
+ */
+pub fn intern(string: impl AsRef<str>) -> InternedStr {
+  with(|interner| interner.intern(string))
+}
+
+/**
+ * Returns whether the given string has been saved in this thread's [`Interner`].
+ * 
+ * # Panics
+ * This function panics if it is called from within [`with`].
+ */
+pub fn contains(string: impl AsRef<str>) -> bool {
+  with(|interner| interner.contains(string))
+}
+
+/**
+ * If the given string has been saved in this thread's [`Interner`], returns the saved string, or `None` otherwise.
+ * 
+ * # Panics
+ * This function panics if it is called from within [`with`].
+ */
+pub fn get(string: impl AsRef<str>) -> Option<InternedStr> {
+  with(|interner| interner.get(string))
+}
+
+/**
+ * Removes all of the strings saved in this thread's [`Interner`].
+ * 
+ * # Panics
+ * This function panics if it is called from within [`with`].
+ */
+pub fn clear() {
+  with(Interner::clear)
+}
+
+/**
+ * Calls the given function with this thread's [`Interner`], and returns its result.
+ * 
+ * This is useful for anything the other functions in this module can't do (e.g., iterating over the interned strings).
+ * 
+ * # Panics
+ * This function panics if it is called from within another call to `with` (including the given function),
+ * since the same `Interner` can't be borrowed twice.
+ */
+pub fn with<R>(f: impl FnOnce(&mut Interner) -> R) -> R {
+  LOCAL.with(|interner| f(&mut interner.borrow_mut()))
+}
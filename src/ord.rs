@@ -0,0 +1,112 @@
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::collections::btree_set::{Iter as SetIter, Range as SetRange};
+use std::ops::{Bound, RangeBounds};
+
+/**
+ * The storage behind both kinds of `OrdInterner`.
+ */
+#[derive(Clone, Default)]
+pub(crate) struct OrdTable<T> {
+  
+  pub(crate) strings: BTreeSet<T>
+  
+}
+
+impl<T: Clone + Ord + Borrow<str> + for<'a> From<&'a str>> OrdTable<T> {
+  
+  /**
+   * Returns the saved allocation for the given string, or saves a new one if there isn't one.
+   */
+  pub(crate) fn intern(&mut self, string: &str) -> T {
+    if let Some(saved) = self.strings.get(string) {
+      return T::clone(saved);
+    }
+    let saved = T::from(string);
+    self.strings.insert(T::clone(&saved));
+    saved
+  }
+  
+  pub(crate) fn iter(&self) -> BTreeIter<'_, T> {
+    BTreeIter::All(self.strings.iter())
+  }
+  
+  /**
+   * # Panics
+   * This method panics if the start of the range is after its end, or if they are equal and both excluded.
+   */
+  pub(crate) fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> BTreeIter<'_, T> {
+    // `Range<&str>` and friends only implement `RangeBounds<&str>`, but `BTreeSet::range` needs `RangeBounds<str>` to look up an `InternedStr`.
+    BTreeIter::Range(self.strings.range::<str, _>((range.start_bound().cloned(), range.end_bound().cloned())))
+  }
+  
+  /**
+   * Returns an iterator over the saved allocations which start with the given prefix.
+   */
+  pub(crate) fn prefix(&self, prefix: &str) -> BTreeIter<'_, T> {
+    let end = prefix_end(prefix);
+    self.range((Bound::Included(prefix), end.as_deref().map_or(Bound::Unbounded, Bound::Excluded)))
+  }
+  
+}
+
+/**
+ * Returns the first string after every string which starts with the given prefix, or `None` if there isn't one
+ * (i.e., if the prefix is empty or made up entirely of [`char::MAX`]).
+ */
+fn prefix_end(prefix: &str) -> Option<String> {
+  let mut end = String::from(prefix);
+  while let Some(last) = end.pop() {
+    // Skip over the surrogates, which aren't `char`s.
+    let next = if last == '\u{D7FF}' { Some('\u{E000}') } else { char::from_u32(last as u32 + 1) };
+    if let Some(next) = next {
+      end.push(next);
+      return Some(end);
+    }
+  }
+  None
+}
+
+/**
+ * The part of `iter` and `range` that each kind of `OrdIter` wraps.
+ * 
+ * [`BTreeSet::iter`] knows exactly how many strings it will yield, but [`BTreeSet::range`] doesn't, so they have to be kept separate.
+ */
+#[derive(Clone, Debug)]
+pub(crate) enum BTreeIter<'a, T> {
+  
+  All(SetIter<'a, T>),
+  Range(SetRange<'a, T>)
+  
+}
+
+impl<'a, T> Iterator for BTreeIter<'a, T> {
+  
+  type Item = &'a T;
+  
+  fn next(&mut self) -> Option<&'a T> {
+    match self {
+      BTreeIter::All(iter) => iter.next(),
+      BTreeIter::Range(iter) => iter.next()
+    }
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match self {
+      BTreeIter::All(iter) => iter.size_hint(),
+      BTreeIter::Range(iter) => iter.size_hint()
+    }
+  }
+  
+}
+
+impl<'a, T> DoubleEndedIterator for BTreeIter<'a, T> {
+  
+  fn next_back(&mut self) -> Option<&'a T> {
+    match self {
+      BTreeIter::All(iter) => iter.next_back(),
+      BTreeIter::Range(iter) => iter.next_back()
+    }
+  }
+  
+}
@@ -0,0 +1,90 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/**
+ * The storage behind both kinds of `OrderedInterner`.
+ * 
+ * Each string is saved both in `strings`, which keeps them in the order they were first interned, and as a key in `indices`, which maps it to its position in `strings`.
+ */
+#[derive(Clone)]
+pub(crate) struct OrderedTable<T, S> {
+  
+  pub(crate) strings: Vec<T>,
+  indices: HashMap<T, usize, S>
+  
+}
+
+impl<T, S> OrderedTable<T, S> {
+  
+  pub(crate) fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { strings: Vec::with_capacity(capacity), indices: HashMap::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  pub(crate) fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  pub(crate) fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  pub(crate) fn clear(&mut self) {
+    self.strings.clear();
+    self.indices.clear();
+  }
+  
+  pub(crate) fn get_index(&self, index: usize) -> Option<&T> {
+    self.strings.get(index)
+  }
+  
+}
+
+impl<T: Clone + Eq + Hash + Borrow<str> + for<'a> From<&'a str>, S: BuildHasher> OrderedTable<T, S> {
+  
+  pub(crate) fn index_of(&self, string: &str) -> Option<usize> {
+    self.indices.get(string).copied()
+  }
+  
+  pub(crate) fn get(&self, string: &str) -> Option<&T> {
+    Some(&self.strings[self.index_of(string)?])
+  }
+  
+  /**
+   * Returns the saved allocation for the given string, or saves a new one at the end if there isn't one.
+   */
+  pub(crate) fn intern(&mut self, string: &str) -> T {
+    if let Some(saved) = self.get(string) {
+      return T::clone(saved);
+    }
+    let saved = T::from(string);
+    self.indices.insert(T::clone(&saved), self.strings.len());
+    self.strings.push(T::clone(&saved));
+    saved
+  }
+  
+  /**
+   * Removes the saved allocation for the given string, if there is one, by moving the last one into its place.
+   */
+  pub(crate) fn swap_remove(&mut self, string: &str) -> Option<T> {
+    let index = self.indices.remove(string)?;
+    let removed = self.strings.swap_remove(index);
+    if let Some(moved) = self.strings.get(index) {
+      *self.indices.get_mut(moved.borrow()).unwrap() = index;
+    }
+    Some(removed)
+  }
+  
+  /**
+   * Removes the saved allocation for the given string, if there is one, by shifting every later one back by one.
+   */
+  pub(crate) fn shift_remove(&mut self, string: &str) -> Option<T> {
+    let index = self.indices.remove(string)?;
+    let removed = self.strings.remove(index);
+    for moved in &self.strings[index..] {
+      *self.indices.get_mut(moved.borrow()).unwrap() -= 1;
+    }
+    Some(removed)
+  }
+  
+}
@@ -0,0 +1,374 @@
+/*!
+ * Interners for file paths, which need not be valid UTF-8.
+ * 
+ * These mirror the [`str`] interners: [`Interner`] is not thread-safe, and the [`sync`] module provides a thread-safe variant.
+ * 
+ * Paths are compared exactly as [`OsStr`]s, rather than as [`Path`]s, so no separators are normalized:
+ * e.g., `a/b`, `a//b`, and `a/b/` are all interned separately.
+ */
+
+pub mod sync;
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
+use std::fmt::{self, Debug, Formatter};
+use std::ffi::OsStr;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::Deref;
+use std::path::Path;
+use std::rc::Rc;
+
+/**
+ * The type of paths that have been interned.
+ * 
+ * Like [`InternedStr`](crate::InternedStr), this is just a type alias.
+ */
+pub type InternedPath = Rc<Path>;
+
+/**
+ * A saved path, which is hashed and compared as an [`OsStr`], so that it can be looked up by one.
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub(crate) struct Saved<P>(pub(crate) P);
+
+impl<P: Deref<Target = Path>> PartialEq for Saved<P> {
+  
+  fn eq(&self, other: &Self) -> bool {
+    self.0.as_os_str() == other.0.as_os_str()
+  }
+  
+}
+
+impl<P: Deref<Target = Path>> Eq for Saved<P> {}
+
+impl<P: Deref<Target = Path>> Hash for Saved<P> {
+  
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.as_os_str().hash(state)
+  }
+  
+}
+
+impl<P: Deref<Target = Path>> Borrow<OsStr> for Saved<P> {
+  
+  fn borrow(&self) -> &OsStr {
+    self.0.as_os_str()
+  }
+  
+}
+
+impl<P: Debug> Debug for Saved<P> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.0.fmt(f)
+  }
+  
+}
+
+/**
+ * An interner will keep track of paths and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use std::path::PathBuf;
+ * # use str_intern::path::{Interner, InternedPath};
+ * let mut interner = Interner::new();
+ * let main0 = interner.intern("src/main.rs");
+ * let main1 = interner.intern(PathBuf::from("src").join("main.rs"));
+ * assert!(InternedPath::ptr_eq(&main0, &main1));
+ * ```
+ * 
+ * This `Interner` is not thread-safe (which is to say, it is implements neither [`Send`] nor [`Sync`]). For a thread-safe variant, see the [`sync`] module.
+ */
+#[repr(transparent)]
+pub struct Interner<S = RandomState> {
+  
+  paths: HashSet<Saved<InternedPath>, S>
+  
+}
+
+impl Interner {
+  
+  /**
+   * Constructs a new `Interner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> Interner<S> {
+  
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { paths: HashSet::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { paths: HashSet::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of paths that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.paths.len()
+  }
+  
+  /**
+   * Returns whether no paths are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.paths.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned paths.
+   */
+  pub fn clear(&mut self) {
+    self.paths.clear()
+  }
+  
+  /**
+   * Removes every interned path which is not referenced anywhere besides this `Interner`, and returns how many were removed.
+   */
+  pub fn gc(&mut self) -> usize {
+    let len = self.paths.len();
+    self.paths.retain(|path| Rc::strong_count(&path.0) > 1);
+    len - self.paths.len()
+  }
+  
+  /**
+   * An iterator over all of the currently interned paths.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.paths.iter())
+  }
+  
+}
+
+impl<S: BuildHasher> Interner<S> {
+  
+  /**
+   * Saves the given path if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, path: impl AsRef<Path>) -> InternedPath {
+    let path = path.as_ref();
+    if let Some(saved) = self.paths.get(path.as_os_str()) {
+      return InternedPath::clone(&saved.0);
+    }
+    let saved = InternedPath::from(path);
+    self.paths.insert(Saved(InternedPath::clone(&saved)));
+    saved
+  }
+  
+  /**
+   * Returns whether the given path has been saved.
+   */
+  pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+    self.paths.contains(path.as_ref().as_os_str())
+  }
+  
+  /**
+   * If the given path has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths.get(path.as_ref().as_os_str()).map(|saved| InternedPath::clone(&saved.0))
+  }
+  
+  /**
+   * Removes the given path, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths.take(path.as_ref().as_os_str()).map(|saved| saved.0)
+  }
+  
+}
+
+impl<S: Clone> Clone for Interner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { paths: self.paths.clone() }
+  }
+  
+}
+
+impl<S> Debug for Interner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&self.paths).finish()
+  }
+  
+}
+
+impl<S: Default> Default for Interner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<S> IntoIterator for Interner<S> {
+  
+  type Item = InternedPath;
+  type IntoIter = IntoIter;
+  
+  fn into_iter(self) -> IntoIter {
+    IntoIter::new(self.paths.into_iter())
+  }
+  
+}
+
+impl<'a, S> IntoIterator for &'a Interner<S> {
+  
+  type Item = &'a InternedPath;
+  type IntoIter = Iter<'a>;
+  
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
+  }
+  
+}
+
+impl<T: AsRef<Path>, S: BuildHasher> Extend<T> for Interner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for path in iter {
+      self.intern(path);
+    }
+  }
+  
+}
+
+impl<T: AsRef<Path>, S: BuildHasher + Default> FromIterator<T> for Interner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An iterator over the paths in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](Interner::iter) method on [`Interner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, Saved<InternedPath>>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, Saved<InternedPath>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item = &'a InternedPath;
+  
+  fn next(&mut self) -> Option<&'a InternedPath> {
+    self.iter.next().map(|saved| &saved.0)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+impl<'a> Debug for Iter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * An owning iterator over the paths that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+pub struct IntoIter {
+  
+  iter: SetIntoIter<Saved<InternedPath>>
+  
+}
+
+impl IntoIter {
+  
+  fn new(iter: SetIntoIter<Saved<InternedPath>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl Iterator for IntoIter {
+  
+  type Item = InternedPath;
+  
+  fn next(&mut self) -> Option<InternedPath> {
+    self.iter.next().map(|saved| saved.0)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl ExactSizeIterator for IntoIter {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl FusedIterator for IntoIter {}
+
+impl Debug for IntoIter {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
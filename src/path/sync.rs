@@ -0,0 +1,469 @@
+/*!
+ * A thread-safe variant of the path interner.
+ */
+
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::iter::FusedIterator;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::Saved;
+
+/**
+ * The type of paths that have been interned.
+ * 
+ * Like [`sync::InternedStr`](crate::sync::InternedStr), this is just a type alias.
+ */
+pub type InternedPath = Arc<Path>;
+
+/**
+ * An interner will keep track of paths and ensure there is only one allocation for any given contents.
+ * 
+ * For example:
+ * ```rust
+ * # use std::path::PathBuf;
+ * # use str_intern::path::sync::{Interner, InternedPath};
+ * let interner = Interner::new();
+ * let main0 = interner.intern("src/main.rs");
+ * let main1 = interner.intern(PathBuf::from("src").join("main.rs"));
+ * assert!(InternedPath::ptr_eq(&main0, &main1));
+ * ```
+ * 
+ * This `Interner` is thread-safe, meaning that it implements both [`Send`] and [`Sync`] (when S implements [`Send`], which the default does).
+ */
+pub struct Interner<S = RandomState> {
+  
+  paths: Mutex<HashSet<Saved<InternedPath>, S>>
+  
+}
+
+impl Interner {
+  
+  /**
+   * Constructs a new `Interner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> Interner<S> {
+  
+  const POISON_MESSAGE: &'static str = "Interner mutex was poisoned";
+  
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
+  }
+  
+  /**
+   * Constructs a new `Interner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
+  }
+  
+  fn from_set(paths: HashSet<Saved<InternedPath>, S>) -> Self {
+    Self { paths: Mutex::new(paths) }
+  }
+  
+  fn paths(&self) -> MutexGuard<'_, HashSet<Saved<InternedPath>, S>> {
+    self.paths.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `Interner` and returns the number of paths that are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn len(&self) -> usize {
+    self.paths().len()
+  }
+  
+  /**
+   * Locks this `Interner` and returns whether no paths are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.paths().is_empty()
+  }
+  
+  /**
+   * Locks this `Interner` and removes all of the interned paths, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn clear(&self) {
+    self.paths().clear()
+  }
+  
+  /**
+   * Locks this `Interner` and removes every interned path which is not referenced anywhere besides this `Interner`,
+   * and returns how many were removed, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn gc(&self) -> usize {
+    self.lock().gc()
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * While it is locked, the current thread has exclusive access to this `Interner`'s methods
+   * (accessible from the [`LockedInterner`]; any methods used directly on `self` may panic).
+   * This enables iterating over the interned paths, with [`LockedInterner::iter`].
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn lock(&self) -> LockedInterner<'_, S> {
+    LockedInterner::new(self.paths())
+  }
+  
+}
+
+impl<S: BuildHasher> Interner<S> {
+  
+  /**
+   * Locks this `Interner`, saves the given path if it is not already saved,
+   * and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern(&self, path: impl AsRef<Path>) -> InternedPath {
+    self.lock().intern(path)
+  }
+  
+  /**
+   * Locks this `Interner` and returns whether the given path has been saved, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+    self.paths().contains(path.as_ref().as_os_str())
+  }
+  
+  /**
+   * Locks this `Interner`, and if the given path has been saved, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn get(&self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths().get(path.as_ref().as_os_str()).map(|saved| InternedPath::clone(&saved.0))
+  }
+  
+  /**
+   * Locks this `Interner`, removes the given path, if it has been saved, and returns the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn remove(&self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths().take(path.as_ref().as_os_str()).map(|saved| saved.0)
+  }
+  
+}
+
+impl<S: Clone> Clone for Interner<S> {
+  
+  fn clone(&self) -> Self {
+    Self::from_set(self.paths().clone())
+  }
+  
+}
+
+impl<S> Debug for Interner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&*self.paths()).finish()
+  }
+  
+}
+
+impl<S: Default> Default for Interner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<S> IntoIterator for Interner<S> {
+  
+  type Item = InternedPath;
+  type IntoIter = IntoIter;
+  
+  fn into_iter(self) -> IntoIter {
+    IntoIter::new(self.paths.into_inner().expect(Self::POISON_MESSAGE).into_iter())
+  }
+  
+}
+
+impl<T: AsRef<Path>, S: BuildHasher> Extend<T> for Interner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.lock().extend(iter)
+  }
+  
+}
+
+impl<T: AsRef<Path>, S: BuildHasher + Default> FromIterator<T> for Interner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * A locked [`Interner`]. This `struct` is created by [`Interner::lock`]; see its documentation for more details.
+ */
+#[repr(transparent)]
+pub struct LockedInterner<'a, S = RandomState> {
+  
+  paths: MutexGuard<'a, HashSet<Saved<InternedPath>, S>>
+  
+}
+
+impl<'a, S> LockedInterner<'a, S> {
+  
+  fn new(paths: MutexGuard<'a, HashSet<Saved<InternedPath>, S>>) -> Self {
+    Self { paths }
+  }
+  
+  /**
+   * Returns the number of paths that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.paths.len()
+  }
+  
+  /**
+   * Returns whether no paths are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.paths.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned paths.
+   */
+  pub fn clear(&mut self) {
+    self.paths.clear()
+  }
+  
+  /**
+   * Removes every interned path which is not referenced anywhere besides this `Interner`, and returns how many were removed.
+   */
+  pub fn gc(&mut self) -> usize {
+    let len = self.paths.len();
+    self.paths.retain(|path| Arc::strong_count(&path.0) > 1);
+    len - self.paths.len()
+  }
+  
+  /**
+   * An iterator over all of the currently interned paths.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.paths.iter())
+  }
+  
+}
+
+impl<'a, S: BuildHasher> LockedInterner<'a, S> {
+  
+  /**
+   * Saves the given path if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, path: impl AsRef<Path>) -> InternedPath {
+    let path = path.as_ref();
+    if let Some(saved) = self.paths.get(path.as_os_str()) {
+      return InternedPath::clone(&saved.0);
+    }
+    let saved = InternedPath::from(path);
+    self.paths.insert(Saved(InternedPath::clone(&saved)));
+    saved
+  }
+  
+  /**
+   * Returns whether the given path has been saved.
+   */
+  pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+    self.paths.contains(path.as_ref().as_os_str())
+  }
+  
+  /**
+   * If the given path has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths.get(path.as_ref().as_os_str()).map(|saved| InternedPath::clone(&saved.0))
+  }
+  
+  /**
+   * Removes the given path, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<InternedPath> {
+    self.paths.take(path.as_ref().as_os_str()).map(|saved| saved.0)
+  }
+  
+}
+
+impl<'a, S> Debug for LockedInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("LockedInterner").field(&*self.paths).finish()
+  }
+  
+}
+
+impl<'a, 'b, S> IntoIterator for &'b LockedInterner<'a, S> {
+  
+  type Item = &'b InternedPath;
+  type IntoIter = Iter<'b>;
+  
+  fn into_iter(self) -> Iter<'b> {
+    self.iter()
+  }
+  
+}
+
+impl<'a, T: AsRef<Path>, S: BuildHasher> Extend<T> for LockedInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for path in iter {
+      self.intern(path);
+    }
+  }
+  
+}
+
+/**
+ * An iterator over the paths in an [`Interner`].
+ * 
+ * This `struct` is created by the [`iter`](LockedInterner::iter) method on [`LockedInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, Saved<InternedPath>>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, Saved<InternedPath>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item = &'a InternedPath;
+  
+  fn next(&mut self) -> Option<&'a InternedPath> {
+    self.iter.next().map(|saved| &saved.0)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+impl<'a> Debug for Iter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * An owning iterator over the paths that were in an [`Interner`].
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+pub struct IntoIter {
+  
+  iter: SetIntoIter<Saved<InternedPath>>
+  
+}
+
+impl IntoIter {
+  
+  fn new(iter: SetIntoIter<Saved<InternedPath>>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl Iterator for IntoIter {
+  
+  type Item = InternedPath;
+  
+  fn next(&mut self) -> Option<InternedPath> {
+    self.iter.next().map(|saved| saved.0)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl ExactSizeIterator for IntoIter {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl FusedIterator for IntoIter {}
+
+impl Debug for IntoIter {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
@@ -0,0 +1,97 @@
+use std::fmt::{self, Write};
+use std::ops::Deref;
+use std::str;
+
+/**
+ * A buffer for building a string that will only be copied into an interned allocation if it has not already been saved.
+ * 
+ * Short strings are built on the stack; longer ones spill over into a [`String`].
+ */
+pub(crate) struct ScratchBuffer {
+  
+  inline: [u8; Self::INLINE_CAPACITY],
+  len: usize,
+  spilled: Option<String>
+  
+}
+
+impl ScratchBuffer {
+  
+  const INLINE_CAPACITY: usize = 128;
+  
+  pub(crate) fn new() -> Self {
+    Self { inline: [0; Self::INLINE_CAPACITY], len: 0, spilled: None }
+  }
+  
+  /**
+   * Formats the given arguments into a new `ScratchBuffer`.
+   * 
+   * # Panics
+   * This function panics if a formatting trait implementation returns an error, just like [`format!`].
+   */
+  pub(crate) fn format(args: fmt::Arguments<'_>) -> Self {
+    let mut buffer = Self::new();
+    buffer.write_fmt(args).expect("a formatting trait implementation returned an error when the underlying stream did not");
+    buffer
+  }
+  
+  /**
+   * Concatenates the given parts into a new `ScratchBuffer`, with the given separator between each of them.
+   */
+  pub(crate) fn join<I: IntoIterator>(separator: &str, parts: I) -> Self where I::Item: AsRef<str> {
+    let mut buffer = Self::new();
+    let mut parts = parts.into_iter();
+    if let Some(first) = parts.next() {
+      buffer.push_str(first.as_ref());
+      for part in parts {
+        buffer.push_str(separator);
+        buffer.push_str(part.as_ref());
+      }
+    }
+    buffer
+  }
+  
+  pub(crate) fn push_str(&mut self, string: &str) {
+    match &mut self.spilled {
+      Some(spilled) => spilled.push_str(string),
+      None if self.len + string.len() <= Self::INLINE_CAPACITY => {
+        self.inline[self.len..self.len + string.len()].copy_from_slice(string.as_bytes());
+        self.len += string.len();
+      }
+      None => {
+        let mut spilled = String::with_capacity(2 * (self.len + string.len()));
+        spilled.push_str(self.inline_str());
+        spilled.push_str(string);
+        self.spilled = Some(spilled);
+      }
+    }
+  }
+  
+  fn inline_str(&self) -> &str {
+    // Only whole strs are ever copied into the inline buffer, so it always holds valid UTF-8.
+    str::from_utf8(&self.inline[..self.len]).expect("scratch buffer should contain valid UTF-8")
+  }
+  
+}
+
+impl Deref for ScratchBuffer {
+  
+  type Target = str;
+  
+  fn deref(&self) -> &str {
+    match &self.spilled {
+      Some(spilled) => spilled,
+      None => self.inline_str()
+    }
+  }
+  
+}
+
+impl Write for ScratchBuffer {
+  
+  fn write_str(&mut self, string: &str) -> fmt::Result {
+    self.push_str(string);
+    Ok(())
+  }
+  
+}
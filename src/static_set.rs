@@ -0,0 +1,322 @@
+/*!
+ * Interners for a fixed set of strings known at compile time (e.g., keywords or HTML tag names), which are looked up without hashing.
+ * 
+ * These are usually declared with the [`static_interner!`](crate::static_interner) macro.
+ */
+
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::sync::OnceLock;
+
+use crate::sync::{Interner, InternedStr};
+
+/**
+ * Declares one or more `static` [`StaticInterner`]s, each containing the given strings.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::static_interner;
+ * static_interner! {
+ *   /// The HTML tags this parser knows about.
+ *   pub static TAGS = ["div", "span", "p"];
+ * }
+ * let div0 = TAGS.get("div").unwrap();
+ * let div1 = TAGS.get("div").unwrap();
+ * assert!(std::sync::Arc::ptr_eq(&div0, &div1));
+ * assert_eq!(TAGS.get("table"), None);
+ * ```
+ * 
+ * The strings are sorted at compile time, and listing the same string twice is a compile-time error:
+ * ```rust,compile_fail
+ * # use str_intern::static_interner;
+ * static_interner! {
+ *   static TAGS = ["div", "span", "div"];
+ * }
+ * ```
+ */
+#[macro_export]
+macro_rules! static_interner {
+  ($($(#[$attr:meta])* $vis:vis static $name:ident = [$($string:expr),* $(,)?];)*) => {
+    $(
+      $(#[$attr])*
+      $vis static $name: $crate::static_set::StaticInterner<{ <[&str]>::len(&[$($string),*]) }> = $crate::static_set::StaticInterner::new([$($string),*]);
+    )*
+  };
+}
+
+/**
+ * An interner for a fixed set of strings, which is looked up by binary search instead of hashing.
+ * 
+ * The strings are only allocated the first time one of them is returned as an [`InternedStr`], after which every [`get`](Self::get) returns the same allocation.
+ * Since `StaticInterner`s are meant to be `static`, they return [`sync::InternedStr`](crate::sync::InternedStr)s, rather than [`crate::InternedStr`]s.
+ * 
+ * To intern strings outside of the fixed set as well, see [`FallbackInterner`].
+ */
+pub struct StaticInterner<const N: usize> {
+  
+  /**
+   * These are always sorted, and never contain duplicates.
+   */
+  strings: [&'static str; N],
+  
+  interned: OnceLock<Box<[InternedStr]>>
+  
+}
+
+impl<const N: usize> StaticInterner<N> {
+  
+  /**
+   * Constructs a new `StaticInterner` containing the given strings, in any order.
+   * 
+   * # Panics
+   * This method panics if the same string is given more than once. If it is used to initialize a `static` (as [`static_interner!`](crate::static_interner) does),
+   * that is a compile-time error.
+   */
+  pub const fn new(mut strings: [&'static str; N]) -> Self {
+    // This is an insertion sort, since that is simple enough to do in a `const fn`, and the sets are usually small.
+    let mut i = 1;
+    while i < N {
+      let mut j = i;
+      while j > 0 {
+        match compare(strings[j - 1], strings[j]) {
+          Ordering::Less => break,
+          Ordering::Equal => panic!("a StaticInterner cannot contain the same string twice"),
+          Ordering::Greater => {
+            let string = strings[j];
+            strings[j] = strings[j - 1];
+            strings[j - 1] = string;
+            j -= 1;
+          }
+        }
+      }
+      i += 1;
+    }
+    Self { strings, interned: OnceLock::new() }
+  }
+  
+  /**
+   * Returns the number of strings in this `StaticInterner`.
+   */
+  pub const fn len(&self) -> usize {
+    N
+  }
+  
+  /**
+   * Returns whether this `StaticInterner` has no strings.
+   */
+  pub const fn is_empty(&self) -> bool {
+    N == 0
+  }
+  
+  /**
+   * Returns the position of the given string among this `StaticInterner`'s strings in lexicographic order, if it is one of them.
+   */
+  pub const fn index_of(&self, string: &str) -> Option<usize> {
+    let mut start = 0;
+    let mut end = N;
+    while start < end {
+      let mid = start + (end - start) / 2;
+      match compare(self.strings[mid], string) {
+        Ordering::Less => start = mid + 1,
+        Ordering::Equal => return Some(mid),
+        Ordering::Greater => end = mid
+      }
+    }
+    None
+  }
+  
+  /**
+   * Returns whether the given string is one of this `StaticInterner`'s strings.
+   */
+  pub const fn contains(&self, string: &str) -> bool {
+    self.index_of(string).is_some()
+  }
+  
+  /**
+   * If the given string is one of this `StaticInterner`'s strings, returns the `'static` copy of it, or `None` otherwise.
+   */
+  pub const fn get_static(&self, string: &str) -> Option<&'static str> {
+    match self.index_of(string) {
+      Some(index) => Some(self.strings[index]),
+      None => None
+    }
+  }
+  
+  /**
+   * If the given string is one of this `StaticInterner`'s strings, returns a reference to its allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: &str) -> Option<InternedStr> {
+    Some(self.get_index(self.index_of(string)?))
+  }
+  
+  /**
+   * Returns a reference to the allocation of the string at the given position in lexicographic order.
+   * 
+   * # Panics
+   * This method panics if `index` is not less than [`len`](Self::len).
+   */
+  pub fn get_index(&self, index: usize) -> InternedStr {
+    InternedStr::clone(&self.interned()[index])
+  }
+  
+  /**
+   * Returns all of this `StaticInterner`'s strings, in lexicographic order.
+   */
+  pub const fn as_slice(&self) -> &[&'static str] {
+    &self.strings
+  }
+  
+  fn interned(&self) -> &[InternedStr] {
+    self.interned.get_or_init(|| self.strings.iter().copied().map(InternedStr::from).collect())
+  }
+  
+}
+
+impl<const N: usize> Debug for StaticInterner<N> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("StaticInterner").field(&self.strings).finish()
+  }
+  
+}
+
+/**
+ * Compares two strings lexicographically, like [`Ord`] does, but in a `const fn`.
+ */
+const fn compare(this: &str, other: &str) -> Ordering {
+  let this = this.as_bytes();
+  let other = other.as_bytes();
+  let mut i = 0;
+  while i < this.len() && i < other.len() {
+    if this[i] != other[i] {
+      return if this[i] < other[i] { Ordering::Less } else { Ordering::Greater };
+    }
+    i += 1;
+  }
+  if this.len() < other.len() {
+    Ordering::Less
+  } else if this.len() > other.len() {
+    Ordering::Greater
+  } else {
+    Ordering::Equal
+  }
+}
+
+/**
+ * An interner which looks strings up in a [`StaticInterner`] first, and only interns them in a regular [`Interner`] if they are not there.
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use str_intern::static_interner;
+ * # use str_intern::static_set::FallbackInterner;
+ * static_interner! {
+ *   static KEYWORDS = ["fn", "let", "if", "else"];
+ * }
+ * let interner = FallbackInterner::new(&KEYWORDS);
+ * assert!(Arc::ptr_eq(&interner.intern("let"), &KEYWORDS.get("let").unwrap()));
+ * let x0 = interner.intern("x");
+ * let x1 = interner.intern(String::from("x"));
+ * assert!(Arc::ptr_eq(&x0, &x1));
+ * assert_eq!(interner.dynamic().len(), 1);
+ * ```
+ */
+pub struct FallbackInterner<const N: usize, S = RandomState> {
+  
+  statics: &'static StaticInterner<N>,
+  dynamic: Interner<S>
+  
+}
+
+impl<const N: usize> FallbackInterner<N> {
+  
+  /**
+   * Constructs a new `FallbackInterner` which looks strings up in the given `StaticInterner` first, and interns the rest in a new [`Interner`].
+   */
+  pub fn new(statics: &'static StaticInterner<N>) -> Self {
+    Self::with_interner(statics, Interner::new())
+  }
+  
+}
+
+impl<const N: usize, S> FallbackInterner<N, S> {
+  
+  /**
+   * Constructs a new `FallbackInterner` which looks strings up in the given `StaticInterner` first, and interns the rest in the given [`Interner`].
+   */
+  pub fn with_interner(statics: &'static StaticInterner<N>, dynamic: Interner<S>) -> Self {
+    Self { statics, dynamic }
+  }
+  
+  /**
+   * Returns the `StaticInterner` that strings are looked up in first.
+   */
+  pub fn statics(&self) -> &'static StaticInterner<N> {
+    self.statics
+  }
+  
+  /**
+   * Returns the [`Interner`] that strings which are not in [`statics`](Self::statics) are interned in.
+   */
+  pub fn dynamic(&self) -> &Interner<S> {
+    &self.dynamic
+  }
+  
+  /**
+   * Consumes this `FallbackInterner` and returns the [`Interner`] that strings which are not in [`statics`](Self::statics) were interned in.
+   */
+  pub fn into_dynamic(self) -> Interner<S> {
+    self.dynamic
+  }
+  
+}
+
+impl<const N: usize, S: BuildHasher> FallbackInterner<N, S> {
+  
+  /**
+   * Returns the string from [`statics`](Self::statics), if it is there, without locking anything.
+   * Otherwise, locks the [`dynamic`](Self::dynamic) `Interner`, saves the given string if it is not already saved,
+   * and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the [`dynamic`](Self::dynamic) `Interner` has been poisoned, and it may panic if it is already locked on this thread.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    self.statics.get(string).unwrap_or_else(|| self.dynamic.intern(string))
+  }
+  
+  /**
+   * Returns whether the given string is in [`statics`](Self::statics) or has been saved in the [`dynamic`](Self::dynamic) `Interner`.
+   * 
+   * # Panics
+   * This method panics if the [`dynamic`](Self::dynamic) `Interner` has been poisoned, and it may panic if it is already locked on this thread.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    let string = string.as_ref();
+    self.statics.contains(string) || self.dynamic.contains(string)
+  }
+  
+  /**
+   * If the given string is in [`statics`](Self::statics) or has been saved in the [`dynamic`](Self::dynamic) `Interner`,
+   * returns a reference to its allocation, or `None` otherwise.
+   * 
+   * # Panics
+   * This method panics if the [`dynamic`](Self::dynamic) `Interner` has been poisoned, and it may panic if it is already locked on this thread.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.statics.get(string).or_else(|| self.dynamic.get(string))
+  }
+  
+}
+
+impl<const N: usize, S> Debug for FallbackInterner<N, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("FallbackInterner").field("statics", self.statics).field("dynamic", &self.dynamic).finish()
+  }
+  
+}
@@ -0,0 +1,357 @@
+/*!
+ * An interner which saves substrings of larger strings without copying them.
+ */
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::Iter as SetIter;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+/**
+ * A substring that has been interned in a [`SubstrInterner`].
+ * 
+ * This is a range of a parent string, which it keeps alive; it may be the whole parent string, if it was interned with [`intern`](SubstrInterner::intern).
+ * Two `InternedSubstr`s are equal exactly when they have the same contents, regardless of their parents.
+ */
+#[derive(Clone)]
+pub struct InternedSubstr {
+  
+  parent: Arc<str>,
+  start: usize,
+  end: usize
+  
+}
+
+impl InternedSubstr {
+  
+  /**
+   * Returns the string this is a substring of.
+   */
+  pub fn parent(&self) -> &Arc<str> {
+    &self.parent
+  }
+  
+  /**
+   * Returns the range of [`parent`](Self::parent) that this is.
+   */
+  pub fn range(&self) -> Range<usize> {
+    self.start..self.end
+  }
+  
+  /**
+   * Returns whether two `InternedSubstr`s are the same range of the same parent allocation.
+   */
+  pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+    Arc::ptr_eq(&this.parent, &other.parent) && this.start == other.start && this.end == other.end
+  }
+  
+}
+
+impl Deref for InternedSubstr {
+  
+  type Target = str;
+  
+  fn deref(&self) -> &str {
+    &self.parent[self.start..self.end]
+  }
+  
+}
+
+impl AsRef<str> for InternedSubstr {
+  
+  fn as_ref(&self) -> &str {
+    self
+  }
+  
+}
+
+impl Borrow<str> for InternedSubstr {
+  
+  fn borrow(&self) -> &str {
+    self
+  }
+  
+}
+
+impl PartialEq for InternedSubstr {
+  
+  fn eq(&self, other: &Self) -> bool {
+    Self::ptr_eq(self, other) || **self == **other
+  }
+  
+}
+
+impl Eq for InternedSubstr {}
+
+impl PartialOrd for InternedSubstr {
+  
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+  
+}
+
+impl Ord for InternedSubstr {
+  
+  fn cmp(&self, other: &Self) -> Ordering {
+    str::cmp(self, other)
+  }
+  
+}
+
+impl Hash for InternedSubstr {
+  
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    str::hash(self, state)
+  }
+  
+}
+
+impl Debug for InternedSubstr {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    Debug::fmt(&**self, f)
+  }
+  
+}
+
+impl Display for InternedSubstr {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    Display::fmt(&**self, f)
+  }
+  
+}
+
+/**
+ * An interner which saves substrings as ranges of the strings they came from, rather than copying them.
+ * 
+ * The first substring interned with given contents becomes the canonical one, and keeps its parent alive for as long as it is saved;
+ * interning the same contents again, from any parent, returns that canonical substring.
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use str_intern::substr::{SubstrInterner, InternedSubstr};
+ * let mut interner = SubstrInterner::new();
+ * let document: Arc<str> = Arc::from("let x = y;");
+ * let x = interner.intern_substr(&document, 4..5);
+ * assert_eq!(&*x, "x");
+ * assert!(Arc::ptr_eq(x.parent(), &document));
+ * let other: Arc<str> = Arc::from("x + 1");
+ * assert!(InternedSubstr::ptr_eq(&interner.intern_substr(&other, 0..1), &x));
+ * ```
+ */
+pub struct SubstrInterner<S = RandomState> {
+  
+  strings: HashSet<InternedSubstr, S>
+  
+}
+
+impl SubstrInterner {
+  
+  /**
+   * Constructs a new `SubstrInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `SubstrInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> SubstrInterner<S> {
+  
+  /**
+   * Constructs a new `SubstrInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { strings: HashSet::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `SubstrInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { strings: HashSet::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of interned substrings.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no substrings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned substrings, releasing their parents unless they are still referenced elsewhere.
+   */
+  pub fn clear(&mut self) {
+    self.strings.clear()
+  }
+  
+  /**
+   * An iterator over all of the currently interned substrings.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.strings.iter())
+  }
+  
+}
+
+impl<S: BuildHasher> SubstrInterner<S> {
+  
+  /**
+   * Returns the canonical substring with the same contents as the given range of `parent`,
+   * or saves that range of `parent` as the canonical one if there is none, without copying it.
+   * 
+   * # Panics
+   * 
+   * Panics if `range` is out of bounds for `parent`, or does not start and end on `char` boundaries.
+   */
+  pub fn intern_substr(&mut self, parent: &Arc<str>, range: Range<usize>) -> InternedSubstr {
+    let string = parent.get(range.clone()).unwrap_or_else(|| panic!("range {range:?} is not a valid substring of a string of length {}", parent.len()));
+    if let Some(saved) = self.strings.get(string) {
+      return saved.clone();
+    }
+    let saved = InternedSubstr { parent: Arc::clone(parent), start: range.start, end: range.end };
+    self.strings.insert(saved.clone());
+    saved
+  }
+  
+  /**
+   * Returns the canonical substring with the same contents as the given string,
+   * or saves a copy of the whole string as the canonical one if there is none.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedSubstr {
+    let string = string.as_ref();
+    if let Some(saved) = self.strings.get(string) {
+      return saved.clone();
+    }
+    let saved = InternedSubstr { parent: Arc::from(string), start: 0, end: string.len() };
+    self.strings.insert(saved.clone());
+    saved
+  }
+  
+  /**
+   * Returns whether a substring with the given contents has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains(string.as_ref())
+  }
+  
+  /**
+   * If a substring with the given contents has been saved, returns it, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedSubstr> {
+    self.strings.get(string.as_ref()).cloned()
+  }
+  
+}
+
+impl<S: Clone> Clone for SubstrInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone() }
+  }
+  
+}
+
+impl<S> Debug for SubstrInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("SubstrInterner").field(&self.strings).finish()
+  }
+  
+}
+
+impl<S: Default> Default for SubstrInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<'a, S> IntoIterator for &'a SubstrInterner<S> {
+  
+  type Item = &'a InternedSubstr;
+  type IntoIter = Iter<'a>;
+  
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
+  }
+  
+}
+
+/**
+ * An iterator over the substrings in a [`SubstrInterner`].
+ * 
+ * This `struct` is created by the [`iter`](SubstrInterner::iter) method on [`SubstrInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, InternedSubstr>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, InternedSubstr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item = &'a InternedSubstr;
+  
+  fn next(&mut self) -> Option<&'a InternedSubstr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+impl<'a> Debug for Iter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
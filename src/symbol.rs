@@ -0,0 +1,683 @@
+/*!
+ * An interner which hands out compact [`Symbol`]s instead of reference-counted strings.
+ */
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Enumerate, FusedIterator};
+use std::marker::PhantomData;
+use std::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize};
+use std::ops::Index;
+use std::slice::Iter as SliceIter;
+use std::sync::Arc;
+
+/**
+ * A type that a [`Symbol`] can be stored as, which determines how many strings a [`SymbolInterner`] can hold, and how big its symbols are.
+ * 
+ * This is implemented for all of the unsigned integer types, along with their [`NonZero`](std::num::NonZero) variants,
+ * which can hold one less string, but let an `Option<Symbol<K>>` be the same size as a `Symbol<K>`.
+ * 
+ * Implementations must ensure that `K::try_from_usize(index).map(K::into_usize)` is either `None` or `Some(index)`,
+ * and that if it is `None`, it is also `None` for every greater index.
+ */
+pub trait Key: Copy + Eq + Ord + Hash + Debug {
+  
+  /**
+   * Returns the key for the given index, or `None` if it is out of range for this type.
+   */
+  fn try_from_usize(index: usize) -> Option<Self>;
+  
+  /**
+   * Returns the index that this key was constructed from.
+   */
+  fn into_usize(self) -> usize;
+  
+}
+
+macro_rules! impl_key {
+  ($($int:ty),*) => {$(
+    impl Key for $int {
+      
+      fn try_from_usize(index: usize) -> Option<Self> {
+        Self::try_from(index).ok()
+      }
+      
+      fn into_usize(self) -> usize {
+        self as usize
+      }
+      
+    }
+  )*};
+}
+
+macro_rules! impl_non_zero_key {
+  ($($non_zero:ty),*) => {$(
+    impl Key for $non_zero {
+      
+      fn try_from_usize(index: usize) -> Option<Self> {
+        // Store the index plus one, so that 0 is free to be used as `None`.
+        index.checked_add(1).and_then(|index| index.try_into().ok()).and_then(Self::new)
+      }
+      
+      fn into_usize(self) -> usize {
+        self.get() as usize - 1
+      }
+      
+    }
+  )*};
+}
+
+impl_key!(u8, u16, u32, u64, usize);
+impl_non_zero_key!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize);
+
+/**
+ * The [`Key`] that [`SymbolInterner`] uses unless another one is specified, which makes [`DefaultSymbol`] (and `Option<DefaultSymbol>`) 4 bytes.
+ */
+pub type DefaultKey = NonZeroU32;
+
+/**
+ * The kind of [`Symbol`] that a [`SymbolInterner`] hands out unless another [`Key`] is specified.
+ */
+pub type DefaultSymbol = Symbol<DefaultKey>;
+
+/**
+ * The error returned by [`SymbolInterner::try_intern`] when every [`Key`] has already been used.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct KeysExhausted {
+  
+  /**
+   * How many strings had already been interned, which is the most that the `SymbolInterner`'s [`Key`] can hold.
+   */
+  pub len: usize
+  
+}
+
+impl Display for KeysExhausted {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "every key has been used, since {} strings have already been interned", self.len)
+  }
+  
+}
+
+impl Error for KeysExhausted {}
+
+/**
+ * A handle to a string that has been interned in a [`SymbolInterner`].
+ * 
+ * A `Symbol` is the same size as its [`Key`] (4 bytes by default, and so is an `Option<Symbol>`), and comparing or hashing one never looks at the string itself.
+ * Symbols are numbered in the order their strings were first interned, so symbols from the same `SymbolInterner` are ordered by that, not by their contents.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Symbol<K = DefaultKey>(K);
+
+impl<K: Key> Symbol<K> {
+  
+  fn new(index: usize) -> Option<Self> {
+    K::try_from_usize(index).map(Self)
+  }
+  
+  /**
+   * Returns the position of this `Symbol`'s string in its [`SymbolInterner`], i.e., how many strings were interned in it before this one.
+   */
+  pub fn index(self) -> usize {
+    self.0.into_usize()
+  }
+  
+  /**
+   * Returns the key that this `Symbol` is stored as.
+   */
+  pub fn key(self) -> K {
+    self.0
+  }
+  
+}
+
+impl<K: Key> Debug for Symbol<K> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Symbol").field(&self.index()).finish()
+  }
+  
+}
+
+/**
+ * An interner which maps each distinct string to a [`Symbol`], and each `Symbol` back to its string.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::symbol::SymbolInterner;
+ * let mut interner = SymbolInterner::new();
+ * let foo0 = interner.intern("foo");
+ * let foo1 = interner.intern(&String::from("foo"));
+ * assert_eq!(foo0, foo1);
+ * assert_eq!(interner.resolve(foo0), "foo");
+ * ```
+ * 
+ * Its symbols are stored as `K`, which is [`DefaultKey`] unless another [`Key`] is specified, e.g., `SymbolInterner<u16>` for a small table.
+ * If every key has been used, no more strings can be interned. (See [`try_intern`](Self::try_intern).)
+ * 
+ * Strings can never be removed from a `SymbolInterner`, so a `Symbol` stays valid (and keeps resolving to the same string) for as long as its `SymbolInterner` exists.
+ * 
+ * A `Symbol` does not know which `SymbolInterner` it came from, so it must only be resolved by that one.
+ * Resolving it with any other `SymbolInterner` is a logic error: it may resolve to an unrelated string, or [`resolve`](Self::resolve) may panic.
+ */
+pub struct SymbolInterner<K = DefaultKey, S = RandomState> {
+  
+  /**
+   * The interned strings, indexed by their symbols.
+   */
+  strings: Vec<Arc<str>>,
+  
+  symbols: HashMap<Arc<str>, Symbol<K>, S>
+  
+}
+
+impl SymbolInterner {
+  
+  /**
+   * Constructs a new `SymbolInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `SymbolInterner` with the given capacity. See [`HashMap`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<K: Key, S> SymbolInterner<K, S> {
+  
+  /**
+   * Constructs a new `SymbolInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { strings: Vec::new(), symbols: HashMap::with_hasher(hasher) }
+  }
+  
+  /**
+   * Constructs a new `SymbolInterner` with the given capacity and hasher. See [`HashMap`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { strings: Vec::with_capacity(capacity), symbols: HashMap::with_capacity_and_hasher(capacity, hasher) }
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for.
+   * 
+   * # Panics
+   * This method panics if the given symbol did not come from this `SymbolInterner` and is out of range for it.
+   * (A symbol from another `SymbolInterner` may instead resolve to an unrelated string; see [`SymbolInterner`].)
+   */
+  pub fn resolve(&self, symbol: Symbol<K>) -> &str {
+    self.try_resolve(symbol).expect("symbol did not come from this interner")
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for, or `None` if it is out of range for this `SymbolInterner`.
+   * 
+   * This can only return `None` if the given symbol came from another `SymbolInterner`, which is not guaranteed to be caught. (See [`SymbolInterner`].)
+   */
+  pub fn try_resolve(&self, symbol: Symbol<K>) -> Option<&str> {
+    self.strings.get(symbol.index()).map(|string| &**string)
+  }
+  
+  /**
+   * An iterator over all of the interned strings along with their symbols, in the order they were interned.
+   */
+  pub fn iter(&self) -> Iter<'_, K> {
+    Iter::new(self.strings.iter().enumerate())
+  }
+  
+}
+
+impl<K: Key, S: BuildHasher> SymbolInterner<K, S> {
+  
+  /**
+   * Saves the given string if it is not already saved, and returns its symbol.
+   * 
+   * # Panics
+   * This method panics if the given string is new and every [`Key`] has already been used. (See [`try_intern`](Self::try_intern).)
+   */
+  pub fn intern(&mut self, string: &str) -> Symbol<K> {
+    self.try_intern(string).unwrap_or_else(|error| panic!("{error}"))
+  }
+  
+  /**
+   * Saves the given string if it is not already saved and there is a [`Key`] left for it, and returns its symbol.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::symbol::SymbolInterner;
+   * let mut interner = SymbolInterner::<u8>::default();
+   * for i in 0..256 {
+   *   interner.intern(&i.to_string());
+   * }
+   * assert!(interner.try_intern("one too many").is_err());
+   * assert!(interner.try_intern("255").is_ok());
+   * ```
+   */
+  pub fn try_intern(&mut self, string: &str) -> Result<Symbol<K>, KeysExhausted> {
+    if let Some(&symbol) = self.symbols.get(string) {
+      return Ok(symbol);
+    }
+    let symbol = Symbol::new(self.strings.len()).ok_or(KeysExhausted { len: self.strings.len() })?;
+    let string = Arc::<str>::from(string);
+    self.strings.push(Arc::clone(&string));
+    self.symbols.insert(string, symbol);
+    Ok(symbol)
+  }
+  
+  /**
+   * Returns the symbol for the given string, if it has been interned.
+   */
+  pub fn get(&self, string: &str) -> Option<Symbol<K>> {
+    self.symbols.get(string).copied()
+  }
+  
+  /**
+   * Returns whether the given string has been interned.
+   */
+  pub fn contains(&self, string: &str) -> bool {
+    self.symbols.contains_key(string)
+  }
+  
+  /**
+   * Consumes this `SymbolInterner` and returns a [`Reader`], which can look up and resolve the same symbols, but cannot intern any more strings.
+   * 
+   * This is useful once every string has been interned (e.g., at the end of parsing), since a `Reader` can be shared between threads (e.g., in an [`Arc`]) without locking.
+   */
+  pub fn into_reader(mut self) -> Reader<K, S> {
+    self.symbols.shrink_to_fit();
+    Reader { strings: self.strings.into_boxed_slice(), symbols: self.symbols }
+  }
+  
+}
+
+impl<K: Clone, S: Clone> Clone for SymbolInterner<K, S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone(), symbols: self.symbols.clone() }
+  }
+  
+}
+
+impl<K: Key, S> Debug for SymbolInterner<K, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+  
+}
+
+impl<K: Key, S: Default> Default for SymbolInterner<K, S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<K: Key, S> Index<Symbol<K>> for SymbolInterner<K, S> {
+  
+  type Output = str;
+  
+  /**
+   * Equivalent to [`resolve`](SymbolInterner::resolve).
+   */
+  fn index(&self, symbol: Symbol<K>) -> &str {
+    self.resolve(symbol)
+  }
+  
+}
+
+impl<'a, K: Key, S> IntoIterator for &'a SymbolInterner<K, S> {
+  
+  type Item = (Symbol<K>, &'a str);
+  type IntoIter = Iter<'a, K>;
+  
+  fn into_iter(self) -> Iter<'a, K> {
+    self.iter()
+  }
+  
+}
+
+/**
+ * A read-only [`SymbolInterner`], which can look up and resolve symbols, but cannot intern any more strings.
+ * 
+ * This `struct` is created by the [`into_reader`](SymbolInterner::into_reader) method on [`SymbolInterner`].
+ * Every symbol from that `SymbolInterner` resolves to the same string with this `Reader`.
+ * If strings only need to be resolved, not looked up, a `Reader` can be turned into a smaller [`Resolver`] with [`into_resolver`](Self::into_resolver).
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use std::thread;
+ * # use str_intern::symbol::SymbolInterner;
+ * let mut interner = SymbolInterner::new();
+ * let foo = interner.intern("foo");
+ * let reader = Arc::new(interner.into_reader());
+ * let handle = thread::spawn({
+ *   let reader = Arc::clone(&reader);
+ *   move || reader.get("foo")
+ * });
+ * assert_eq!(handle.join().unwrap(), Some(foo));
+ * assert_eq!(reader.resolve(foo), "foo");
+ * ```
+ */
+pub struct Reader<K = DefaultKey, S = RandomState> {
+  
+  /**
+   * The interned strings, indexed by their symbols.
+   */
+  strings: Box<[Arc<str>]>,
+  
+  symbols: HashMap<Arc<str>, Symbol<K>, S>
+  
+}
+
+impl<K: Key, S> Reader<K, S> {
+  
+  /**
+   * Returns the number of strings in this `Reader`.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether this `Reader` contains no strings.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for.
+   * 
+   * # Panics
+   * This method panics if the given symbol did not come from the same `SymbolInterner` as this `Reader` and is out of range for it.
+   * (A symbol from another `SymbolInterner` may instead resolve to an unrelated string; see [`SymbolInterner`].)
+   */
+  pub fn resolve(&self, symbol: Symbol<K>) -> &str {
+    self.try_resolve(symbol).expect("symbol did not come from this interner")
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for, or `None` if it is out of range for this `Reader`.
+   * 
+   * This can only return `None` if the given symbol came from another `SymbolInterner`, which is not guaranteed to be caught. (See [`SymbolInterner`].)
+   */
+  pub fn try_resolve(&self, symbol: Symbol<K>) -> Option<&str> {
+    self.strings.get(symbol.index()).map(|string| &**string)
+  }
+  
+  /**
+   * An iterator over all of the strings in this `Reader` along with their symbols, in the order they were interned.
+   */
+  pub fn iter(&self) -> Iter<'_, K> {
+    Iter::new(self.strings.iter().enumerate())
+  }
+  
+  /**
+   * Consumes this `Reader` and returns a [`Resolver`], which can resolve the same symbols, but cannot look up strings.
+   * 
+   * A `Resolver` only keeps the strings themselves, so it drops the map from strings to symbols, which is most of this `Reader`'s memory besides the strings.
+   */
+  pub fn into_resolver(self) -> Resolver<K> {
+    Resolver { strings: self.strings, key: PhantomData }
+  }
+  
+}
+
+impl<K: Key, S: BuildHasher> Reader<K, S> {
+  
+  /**
+   * Returns the symbol for the given string, if it was interned.
+   */
+  pub fn get(&self, string: &str) -> Option<Symbol<K>> {
+    self.symbols.get(string).copied()
+  }
+  
+  /**
+   * Returns whether the given string was interned.
+   */
+  pub fn contains(&self, string: &str) -> bool {
+    self.symbols.contains_key(string)
+  }
+  
+}
+
+impl<K: Clone, S: Clone> Clone for Reader<K, S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone(), symbols: self.symbols.clone() }
+  }
+  
+}
+
+impl<K: Key, S> Debug for Reader<K, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+  
+}
+
+impl<K: Key, S> Index<Symbol<K>> for Reader<K, S> {
+  
+  type Output = str;
+  
+  /**
+   * Equivalent to [`resolve`](Reader::resolve).
+   */
+  fn index(&self, symbol: Symbol<K>) -> &str {
+    self.resolve(symbol)
+  }
+  
+}
+
+impl<'a, K: Key, S> IntoIterator for &'a Reader<K, S> {
+  
+  type Item = (Symbol<K>, &'a str);
+  type IntoIter = Iter<'a, K>;
+  
+  fn into_iter(self) -> Iter<'a, K> {
+    self.iter()
+  }
+  
+}
+
+/**
+ * A read-only [`SymbolInterner`] which can only resolve symbols to their strings.
+ * 
+ * This `struct` is created by the [`into_resolver`](Reader::into_resolver) method on [`Reader`].
+ * Every symbol from the original `SymbolInterner` resolves to the same string with this `Resolver`.
+ */
+pub struct Resolver<K = DefaultKey> {
+  
+  /**
+   * The interned strings, indexed by their symbols.
+   */
+  strings: Box<[Arc<str>]>,
+  key: PhantomData<K>
+  
+}
+
+impl<K: Key> Resolver<K> {
+  
+  /**
+   * Returns the number of strings in this `Resolver`.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether this `Resolver` contains no strings.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for.
+   * 
+   * # Panics
+   * This method panics if the given symbol did not come from the same `SymbolInterner` as this `Resolver` and is out of range for it.
+   * (A symbol from another `SymbolInterner` may instead resolve to an unrelated string; see [`SymbolInterner`].)
+   */
+  pub fn resolve(&self, symbol: Symbol<K>) -> &str {
+    self.try_resolve(symbol).expect("symbol did not come from this interner")
+  }
+  
+  /**
+   * Returns the string that the given symbol stands for, or `None` if it is out of range for this `Resolver`.
+   * 
+   * This can only return `None` if the given symbol came from another `SymbolInterner`, which is not guaranteed to be caught. (See [`SymbolInterner`].)
+   */
+  pub fn try_resolve(&self, symbol: Symbol<K>) -> Option<&str> {
+    self.strings.get(symbol.index()).map(|string| &**string)
+  }
+  
+  /**
+   * An iterator over all of the strings in this `Resolver` along with their symbols, in the order they were interned.
+   */
+  pub fn iter(&self) -> Iter<'_, K> {
+    Iter::new(self.strings.iter().enumerate())
+  }
+  
+}
+
+impl<K> Clone for Resolver<K> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone(), key: PhantomData }
+  }
+  
+}
+
+impl<K: Key> Debug for Resolver<K> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+  
+}
+
+impl<K: Key> Index<Symbol<K>> for Resolver<K> {
+  
+  type Output = str;
+  
+  /**
+   * Equivalent to [`resolve`](Resolver::resolve).
+   */
+  fn index(&self, symbol: Symbol<K>) -> &str {
+    self.resolve(symbol)
+  }
+  
+}
+
+impl<'a, K: Key> IntoIterator for &'a Resolver<K> {
+  
+  type Item = (Symbol<K>, &'a str);
+  type IntoIter = Iter<'a, K>;
+  
+  fn into_iter(self) -> Iter<'a, K> {
+    self.iter()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in a [`SymbolInterner`], [`Reader`], or [`Resolver`] along with their symbols.
+ * 
+ * This `struct` is created by the `iter` method on [`SymbolInterner`], [`Reader`], and [`Resolver`].
+ */
+pub struct Iter<'a, K = DefaultKey> {
+  
+  iter: Enumerate<SliceIter<'a, Arc<str>>>,
+  key: PhantomData<K>
+  
+}
+
+impl<'a, K> Iter<'a, K> {
+  
+  fn new(iter: Enumerate<SliceIter<'a, Arc<str>>>) -> Self {
+    Self { iter, key: PhantomData }
+  }
+  
+}
+
+impl<'a, K: Key> Iterator for Iter<'a, K> {
+  
+  type Item = (Symbol<K>, &'a str);
+  
+  fn next(&mut self) -> Option<(Symbol<K>, &'a str)> {
+    // Every index in `strings` was checked when its string was interned, so `Symbol::new` can't fail here.
+    self.iter.next().map(|(index, string)| (Symbol::new(index).unwrap(), &**string))
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, K: Key> DoubleEndedIterator for Iter<'a, K> {
+  
+  fn next_back(&mut self) -> Option<(Symbol<K>, &'a str)> {
+    self.iter.next_back().map(|(index, string)| (Symbol::new(index).unwrap(), &**string))
+  }
+  
+}
+
+impl<'a, K: Key> ExactSizeIterator for Iter<'a, K> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a, K: Key> FusedIterator for Iter<'a, K> {}
+
+impl<'a, K> Clone for Iter<'a, K> {
+  
+  fn clone(&self) -> Self {
+    Self::new(self.iter.clone())
+  }
+  
+}
+
+impl<'a, K: Key> Debug for Iter<'a, K> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
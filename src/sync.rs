@@ -3,16 +3,59 @@
  * Also provides a global interner (when the `global` feature is enabled), which comes with a free function `intern`, as well as an `intern` method for a few string types.
  */
 
+mod mutex;
+
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::RandomState;
-use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter};
-use std::fmt::{self, Debug, Formatter};
+use std::collections::hash_set::{Iter as SetIter, IntoIter as SetIntoIter, Drain as SetDrain};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::BuildHasher;
-use std::iter::{Sum, Product, FusedIterator};
 #[cfg(feature = "global")]
-use std::ops::Deref;
-use std::sync::{Arc, OnceLock, Mutex, MutexGuard};
+use std::hash::DefaultHasher;
+use std::io::{self, BufRead};
+use std::iter::{Sum, Product, FusedIterator};
+use std::num::NonZeroUsize;
+use std::ops::{Deref, DerefMut, Index, RangeBounds};
+use std::ptr;
+use std::slice::Iter as SliceIter;
+use std::str::{self, Split, Utf8Error};
+use std::vec::IntoIter as VecIntoIter;
+use std::sync::{Arc, Weak, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+#[cfg(any(feature = "global", feature = "global-weak"))]
+use std::sync::OnceLock;
+use std::sync::atomic;
+#[cfg(not(str_intern_loom))]
+use std::sync::atomic::AtomicU64;
+use std::thread;
+
+#[cfg(str_intern_loom)]
+use loom::sync::atomic::AtomicU64;
+#[cfg(str_intern_loom)]
+use loom::thread_local;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator, IndexedParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::iter::plumbing::{Consumer, UnindexedConsumer, ProducerCallback};
+#[cfg(feature = "rayon")]
+use rayon::slice::ParallelSlice;
+#[cfg(feature = "rayon")]
+use rayon::vec::IntoIter as ParVecIntoIter;
+#[cfg(feature = "tokio")]
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+
+use crate::ord::{OrdTable, BTreeIter};
+use crate::ordered::OrderedTable;
+use crate::scratch::ScratchBuffer;
+use crate::table::{self, Table};
+use crate::weak::WeakTable;
+
+pub use crate::{ValidationError, TryInternError, GcReport, MemoryUsage, InternStats, LenStats, RemovalCause, FixedState, InternInExt};
+
+pub use std::collections::TryReserveError;
 
 /**
  * The type of strings that have been interned.
@@ -21,6 +64,14 @@ use std::sync::{Arc, OnceLock, Mutex, MutexGuard};
  */
 pub type InternedStr = Arc<str>;
 
+/**
+ * A shared handle to an [`Interner`], which can be cloned cheaply and sent to other threads.
+ * 
+ * Unlike [`crate::SharedInterner`], this is just a type alias, since an [`Interner`] can already intern strings through a shared reference.
+ * (See [`Interner::shared`] to construct one, or [`Interner::leak`] for a handle which never needs to be freed.)
+ */
+pub type SharedInterner<S = RandomState> = Arc<Interner<S>>;
+
 /**
  * An interner will keep track of strings and ensure there is only one allocation for any given string contents.
  * 
@@ -40,11 +91,28 @@ pub type InternedStr = Arc<str>;
  * (e.g., if 200 different structs contain the string `"foo"`, an interner allows there to be 200 pointers to one allocation, rather than 200 different allocations).
  * 
  * This `Interner` is thread-safe, meaning that it implements both [`Send`] and [`Sync`] (when S implements [`Send`], which the default does).
+ * If most strings have usually already been saved, an [`RwInterner`] lets threads look them up without contending.
+ * 
+ * # Poisoning
+ * If a thread panics while this `Interner` is locked, it becomes [poisoned](https://doc.rust-lang.org/std/sync/struct.Mutex.html#poisoning),
+ * and most of its methods panic from then on; the checked methods (e.g., [`lock_checked`](Self::lock_checked)) and [`recover`](Self::recover) can be used instead.
+ * 
+ * When the `parking_lot` feature is enabled, this `Interner` uses a `parking_lot` mutex instead of a [`std::sync::Mutex`], so it is never poisoned:
+ * none of its methods panic for that reason, and the checked methods always succeed.
  */
-#[repr(transparent)]
 pub struct Interner<S = RandomState> {
   
-  strings: Mutex<HashSet<InternedStr, S>>
+  table: mutex::Mutex<Table<Shared, S>>,
+  
+  /**
+   * The version of `table` as of the last time it was unlocked, so that it can be read without locking.
+   */
+  version: AtomicU64,
+  
+  /**
+   * How many times a string had been removed from `table` as of the last time it was unlocked, so that a [`CachedInterner`] can check it without locking.
+   */
+  removals: AtomicU64
   
 }
 
@@ -57,638 +125,6500 @@ impl Interner {
     Self::from_set(HashSet::new())
   }
   
-}
-
-impl<S> Interner<S> {
-  
-  const POISON_MESSAGE: &'static str = "Interner mutex was poisoned";
+  /**
+   * Constructs a new `Interner` with space for at least `capacity` strings before it needs to reallocate.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::from_set(HashSet::with_capacity(capacity))
+  }
   
   /**
-   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   * Constructs a new `Interner` behind a [`SharedInterner`].
+   * 
+   * `Interner::shared()` is equivalent to `Arc::new(Interner::new())`.
    */
-  pub fn with_hasher(hasher: S) -> Self {
-    Self::from_set(HashSet::with_hasher(hasher))
+  pub fn shared() -> SharedInterner {
+    Arc::new(Self::new())
   }
   
   /**
-   * Construct a new `Interner` with the given set's contents already interned.
-   * The new `Interner` will also use the given set's hasher.
+   * Returns an [`InternerBuilder`], which can combine any of the options that the other constructors each set on their own, with any hasher.
    */
-  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
-    Self { strings: Mutex::new(strings) }
+  pub fn builder() -> InternerBuilder {
+    InternerBuilder::new()
   }
   
   /**
-   * Consume this `Interner` and return a set containing all of strings that were interned.
-   * The returned set also uses the same hasher.
+   * Constructs a new `Interner` which only saves strings that the given validator accepts.
    * 
-   * # Panics
-   * This method panics if this `Interner` has been poisoned.
+   * `Interner::with_validator(validator)` is equivalent to `Interner::builder().validator(validator).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * [`try_intern`](Self::try_intern) returns the validator's error (as [`TryInternError::Invalid`]) instead of saving a rejected string.
+   * Every other method which saves strings (e.g., [`intern`](Self::intern)) panics instead, so they should not be used with untrusted strings.
+   * Strings which have already been saved are not validated again.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{ValidationError, sync::Interner};
+   * let interner = Interner::with_validator(|string| {
+   *   if string.chars().any(char::is_control) {
+   *     Err(ValidationError::new("control characters are not allowed"))
+   *   } else {
+   *     Ok(())
+   *   }
+   * });
+   * assert!(interner.try_intern("foo").is_ok());
+   * assert!(interner.try_intern("foo\n").is_err());
+   * assert!(!interner.contains("foo\n"));
+   * ```
    */
-  pub fn into_set(self) -> HashSet<InternedStr, S> {
-    self.strings.into_inner().expect(Self::POISON_MESSAGE)
+  pub fn with_validator(validator: impl Fn(&str) -> Result<(), ValidationError> + Send + Sync + 'static) -> Self {
+    Self::builder().validator(validator).build()
   }
   
-  fn strings(&self) -> MutexGuard<HashSet<InternedStr, S>> {
-    self.strings.lock().expect(Self::POISON_MESSAGE)
+  /**
+   * Constructs a new `Interner` which only saves strings that are no longer than `max_len` bytes.
+   * 
+   * `Interner::with_max_len(max_len)` is equivalent to `Interner::builder().max_len(max_len).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * [`try_intern`](Self::try_intern) returns [`TryInternError::TooLong`] instead of saving a string that is too long,
+   * without allocating anything for it.
+   * Every other method which saves strings (e.g., [`intern`](Self::intern)) panics instead, so they should not be used with untrusted strings.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::{TryInternError, sync::Interner};
+   * let interner = Interner::with_max_len(3);
+   * assert!(interner.try_intern("foo").is_ok());
+   * assert_eq!(interner.try_intern("foobar"), Err(TryInternError::TooLong { len: 6, max: 3 }));
+   * ```
+   */
+  pub fn with_max_len(max_len: usize) -> Self {
+    Self::builder().max_len(max_len).build()
   }
   
   /**
-   * Locks this `Interner` and removes all of the interned strings, or blocks until it is able to do so.
+   * Constructs a new `Interner` which holds at most `max_entries` strings.
    * 
-   * `interner.clear()` is equivalent to `intenerer.lock().clear()`.
-   * (See [`LockedInterner::clear`].)
+   * `Interner::with_max_entries(max_entries)` is equivalent to `Interner::builder().max_entries(max_entries).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * When a new string is saved while the `Interner` is full, the least recently used string is evicted to make room for it.
+   * Interning a string counts as using it, whether or not it was already saved, and so does looking it up with [`get`](Self::get), [`contains`](Self::contains), or [`LockedInterner::entry`].
+   * Evicted strings remain valid for anything that still refers to them, but they are no longer canonical:
+   * interning the same contents again saves a new allocation.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::{Interner, InternedStr};
+   * let interner = Interner::with_max_entries(2);
+   * let foo = interner.intern("foo");
+   * interner.intern("bar");
+   * interner.intern("foo");
+   * interner.intern("baz");
+   * assert!(!interner.contains("bar"));
+   * assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+   * ```
    * 
    * # Panics
-   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   * This method panics if `max_entries` is 0.
    */
-  pub fn clear(&self) {
-    self.strings().clear();
+  pub fn with_max_entries(max_entries: usize) -> Self {
+    Self::builder().max_entries(max_entries).build()
   }
   
   /**
-   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so.
+   * Constructs a new `Interner` whose strings are at most `max_bytes` long in total.
    * 
-   * While it is locked, the current thread has exclusive access to this `Interner`'s methods
-   * (accessible from the [`LockedInterner`]; any methods used directly on `self` may panic).
-   * This enables some additional functionality, most notably [`LockedInterner::iter`].
+   * `Interner::with_max_bytes(max_bytes)` is equivalent to `Interner::builder().max_bytes(max_bytes).build()`. (See [`InternerBuilder`] to combine it with other options.)
    * 
-   * If a panic occurs on the current thread while this `Interner` is locked, it will become [poisoned](https://doc.rust-lang.org/std/sync/struct.Mutex.html#poisoning).
+   * When a new string is saved, the least recently used strings are evicted until there is room for it, just like [`with_max_entries`](Interner::with_max_entries).
+   * A string which is longer than `max_bytes` by itself is still returned, but it is never saved, so that it doesn't evict everything else.
+   * Only the lengths of the strings themselves count toward the limit, not any other memory that the `Interner` uses (see [`interned_bytes`](Self::interned_bytes)).
    * 
-   * # Panics
-   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::with_max_bytes(8);
+   * interner.intern("foo");
+   * interner.intern("bar");
+   * interner.intern("bazz");
+   * assert!(!interner.contains("foo"));
+   * assert_eq!(interner.interned_bytes(), 7);
+   * interner.intern("too long!");
+   * assert!(!interner.contains("too long!"));
+   * assert!(interner.contains("bar"));
+   * ```
    */
-  pub fn lock(&self) -> LockedInterner<S> {
-    LockedInterner::new(self.strings())
+  pub fn with_max_bytes(max_bytes: usize) -> Self {
+    Self::builder().max_bytes(max_bytes).build()
   }
   
-}
-
-impl<S: BuildHasher> Interner<S> {
-  
   /**
-   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * Constructs a new `Interner` which counts how many times each string is interned.
    * 
-   * `interner.intern(string)` is equivalent to `interner.lock().intern(string)`.
-   * (See [`LockedInterner::intern`].)
+   * `Interner::with_counting()` is equivalent to `Interner::builder().counting().build()`. (See [`InternerBuilder`] to combine it with other options.)
    * 
-   * # Panics
-   * This method panics if this `Interner` has been poisoned, and it may panic if this `Interner` is already locked on this thread.
+   * Every call that interns a string (e.g., [`intern`](Self::intern)) adds 1 to its count, whether or not it was already saved,
+   * and the counts can be read with [`count_of`](Self::count_of) and [`most_common`](Self::most_common).
+   * Looking a string up (e.g., with [`get`](Self::get)) does not count.
+   * A string's count is discarded along with it when it is removed (including by [`gc`](Self::gc), which ignores counts),
+   * so it starts over from 0 if it is interned again.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::with_counting();
+   * for message in ["disk full", "retrying", "disk full"] {
+   *   interner.intern(message);
+   * }
+   * assert_eq!(interner.count_of("disk full"), Some(2));
+   * assert_eq!(interner.most_common(1)[0].1, 2);
+   * ```
    */
-  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr where S: BuildHasher {
-    self.lock().intern(string)
+  pub fn with_counting() -> Self {
+    Self::builder().counting().build()
   }
   
   /**
-   * Returns whether the given string has already been saved, or blocks until it is able to do so.
+   * Constructs a new `Interner` which ignores ASCII case when checking whether a string has already been saved.
+   * 
+   * `Interner::new_ascii_case_insensitive()` is equivalent to `Interner::builder().ascii_case_insensitive().build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * Only the first casing of a string to be interned is saved, and all other casings of it return a reference to that allocation.
+   * [`get`](Self::get), [`contains`](Self::contains), and [`remove`](Self::remove) ignore ASCII case in the same way.
+   * Non-ASCII characters are compared exactly as they are.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::new_ascii_case_insensitive();
+   * let content_type = interner.intern("Content-Type");
+   * assert_eq!(interner.intern("content-type"), content_type);
+   * assert_eq!(&*content_type, "Content-Type");
+   * ```
    */
-  pub fn contains(&self, string: impl AsRef<str>) -> bool {
-    self.lock().contains(string)
+  pub fn new_ascii_case_insensitive() -> Self {
+    Self::with_hasher_ascii_case_insensitive(RandomState::new())
   }
   
   /**
-   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise, or blocks until it is able to do so.
+   * Constructs a new `Interner` which normalizes strings to [Unicode Normalization Form C](https://www.unicode.org/reports/tr15/) (NFC)
+   * before checking whether they have already been saved, and before saving them.
+   * 
+   * `Interner::new_nfc()` is equivalent to `Interner::builder().nfc().build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * This means that different representations of the same text (e.g., `"é"` as a single code point, or as `"e"` followed by a combining accent)
+   * all return a reference to the same allocation, which contains the NFC form.
+   * Strings that are already in NFC (including all ASCII strings) are not copied in order to normalize them.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::new_nfc();
+   * let composed = interner.intern("caf\u{e9}");
+   * assert_eq!(interner.intern("cafe\u{301}"), composed);
+   * assert_eq!(&*composed, "caf\u{e9}");
+   * ```
    */
-  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
-    self.lock().get(string)
+  #[cfg(feature = "unicode-normalization")]
+  pub fn new_nfc() -> Self {
+    Self::with_hasher_nfc(RandomState::new())
   }
   
-}
-
-impl<S: Clone> Clone for Interner<S> {
-  
-  fn clone(&self) -> Self {
-    Interner { strings: Mutex::new(self.strings().clone()) }
+  /**
+   * Constructs a new `Interner` with each of the given strings already interned,
+   * and returns it along with references to the saved allocations, in the same order (including any duplicates).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::{Interner, InternedStr};
+   * let (interner, keywords) = Interner::with_seeds(["if", "else", "while"]);
+   * assert!(InternedStr::ptr_eq(&interner.intern("if"), &keywords[0]));
+   * ```
+   */
+  pub fn with_seeds<I: IntoIterator>(seeds: I) -> (Self, Vec<InternedStr>) where I::Item: AsRef<str> {
+    let interner = Self::new();
+    let seeds = interner.intern_all(seeds);
+    (interner, seeds)
   }
   
-  fn clone_from(&mut self, source: &Self) {
-    self.strings().clone_from(&source.strings())
+  /**
+   * Constructs a new `Interner` with each line of the given string already interned.
+   * 
+   * Lines are split on `\n`, with any trailing `\r` removed, and empty lines are skipped.
+   */
+  pub fn from_lines(string: &str) -> Self {
+    // Counting the lines ahead of time overestimates if there are duplicates, but that's better than rehashing over and over.
+    let interner = Self::with_capacity(string.bytes().filter(|&byte| byte == b'\n').count() + 1);
+    let mut locked = interner.lock();
+    for line in string.lines().filter(|line| !line.is_empty()) {
+      locked.intern_from(line);
+    }
+    drop(locked);
+    interner
   }
   
 }
 
-impl<S: BuildHasher> PartialEq for Interner<S> {
+impl<S> Interner<S> {
   
-  fn eq(&self, other: &Self) -> bool {
-    self.strings().eq(&other.strings())
+  const POISON_MESSAGE: &'static str = "Interner mutex was poisoned";
+  
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   * 
+   * This is a `const fn`, so an `Interner` can be a `static` without any lazy initialization,
+   * as long as its hasher can be constructed in a `const` context (which [`RandomState`] can't, but [`FixedState`] can):
+   * ```rust
+   * # use str_intern::FixedState;
+   * # use str_intern::sync::{Interner, InternedStr};
+   * static INTERNER: Interner<FixedState> = Interner::with_hasher(FixedState::new());
+   * 
+   * let foo = INTERNER.intern("foo");
+   * assert!(InternedStr::ptr_eq(&INTERNER.intern("foo"), &foo));
+   * ```
+   */
+  #[cfg(not(str_intern_loom))]
+  pub const fn with_hasher(hasher: S) -> Self {
+    Self { table: mutex::Mutex::new(Table::with_hasher(hasher)), version: AtomicU64::new(0), removals: AtomicU64::new(0) }
   }
   
-  fn ne(&self, other: &Self) -> bool {
-    self.strings().ne(&other.strings())
+  /**
+   * Constructs a new `Interner` with the given hasher. See [`BuildHasher`] for more information.
+   * 
+   * (`loom`'s types can't be constructed in a `const` context, so this isn't a `const fn` when compiled with `--cfg str_intern_loom`.)
+   */
+  #[cfg(str_intern_loom)]
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { table: mutex::Mutex::new(Table::with_hasher(hasher)), version: AtomicU64::new(0), removals: AtomicU64::new(0) }
   }
   
-}
-
-impl<S: BuildHasher> Eq for Interner<S> {}
-
-impl<S> Debug for Interner<S> {
+  /**
+   * Constructs a new `Interner` with the given hasher, and with space for at least `capacity` strings before it needs to reallocate.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
+  }
   
-  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    f.debug_tuple("Interner").field(&self.strings()).finish()
+  /**
+   * Constructs a new `Interner` with the given hasher, which ignores ASCII case when checking whether a string has already been saved.
+   * (See [`new_ascii_case_insensitive`](Interner::new_ascii_case_insensitive).)
+   */
+  pub fn with_hasher_ascii_case_insensitive(hasher: S) -> Self {
+    InternerBuilder::with_hasher(hasher).ascii_case_insensitive().build()
   }
   
-}
-
-impl<S: Default> Default for Interner<S> {
+  /**
+   * Constructs a new `Interner` with the given hasher, which applies the given normalizer to every string before checking whether it has already been saved, and before saving it.
+   * 
+   * `Interner::with_normalizer(hasher, normalizer)` is equivalent to `InternerBuilder::with_hasher(hasher).normalizer(normalizer).build()`. (See [`InternerBuilder`] to combine it with other options.)
+   * 
+   * Only the normalized form of a string is ever saved, so [`get`](Self::get), [`contains`](Self::contains), and [`remove`](Self::remove)
+   * also apply the normalizer, and [`LockedInterner::iter`] yields normalized strings.
+   * The normalizer should return [`Cow::Borrowed`] if a string is already normalized, so that it does not need to be copied.
+   * 
+   * For example:
+   * ```rust
+   * # use std::borrow::Cow;
+   * # use std::collections::hash_map::RandomState;
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::with_normalizer(RandomState::new(), |string| Cow::Borrowed(string.trim()));
+   * let foo = interner.intern(" foo ");
+   * assert_eq!(interner.intern("foo"), foo);
+   * assert_eq!(&*foo, "foo");
+   * ```
+   */
+  pub fn with_normalizer(hasher: S, normalizer: impl Fn(&str) -> Cow<'_, str> + Send + Sync + 'static) -> Self {
+    InternerBuilder::with_hasher(hasher).normalizer(normalizer).build()
+  }
   
-  fn default() -> Self {
-    Self { strings: Mutex::default() }
+  /**
+   * Constructs a new `Interner` with the given hasher, which normalizes strings to NFC before checking whether they have already been saved, and before saving them.
+   * (See [`new_nfc`](Interner::new_nfc).)
+   */
+  #[cfg(feature = "unicode-normalization")]
+  pub fn with_hasher_nfc(hasher: S) -> Self {
+    Self::with_normalizer(hasher, table::nfc)
   }
   
-}
-
-impl<S> IntoIterator for Interner<S> {
+  /**
+   * Construct a new `Interner` with the given set's contents already interned.
+   * The new `Interner` will also use the given set's hasher.
+   */
+  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
+    Self::from_table(Table::new(strings))
+  }
   
-  type Item = InternedStr;
-  type IntoIter = IntoIter;
+  /**
+   * Consume this `Interner` and return a set containing all of strings that were interned.
+   * The returned set also uses the same hasher.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning).
+   */
+  pub fn into_set(self) -> HashSet<InternedStr, S> {
+    self.table.into_inner().expect(Self::POISON_MESSAGE).into_set()
+  }
   
-  fn into_iter(self) -> IntoIter {
-    IntoIter::new(self.into_set().into_iter())
+  /**
+   * Consume this `Interner` and return all of the strings that were interned, in lexicographic order. (See [`LockedInterner::iter_sorted`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning).
+   */
+  pub fn into_sorted_vec(self) -> Vec<InternedStr> {
+    let mut strings: Vec<_> = self.into_set().into_iter().collect();
+    strings.sort_unstable();
+    strings
   }
   
-}
-
-impl<A, S> FromIterator<A> for Interner<S> where HashSet<InternedStr, S>: FromIterator<A> {
+  /**
+   * Consume this `Interner` and return a [`FrozenInterner`] containing all of the strings that were interned, which can be read without locking.
+   * The returned `FrozenInterner` also uses the same hasher.
+   * 
+   * Anything else configured on this `Interner` (e.g., a [normalizer](Self::with_normalizer) or hooks) is discarded,
+   * so strings looked up in the `FrozenInterner` must be exactly equal to the interned strings.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning).
+   */
+  pub fn freeze(self) -> FrozenInterner<S> {
+    FrozenInterner::from_set(self.into_set())
+  }
   
-  fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
-    Self::from_set(HashSet::from_iter(iter))
+  /**
+   * Consume this `Interner` and leak it, returning a reference to it which lasts for the rest of the program.
+   * 
+   * This is useful for an `Interner` that is used until the program exits anyway (e.g., in a command-line tool),
+   * since the reference can be copied into anything that needs it, without lifetimes or reference counting.
+   * (If the `global` feature is enabled, [`GlobalInterner`] is another option.)
+   * The `Interner` and all of its strings are never freed, although the strings can still be removed from it.
+   * 
+   * For example:
+   * ```rust
+   * # use std::thread;
+   * # use str_intern::sync::{Interner, InternedStr};
+   * let interner: &'static Interner = Interner::new().leak();
+   * let foo = thread::spawn(|| interner.intern("foo")).join().unwrap();
+   * assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+   * ```
+   */
+  pub fn leak(self) -> &'static Self where S: 'static {
+    Box::leak(Box::new(self))
   }
   
-}
-
-/**
- * A locked [`Interner`]. This `struct` is created by [`Interner::lock`]; see its documentation for more details.
- */
-#[repr(transparent)]
-pub struct LockedInterner<'a, S = RandomState> {
+  fn from_table(table: Table<Shared, S>) -> Self {
+    Self { version: AtomicU64::new(table.version()), removals: AtomicU64::new(table.removals()), table: mutex::Mutex::new(table) }
+  }
   
-  strings: MutexGuard<'a, HashSet<InternedStr, S>>
+  fn table(&self) -> TableGuard<'_, S> {
+    self.table_guard(self.table.lock().expect(Self::POISON_MESSAGE))
+  }
   
-}
-
-impl<'a, S> LockedInterner<'a, S> {
+  /**
+   * Locks both `self` and `other`, which must be different `Interner`s, and returns their locks in that order.
+   * 
+   * Anything that locks two `Interner`s at once must use this, so that they are always locked in the same order (by address),
+   * and two threads locking the same two `Interner`s can't each be waiting for the other.
+   */
+  fn lock_both<'a>(&'a self, other: &'a Self) -> (TableGuard<'a, S>, TableGuard<'a, S>) {
+    debug_assert!(!ptr::eq(self, other));
+    if (self as *const Self) < (other as *const Self) {
+      let table = self.table();
+      (table, other.table())
+    } else {
+      let other = other.table();
+      (self.table(), other)
+    }
+  }
   
-  fn new(strings: MutexGuard<'a, HashSet<InternedStr, S>>) -> Self {
-    Self { strings }
+  fn table_guard<'a>(&'a self, table: mutex::MutexGuard<'a, Table<Shared, S>>) -> TableGuard<'a, S> {
+    TableGuard { table, version: &self.version, removals: &self.removals }
   }
   
   /**
-   * Removes all of the interned strings.
+   * Locks this `Interner` and returns the number of strings that can be interned without reallocating, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
    */
-  pub fn clear(&mut self) {
-    self.strings.clear();
+  pub fn capacity(&self) -> usize {
+    self.table().strings.capacity()
   }
   
   /**
-   * An iterator over all of the currently interned strings.
+   * Locks this `Interner` and returns the number of strings that are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
    */
-  pub fn iter(&self) -> Iter {
-    Iter::new(self.strings.iter())
+  pub fn len(&self) -> usize {
+    self.table().strings.len()
   }
   
-}
-
-impl<'a, S: BuildHasher> LockedInterner<'a, S> {
-  
   /**
-   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * Locks this `Interner` and returns whether no strings are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
    */
-  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
-    // Sorrow abounds, for behold: HashSet::get_or_insert_with doesn't exist yet.
-    let string = string.as_ref();
-    match self.strings.get(string) {
-      Some(string) => string.clone(),
-      None => {
-        let string = InternedStr::from(string);
-        self.strings.insert(InternedStr::clone(&string));
-        string
-      }
-    }
+  pub fn is_empty(&self) -> bool {
+    self.table().strings.is_empty()
   }
   
   /**
-   * Returns whether the given string has already been saved.
+   * Locks this `Interner` and returns the total length in bytes of the strings that are currently interned, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
    */
-  pub fn contains(&self, string: impl AsRef<str>) -> bool {
-    self.strings.contains(string.as_ref())
+  pub fn interned_bytes(&self) -> usize {
+    self.table().bytes()
   }
   
   /**
-   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise.
+   * Locks this `Interner` and returns an estimate of how much memory it uses, including the interned strings themselves, or blocks until it is able to do so.
+   * 
+   * `interner.memory_usage()` is equivalent to `interner.lock().memory_usage()`.
+   * (See [`LockedInterner::memory_usage`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
    */
-  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
-    self.strings.get(string.as_ref()).cloned()
+  pub fn memory_usage(&self) -> MemoryUsage {
+    self.table().memory_usage()
   }
   
-}
-
-impl<'a, S: BuildHasher> PartialEq for LockedInterner<'a, S> {
+  /**
+   * Locks this `Interner` and returns how many times strings have been interned into it and found to be already saved or not,
+   * or blocks until it is able to do so.
+   * 
+   * The counters are updated while this `Interner` is locked to intern each string, so keeping track of them costs next to nothing.
+   * 
+   * `interner.stats()` is equivalent to `interner.lock().stats()`.
+   * (See [`LockedInterner::stats`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn stats(&self) -> InternStats {
+    self.table().stats()
+  }
   
-  fn eq(&self, other: &Self) -> bool {
-    self.strings.eq(&other.strings)
+  /**
+   * Locks this `Interner` and resets all of the counters returned by [`stats`](Self::stats) to 0, or blocks until it is able to do so.
+   * 
+   * `interner.reset_stats()` is equivalent to `interner.lock().reset_stats()`.
+   * (See [`LockedInterner::reset_stats`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn reset_stats(&self) {
+    self.table().reset_stats()
   }
   
-  fn ne(&self, other: &Self) -> bool {
-    self.strings.ne(&other.strings)
+  /**
+   * Returns a counter which increases every time the set of interned strings changes, without locking this `Interner`.
+   * (See [`LockedInterner::version`].)
+   * 
+   * This is only updated when this `Interner` is unlocked, so any changes made through a [`LockedInterner`] that still exists are not reflected yet.
+   * Two equal versions from the same `Interner` mean that it contains the same strings,
+   * but versions from different `Interner`s (including clones, which start from the same version) cannot be compared.
+   */
+  pub fn version(&self) -> u64 {
+    self.version.load(atomic::Ordering::Acquire)
   }
   
-}
-
-impl<'a, S: BuildHasher> Eq for LockedInterner<'a, S> {}
-
-impl<'a, S> Debug for LockedInterner<'a, S> {
+  /**
+   * Locks this `Interner` and returns all of the interned strings, in an arbitrary order, or blocks until it is able to do so.
+   * 
+   * This only keeps this `Interner` locked for as long as it takes to clone each string's [`Arc`], rather than for as long as the strings are being used,
+   * unlike [`LockedInterner::iter`]. Strings which are interned or removed afterwards do not affect the returned `Vec`.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn snapshot(&self) -> Vec<InternedStr> {
+    self.snapshot_into()
+  }
   
-  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    f.debug_tuple("Interner").field(&self.strings).finish()
+  /**
+   * Locks this `Interner` and collects all of the interned strings into any collection, in an arbitrary order, or blocks until it is able to do so.
+   * 
+   * This is like [`snapshot`](Self::snapshot), except that it can build any collection, e.g., a [`BTreeSet`](std::collections::BTreeSet) to sort them.
+   * The collection is built while this `Interner` is locked, so it should be something that is cheap to build.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn snapshot_into<B: FromIterator<InternedStr>>(&self) -> B {
+    self.table().strings.iter().cloned().collect()
   }
   
-}
-
-impl<'a, 'b, S> IntoIterator for &'b LockedInterner<'a, S> {
+  /**
+   * Locks this `Interner` and returns a parallel iterator over all of the interned strings, or blocks until it is able to do so.
+   * 
+   * Like [`snapshot`](Self::snapshot), this only keeps this `Interner` locked for as long as it takes to clone each string's [`Arc`],
+   * so it can still be used while the strings are being processed, but strings which are interned or removed afterwards do not affect the iterator.
+   * `interner.par_snapshot()` is equivalent to `interner.par_iter()` (provided by the [`IntoParallelRefIterator`](rayon::iter::IntoParallelRefIterator) trait).
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  #[cfg(feature = "rayon")]
+  pub fn par_snapshot(&self) -> ParIntoIter {
+    ParIntoIter::new(self.snapshot().into_par_iter())
+  }
+  
+  /**
+   * Locks this `Interner` and returns statistics about the lengths of the interned strings, including a histogram, or blocks until it is able to do so.
+   * 
+   * The statistics are all computed while the lock is held, so they are consistent with each other.
+   * 
+   * `interner.len_stats()` is equivalent to `interner.lock().len_stats()`.
+   * (See [`LockedInterner::len_stats`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn len_stats(&self) -> LenStats {
+    self.lock().len_stats()
+  }
+  
+  /**
+   * Locks this `Interner` and removes all of the interned strings, or blocks until it is able to do so.
+   * 
+   * `interner.clear()` is equivalent to `intenerer.lock().clear()`.
+   * (See [`LockedInterner::clear`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn clear(&self) {
+    self.table().clear();
+  }
+  
+  /**
+   * Locks this `Interner`, removes every interned string which is not referenced outside of it, and returns how many strings were removed, and how many bytes they contained,
+   * or blocks until it is able to do so.
+   * 
+   * `interner.gc()` is equivalent to `interner.lock().gc()`.
+   * (See [`LockedInterner::gc`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn gc(&self) -> GcReport {
+    self.lock().gc()
+  }
+  
+  /**
+   * Locks this `Interner` and returns how many strings are saved between each automatic [`gc`](Self::gc), or blocks until it is able to do so.
+   * (See [`LockedInterner::gc_threshold`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn gc_threshold(&self) -> usize {
+    self.lock().gc_threshold()
+  }
+  
+  /**
+   * Locks this `Interner` and makes it automatically [`gc`](Self::gc) after saving every `threshold` new strings, or never if `threshold` is 0,
+   * or blocks until it is able to do so.
+   * 
+   * `interner.set_gc_threshold(threshold)` is equivalent to `interner.lock().set_gc_threshold(threshold)`.
+   * (See [`LockedInterner::set_gc_threshold`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn set_gc_threshold(&self, threshold: usize) {
+    self.lock().set_gc_threshold(threshold)
+  }
+  
+  /**
+   * Locks this `Interner` and removes every interned string for which the given predicate returns `false`, or blocks until it is able to do so.
+   * 
+   * `interner.retain(predicate)` is equivalent to `interner.lock().retain(predicate)`.
+   * (See [`LockedInterner::retain`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn retain(&self, predicate: impl FnMut(&InternedStr) -> bool) {
+    self.lock().retain(predicate)
+  }
+  
+  /**
+   * Locks this `Interner` and makes it call the given function with every string that is removed from it, along with why it was removed,
+   * or blocks until it is able to do so.
+   * 
+   * `interner.set_removal_hook(hook)` is equivalent to `interner.lock().set_removal_hook(hook)`.
+   * (See [`LockedInterner::set_removal_hook`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn set_removal_hook(&self, hook: impl FnMut(&InternedStr, RemovalCause) + Send + 'static) {
+    self.lock().set_removal_hook(hook)
+  }
+  
+  /**
+   * Locks this `Interner` and makes it call the given function with every string that is newly saved in it, or blocks until it is able to do so.
+   * 
+   * `interner.set_insert_hook(hook)` is equivalent to `interner.lock().set_insert_hook(hook)`.
+   * (See [`LockedInterner::set_insert_hook`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn set_insert_hook(&self, hook: impl FnMut(&InternedStr) + Send + 'static) {
+    self.lock().set_insert_hook(hook)
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * While it is locked, the current thread has exclusive access to this `Interner`'s methods
+   * (accessible from the [`LockedInterner`]; any methods used directly on `self` may panic).
+   * This enables some additional functionality, most notably [`LockedInterner::iter`].
+   * To do something with the [`LockedInterner`] without holding on to it, see [`visit`](Self::visit).
+   * 
+   * If a panic occurs on the current thread while this `Interner` is locked, it will become [poisoned](Interner#poisoning) (unless the `parking_lot` feature is enabled).
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn lock(&self) -> LockedInterner<'_, S> {
+    LockedInterner::new(self.table())
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, if it is not already locked, without blocking.
+   * 
+   * This returns `None` if this `Interner` is currently locked (whether on this thread or another), which does not mean that it has been poisoned;
+   * to tell if it has been, see [`try_lock_checked`](Self::try_lock_checked).
+   * Otherwise, this is the same as [`lock`](Self::lock).
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning).
+   */
+  pub fn try_lock(&self) -> Option<LockedInterner<'_, S>> {
+    self.try_lock_checked().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, if it is not already locked, without blocking.
+   * 
+   * This is like [`try_lock`](Self::try_lock), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning),
+   * from which the [`LockedInterner`] can still be recovered.
+   * It returns `Ok(None)` if this `Interner` is currently locked (whether on this thread or another).
+   */
+  pub fn try_lock_checked(&self) -> Result<Option<LockedInterner<'_, S>>, PoisonedInterner<LockedInterner<'_, S>>> {
+    match self.table.try_lock() {
+      Ok(table) => Ok(Some(self.guard(table))),
+      Err(TryLockError::WouldBlock) => Ok(None),
+      Err(TryLockError::Poisoned(error)) => Err(PoisonedInterner::new(self.guard(error.into_inner())))
+    }
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * This is like [`lock`](Self::lock), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning),
+   * from which the [`LockedInterner`] can still be recovered.
+   * 
+   * # Panics
+   * This method may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn lock_checked(&self) -> Result<LockedInterner<'_, S>, PoisonedInterner<LockedInterner<'_, S>>> {
+    self.table.lock().map(|table| self.guard(table)).map_err(|error| PoisonedInterner::new(self.guard(error.into_inner())))
+  }
+  
+  /**
+   * Clears the poisoned state of this `Interner`, if it has been poisoned, so that its other methods stop panicking (or returning [`PoisonedInterner`]).
+   * 
+   * This is safe for an `Interner` because a panic can never leave its set of strings in an inconsistent state:
+   * each string is either saved or not, and a saved string is never modified.
+   * At worst, a panic in a hook (see [`set_insert_hook`](Self::set_insert_hook) and [`set_removal_hook`](Self::set_removal_hook))
+   * can leave bookkeeping like [`stats`](Self::stats) slightly inaccurate.
+   * 
+   * When the `parking_lot` feature is enabled, this does nothing, since an `Interner` is never poisoned.
+   */
+  pub fn clear_poison(&self) {
+    self.table.clear_poison()
+  }
+  
+  /**
+   * Locks this `Interner` on the current thread until the returned [`LockedInterner`] is dropped, or blocks until it is able to do so,
+   * clearing its poisoned state if it has been poisoned. (See [`clear_poison`](Self::clear_poison) for why this is safe.)
+   * 
+   * # Panics
+   * This method may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn recover(&self) -> LockedInterner<'_, S> {
+    self.lock_checked().unwrap_or_else(|poisoned| {
+      self.table.clear_poison();
+      poisoned.into_inner()
+    })
+  }
+  
+  /**
+   * Locks this `Interner`, calls the given function with the [`LockedInterner`], and unlocks it again, or blocks until it is able to do so.
+   * 
+   * This is the recommended way to do several things with this `Interner` atomically, since no other thread can use it in the meantime,
+   * and unlike with [`lock`](Self::lock), the lock can't accidentally be held for longer than intended (e.g., across an `.await`).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::new();
+   * let newly_saved = interner.visit(|locked| {
+   *   let newly_saved = !locked.contains("foo");
+   *   locked.intern("foo");
+   *   newly_saved
+   * });
+   * assert!(newly_saved);
+   * ```
+   * 
+   * The function must not use this `Interner` directly, since it is already locked on this thread.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   * If the given function panics, this `Interner` becomes poisoned (unless the `parking_lot` feature is enabled).
+   */
+  pub fn visit<R>(&self, f: impl FnOnce(&mut LockedInterner<'_, S>) -> R) -> R {
+    f(&mut self.lock())
+  }
+  
+  /**
+   * Locks this `Interner`, calls the given function with each of the interned strings, in an arbitrary order, and unlocks it again,
+   * or blocks until it is able to do so.
+   * 
+   * This is lighter than a [`snapshot`](Self::snapshot), but this `Interner` stays locked for as long as the function takes with all of the strings.
+   * The function must not use this `Interner`, since it is already locked on this thread.
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   * If the given function panics, this `Interner` becomes poisoned (unless the `parking_lot` feature is enabled).
+   */
+  pub fn for_each(&self, f: impl FnMut(&InternedStr)) {
+    self.lock().iter().for_each(f)
+  }
+  
+  fn guard<'a>(&'a self, table: mutex::MutexGuard<'a, Table<Shared, S>>) -> LockedInterner<'a, S> {
+    LockedInterner::new(self.table_guard(table))
+  }
+  
+  /**
+   * Locks this `Interner`, calls `f` with it, and returns the result, or a [`PoisonedInterner`] containing the result if this `Interner` has been [poisoned](Interner#poisoning).
+   */
+  fn checked<T>(&self, f: impl FnOnce(&mut LockedInterner<'_, S>) -> T) -> Result<T, PoisonedInterner<T>> {
+    match self.lock_checked() {
+      Ok(mut locked) => Ok(f(&mut locked)),
+      Err(poisoned) => Err(poisoned.map(|mut locked| f(&mut locked)))
+    }
+  }
+  
+  /**
+   * Locks this `Interner` and removes all of the interned strings, or blocks until it is able to do so.
+   * 
+   * This is like [`clear`](Self::clear), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning)
+   * (in which case the strings are still removed).
+   * 
+   * # Panics
+   * This method may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn try_clear(&self) -> Result<(), PoisonedInterner<()>> {
+    self.checked(|locked| locked.clear())
+  }
+  
+  /**
+   * Consume this `Interner` and return a set containing all of strings that were interned.
+   * 
+   * This is like [`into_set`](Self::into_set), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning),
+   * from which the set can still be recovered.
+   */
+  pub fn try_into_set(self) -> Result<HashSet<InternedStr, S>, PoisonedSet<S>> {
+    self.table.into_inner().map(Table::into_set).map_err(|error| PoisonedInterner::new(error.into_inner().into_set()))
+  }
+  
+  /**
+   * Consume this `Interner` and return an owning iterator over all of the strings that were interned.
+   * 
+   * This is like [`into_iter`](IntoIterator::into_iter), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning),
+   * from which the iterator can still be recovered.
+   */
+  pub fn try_into_iter(self) -> Result<IntoIter, PoisonedInterner<IntoIter>> {
+    self.try_into_set().map(|set| IntoIter::new(set.into_iter())).map_err(|error| error.map(|set| IntoIter::new(set.into_iter())))
+  }
+  
+}
+
+impl<S: BuildHasher> Interner<S> {
+  
+  /**
+   * Locks this `Interner` and reserves space for at least `additional` more strings to be interned without reallocating, or blocks until it is able to do so.
+   * 
+   * `interner.reserve(additional)` is equivalent to `interner.lock().reserve(additional)`.
+   * (See [`LockedInterner::reserve`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn reserve(&self, additional: usize) {
+    self.lock().reserve(additional)
+  }
+  
+  /**
+   * Locks this `Interner` and tries to reserve space for at least `additional` more strings to be interned without reallocating, or blocks until it is able to do so.
+   * 
+   * `interner.try_reserve(additional)` is equivalent to `interner.lock().try_reserve(additional)`.
+   * (See [`LockedInterner::try_reserve`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn try_reserve(&self, additional: usize) -> Result<(), TryReserveError> {
+    self.lock().try_reserve(additional)
+  }
+  
+  /**
+   * Locks this `Interner` and shrinks its capacity as much as possible while still holding all of the currently interned strings, or blocks until it is able to do so.
+   * 
+   * `interner.shrink_to_fit()` is equivalent to `interner.lock().shrink_to_fit()`.
+   * (See [`LockedInterner::shrink_to_fit`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn shrink_to_fit(&self) {
+    self.lock().shrink_to_fit()
+  }
+  
+  /**
+   * Locks this `Interner`, shrinks its capacity as much as possible while still holding all of the currently interned strings, but not below `min_capacity`,
+   * and returns how much the capacity decreased, or blocks until it is able to do so.
+   * 
+   * `interner.compact(min_capacity)` is equivalent to `interner.lock().compact(min_capacity)`.
+   * (See [`LockedInterner::compact`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn compact(&self, min_capacity: usize) -> usize {
+    self.lock().compact(min_capacity)
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern(string)` is equivalent to `interner.lock().intern(string)`.
+   * (See [`LockedInterner::intern`].)
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Unlike with [`LockedInterner::intern`], this `Interner` is not poisoned if the given string is rejected.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr where S: BuildHasher {
+    // Don't panic while locked, since that would poison this Interner.
+    // The string is hashed while locked, too: it has to be normalized first (by the Table, which is behind the lock),
+    // and HashSet has no stable way to look up a precomputed hash anyway.
+    self.try_intern(string).unwrap_or_else(|error| panic!("string was rejected by the interner: {error}"))
+  }
+  
+  /**
+   * Unwraps the result of saving some strings, panicking if any of them was rejected.
+   * 
+   * This must only be called once this `Interner` is no longer locked, since panicking while it is locked would poison it.
+   */
+  fn accept<T>(result: Result<T, TryInternError>) -> T {
+    result.unwrap_or_else(|error| panic!("string was rejected by the interner: {error}"))
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * This is like [`intern`](Self::intern), except that it returns a [`PoisonedInterner`] instead of panicking if this `Interner` has been [poisoned](Interner#poisoning)
+   * (in which case the string is still saved, and the saved allocation can be recovered from the error).
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if the given string is rejected.
+   */
+  pub fn try_intern_checked(&self, string: impl AsRef<str>) -> Result<InternedStr, PoisonedInterner<InternedStr>> where S: BuildHasher {
+    // Don't panic while locked, since that would poison this Interner.
+    self.checked(|locked| locked.try_intern(string)).map(Self::accept).map_err(|poisoned| poisoned.map(Self::accept))
+  }
+  
+  /**
+   * If this `Interner` is not currently locked, locks it, saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * Otherwise, returns `None` without blocking.
+   * 
+   * This is useful when it is better to use an allocation that is not interned (e.g., [`InternedStr::from`]) than to wait,
+   * in which case that allocation can be interned later (e.g., with [`canonicalize`](Self::canonicalize)).
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, or if this `Interner` has been [poisoned](Interner#poisoning).
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if the given string is rejected.
+   */
+  pub fn try_intern_nonblocking(&self, string: impl AsRef<str>) -> Option<InternedStr> where S: BuildHasher {
+    let result = self.try_lock()?.try_intern(string);
+    Some(result.unwrap_or_else(|error| panic!("string was rejected by the interner: {error}")))
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * or returns an error if the string is rejected, or blocks until it is able to do so.
+   * 
+   * `interner.try_intern(string)` is equivalent to `interner.lock().try_intern(string)`.
+   * (See [`LockedInterner::try_intern`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn try_intern(&self, string: impl AsRef<str>) -> Result<InternedStr, TryInternError> where S: BuildHasher {
+    self.lock().try_intern(string)
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * along with whether it was newly saved (i.e., `true` if it was not already saved), or blocks until it is able to do so.
+   * A string which is too long to ever be saved by an `Interner` [with a maximum number of bytes](Interner::with_max_bytes) is returned with `false`, since it was not saved.
+   * 
+   * `interner.intern_full(string)` is equivalent to `interner.lock().intern_full(string)`.
+   * (See [`LockedInterner::intern_full`].)
+   * Unlike checking [`contains`](Self::contains) before calling [`intern`](Self::intern), no other thread can save the string in between.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn intern_full(&self, string: impl AsRef<str>) -> (InternedStr, bool) {
+    // Don't panic while locked, since that would poison this Interner.
+    let result = self.table().try_intern(string.as_ref());
+    Self::accept(result)
+  }
+  
+  /**
+   * Locks this `Interner`, makes the given allocation canonical if its contents are not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.canonicalize(string)` is equivalent to `interner.lock().canonicalize(string)`.
+   * (See [`LockedInterner::canonicalize`].)
+   * 
+   * # Panics
+   * This method panics if the contents of the given string are rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn canonicalize(&self, string: InternedStr) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Locks this `Interner`, replaces each of the given references with a reference to the saved allocation for its contents,
+   * and returns how many references were replaced, or blocks until it is able to do so.
+   * 
+   * `interner.dedupe_in_place(strings)` is equivalent to `interner.lock().dedupe_in_place(strings)`.
+   * (See [`LockedInterner::dedupe_in_place`].)
+   * 
+   * # Panics
+   * This method panics if the contents of any of the given strings are rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn dedupe_in_place(&self, strings: &mut [InternedStr]) -> usize {
+    let result = {
+      let mut table = self.table();
+      let mut collapsed = 0;
+      strings.iter_mut().try_for_each(|string| {
+        let (canonical, _) = table.try_intern(InternedStr::clone(string))?;
+        if !InternedStr::ptr_eq(&canonical, string) {
+          *string = canonical;
+          collapsed += 1;
+        }
+        Ok(())
+      }).map(|()| collapsed)
+    };
+    Self::accept(result)
+  }
+  
+  /**
+   * Locks this `Interner`, saves each of the given strings if they are not already saved, and returns references to the saved allocations, in the same order, or blocks until it is able to do so.
+   * 
+   * `interner.intern_all(strings)` is equivalent to `interner.lock().intern_all(strings)`.
+   * (See [`LockedInterner::intern_all`].)
+   * This only locks this `Interner` once, rather than once per string, and it stays locked until every string has been interned
+   * (including while the given iterator produces them).
+   * 
+   * # Panics
+   * This method panics if any of the given strings is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  #[doc(alias = "intern_many")]
+  pub fn intern_all<I: IntoIterator>(&self, strings: I) -> Vec<InternedStr> where I::Item: AsRef<str> {
+    let result = {
+      let mut table = self.table();
+      let strings = strings.into_iter();
+      table.strings.reserve(strings.size_hint().0);
+      strings.map(|string| table.try_intern(string.as_ref()).map(|(string, _)| string)).collect()
+    };
+    Self::accept(result)
+  }
+  
+  /**
+   * Saves each of the given strings if they are not already saved, and returns references to the saved allocations, in the same order,
+   * using every thread in the current [`rayon`] thread pool.
+   * 
+   * The strings are split into one chunk per thread, and duplicates within each chunk are removed in parallel before this `Interner` is locked,
+   * so it is only locked once, and only while each chunk's distinct strings are interned (e.g., a chunk which contains `"foo"` a thousand times only interns it once).
+   * This returns the same strings as [`intern_all`](Self::intern_all), but it can be faster for large slices with many duplicates when there are several threads to use.
+   * 
+   * # Panics
+   * This method panics if any of the given strings is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  #[cfg(feature = "rayon")]
+  pub fn par_intern_all<T: AsRef<str> + Sync>(&self, strings: &[T]) -> Vec<InternedStr> {
+    let chunk_len = strings.len().div_ceil(rayon::current_num_threads()).max(1);
+    let chunks = strings.par_chunks(chunk_len).map(|chunk| {
+      let mut indices = HashMap::with_capacity(chunk.len());
+      let mut distinct = Vec::new();
+      let positions = chunk.iter().map(|string| {
+        let string = string.as_ref();
+        *indices.entry(string).or_insert_with(|| {
+          distinct.push(string);
+          distinct.len() - 1
+        })
+      }).collect::<Vec<_>>();
+      (distinct, positions)
+    }).collect::<Vec<_>>();
+    let interned = {
+      let mut table = self.table();
+      chunks.iter().map(|(distinct, _)| {
+        distinct.iter().map(|&string| table.try_intern(string).map(|(string, _)| string)).collect::<Result<Vec<_>, _>>()
+      }).collect::<Result<Vec<_>, _>>()
+    };
+    let interned = Self::accept(interned);
+    chunks.par_iter().zip(&interned).flat_map_iter(|((_, positions), interned)| {
+      positions.iter().map(|&i| InternedStr::clone(&interned[i]))
+    }).collect()
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_from(string)` is equivalent to `interner.lock().intern_from(string)`.
+   * (See [`LockedInterner::intern_from`].)
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn intern_from(&self, string: impl IntoInterned) -> InternedStr {
+    let result = self.table().try_intern(string);
+    Self::accept(result).0
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_cow(string)` is equivalent to `interner.lock().intern_cow(string)`.
+   * (See [`LockedInterner::intern_cow`].)
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn intern_cow(&self, string: Cow<'_, str>) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Validates that the given bytes are UTF-8, then locks this `Interner`, saves them as a string if they are not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_utf8(bytes)` is equivalent to `interner.lock().intern_utf8(bytes)`, except that the bytes are validated before this `Interner` is locked.
+   * (See [`LockedInterner::intern_utf8`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern_utf8(&self, bytes: &[u8]) -> Result<InternedStr, Utf8Error> {
+    str::from_utf8(bytes).map(|string| self.intern_from(string))
+  }
+  
+  /**
+   * Locks this `Interner`, saves the given bytes as a string if they are not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_utf8_lossy(bytes)` is equivalent to `interner.lock().intern_utf8_lossy(bytes)`, except that any invalid UTF-8 sequences are replaced before this `Interner` is locked.
+   * (See [`LockedInterner::intern_utf8_lossy`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern_utf8_lossy(&self, bytes: &[u8]) -> InternedStr {
+    self.intern_from(String::from_utf8_lossy(bytes))
+  }
+  
+  /**
+   * Formats the given arguments, then locks this `Interner`, saves the result if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_fmt(args)` is equivalent to `interner.lock().intern_fmt(args)`, except that the arguments are formatted before this `Interner` is locked.
+   * (See [`LockedInterner::intern_fmt`].)
+   * 
+   * # Panics
+   * This method panics if a formatting trait implementation returns an error, if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern_fmt(&self, args: fmt::Arguments<'_>) -> InternedStr {
+    match args.as_str() {
+      Some(string) => self.intern_from(string),
+      None => self.intern_from(&*ScratchBuffer::format(args))
+    }
+  }
+  
+  /**
+   * Formats the given value with its [`Display`] implementation, then locks this `Interner`, saves the result if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_display(&value)` is equivalent to `interner.lock().intern_display(&value)`, except that the value is formatted before this `Interner` is locked,
+   * so this `Interner` will not become poisoned if the value's [`Display`] implementation panics.
+   * (See [`LockedInterner::intern_display`].)
+   * 
+   * # Panics
+   * This method panics if the value's [`Display`] implementation panics or returns an error, if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern_display<T: Display + ?Sized>(&self, value: &T) -> InternedStr {
+    self.intern_fmt(format_args!("{value}"))
+  }
+  
+  /**
+   * Concatenates the given parts, with the given separator between each of them,
+   * then locks this `Interner`, saves the result if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * `interner.intern_join(separator, parts)` is equivalent to `interner.lock().intern_join(separator, parts)`, except that the parts are concatenated before this `Interner` is locked.
+   * (See [`LockedInterner::intern_join`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn intern_join<I: IntoIterator>(&self, separator: &str, parts: I) -> InternedStr where I::Item: AsRef<str> {
+    self.intern_from(&*ScratchBuffer::join(separator, parts))
+  }
+  
+  /**
+   * Locks this `Interner`, splits the given string by the given separator, saves each substring if it is not already saved, and returns references to the saved allocations, or blocks until it is able to do so.
+   * 
+   * `interner.intern_split(string, separator)` is equivalent to `interner.lock().intern_split(string, separator).collect()`.
+   * (See [`LockedInterner::intern_split`].)
+   * 
+   * # Panics
+   * This method panics if any of the substrings is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn intern_split(&self, string: &str, separator: char) -> Vec<InternedStr> {
+    let result = {
+      let mut table = self.table();
+      string.split(separator).map(|string| table.try_intern(string).map(|(string, _)| string)).collect()
+    };
+    Self::accept(result)
+  }
+  
+  /**
+   * Locks this `Interner`, reads each line from the given reader, saves it if it is not already saved, and returns how many lines were newly saved, or blocks until it is able to do so.
+   * 
+   * `interner.intern_lines(reader)` is equivalent to `interner.lock().intern_lines(reader)`.
+   * (See [`LockedInterner::intern_lines`].)
+   * Note that this `Interner` remains locked while reading, so other threads may be blocked for a long time if the reader is slow.
+   * 
+   * # Errors
+   * This method returns any error encountered while reading, in which case any lines that were read before the error remain saved.
+   * 
+   * # Panics
+   * This method panics if any of the lines is rejected, if this `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if this `Interner` is already locked on this thread.
+   * Like with [`intern`](Self::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  pub fn intern_lines<R: BufRead>(&self, reader: R) -> io::Result<usize> {
+    let result = LockedInterner::try_intern_lines(&mut self.table(), reader);
+    result.map(Self::accept)
+  }
+  
+  /**
+   * Returns whether the given string has already been saved, or blocks until it is able to do so.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.lock().contains(string)
+  }
+  
+  /**
+   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise, or blocks until it is able to do so.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.lock().get(string)
+  }
+  
+  /**
+   * Locks this `Interner` and returns how many times the given string has been interned since it was saved,
+   * or `None` if it has not been saved or this `Interner` does not count, or blocks until it is able to do so.
+   * 
+   * `interner.count_of(string)` is equivalent to `interner.lock().count_of(string)`.
+   * (See [`LockedInterner::count_of`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn count_of(&self, string: impl AsRef<str>) -> Option<u64> {
+    self.lock().count_of(string)
+  }
+  
+  /**
+   * Locks this `Interner` and returns up to `n` of the interned strings which have been interned the most times,
+   * along with how many times each of them has been interned, or blocks until it is able to do so.
+   * 
+   * `interner.most_common(n)` is equivalent to `interner.lock().most_common(n)`.
+   * (See [`LockedInterner::most_common`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn most_common(&self, n: usize) -> Vec<(InternedStr, u64)> {
+    self.lock().most_common(n)
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents, or blocks until it is able to do so.
+   * (See [`LockedInterner::is_canonical`].)
+   */
+  pub fn is_canonical(&self, string: &InternedStr) -> bool {
+    self.lock().is_canonical(string)
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents, or blocks until it is able to do so.
+   * 
+   * This is an alias for [`is_canonical`](Self::is_canonical).
+   */
+  pub fn contains_ptr(&self, string: &InternedStr) -> bool {
+    self.is_canonical(string)
+  }
+  
+  /**
+   * Locks this `Interner` and returns a reference to the saved allocation for the given string, or blocks until it is able to do so.
+   * 
+   * This is the equivalent of indexing (i.e., `interner[string]`), which cannot be implemented for `Interner` because the returned reference could not outlive the lock.
+   * 
+   * # Panics
+   * This method panics if the given string has not been saved, if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn expect_interned(&self, string: impl AsRef<str>) -> InternedStr {
+    // Don't index the LockedInterner directly, since panicking while it's locked would poison this Interner.
+    let string = string.as_ref();
+    self.get(string).unwrap_or_else(|| panic!("{string:?} has not been interned"))
+  }
+  
+  /**
+   * Locks this `Interner`, removes the given string if it has been saved, and returns the saved allocation, or `None` if it was not saved, or blocks until it is able to do so.
+   * 
+   * `interner.remove(string)` is equivalent to `interner.lock().remove(string)`.
+   * (See [`LockedInterner::remove`].)
+   * 
+   * # Panics
+   * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+   */
+  pub fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.lock().remove(string)
+  }
+  
+}
+
+impl<S: Clone> Clone for Interner<S> {
+  
+  fn clone(&self) -> Self {
+    Self::from_table(self.table().clone())
+  }
+  
+  fn clone_from(&mut self, source: &Self) {
+    // Nothing else can have `self` locked, so only `source` needs to be locked, which can't deadlock with anything else.
+    let table = self.table.get_mut().expect(Self::POISON_MESSAGE);
+    table.clone_from(&source.table());
+    // These would be `get_mut`, but `loom`'s atomics don't have it.
+    self.version.store(table.version(), atomic::Ordering::Relaxed);
+    self.removals.store(table.removals(), atomic::Ordering::Relaxed);
+  }
+  
+}
+
+impl<S: BuildHasher> PartialEq for Interner<S> {
+  
+  fn eq(&self, other: &Self) -> bool {
+    if ptr::eq(self, other) {
+      return true;
+    }
+    let (table, other) = self.lock_both(other);
+    table.strings.eq(&other.strings)
+  }
+  
+}
+
+impl<S: BuildHasher> Eq for Interner<S> {}
+
+impl<S> Debug for Interner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&self.table().strings).finish()
+  }
+  
+}
+
+impl<S: Default> Default for Interner<S> {
+  
+  fn default() -> Self {
+    Self::from_table(Table::default())
+  }
+  
+}
+
+/**
+ * Consumes this `Interner` and iterates over all of the strings that were interned.
+ * 
+ * # Panics
+ * This method panics if this `Interner` has been [poisoned](Interner#poisoning). (See [`try_into_iter`](Interner::try_into_iter).)
+ */
+impl<S> IntoIterator for Interner<S> {
+  
+  type Item = InternedStr;
+  type IntoIter = IntoIter;
+  
+  fn into_iter(self) -> IntoIter {
+    IntoIter::new(self.into_set().into_iter())
+  }
+  
+}
+
+/**
+ * Iterates over a [`snapshot`](Interner::snapshot) of the interned strings.
+ * 
+ * This `Interner` is only locked while the snapshot is taken, rather than while iterating,
+ * so it can still be used in the meantime, but strings which are interned or removed afterwards do not affect the iterator.
+ * 
+ * # Panics
+ * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+ */
+impl<S> IntoIterator for &Interner<S> {
+  
+  type Item = InternedStr;
+  type IntoIter = SnapshotIter;
+  
+  fn into_iter(self) -> SnapshotIter {
+    SnapshotIter::new(self.snapshot().into_iter())
+  }
+  
+}
+
+/**
+ * Consumes this `Interner` and iterates over all of the strings that were interned, in parallel.
+ * 
+ * # Panics
+ * This method panics if this `Interner` has been [poisoned](Interner#poisoning).
+ */
+#[cfg(feature = "rayon")]
+impl<S> IntoParallelIterator for Interner<S> {
+  
+  type Item = InternedStr;
+  type Iter = ParIntoIter;
+  
+  fn into_par_iter(self) -> ParIntoIter {
+    ParIntoIter::new(self.into_set().into_iter().collect::<Vec<_>>().into_par_iter())
+  }
+  
+}
+
+/**
+ * Iterates over a [`snapshot`](Interner::snapshot) of the interned strings, in parallel. (See [`par_snapshot`](Interner::par_snapshot).)
+ * 
+ * # Panics
+ * This method panics if this `Interner` has been [poisoned](Interner#poisoning), and it may panic if this `Interner` is already locked on this thread.
+ */
+#[cfg(feature = "rayon")]
+impl<S> IntoParallelIterator for &Interner<S> {
+  
+  type Item = InternedStr;
+  type Iter = ParIntoIter;
+  
+  fn into_par_iter(self) -> ParIntoIter {
+    self.par_snapshot()
+  }
+  
+}
+
+impl<T: IntoInterned, S: BuildHasher> Extend<T> for Interner<S> {
+  
+  /**
+   * Locks this `Interner`, and saves each of the given strings if they are not already saved, or blocks until it is able to do so.
+   * This only locks this `Interner` once, rather than once per string.
+   * 
+   * # Panics
+   * This method panics if any of the given strings is rejected, or if this `Interner` has been [poisoned](Interner#poisoning).
+   * Like with [`intern`](Interner::intern), this `Interner` is not poisoned if a string is rejected.
+   */
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let result = {
+      let mut table = self.table();
+      let mut iter = iter.into_iter();
+      table.strings.reserve(iter.size_hint().0);
+      iter.try_for_each(|string| table.try_intern(string).map(drop))
+    };
+    Self::accept(result)
+  }
+  
+}
+
+/**
+ * Constructs a new `Interner` with each of the given strings already interned.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::Interner;
+ * let interner = Interner::from(["if", "else", "while", "if"]);
+ * assert_eq!(interner.len(), 3);
+ * ```
+ */
+impl<T: IntoInterned, const N: usize> From<[T; N]> for Interner {
+  
+  fn from(strings: [T; N]) -> Self {
+    let mut interner = Self::with_capacity(N);
+    interner.extend(strings);
+    interner
+  }
+  
+}
+
+/**
+ * Constructs a new `Interner` with each of the given strings already interned.
+ */
+impl<T: AsRef<str>> From<&[T]> for Interner {
+  
+  fn from(strings: &[T]) -> Self {
+    let mut interner = Self::with_capacity(strings.len());
+    interner.extend(strings.iter().map(AsRef::as_ref));
+    interner
+  }
+  
+}
+
+/**
+ * Collects strings into a new `Interner`, saving each of them if they are not already saved.
+ * 
+ * For example:
+ * ```rust
+ * # use std::borrow::Cow;
+ * # use str_intern::sync::{Interner, InternedStr};
+ * let interner: Interner = "foo bar foo".split(' ').collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = vec![String::from("foo"), String::from("bar")].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = [Cow::Borrowed("foo"), Cow::Owned(String::from("bar"))].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * let interner: Interner = [InternedStr::from("foo"), InternedStr::from("bar")].into_iter().collect();
+ * assert_eq!(interner.len(), 2);
+ * ```
+ */
+impl<A: IntoInterned, S: BuildHasher + Default> FromIterator<A> for Interner<S> {
+  
+  fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * The lock on an [`Interner`], which updates [`Interner::version`] (and [`Interner::removals`]) when it is released.
+ */
+struct TableGuard<'a, S> {
+  
+  table: mutex::MutexGuard<'a, Table<Shared, S>>,
+  version: &'a AtomicU64,
+  removals: &'a AtomicU64
+  
+}
+
+impl<'a, S> Deref for TableGuard<'a, S> {
+  
+  type Target = Table<Shared, S>;
+  
+  fn deref(&self) -> &Table<Shared, S> {
+    &self.table
+  }
+  
+}
+
+impl<'a, S> DerefMut for TableGuard<'a, S> {
+  
+  fn deref_mut(&mut self) -> &mut Table<Shared, S> {
+    &mut self.table
+  }
+  
+}
+
+impl<'a, S> Drop for TableGuard<'a, S> {
+  
+  fn drop(&mut self) {
+    // This happens before the lock is actually released, so the version can never go backwards.
+    self.version.store(self.table.version(), atomic::Ordering::Release);
+    self.removals.store(self.table.removals(), atomic::Ordering::Release);
+  }
+  
+}
+
+/**
+ * The error returned by the checked methods of [`Interner`] (e.g., [`try_lock_checked`](Interner::try_lock_checked))
+ * when the `Interner` has been [poisoned](https://doc.rust-lang.org/std/sync/struct.Mutex.html#poisoning).
+ * 
+ * Like [`PoisonError`](std::sync::PoisonError), this does not mean the operation failed:
+ * it has still been performed, and its result can be recovered with [`into_inner`](Self::into_inner).
+ * This is always safe for an `Interner`, since none of its methods can leave it in an inconsistent state if they panic.
+ */
+pub struct PoisonedInterner<T> {
+  
+  inner: T
+  
+}
+
+impl<T> PoisonedInterner<T> {
+  
+  fn new(inner: T) -> Self {
+    Self { inner }
+  }
+  
+  fn map<U>(self, f: impl FnOnce(T) -> U) -> PoisonedInterner<U> {
+    PoisonedInterner::new(f(self.inner))
+  }
+  
+  /**
+   * Consumes this error and returns the result of the operation, as if the `Interner` had not been poisoned.
+   */
+  pub fn into_inner(self) -> T {
+    self.inner
+  }
+  
+  /**
+   * Returns a reference to the result of the operation, as if the `Interner` had not been poisoned.
+   */
+  pub fn get_ref(&self) -> &T {
+    &self.inner
+  }
+  
+  /**
+   * Returns a mutable reference to the result of the operation, as if the `Interner` had not been poisoned.
+   */
+  pub fn get_mut(&mut self) -> &mut T {
+    &mut self.inner
+  }
+  
+}
+
+impl<T> Debug for PoisonedInterner<T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("PoisonedInterner").finish_non_exhaustive()
+  }
+  
+}
+
+impl<T> Display for PoisonedInterner<T> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "interner was poisoned")
+  }
+  
+}
+
+impl<T> Error for PoisonedInterner<T> {}
+
+/**
+ * The error returned by [`Interner::try_into_set`], from which the set can still be recovered with [`into_inner`](PoisonedInterner::into_inner).
+ */
+pub type PoisonedSet<S = RandomState> = PoisonedInterner<HashSet<InternedStr, S>>;
+
+/**
+ * A builder for an [`Interner`], which can combine any of the ways an `Interner` can behave, with any hasher.
+ * 
+ * Each of the specialized constructors on [`Interner`] (e.g., [`with_max_entries`](Interner::with_max_entries)) is a shortcut for a builder with just one option set.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{Interner, FixedState};
+ * let interner = Interner::builder()
+ *   .hasher(FixedState::with_seed(42))
+ *   .ascii_case_insensitive()
+ *   .max_entries(2)
+ *   .build();
+ * let content_type = interner.intern("Content-Type");
+ * assert_eq!(interner.intern("content-type"), content_type);
+ * interner.intern("Accept");
+ * interner.intern("Host");
+ * assert!(!interner.contains("CONTENT-TYPE"));
+ * ```
+ */
+pub struct InternerBuilder<S = RandomState> {
+  
+  hasher: S,
+  capacity: usize,
+  options: table::Options<Shared>
+  
+}
+
+impl InternerBuilder {
+  
+  /**
+   * Constructs a new `InternerBuilder`, which builds the same `Interner` as [`Interner::new`] until any options are set.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+}
+
+impl<S> InternerBuilder<S> {
+  
+  /**
+   * Constructs a new `InternerBuilder` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { hasher, capacity: 0, options: table::Options::new() }
+  }
+  
+  /**
+   * Uses the given hasher instead of the current one. See [`BuildHasher`] for more information.
+   */
+  pub fn hasher<T>(self, hasher: T) -> InternerBuilder<T> {
+    InternerBuilder { hasher, capacity: self.capacity, options: self.options }
+  }
+  
+  /**
+   * Makes space for at least `capacity` strings before the `Interner` needs to reallocate. (See [`Interner::with_capacity`].)
+   */
+  pub fn capacity(mut self, capacity: usize) -> Self {
+    self.capacity = capacity;
+    self
+  }
+  
+  /**
+   * Applies the given normalizer to every string before checking whether it has already been saved, and before saving it, replacing any previous normalizer.
+   * (See [`Interner::with_normalizer`].)
+   */
+  pub fn normalizer(mut self, normalizer: impl Fn(&str) -> Cow<'_, str> + Send + Sync + 'static) -> Self {
+    self.options.normalizer = Some(Arc::new(normalizer));
+    self
+  }
+  
+  /**
+   * Normalizes strings to NFC before checking whether they have already been saved, and before saving them, replacing any previous normalizer.
+   * (See [`Interner::new_nfc`].)
+   */
+  #[cfg(feature = "unicode-normalization")]
+  pub fn nfc(self) -> Self {
+    self.normalizer(table::nfc)
+  }
+  
+  /**
+   * Only saves strings that the given validator accepts, replacing any previous validator. (See [`Interner::with_validator`].)
+   * 
+   * If there is also a normalizer, the validator is given the normalized string.
+   */
+  pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), ValidationError> + Send + Sync + 'static) -> Self {
+    self.options.validator = Some(Arc::new(validator));
+    self
+  }
+  
+  /**
+   * Only saves strings that are no longer than `max_len` bytes (before they are normalized). (See [`Interner::with_max_len`].)
+   */
+  pub fn max_len(mut self, max_len: usize) -> Self {
+    self.options.max_len = Some(max_len);
+    self
+  }
+  
+  /**
+   * Holds at most `max_entries` strings, evicting the least recently used one to make room for a new one. (See [`Interner::with_max_entries`].)
+   * 
+   * This can be combined with [`max_bytes`](Self::max_bytes), in which case strings are evicted until both limits are met.
+   * 
+   * # Panics
+   * This method panics if `max_entries` is 0.
+   */
+  pub fn max_entries(mut self, max_entries: usize) -> Self {
+    assert!(max_entries != 0, "an interner must be able to hold at least one string");
+    self.options.max_entries = Some(max_entries);
+    self
+  }
+  
+  /**
+   * Holds strings which are at most `max_bytes` long in total, evicting the least recently used ones to make room for a new one.
+   * (See [`Interner::with_max_bytes`].)
+   * 
+   * This can be combined with [`max_entries`](Self::max_entries), in which case strings are evicted until both limits are met.
+   */
+  pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+    self.options.max_bytes = Some(max_bytes);
+    self
+  }
+  
+  /**
+   * Ignores ASCII case when checking whether a string has already been saved. (See [`Interner::new_ascii_case_insensitive`].)
+   * 
+   * If there is also a normalizer, ASCII case is ignored after normalizing.
+   */
+  pub fn ascii_case_insensitive(mut self) -> Self {
+    self.options.ascii_case_insensitive = true;
+    self
+  }
+  
+  /**
+   * Counts how many times each string is interned. (See [`Interner::with_counting`].)
+   */
+  pub fn counting(mut self) -> Self {
+    self.options.counting = true;
+    self
+  }
+  
+  /**
+   * Constructs a new, empty `Interner` with all of the options that have been set.
+   */
+  pub fn build(self) -> Interner<S> {
+    Interner::from_table(Table::with_options(HashSet::with_capacity_and_hasher(self.capacity, self.hasher), self.options))
+  }
+  
+}
+
+impl<S: Clone> Clone for InternerBuilder<S> {
+  
+  fn clone(&self) -> Self {
+    Self { hasher: self.hasher.clone(), capacity: self.capacity, options: self.options.clone() }
+  }
+  
+}
+
+impl<S: Debug> Debug for InternerBuilder<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("InternerBuilder").field("hasher", &self.hasher).field("capacity", &self.capacity).field("options", &self.options).finish()
+  }
+  
+}
+
+impl<S: Default> Default for InternerBuilder<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+/**
+ * A locked [`Interner`]. This `struct` is created by [`Interner::lock`] (or [`Interner::try_lock`]); see its documentation for more details.
+ */
+#[repr(transparent)]
+pub struct LockedInterner<'a, S = RandomState> {
+  
+  table: TableGuard<'a, S>
+  
+}
+
+impl<'a, S> LockedInterner<'a, S> {
+  
+  fn new(table: TableGuard<'a, S>) -> Self {
+    Self { table }
+  }
+  
+  /**
+   * Returns the number of strings that can be interned without reallocating.
+   */
+  pub fn capacity(&self) -> usize {
+    self.table.strings.capacity()
+  }
+  
+  /**
+   * Returns the number of strings that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.table.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.strings.is_empty()
+  }
+  
+  /**
+   * Returns the total length in bytes of the strings that are currently interned.
+   * 
+   * This is kept up to date as strings are saved and removed, so it does not need to look at every string.
+   */
+  pub fn interned_bytes(&self) -> usize {
+    self.table.bytes()
+  }
+  
+  /**
+   * Returns an estimate of how much memory this `Interner` uses, including the interned strings themselves.
+   * 
+   * Like [`interned_bytes`](Self::interned_bytes), this does not need to look at every string.
+   */
+  pub fn memory_usage(&self) -> MemoryUsage {
+    self.table.memory_usage()
+  }
+  
+  /**
+   * Returns how many times strings have been interned into this `Interner` and found to be already saved or not,
+   * since it was constructed or since the last [`reset_stats`](Self::reset_stats).
+   */
+  pub fn stats(&self) -> InternStats {
+    self.table.stats()
+  }
+  
+  /**
+   * Resets all of the counters returned by [`stats`](Self::stats) to 0.
+   */
+  pub fn reset_stats(&mut self) {
+    self.table.reset_stats()
+  }
+  
+  /**
+   * Returns a counter which increases every time the set of interned strings changes,
+   * i.e., whenever a string is newly saved or removed, but not when a string is interned which has already been saved.
+   * 
+   * This can be used to tell whether anything built from the interned strings (e.g., with [`iter`](Self::iter)) is out of date.
+   * Two equal versions from the same `Interner` mean that it contains the same strings,
+   * but versions from different `Interner`s (including clones, which start from the same version) cannot be compared.
+   */
+  pub fn version(&self) -> u64 {
+    self.table.version()
+  }
+  
+  /**
+   * Returns statistics about the lengths of the interned strings, including a histogram.
+   * 
+   * This has to look at every string, so it takes time proportional to the number of strings.
+   */
+  pub fn len_stats(&self) -> LenStats {
+    LenStats::new(self.table.strings.iter().map(|string| &**string))
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.table.clear();
+  }
+  
+  /**
+   * Removes all of the interned strings, and returns an iterator over them.
+   * 
+   * If the returned iterator is dropped before being fully consumed, the remaining strings are removed anyway.
+   * 
+   * Because this `LockedInterner` remains borrowed until the returned iterator is dropped, other threads will never observe a partially drained `Interner`.
+   */
+  pub fn drain(&mut self) -> Drain<'_> {
+    Drain::new(self.table.drain())
+  }
+  
+  /**
+   * Returns an iterator which removes and yields each interned string for which the given predicate returns `true`.
+   * 
+   * Strings are only removed as the returned iterator is advanced; if it is dropped before being fully consumed,
+   * any strings it has not yet visited are kept, whether or not they match the predicate.
+   */
+  pub fn extract_if<F: FnMut(&InternedStr) -> bool>(&mut self, predicate: F) -> ExtractIf<'_, F> {
+    ExtractIf::new(self.table.extract_if(predicate, RemovalCause::Retain))
+  }
+  
+  /**
+   * Removes every interned string for which the given predicate returns `false`.
+   */
+  pub fn retain(&mut self, predicate: impl FnMut(&InternedStr) -> bool) {
+    self.table.retain(predicate)
+  }
+  
+  /**
+   * Removes every interned string which is not referenced outside of this `Interner`, and returns how many strings were removed, and how many bytes they contained.
+   * 
+   * Any string that is still referenced elsewhere keeps its saved allocation.
+   * A string that was removed is saved in a new allocation if it is interned again.
+   * 
+   * Because this `LockedInterner` holds the lock for the whole sweep, no other thread can get a new reference to a string while it is being checked,
+   * so a string is never removed while another thread is interning it.
+   */
+  pub fn gc(&mut self) -> GcReport {
+    self.table.gc()
+  }
+  
+  /**
+   * Returns an iterator over the interned strings, along with how many times each of them is referenced outside of this `Interner`,
+   * i.e., how many [`InternedStr`]s there are for it besides the ones this `Interner` holds itself.
+   * 
+   * A count of 0 means the string would be removed by [`gc`](Self::gc).
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::new();
+   * let mut interner = interner.lock();
+   * let foo = interner.intern("foo");
+   * let foo_again = foo.clone();
+   * interner.intern("bar");
+   * let mut counts: Vec<_> = interner.ref_counts().map(|(string, count)| (&**string, count)).collect();
+   * counts.sort();
+   * assert_eq!(counts, [("bar", 0), ("foo", 2)]);
+   * ```
+   */
+  pub fn ref_counts(&self) -> RefCounts<'_> {
+    RefCounts::new(self.table.ref_counts(0))
+  }
+  
+  /**
+   * Returns an iterator over the interned strings which are referenced at least `min_refs` times outside of this `Interner`,
+   * along with how many times each of them is referenced. (See [`ref_counts`](Self::ref_counts).)
+   */
+  pub fn leaked(&self, min_refs: usize) -> RefCounts<'_> {
+    RefCounts::new(self.table.ref_counts(min_refs))
+  }
+  
+  /**
+   * Returns how many strings are saved between each automatic [`gc`](Self::gc), or 0 if this `Interner` never sweeps automatically.
+   * (See [`set_gc_threshold`](Self::set_gc_threshold).)
+   */
+  pub fn gc_threshold(&self) -> usize {
+    self.table.gc_threshold()
+  }
+  
+  /**
+   * Makes this `Interner` automatically [`gc`](Self::gc) after saving every `threshold` new strings, or never if `threshold` is 0 (the default).
+   * 
+   * Interning a string which has already been saved does not count toward the threshold.
+   * The string that triggers a sweep is never removed by it, since a reference to it is returned.
+   * Since this `LockedInterner` already holds the lock, the sweep does not need to lock again.
+   */
+  pub fn set_gc_threshold(&mut self, threshold: usize) {
+    self.table.set_gc_threshold(threshold)
+  }
+  
+  /**
+   * Makes this `Interner` call the given function with every string that is removed from it, along with why it was removed,
+   * replacing any function that was set before.
+   * 
+   * The function is called exactly once for each removed string, as soon as it is removed
+   * (which, for [`extract_if`](Self::extract_if), is when the returned iterator yields it, and for [`drain`](Self::drain), is right away).
+   * Strings which leave this `Interner` without being removed from it, e.g., through [`into_iter`](IntoIterator::into_iter), are not reported.
+   * A clone of this `Interner` does not keep the function.
+   * 
+   * The function is called while the lock is still held, on whichever thread removed the string,
+   * so it only needs to be [`Send`], and no other thread can observe the `Interner` before the function has seen what was removed from it.
+   * This also means that the function must not use this `Interner`, which may deadlock or panic.
+   */
+  pub fn set_removal_hook(&mut self, hook: impl FnMut(&InternedStr, RemovalCause) + Send + 'static) {
+    self.table.set_removal_hook(Box::new(hook))
+  }
+  
+  /**
+   * Makes this `Interner` call the given function with every string that is newly saved in it, replacing any function that was set before.
+   * 
+   * The function is called exactly once for each saved string, with the saved allocation, no matter which method saved it
+   * (including [`extend`](Extend::extend), [`intern_all`](Self::intern_all), and [`intern_lines`](Self::intern_lines)).
+   * It is not called when a string is interned which has already been saved, or which is rejected or never fits (see [`with_max_bytes`](Interner::with_max_bytes)).
+   * Strings which are already in this `Interner` when the function is set (e.g., from [`from_lines`](Interner::from_lines)) are not reported.
+   * A clone of this `Interner` does not keep the function.
+   * 
+   * Like the removal hook (see [`set_removal_hook`](Self::set_removal_hook)), the function is called while the lock is still held,
+   * so it only needs to be [`Send`], and it must not use this `Interner`.
+   */
+  pub fn set_insert_hook(&mut self, hook: impl FnMut(&InternedStr) + Send + 'static) {
+    self.table.set_insert_hook(Box::new(hook))
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.table.strings.iter())
+  }
+  
+  /**
+   * An iterator over the currently interned strings which start with the given prefix, in no particular order.
+   * 
+   * An empty prefix matches every string, and the prefix itself is included if it has been interned.
+   * This has to check every string, so it takes time proportional to the number of strings, no matter how many match.
+   * (An [`OrdInterner`](LockedOrdInterner::iter_prefix) can find them without checking the others.)
+   */
+  pub fn iter_prefix<'b>(&'b self, prefix: &'b str) -> IterPrefix<'b> {
+    IterPrefix::new(self.table.strings.iter(), prefix)
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings, in lexicographic order (i.e., the order of their UTF-8 bytes, like [`str`'s `Ord`](Ord)).
+   * 
+   * This collects a reference to every string into a temporary [`Vec`] and sorts it before yielding anything,
+   * which is all done while this `LockedInterner` holds the lock, so the order is a consistent snapshot.
+   * It takes time proportional to `n log n` for `n` strings, and allocates space for `n` references.
+   */
+  pub fn iter_sorted(&self) -> IterSorted<'_> {
+    IterSorted::new(self.table.strings.iter().collect())
+  }
+  
+}
+
+impl<'a, S: BuildHasher> LockedInterner<'a, S> {
+  
+  /**
+   * Reserves space for at least `additional` more strings to be interned without reallocating.
+   */
+  pub fn reserve(&mut self, additional: usize) {
+    self.table.strings.reserve(additional)
+  }
+  
+  /**
+   * Tries to reserve space for at least `additional` more strings to be interned without reallocating.
+   * 
+   * Unlike [`reserve`](Self::reserve), this returns an error instead of panicking or aborting if the capacity overflows or the allocation fails.
+   */
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.table.strings.try_reserve(additional)
+  }
+  
+  /**
+   * Shrinks the capacity as much as possible while still holding all of the currently interned strings.
+   * 
+   * This is particularly useful after a [`clear`](Self::clear), which does not release any memory by itself.
+   */
+  pub fn shrink_to_fit(&mut self) {
+    self.table.shrink_to(0);
+  }
+  
+  /**
+   * Shrinks the capacity as much as possible while still holding all of the currently interned strings, but not below `min_capacity`,
+   * and returns how much the capacity decreased.
+   * 
+   * Anything else this `Interner` uses to keep track of its strings (e.g., for [`new_ascii_case_insensitive`](Interner::new_ascii_case_insensitive)
+   * or [`with_max_entries`](Interner::with_max_entries)) is shrunk as well.
+   * This is particularly useful after removing most of the strings, e.g., with [`gc`](Self::gc) or [`extract_if`](Self::extract_if),
+   * since removing strings does not release any memory by itself.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::with_capacity(100);
+   * let mut interner = interner.lock();
+   * interner.intern("foo");
+   * assert!(interner.compact(0) > 0);
+   * assert_eq!(interner.compact(0), 0);
+   * ```
+   */
+  pub fn compact(&mut self, min_capacity: usize) -> usize {
+    self.table.shrink_to(min_capacity)
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, which poisons it. (See [`try_intern`](Self::try_intern).)
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.intern_from(string.as_ref())
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * or returns an error if the string is rejected, in which case nothing is saved.
+   * 
+   * # Errors
+   * This method returns [`TryInternError::TooLong`] if the string is longer than this `Interner`'s maximum length (see [`with_max_len`](Interner::with_max_len)),
+   * or [`TryInternError::Invalid`] if it is rejected by this `Interner`'s validator (see [`with_validator`](Interner::with_validator)).
+   */
+  pub fn try_intern(&mut self, string: impl AsRef<str>) -> Result<InternedStr, TryInternError> {
+    self.table.try_intern(string.as_ref()).map(|(string, _)| string)
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * along with whether it was newly saved (i.e., `true` if it was not already saved).
+   * A string which is too long to ever be saved by an `Interner` [with a maximum number of bytes](Interner::with_max_bytes) is returned with `false`, since it was not saved.
+   */
+  pub fn intern_full(&mut self, string: impl AsRef<str>) -> (InternedStr, bool) {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Saves each of the given strings if they are not already saved, and returns references to the saved allocations, in the same order.
+   */
+  #[doc(alias = "intern_many")]
+  pub fn intern_all<I: IntoIterator>(&mut self, strings: I) -> Vec<InternedStr> where I::Item: AsRef<str> {
+    let strings = strings.into_iter();
+    self.table.strings.reserve(strings.size_hint().0);
+    strings.map(|string| self.intern(string)).collect()
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is like [`intern`](Self::intern), except that it takes ownership of the string, which is simply dropped if it has already been saved.
+   * See [`IntoInterned`] for more information.
+   */
+  pub fn intern_from(&mut self, string: impl IntoInterned) -> InternedStr {
+    self.table.intern(string).0
+  }
+  
+  /**
+   * Makes the given allocation canonical if its contents are not already saved, and returns a reference to the saved allocation.
+   * 
+   * If the contents have already been saved, the given allocation is dropped and the existing one is returned instead;
+   * otherwise, the given allocation itself is saved, without being copied.
+   * This is equivalent to [`intern_from`](Self::intern_from), but is more explicit about what it accepts.
+   */
+  pub fn canonicalize(&mut self, string: InternedStr) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Replaces each of the given references with a reference to the saved allocation for its contents, saving any contents that are not already saved,
+   * and returns how many references were replaced (i.e., how many referred to an allocation other than the saved one).
+   * 
+   * Contents that are not already saved adopt the first given allocation with those contents, so nothing is copied.
+   */
+  pub fn dedupe_in_place(&mut self, strings: &mut [InternedStr]) -> usize {
+    let mut collapsed = 0;
+    for string in strings {
+      let canonical = self.canonicalize(InternedStr::clone(string));
+      if !InternedStr::ptr_eq(&canonical, string) {
+        *string = canonical;
+        collapsed += 1;
+      }
+    }
+    collapsed
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is equivalent to [`intern_from`](Self::intern_from), but is more explicit about what it accepts.
+   * Note that because an [`InternedStr`] keeps its reference counts in the same allocation as its contents,
+   * a [`Cow::Owned`] string cannot reuse its buffer, and is still copied into a new allocation if it has not already been saved.
+   */
+  pub fn intern_cow(&mut self, string: Cow<'_, str>) -> InternedStr {
+    self.intern_from(string)
+  }
+  
+  /**
+   * Validates that the given bytes are UTF-8, then saves them as a string if they are not already saved, and returns a reference to the saved allocation.
+   * 
+   * If the given bytes are not valid UTF-8, nothing is saved, and the returned [`Utf8Error`] describes where the invalid bytes begin.
+   */
+  pub fn intern_utf8(&mut self, bytes: &[u8]) -> Result<InternedStr, Utf8Error> {
+    str::from_utf8(bytes).map(|string| self.intern_from(string))
+  }
+  
+  /**
+   * Saves the given bytes as a string if they are not already saved, and returns a reference to the saved allocation.
+   * 
+   * Any invalid UTF-8 sequences are replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER), as with [`String::from_utf8_lossy`].
+   * If the given bytes are already valid UTF-8, no intermediate allocation is made.
+   */
+  pub fn intern_utf8_lossy(&mut self, bytes: &[u8]) -> InternedStr {
+    self.intern_from(String::from_utf8_lossy(bytes))
+  }
+  
+  /**
+   * Formats the given arguments, then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * This is intended to be used with [`format_args!`], e.g., `interner.intern_fmt(format_args!("{}:{}", module, name))`.
+   * Unlike interning the result of [`format!`], short results are formatted into a buffer on the stack,
+   * so no heap allocation is made unless the result has not already been saved.
+   * 
+   * # Panics
+   * This method panics if a formatting trait implementation returns an error, just like [`format!`].
+   */
+  pub fn intern_fmt(&mut self, args: fmt::Arguments<'_>) -> InternedStr {
+    match args.as_str() {
+      Some(string) => self.intern_from(string),
+      None => self.intern_from(&*ScratchBuffer::format(args))
+    }
+  }
+  
+  /**
+   * Formats the given value with its [`Display`] implementation, then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * `interner.intern_display(&value)` is equivalent to `interner.intern_fmt(format_args!("{}", value))`.
+   * (See [`intern_fmt`](Self::intern_fmt).)
+   * 
+   * If this panics, this `LockedInterner`'s [`Interner`] will become [poisoned](Interner#poisoning); [`Interner::intern_display`] avoids this by formatting the value before locking.
+   * 
+   * # Panics
+   * This method panics if the value's [`Display`] implementation panics or returns an error.
+   */
+  pub fn intern_display<T: Display + ?Sized>(&mut self, value: &T) -> InternedStr {
+    self.intern_fmt(format_args!("{value}"))
+  }
+  
+  /**
+   * Concatenates the given parts, with the given separator between each of them,
+   * then saves the result if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * Unlike interning the result of [`join`](slice::join), short results are concatenated into a buffer on the stack,
+   * so no heap allocation is made unless the result has not already been saved.
+   * If there are no parts, the empty string is saved.
+   */
+  pub fn intern_join<I: IntoIterator>(&mut self, separator: &str, parts: I) -> InternedStr where I::Item: AsRef<str> {
+    self.intern_from(&*ScratchBuffer::join(separator, parts))
+  }
+  
+  /**
+   * Returns an iterator which splits the given string by the given separator, saves each substring if it is not already saved, and yields references to the saved allocations.
+   * 
+   * Substrings are split exactly like [`str::split`], including any empty substrings, and are only saved as the returned iterator is advanced.
+   * Since this `LockedInterner` remains locked the whole time, this only needs to lock its [`Interner`] once for the whole string.
+   */
+  pub fn intern_split<'b>(&'b mut self, string: &'b str, separator: char) -> InternSplit<'b, S> {
+    InternSplit::new(&mut self.table, string.split(separator))
+  }
+  
+  /**
+   * Reads each line from the given reader, saves it if it is not already saved, and returns how many lines were newly saved.
+   * 
+   * Lines are split on `\n`, with any trailing `\r` removed, and empty lines are skipped.
+   * 
+   * # Errors
+   * This method returns any error encountered while reading, in which case any lines that were read before the error remain saved.
+   */
+  pub fn intern_lines<R: BufRead>(&mut self, reader: R) -> io::Result<usize> {
+    Self::try_intern_lines(&mut self.table, reader).map(|result| result.unwrap_or_else(|error| panic!("string was rejected by the interner: {error}")))
+  }
+  
+  /**
+   * Reads each line from the given reader and saves it in the given table if it is not already saved, stopping at the first line that is rejected,
+   * and returns how many lines were newly saved.
+   * 
+   * This doesn't panic if a line is rejected, so that [`Interner::intern_lines`] can panic once it is no longer locked.
+   */
+  fn try_intern_lines<R: BufRead>(table: &mut Table<Shared, S>, mut reader: R) -> io::Result<Result<usize, TryInternError>> {
+    // Count what was actually saved, since the table can shrink along the way (through eviction or automatic collection).
+    let mut saved = 0;
+    let mut line = String::new();
+    while reader.read_line(&mut line)? != 0 {
+      let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+      let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+      if !trimmed.is_empty() {
+        match table.try_intern(trimmed) {
+          Ok((_, true)) => saved += 1,
+          Ok((_, false)) => {}
+          Err(error) => return Ok(Err(error))
+        }
+      }
+      line.clear();
+    }
+    Ok(Ok(saved))
+  }
+  
+  /**
+   * If the given key has already been saved, returns a reference to the saved allocation;
+   * otherwise, saves the result of `make`, and returns a reference to the new saved allocation.
+   * `make` is only called if the key has not already been saved.
+   * 
+   * `interner.get_or_intern_with(key, make)` is equivalent to `interner.entry(key).or_insert_with(make)`.
+   * (See [`entry`](Self::entry) and [`Entry::or_insert_with`].)
+   * 
+   * # Panics
+   * This method panics if `make` returns a string with different contents than the key.
+   */
+  pub fn get_or_intern_with<T: Into<InternedStr>>(&mut self, key: &str, make: impl FnOnce() -> T) -> InternedStr {
+    self.entry(key).or_insert_with(make)
+  }
+  
+  /**
+   * Gets the entry for the given string, which can be used to save it only if it is not already saved.
+   * 
+   * For example:
+   * ```rust
+   * # use str_intern::sync::Interner;
+   * let interner = Interner::new();
+   * let mut locked = interner.lock();
+   * let foo = locked.entry("foo").or_insert_with(|| String::from("foo"));
+   * assert_eq!(locked.entry("foo").or_insert_with(|| -> String { unreachable!() }), foo);
+   * ```
+   */
+  pub fn entry<'b>(&'b mut self, key: &'b str) -> Entry<'b, S> {
+    Entry::new(&mut self.table, key)
+  }
+  
+  /**
+   * Returns whether the given string has already been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.get(string.as_ref()).is_some()
+  }
+  
+  /**
+   * If the given string has already been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Returns how many times the given string has been interned since it was saved,
+   * or `None` if it has not been saved or this `Interner` does not count (see [`with_counting`](Interner::with_counting)).
+   */
+  pub fn count_of(&self, string: impl AsRef<str>) -> Option<u64> {
+    self.table.count_of(string.as_ref())
+  }
+  
+  /**
+   * Returns up to `n` of the interned strings which have been interned the most times, along with how many times each of them has been interned.
+   * 
+   * They are sorted by count from most to least, and strings with the same count are sorted by their contents.
+   * If this `Interner` does not count (see [`with_counting`](Interner::with_counting)), this returns an empty `Vec`.
+   */
+  pub fn most_common(&self, n: usize) -> Vec<(InternedStr, u64)> {
+    self.table.most_common(n)
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents,
+   * rather than merely to an allocation with the same contents (e.g., one saved by a different interner).
+   */
+  pub fn is_canonical(&self, string: &InternedStr) -> bool {
+    self.table.get(string).is_some_and(|saved| InternedStr::ptr_eq(saved, string))
+  }
+  
+  /**
+   * Returns whether the given reference is to the saved allocation for its contents.
+   * 
+   * This is an alias for [`is_canonical`](Self::is_canonical).
+   */
+  pub fn contains_ptr(&self, string: &InternedStr) -> bool {
+    self.is_canonical(string)
+  }
+  
+  /**
+   * Removes the given string if it has been saved, and returns the saved allocation, or `None` if it was not saved.
+   * 
+   * Any outstanding references to the saved allocation remain valid; they are simply no longer canonical,
+   * so interning the same contents again will create a new allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.remove(string.as_ref())
+  }
+  
+}
+
+impl<'a, S: BuildHasher> PartialEq for LockedInterner<'a, S> {
+  
+  fn eq(&self, other: &Self) -> bool {
+    self.table.strings.eq(&other.table.strings)
+  }
+  
+}
+
+impl<'a, S: BuildHasher> Eq for LockedInterner<'a, S> {}
+
+impl<'a, T: IntoInterned, S: BuildHasher> Extend<T> for LockedInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.table.strings.reserve(iter.size_hint().0);
+    iter.for_each(|string| {
+      self.intern_from(string);
+    });
+  }
+  
+}
+
+impl<'a, S: BuildHasher> Index<&str> for LockedInterner<'a, S> {
+  
+  type Output = InternedStr;
+  
+  /**
+   * Returns a reference to the saved allocation for the given string.
+   * 
+   * # Panics
+   * This method panics if the given string has not been saved.
+   */
+  fn index(&self, string: &str) -> &InternedStr {
+    self.table.get(string).unwrap_or_else(|| panic!("{string:?} has not been interned"))
+  }
+  
+}
+
+impl<'a, S> Debug for LockedInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("Interner").field(&self.table.strings).finish()
+  }
+  
+}
+
+impl<'a, 'b, S> IntoIterator for &'b LockedInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = Iter<'b>;
+  
+  fn into_iter(self) -> Iter<'b> {
+    Iter::new(self.table.strings.iter())
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, 'b, S> IntoParallelIterator for &'b LockedInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type Iter = ParIter<'b>;
+  
+  fn into_par_iter(self) -> ParIter<'b> {
+    ParIter::new(self.table.strings.iter().collect::<Vec<_>>().into_par_iter())
+  }
+  
+}
+
+/**
+ * A string that can be passed to [`intern_from`](LockedInterner::intern_from) (and friends), which takes ownership of it.
+ * 
+ * Owned strings are only converted into an [`InternedStr`] if their contents have not already been saved; otherwise, they are simply dropped.
+ * An [`InternedStr`] that has not already been saved becomes the saved allocation itself, without being copied.
+ * Other owned strings must still be copied into a new allocation, since an [`InternedStr`] keeps its reference counts alongside its contents.
+ */
+pub trait IntoInterned {
+  
+  /**
+   * Returns the contents of this string, which are used to check whether it has already been saved.
+   */
+  fn as_str(&self) -> &str;
+  
+  /**
+   * Converts this string into an [`InternedStr`]. This is only called if it has not already been saved.
+   */
+  fn into_interned(self) -> InternedStr;
+  
+}
+
+impl<T: IntoInterned> table::Source<InternedStr> for T {
+  
+  fn as_str(&self) -> &str {
+    IntoInterned::as_str(self)
+  }
+  
+  fn into_saved(self) -> InternedStr {
+    self.into_interned()
+  }
+  
+}
+
+/**
+ * The kind of table behind [`Interner`] and [`LockedInterner`].
+ */
+enum Shared {}
+
+impl table::Kind for Shared {
+  
+  type Str = InternedStr;
+  type Normalizer = Arc<dyn Fn(&str) -> Cow<'_, str> + Send + Sync>;
+  type Validator = Arc<dyn Fn(&str) -> Result<(), ValidationError> + Send + Sync>;
+  type RemovalHook = Box<dyn FnMut(&InternedStr, RemovalCause) + Send>;
+  type InsertHook = Box<dyn FnMut(&InternedStr) + Send>;
+  
+  fn normalize<'s>(normalizer: &Self::Normalizer, string: &'s str) -> Cow<'s, str> {
+    normalizer(string)
+  }
+  
+  fn validate(validator: &Self::Validator, string: &str) -> Result<(), ValidationError> {
+    validator(string)
+  }
+  
+  fn strong_count(string: &InternedStr) -> usize {
+    Arc::strong_count(string)
+  }
+  
+}
+
+impl IntoInterned for &str {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for String {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for Box<str> {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for Cow<'_, str> {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    InternedStr::from(self)
+  }
+  
+}
+
+impl IntoInterned for InternedStr {
+  
+  fn as_str(&self) -> &str {
+    self
+  }
+  
+  fn into_interned(self) -> InternedStr {
+    self
+  }
+  
+}
+
+/**
+ * A view into a single string in a `LockedInterner`, which may or may not have been saved.
+ * 
+ * This `enum` is created by the [`entry`](LockedInterner::entry) method on [`LockedInterner`].
+ */
+pub enum Entry<'a, S = RandomState> {
+  
+  /**
+   * The string has already been saved.
+   */
+  Occupied(OccupiedEntry<'a, S>),
+  
+  /**
+   * The string has not been saved yet.
+   */
+  Vacant(VacantEntry<'a, S>)
+  
+}
+
+impl<'a, S: BuildHasher> Entry<'a, S> {
+  
+  fn new(table: &'a mut Table<Shared, S>, key: &'a str) -> Self {
+    match table.get(key).cloned() {
+      Some(string) => {
+        table.hit(&string);
+        Entry::Occupied(OccupiedEntry::new(table, string))
+      }
+      None => Entry::Vacant(VacantEntry::new(table, key))
+    }
+  }
+  
+  /**
+   * Returns the string that this entry was created with.
+   */
+  pub fn key(&self) -> &str {
+    match self {
+      Entry::Occupied(entry) => entry.key(),
+      Entry::Vacant(entry) => entry.key()
+    }
+  }
+  
+  /**
+   * Saves a copy of the key if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn or_insert(self) -> InternedStr {
+    match self {
+      Entry::Occupied(entry) => entry.get(),
+      Entry::Vacant(entry) => {
+        let key = entry.key;
+        entry.insert(key)
+      }
+    }
+  }
+  
+  /**
+   * Saves the result of `f` if the key is not already saved, and returns a reference to the saved allocation.
+   * `f` is only called if the key is not already saved.
+   * 
+   * # Panics
+   * This method panics if `f` returns a string with different contents than the key.
+   */
+  pub fn or_insert_with<T: Into<InternedStr>, F: FnOnce() -> T>(self, f: F) -> InternedStr {
+    match self {
+      Entry::Occupied(entry) => entry.get(),
+      Entry::Vacant(entry) => entry.insert(f())
+    }
+  }
+  
+}
+
+impl<'a, S> Debug for Entry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+      Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish()
+    }
+  }
+  
+}
+
+/**
+ * A view into a string in a `LockedInterner` that has already been saved. It is part of the [`Entry`] `enum`.
+ */
+pub struct OccupiedEntry<'a, S = RandomState> {
+  
+  table: &'a mut Table<Shared, S>,
+  string: InternedStr
+  
+}
+
+impl<'a, S> OccupiedEntry<'a, S> {
+  
+  fn new(table: &'a mut Table<Shared, S>, string: InternedStr) -> Self {
+    Self { table, string }
+  }
+  
+  /**
+   * Returns the saved string.
+   */
+  pub fn key(&self) -> &str {
+    &self.string
+  }
+  
+  /**
+   * Returns a reference to the saved allocation.
+   */
+  pub fn get(&self) -> InternedStr {
+    self.string.clone()
+  }
+  
+}
+
+impl<'a, S: BuildHasher> OccupiedEntry<'a, S> {
+  
+  /**
+   * Removes the saved string, and returns the saved allocation.
+   */
+  pub fn remove(self) -> InternedStr {
+    self.table.remove(&self.string);
+    self.string
+  }
+  
+}
+
+impl<'a, S> Debug for OccupiedEntry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OccupiedEntry").field(&self.string).finish()
+  }
+  
+}
+
+/**
+ * A view into a string in a `LockedInterner` that has not been saved yet. It is part of the [`Entry`] `enum`.
+ */
+pub struct VacantEntry<'a, S = RandomState> {
+  
+  table: &'a mut Table<Shared, S>,
+  key: &'a str
+  
+}
+
+impl<'a, S> VacantEntry<'a, S> {
+  
+  fn new(table: &'a mut Table<Shared, S>, key: &'a str) -> Self {
+    Self { table, key }
+  }
+  
+  /**
+   * Returns the string that this entry was created with.
+   */
+  pub fn key(&self) -> &str {
+    self.key
+  }
+  
+}
+
+impl<'a, S: BuildHasher> VacantEntry<'a, S> {
+  
+  /**
+   * Saves the given string, and returns a reference to the saved allocation.
+   * 
+   * # Panics
+   * This method panics if the given string has different contents than the key.
+   */
+  pub fn insert(self, string: impl Into<InternedStr>) -> InternedStr {
+    let string = string.into();
+    assert_eq!(&*string, self.key, "interned string must match its entry's key");
+    // The entry already looked the key up, so don't look it up again.
+    self.table.intern_vacant(string).0
+  }
+  
+}
+
+impl<'a, S> Debug for VacantEntry<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("VacantEntry").field(&self.key).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in a `LockedInterner` or a `FrozenInterner`.
+ * 
+ * This `struct` is created by the [`iter`](LockedInterner::iter) method on `LockedInterner`, and the [`iter`](FrozenInterner::iter) method on `FrozenInterner`.
+ */
+#[repr(transparent)]
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+  
+  iter: SetIter<'a, InternedStr>
+  
+}
+
+impl<'a> Iter<'a> {
+  
+  fn new(iter: SetIter<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Iter<'a> {
+  
+  type Item =  &'a InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.ge(other)
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+/**
+ * An iterator over the strings in an [`Interner`], in lexicographic order.
+ * 
+ * This `struct` is created by the [`iter_sorted`](LockedInterner::iter_sorted) method on [`LockedInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct IterSorted<'a> {
+  
+  iter: VecIntoIter<&'a InternedStr>
+  
+}
+
+impl<'a> IterSorted<'a> {
+  
+  fn new(mut strings: Vec<&'a InternedStr>) -> Self {
+    // Equal strings are never saved twice, so there's no order among them to preserve.
+    strings.sort_unstable();
+    Self { iter: strings.into_iter() }
+  }
+  
+}
+
+impl<'a> Iterator for IterSorted<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> DoubleEndedIterator for IterSorted<'a> {
+  
+  fn next_back(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for IterSorted<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for IterSorted<'a> {}
+
+impl<'a> Debug for IterSorted<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.iter.as_slice()).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an [`Interner`] which start with a given prefix.
+ * 
+ * This `struct` is created by the [`iter_prefix`](LockedInterner::iter_prefix) method on [`LockedInterner`].
+ */
+#[derive(Clone)]
+pub struct IterPrefix<'a> {
+  
+  iter: SetIter<'a, InternedStr>,
+  prefix: &'a str
+  
+}
+
+impl<'a> IterPrefix<'a> {
+  
+  fn new(iter: SetIter<'a, InternedStr>, prefix: &'a str) -> Self {
+    Self { iter, prefix }
+  }
+  
+}
+
+impl<'a> Iterator for IterPrefix<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    let prefix = self.prefix;
+    self.iter.find(|string| string.starts_with(prefix))
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.iter.size_hint().1)
+  }
+  
+}
+
+impl<'a> FusedIterator for IterPrefix<'a> {}
+
+impl<'a> Debug for IterPrefix<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
+
+/**
+ * An owning iterator over the strings that were in an `Interner` or a `FrozenInterner`.
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`] and [`FrozenInterner`]
+ * (provided by the [`IntoIterator`] trait).
+ */
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct IntoIter {
+  
+  iter: SetIntoIter<InternedStr>
+  
+}
+
+impl IntoIter {
+  
+  fn new(iter: SetIntoIter<InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl Iterator for IntoIter {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.ge(other)
+  }
+  
+}
+
+impl ExactSizeIterator for IntoIter {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl FusedIterator for IntoIter {}
+
+/**
+ * An iterator over a snapshot of the strings in an `Interner`, which owns its own references to them.
+ * 
+ * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`&Interner`](Interner)
+ * (provided by the [`IntoIterator`] trait).
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct SnapshotIter {
+  
+  iter: VecIntoIter<InternedStr>
+  
+}
+
+impl SnapshotIter {
+  
+  fn new(iter: VecIntoIter<InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl Iterator for SnapshotIter {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl DoubleEndedIterator for SnapshotIter {
+  
+  fn next_back(&mut self) -> Option<InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl ExactSizeIterator for SnapshotIter {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl FusedIterator for SnapshotIter {}
+
+impl Debug for SnapshotIter {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.iter.as_slice()).finish()
+  }
+  
+}
+
+/**
+ * A parallel iterator over the strings in a `LockedInterner`.
+ * 
+ * This `struct` is created by the [`into_par_iter`](IntoParallelIterator::into_par_iter) method on [`&LockedInterner`](LockedInterner)
+ * (provided by the [`IntoParallelIterator`] trait).
+ */
+#[cfg(feature = "rayon")]
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct ParIter<'a> {
+  
+  iter: ParVecIntoIter<&'a InternedStr>
+  
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> ParIter<'a> {
+  
+  fn new(iter: ParVecIntoIter<&'a InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> ParallelIterator for ParIter<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn drive_unindexed<C: UnindexedConsumer<&'a InternedStr>>(self, consumer: C) -> C::Result {
+    self.iter.drive_unindexed(consumer)
+  }
+  
+  fn opt_len(&self) -> Option<usize> {
+    self.iter.opt_len()
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> IndexedParallelIterator for ParIter<'a> {
+  
+  fn drive<C: Consumer<&'a InternedStr>>(self, consumer: C) -> C::Result {
+    self.iter.drive(consumer)
+  }
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+  fn with_producer<CB: ProducerCallback<&'a InternedStr>>(self, callback: CB) -> CB::Output {
+    self.iter.with_producer(callback)
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> Debug for ParIter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * A parallel iterator over strings that were in an `Interner`, which owns its own references to them.
+ * 
+ * This `struct` is created by the [`par_snapshot`](Interner::par_snapshot) method on [`Interner`],
+ * and by the [`into_par_iter`](IntoParallelIterator::into_par_iter) method on [`Interner`] and [`&Interner`](Interner)
+ * (provided by the [`IntoParallelIterator`] trait).
+ */
+#[cfg(feature = "rayon")]
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct ParIntoIter {
+  
+  iter: ParVecIntoIter<InternedStr>
+  
+}
+
+#[cfg(feature = "rayon")]
+impl ParIntoIter {
+  
+  fn new(iter: ParVecIntoIter<InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelIterator for ParIntoIter {
+  
+  type Item = InternedStr;
+  
+  fn drive_unindexed<C: UnindexedConsumer<InternedStr>>(self, consumer: C) -> C::Result {
+    self.iter.drive_unindexed(consumer)
+  }
+  
+  fn opt_len(&self) -> Option<usize> {
+    self.iter.opt_len()
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl IndexedParallelIterator for ParIntoIter {
+  
+  fn drive<C: Consumer<InternedStr>>(self, consumer: C) -> C::Result {
+    self.iter.drive(consumer)
+  }
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+  fn with_producer<CB: ProducerCallback<InternedStr>>(self, callback: CB) -> CB::Output {
+    self.iter.with_producer(callback)
+  }
+  
+}
+
+#[cfg(feature = "rayon")]
+impl Debug for ParIntoIter {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * A draining iterator over the strings that were in a `LockedInterner`.
+ * 
+ * This `struct` is created by the [`drain`](LockedInterner::drain) method on [`LockedInterner`].
+ * When it is dropped, any remaining strings are removed, even if it was not fully consumed.
+ */
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct Drain<'a> {
+  
+  iter: SetDrain<'a, InternedStr>
+  
+}
+
+impl<'a> Drain<'a> {
+  
+  fn new(iter: SetDrain<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for Drain<'a> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+  fn count(self) -> usize {
+    self.iter.count()
+  }
+  
+  fn last(self) -> Option<Self::Item> {
+    self.iter.last()
+  }
+  
+  fn nth(&mut self, n: usize) -> Option<Self::Item> {
+    self.iter.nth(n)
+  }
+  
+  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+    self.iter.for_each(f)
+  }
+  
+  fn collect<B: FromIterator<Self::Item>>(self) -> B {
+    self.iter.collect()
+  }
+  
+  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
+    self.iter.partition(f)
+  }
+  
+  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+    self.iter.fold(init, f)
+  }
+  
+  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
+    self.iter.reduce(f)
+  }
+  
+  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.all(f)
+  }
+  
+  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+    self.iter.any(f)
+  }
+  
+  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+    self.iter.find(predicate)
+  }
+  
+  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+    self.iter.find_map(f)
+  }
+  
+  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+    self.iter.position(predicate)
+  }
+  
+  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.max()
+  }
+  
+  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+    self.iter.min()
+  }
+  
+  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.max_by_key(f)
+  }
+  
+  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.max_by(compare)
+  }
+  
+  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+    self.iter.min_by_key(f)
+  }
+  
+  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
+    self.iter.min_by(compare)
+  }
+  
+  fn sum<S: Sum<Self::Item>>(self) -> S {
+    self.iter.sum()
+  }
+  
+  fn product<P: Product<Self::Item>>(self) -> P {
+    self.iter.product()
+  }
+  
+  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
+    self.iter.cmp(other)
+  }
+  
+  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
+    self.iter.partial_cmp(other)
+  }
+  
+  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.eq(other)
+  }
+  
+  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
+    self.iter.ne(other)
+  }
+  
+  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.lt(other)
+  }
+  
+  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.le(other)
+  }
+  
+  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.gt(other)
+  }
+  
+  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
+    self.iter.ge(other)
+  }
+  
+}
+
+impl<'a> ExactSizeIterator for Drain<'a> {
+  
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+  
+}
+
+impl<'a> FusedIterator for Drain<'a> {}
+
+/**
+ * An iterator which saves each substring of a string in a `LockedInterner`, and yields references to the saved allocations.
+ * 
+ * This `struct` is created by the [`intern_split`](LockedInterner::intern_split) method on [`LockedInterner`].
+ */
+pub struct InternSplit<'a, S = RandomState> {
+  
+  table: &'a mut Table<Shared, S>,
+  split: Split<'a, char>
+  
+}
+
+impl<'a, S> InternSplit<'a, S> {
+  
+  fn new(table: &'a mut Table<Shared, S>, split: Split<'a, char>) -> Self {
+    Self { table, split }
+  }
+  
+}
+
+impl<'a, S: BuildHasher> Iterator for InternSplit<'a, S> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.split.next().map(|string| Entry::new(self.table, string).or_insert())
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.split.size_hint()
+  }
+  
+}
+
+impl<'a, S: BuildHasher> FusedIterator for InternSplit<'a, S> {}
+
+impl<'a, S> Debug for InternSplit<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("InternSplit").field(&self.split).finish()
+  }
+  
+}
+
+/**
+ * An iterator which removes and yields the strings in a `LockedInterner` that match a predicate.
+ * 
+ * This `struct` is created by the [`extract_if`](LockedInterner::extract_if) method on [`LockedInterner`].
+ */
+#[repr(transparent)]
+pub struct ExtractIf<'a, F: FnMut(&InternedStr) -> bool> {
+  
+  iter: table::ExtractIf<'a, Shared, F>
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> ExtractIf<'a, F> {
+  
+  fn new(iter: table::ExtractIf<'a, Shared, F>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> Iterator for ExtractIf<'a, F> {
+  
+  type Item = InternedStr;
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> FusedIterator for ExtractIf<'a, F> {}
+
+impl<'a, F: FnMut(&InternedStr) -> bool> Debug for ExtractIf<'a, F> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("ExtractIf").field(&self.iter).finish()
+  }
+  
+}
+
+/**
+ * An iterator over the strings in an `Interner`, along with how many times each of them is referenced outside of the `Interner`.
+ * 
+ * This `struct` is created by the [`ref_counts`](LockedInterner::ref_counts) and [`leaked`](LockedInterner::leaked) methods on [`LockedInterner`].
+ * Its [`Debug`] implementation shows each string and its count, so it can be printed as is to look for leaks.
+ */
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RefCounts<'a> {
+  
+  iter: table::RefCounts<'a, Shared>
+  
+}
+
+impl<'a> RefCounts<'a> {
+  
+  fn new(iter: table::RefCounts<'a, Shared>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for RefCounts<'a> {
+  
+  type Item = (&'a InternedStr, usize);
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> FusedIterator for RefCounts<'a> {}
+
+impl<'a> Debug for RefCounts<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_map().entries(self.clone()).finish()
+  }
+  
+}
+
+/**
+ * A thread-safe interner which does not keep strings alive by itself.
+ * 
+ * Like an [`Interner`], a `WeakInterner` ensures there is only one allocation for any given string contents,
+ * but it only holds weak references to its strings, so a string is freed as soon as every reference to it outside of the `WeakInterner` is dropped.
+ * Interning the same contents after that creates a new allocation.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{WeakInterner, InternedStr};
+ * let interner = WeakInterner::new();
+ * let foo0 = interner.intern("foo");
+ * let foo1 = interner.intern("foo");
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * drop((foo0, foo1));
+ * assert!(!interner.contains("foo"));
+ * ```
+ * 
+ * Entries for strings that have been freed are removed whenever a string with the same hash is interned, or all at once by [`purge`](Self::purge),
+ * which also happens automatically whenever interning a string doubles the number of entries since the last time they were all purged.
+ * Until then, each of them still takes up a little memory: the reference counts of an [`InternedStr`] share an allocation with its contents,
+ * so that allocation is only released when its entry is removed.
+ * 
+ * Every method locks the `WeakInterner` for its duration, so they all panic if this `WeakInterner` has been poisoned,
+ * and they may panic if this `WeakInterner` is already locked on this thread.
+ */
+pub struct WeakInterner<S = RandomState> {
+  
+  table: Mutex<WeakTable<InternedStr, S>>
+  
+}
+
+impl WeakInterner {
+  
+  /**
+   * Constructs a new `WeakInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+}
+
+impl<S> WeakInterner<S> {
+  
+  const POISON_MESSAGE: &'static str = "WeakInterner mutex was poisoned";
+  
+  /**
+   * Constructs a new `WeakInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self { table: Mutex::new(WeakTable::new(hasher)) }
+  }
+  
+  fn table(&self) -> MutexGuard<'_, WeakTable<InternedStr, S>> {
+    self.table.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `WeakInterner` and returns the number of interned strings which have not been freed, or blocks until it is able to do so.
+   * 
+   * This has to check every entry, so it takes time proportional to the number of entries, including any that have not been purged yet.
+   * Since other threads may drop or intern strings at any time, the result may be out of date by the time it is returned.
+   */
+  pub fn len(&self) -> usize {
+    self.table().len()
+  }
+  
+  /**
+   * Locks this `WeakInterner` and returns whether every interned string has been freed, or blocks until it is able to do so.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table().is_empty()
+  }
+  
+  /**
+   * Locks this `WeakInterner` and returns the number of entries, including the ones for strings which have been freed but not purged yet (see [`purge`](Self::purge)),
+   * or blocks until it is able to do so.
+   * 
+   * Unlike [`len`](Self::len), this doesn't need to check every entry.
+   */
+  pub fn entries(&self) -> usize {
+    self.table().entries()
+  }
+  
+  /**
+   * Locks this `WeakInterner` and removes all of the entries, whether or not their strings have been freed, or blocks until it is able to do so.
+   */
+  pub fn clear(&self) {
+    self.table().clear()
+  }
+  
+  /**
+   * Locks this `WeakInterner` and removes the entries for every string that has been freed, and returns how many were removed,
+   * or blocks until it is able to do so.
+   */
+  pub fn purge(&self) -> usize {
+    self.table().purge()
+  }
+  
+}
+
+impl<S: BuildHasher> WeakInterner<S> {
+  
+  /**
+   * Locks this `WeakInterner`, and returns a reference to the saved allocation for the given string if it has not been freed,
+   * or saves the given string in a new allocation and returns a reference to that otherwise, or blocks until it is able to do so.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.table().intern(string.as_ref())
+  }
+  
+  /**
+   * Locks this `WeakInterner` and returns whether the given string has been saved, and has not been freed, or blocks until it is able to do so.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table().get(string.as_ref()).is_some()
+  }
+  
+  /**
+   * Locks this `WeakInterner`, and if the given string has been saved, and has not been freed, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().get(string.as_ref())
+  }
+  
+}
+
+impl<S> Debug for WeakInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("WeakInterner").field(&self.table().live().collect::<Vec<_>>()).finish()
+  }
+  
+}
+
+impl<S: Default> Default for WeakInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+/**
+ * An interner which remembers the order its strings were first interned in.
+ * 
+ * Like an [`Interner`], an `OrderedInterner` ensures there is only one allocation for any given string contents,
+ * but [`to_vec`](Self::to_vec) always returns its strings in the order they were first interned, and each string can be looked up by its position in that order.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::OrderedInterner;
+ * let interner = OrderedInterner::new();
+ * for string in ["foo", "bar", "foo", "baz"] {
+ *   interner.intern(string);
+ * }
+ * assert_eq!(interner.to_vec().iter().map(|string| &**string).collect::<Vec<_>>(), ["foo", "bar", "baz"]);
+ * assert_eq!(interner.index_of("bar"), Some(1));
+ * assert_eq!(interner.get_index(2).as_deref(), Some("baz"));
+ * ```
+ * 
+ * There are two ways to remove a string, which treat the order of the remaining strings differently:
+ * [`swap_remove`](Self::swap_remove) moves the last string into the removed one's position, which is fast but changes the order,
+ * while [`shift_remove`](Self::shift_remove) moves every later string back by one position, which keeps the order but takes time proportional to the number of later strings.
+ * Since other threads may remove strings at any time, a position is only meaningful while no other thread is removing strings.
+ * 
+ * Every method locks the `OrderedInterner` for its duration, so they all panic if this `OrderedInterner` has been poisoned,
+ * and they may panic if this `OrderedInterner` is already locked on this thread.
+ */
+pub struct OrderedInterner<S = RandomState> {
+  
+  table: Mutex<OrderedTable<InternedStr, S>>
+  
+}
+
+impl OrderedInterner {
+  
+  /**
+   * Constructs a new `OrderedInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> OrderedInterner<S> {
+  
+  const POISON_MESSAGE: &'static str = "OrderedInterner mutex was poisoned";
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::with_capacity_and_hasher(0, hasher)
+  }
+  
+  /**
+   * Constructs a new `OrderedInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self { table: Mutex::new(OrderedTable::with_capacity_and_hasher(capacity, hasher)) }
+  }
+  
+  /**
+   * Consume this `OrderedInterner` and return all of the strings that were interned, in the order they were first interned.
+   * 
+   * # Panics
+   * This method panics if this `OrderedInterner` has been poisoned.
+   */
+  pub fn into_vec(self) -> Vec<InternedStr> {
+    self.table.into_inner().expect(Self::POISON_MESSAGE).strings
+  }
+  
+  fn table(&self) -> MutexGuard<'_, OrderedTable<InternedStr, S>> {
+    self.table.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns the number of interned strings, or blocks until it is able to do so.
+   */
+  pub fn len(&self) -> usize {
+    self.table().len()
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns whether no strings have been interned, or blocks until it is able to do so.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table().is_empty()
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and removes all of the interned strings, or blocks until it is able to do so.
+   */
+  pub fn clear(&self) {
+    self.table().clear()
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns the interned string at the given position, if there is one, or blocks until it is able to do so.
+   */
+  pub fn get_index(&self, index: usize) -> Option<InternedStr> {
+    self.table().get_index(index).cloned()
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns all of the interned strings, in the order they were first interned, or blocks until it is able to do so.
+   * 
+   * The strings are all copied while the lock is held, so they are consistent with each other.
+   */
+  pub fn to_vec(&self) -> Vec<InternedStr> {
+    self.table().strings.clone()
+  }
+  
+}
+
+impl<S: BuildHasher> OrderedInterner<S> {
+  
+  /**
+   * Locks this `OrderedInterner`, saves the given string if it is not already saved (after all of the strings that are),
+   * and returns a reference to the saved allocation, or blocks until it is able to do so.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.table().intern(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns whether the given string has been saved, or blocks until it is able to do so.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table().index_of(string.as_ref()).is_some()
+  }
+  
+  /**
+   * Locks this `OrderedInterner`, and if the given string has been saved, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Locks this `OrderedInterner` and returns the position of the given string, if it has been saved, or blocks until it is able to do so.
+   */
+  pub fn index_of(&self, string: impl AsRef<str>) -> Option<usize> {
+    self.table().index_of(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrderedInterner`, removes the given string, if it has been saved, by moving the last string into its position,
+   * and returns the saved allocation, or blocks until it is able to do so.
+   * 
+   * This takes constant time, but changes the position of the last string. (See [`shift_remove`](Self::shift_remove).)
+   */
+  pub fn swap_remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().swap_remove(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrderedInterner`, removes the given string, if it has been saved, by moving every later string back by one position,
+   * and returns the saved allocation, or blocks until it is able to do so.
+   * 
+   * This keeps the rest of the strings in order, but takes time proportional to the number of later strings. (See [`swap_remove`](Self::swap_remove).)
+   */
+  pub fn shift_remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().shift_remove(string.as_ref())
+  }
+  
+}
+
+impl<S: Clone> Clone for OrderedInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { table: Mutex::new(self.table().clone()) }
+  }
+  
+}
+
+impl<S> Debug for OrderedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OrderedInterner").field(&self.table().strings).finish()
+  }
+  
+}
+
+impl<S: Default> Default for OrderedInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for OrderedInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let table = self.table.get_mut().expect(Self::POISON_MESSAGE);
+    for string in iter {
+      table.intern(string.as_ref());
+    }
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Default> FromIterator<T> for OrderedInterner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * An interner which keeps its strings sorted, so that they can be queried by range.
+ * 
+ * Like an [`Interner`], an `OrdInterner` ensures there is only one allocation for any given string contents,
+ * but it keeps its strings in a [`BTreeSet`](std::collections::BTreeSet) rather than a [`HashSet`], so it does not need a hasher,
+ * and while it is locked (see [`lock`](Self::lock)), its strings can be iterated over in lexicographic order, or just the ones between two bounds.
+ * Looking up a string takes time proportional to the logarithm of the number of strings, rather than constant time.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::OrdInterner;
+ * let interner = OrdInterner::new();
+ * for string in ["user:1001:name", "user:1000:name", "user:1000:email", "group:7:name"] {
+ *   interner.intern(string);
+ * }
+ * let interner = interner.lock();
+ * let user = interner.range("user:1000:".."user:1001:").map(|string| &**string).collect::<Vec<_>>();
+ * assert_eq!(user, ["user:1000:email", "user:1000:name"]);
+ * assert_eq!(interner.first().map(|string| &**string), Some("group:7:name"));
+ * ```
+ * 
+ * Every method besides [`lock`](Self::lock) locks the `OrdInterner` for its duration, so they all panic if this `OrdInterner` has been poisoned,
+ * and they may panic if this `OrdInterner` is already locked on this thread.
+ */
+#[derive(Default)]
+#[repr(transparent)]
+pub struct OrdInterner {
+  
+  table: Mutex<OrdTable<InternedStr>>
+  
+}
+
+impl OrdInterner {
+  
+  const POISON_MESSAGE: &'static str = "OrdInterner mutex was poisoned";
+  
+  /**
+   * Constructs a new `OrdInterner`.
+   */
+  pub fn new() -> Self {
+    Self { table: Mutex::new(OrdTable::default()) }
+  }
+  
+  fn table(&self) -> MutexGuard<'_, OrdTable<InternedStr>> {
+    self.table.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Locks this `OrdInterner` and returns the number of interned strings, or blocks until it is able to do so.
+   */
+  pub fn len(&self) -> usize {
+    self.table().strings.len()
+  }
+  
+  /**
+   * Locks this `OrdInterner` and returns whether no strings have been interned, or blocks until it is able to do so.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table().strings.is_empty()
+  }
+  
+  /**
+   * Locks this `OrdInterner` and removes all of the interned strings, or blocks until it is able to do so.
+   */
+  pub fn clear(&self) {
+    self.table().strings.clear()
+  }
+  
+  /**
+   * Locks this `OrdInterner`, saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * or blocks until it is able to do so.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.table().intern(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrdInterner` and returns whether the given string has been saved, or blocks until it is able to do so.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table().strings.contains(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrdInterner`, and if the given string has been saved, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().strings.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Locks this `OrdInterner`, removes the given string, if it has been saved, and returns the saved allocation, or blocks until it is able to do so.
+   */
+  pub fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table().strings.take(string.as_ref())
+  }
+  
+  /**
+   * Locks this `OrdInterner` and returns the lexicographically first interned string, if there is one, or blocks until it is able to do so.
+   */
+  pub fn first(&self) -> Option<InternedStr> {
+    self.table().strings.first().cloned()
+  }
+  
+  /**
+   * Locks this `OrdInterner` and returns the lexicographically last interned string, if there is one, or blocks until it is able to do so.
+   */
+  pub fn last(&self) -> Option<InternedStr> {
+    self.table().strings.last().cloned()
+  }
+  
+  /**
+   * Locks this `OrdInterner` on the current thread until the returned [`LockedOrdInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * While it is locked, the current thread has exclusive access to this `OrdInterner`'s methods
+   * (accessible from the [`LockedOrdInterner`]; any methods used directly on `self` may panic).
+   * This enables iterating over the interned strings, with [`LockedOrdInterner::iter`] and [`LockedOrdInterner::range`].
+   * 
+   * # Panics
+   * This method panics if this `OrdInterner` has been poisoned, and it may panic if this `OrdInterner` is already locked on this thread.
+   */
+  pub fn lock(&self) -> LockedOrdInterner<'_> {
+    LockedOrdInterner::new(self.table())
+  }
+  
+}
+
+impl Clone for OrdInterner {
+  
+  fn clone(&self) -> Self {
+    Self { table: Mutex::new(self.table().clone()) }
+  }
+  
+}
+
+impl Debug for OrdInterner {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("OrdInterner").field(&self.table().strings).finish()
+  }
+  
+}
+
+impl<T: AsRef<str>> Extend<T> for OrdInterner {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.lock().extend(iter)
+  }
+  
+}
+
+impl<T: AsRef<str>> FromIterator<T> for OrdInterner {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::new();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * A locked [`OrdInterner`]. This `struct` is created by [`OrdInterner::lock`]; see its documentation for more details.
+ */
+#[repr(transparent)]
+pub struct LockedOrdInterner<'a> {
+  
+  table: MutexGuard<'a, OrdTable<InternedStr>>
+  
+}
+
+impl<'a> LockedOrdInterner<'a> {
+  
+  fn new(table: MutexGuard<'a, OrdTable<InternedStr>>) -> Self {
+    Self { table }
+  }
+  
+  /**
+   * Returns the number of interned strings.
+   */
+  pub fn len(&self) -> usize {
+    self.table.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings have been interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.table.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.table.strings.clear()
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    self.table.intern(string.as_ref())
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.table.strings.contains(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.strings.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.table.strings.take(string.as_ref())
+  }
+  
+  /**
+   * Returns the lexicographically first interned string, if there is one.
+   */
+  pub fn first(&self) -> Option<&InternedStr> {
+    self.table.strings.first()
+  }
+  
+  /**
+   * Returns the lexicographically last interned string, if there is one.
+   */
+  pub fn last(&self) -> Option<&InternedStr> {
+    self.table.strings.last()
+  }
+  
+  /**
+   * An iterator over the interned strings within the given range, in lexicographic order.
+   * 
+   * Any range of `&str`s can be used, e.g., `"a".."b"`, `"a"..="b"`, `"a"..`, or `..`,
+   * as can a pair of [`Bound`](std::ops::Bound)s (e.g., `(Bound::Excluded("a"), Bound::Unbounded)`).
+   * 
+   * # Panics
+   * This method panics if the start of the range is after its end, or if they are equal and both excluded.
+   */
+  pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> OrdIter<'_> {
+    OrdIter::new(self.table.range(range))
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings, in lexicographic order.
+   */
+  pub fn iter(&self) -> OrdIter<'_> {
+    OrdIter::new(self.table.iter())
+  }
+  
+  /**
+   * An iterator over the interned strings which start with the given prefix, in lexicographic order.
+   * 
+   * An empty prefix matches every string, and the prefix itself is included if it has been interned.
+   * Since every string with the same prefix is next to each other, this is a [`range`](Self::range) query, so it never checks any strings that don't match.
+   */
+  pub fn iter_prefix(&self, prefix: &str) -> OrdIter<'_> {
+    OrdIter::new(self.table.prefix(prefix))
+  }
+  
+}
+
+impl<'a> Debug for LockedOrdInterner<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("LockedOrdInterner").field(&self.table.strings).finish()
+  }
+  
+}
+
+impl<'a, 'b> IntoIterator for &'b LockedOrdInterner<'a> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = OrdIter<'b>;
+  
+  fn into_iter(self) -> OrdIter<'b> {
+    self.iter()
+  }
+  
+}
+
+impl<'a, T: AsRef<str>> Extend<T> for LockedOrdInterner<'a> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+/**
+ * An iterator over some or all of the strings in an [`OrdInterner`], in lexicographic order.
+ * 
+ * This `struct` is created by the [`iter`](LockedOrdInterner::iter) and [`range`](LockedOrdInterner::range) methods on [`LockedOrdInterner`].
+ */
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct OrdIter<'a> {
+  
+  iter: BTreeIter<'a, InternedStr>
+  
+}
+
+impl<'a> OrdIter<'a> {
+  
+  fn new(iter: BTreeIter<'a, InternedStr>) -> Self {
+    Self { iter }
+  }
+  
+}
+
+impl<'a> Iterator for OrdIter<'a> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next()
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a> DoubleEndedIterator for OrdIter<'a> {
+  
+  fn next_back(&mut self) -> Option<&'a InternedStr> {
+    self.iter.next_back()
+  }
+  
+}
+
+impl<'a> FusedIterator for OrdIter<'a> {}
+
+impl<'a> Debug for OrdIter<'a> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
+
+/**
+ * An interner which is optimized for interning strings that have usually already been saved, from many threads at once.
+ * 
+ * Like an [`Interner`], an `RwInterner` ensures there is only one allocation for any given string contents,
+ * but it uses an [`RwLock`] instead of a [`Mutex`], so any number of threads can look up strings at the same time.
+ * [`intern`](Self::intern) only needs exclusive access when the string has not been saved yet,
+ * in which case it checks again once it has exclusive access, in case another thread saved the string in the meantime.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{RwInterner, InternedStr};
+ * let interner = RwInterner::new();
+ * let foo0 = interner.intern(String::from("foo"));
+ * let foo1 = interner.intern(String::from("foo"));
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * ```
+ * 
+ * This only has the basic features of an [`Interner`], since most of the rest (e.g., counting or statistics) need exclusive access even when a string has already been saved.
+ * 
+ * Like a [`Mutex`], an [`RwLock`] is poisoned if a thread panics while it has exclusive access,
+ * in which case every method panics; a thread panicking while it only has shared access does not poison it.
+ */
+#[doc(alias = "ReadMostlyInterner")]
+pub struct RwInterner<S = RandomState> {
+  
+  strings: RwLock<HashSet<InternedStr, S>>
+  
+}
+
+impl RwInterner {
+  
+  /**
+   * Constructs a new `RwInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `RwInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+  
+}
+
+impl<S> RwInterner<S> {
+  
+  const POISON_MESSAGE: &'static str = "RwInterner lock was poisoned";
+  
+  /**
+   * Constructs a new `RwInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
+  }
+  
+  /**
+   * Constructs a new `RwInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
+  }
+  
+  /**
+   * Constructs an `RwInterner` which already has all of the strings in the given set.
+   */
+  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
+    Self { strings: RwLock::new(strings) }
+  }
+  
+  /**
+   * Consume this `RwInterner` and return a set containing all of the strings that were interned.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned.
+   */
+  pub fn into_set(self) -> HashSet<InternedStr, S> {
+    self.strings.into_inner().expect(Self::POISON_MESSAGE)
+  }
+  
+  fn read(&self) -> RwLockReadGuard<'_, HashSet<InternedStr, S>> {
+    self.strings.read().expect(Self::POISON_MESSAGE)
+  }
+  
+  fn write(&self) -> RwLockWriteGuard<'_, HashSet<InternedStr, S>> {
+    self.strings.write().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Returns the number of strings that are currently interned, or blocks until no other thread has exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn len(&self) -> usize {
+    self.read().len()
+  }
+  
+  /**
+   * Returns whether no strings are currently interned, or blocks until no other thread has exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.read().is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings, or blocks until it is able to get exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn clear(&self) {
+    self.write().clear()
+  }
+  
+  /**
+   * Locks this `RwInterner` for exclusive access on the current thread until the returned [`LockedRwInterner`] is dropped,
+   * or blocks until it is able to do so.
+   * 
+   * While it is locked, no other thread can use this `RwInterner` at all, even to look up strings,
+   * and the current thread has exclusive access to its methods (accessible from the [`LockedRwInterner`]; any methods used directly on `self` may panic).
+   * This enables iterating over the interned strings, with [`LockedRwInterner::iter`].
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn lock(&self) -> LockedRwInterner<'_, S> {
+    LockedRwInterner::new(self.write())
+  }
+  
+}
+
+impl<S: BuildHasher> RwInterner<S> {
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   * 
+   * If the string has already been saved, this only blocks until no other thread has exclusive access,
+   * so any number of threads can do so at the same time.
+   * Otherwise, it blocks until it is able to get exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    if let Some(saved) = self.read().get(string) {
+      return InternedStr::clone(saved);
+    }
+    LockedRwInterner::new(self.write()).intern(string)
+  }
+  
+  /**
+   * Returns whether the given string has been saved, or blocks until no other thread has exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.read().contains(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise,
+   * or blocks until no other thread has exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.read().get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation, or blocks until it is able to get exclusive access.
+   * 
+   * # Panics
+   * This method panics if this `RwInterner` has been poisoned, and it may panic if this `RwInterner` is already locked on this thread.
+   */
+  pub fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.write().take(string.as_ref())
+  }
+  
+}
+
+impl<S: Clone> Clone for RwInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self::from_set(self.read().clone())
+  }
+  
+}
+
+impl<S> Debug for RwInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("RwInterner").field(&*self.read()).finish()
+  }
+  
+}
+
+impl<S: Default> Default for RwInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for RwInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.lock().extend(iter)
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Default> FromIterator<T> for RwInterner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * A locked [`RwInterner`]. This `struct` is created by [`RwInterner::lock`]; see its documentation for more details.
+ */
+#[repr(transparent)]
+pub struct LockedRwInterner<'a, S = RandomState> {
+  
+  strings: RwLockWriteGuard<'a, HashSet<InternedStr, S>>
+  
+}
+
+impl<'a, S> LockedRwInterner<'a, S> {
+  
+  fn new(strings: RwLockWriteGuard<'a, HashSet<InternedStr, S>>) -> Self {
+    Self { strings }
+  }
+  
+  /**
+   * Returns the number of strings that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether no strings are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.strings.clear()
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.strings.iter())
+  }
+  
+}
+
+impl<'a, S: BuildHasher> LockedRwInterner<'a, S> {
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    if let Some(saved) = self.strings.get(string) {
+      return InternedStr::clone(saved);
+    }
+    let saved = InternedStr::from(string);
+    self.strings.insert(InternedStr::clone(&saved));
+    saved
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains(string.as_ref())
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.get(string.as_ref()).cloned()
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.take(string.as_ref())
+  }
+  
+}
+
+impl<'a, S> Debug for LockedRwInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("LockedRwInterner").field(&*self.strings).finish()
+  }
+  
+}
+
+impl<'a, 'b, S> IntoIterator for &'b LockedRwInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = Iter<'b>;
+  
+  fn into_iter(self) -> Iter<'b> {
+    self.iter()
+  }
+  
+}
+
+impl<'a, T: AsRef<str>, S: BuildHasher> Extend<T> for LockedRwInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+/**
+ * An interner which splits its strings across several independently locked shards, so that threads interning different strings rarely contend.
+ * 
+ * Like an [`Interner`], a `ShardedInterner` ensures there is only one allocation for any given string contents,
+ * since each string always belongs to the same shard, chosen by its hash.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{ShardedInterner, InternedStr};
+ * let interner = ShardedInterner::with_shards(4);
+ * let foo0 = interner.intern(String::from("foo"));
+ * let foo1 = interner.intern(String::from("foo"));
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * assert_eq!(interner.shard_count(), 4);
+ * ```
+ * 
+ * Methods which only involve one string only lock its shard, while methods which involve every string (e.g., [`len`](Self::len)) lock each shard in turn,
+ * so their result may be inconsistent if other threads are using this `ShardedInterner` at the same time.
+ * For a consistent view, see [`lock_all`](Self::lock_all).
+ */
+pub struct ShardedInterner<S = RandomState> {
+  
+  /**
+   * There is always a power of two of these, so that a shard can be chosen by masking the hash.
+   */
+  shards: Box<[Mutex<HashSet<InternedStr, S>>]>,
+  
+  hasher: S
+  
+}
+
+impl ShardedInterner {
+  
+  /**
+   * Constructs a new `ShardedInterner` with the default number of shards,
+   * which is the smallest power of two that is at least the [available parallelism](std::thread::available_parallelism).
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+  
+  /**
+   * Constructs a new `ShardedInterner` with at least the given number of shards, rounded up to a power of two.
+   */
+  pub fn with_shards(shards: usize) -> Self {
+    Self::with_shards_and_hasher(shards, RandomState::new())
+  }
+  
+}
+
+impl<S: Clone> ShardedInterner<S> {
+  
+  /**
+   * Constructs a new `ShardedInterner` with the default number of shards (see [`new`](ShardedInterner::new)) and the given hasher.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    let shards = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    Self::with_shards_and_hasher(shards, hasher)
+  }
+  
+  /**
+   * Constructs a new `ShardedInterner` with at least the given number of shards, rounded up to a power of two, and the given hasher.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_shards_and_hasher(shards: usize, hasher: S) -> Self {
+    let shards = (0..shards.max(1).next_power_of_two()).map(|_| Mutex::new(HashSet::with_hasher(hasher.clone()))).collect();
+    Self { shards, hasher }
+  }
+  
+}
+
+impl<S> ShardedInterner<S> {
+  
+  const POISON_MESSAGE: &'static str = "ShardedInterner mutex was poisoned";
+  
+  fn lock_shard(shard: &Mutex<HashSet<InternedStr, S>>) -> MutexGuard<'_, HashSet<InternedStr, S>> {
+    shard.lock().expect(Self::POISON_MESSAGE)
+  }
+  
+  /**
+   * Returns the number of shards, which is always a power of two.
+   */
+  pub fn shard_count(&self) -> usize {
+    self.shards.len()
+  }
+  
+  /**
+   * Locks each shard in turn and returns the total number of strings that are interned in them, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if any shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn len(&self) -> usize {
+    self.shards.iter().map(|shard| Self::lock_shard(shard).len()).sum()
+  }
+  
+  /**
+   * Locks each shard in turn and returns whether no strings are interned in any of them, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if any shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.shards.iter().all(|shard| Self::lock_shard(shard).is_empty())
+  }
+  
+  /**
+   * Locks each shard in turn and removes all of the strings interned in it, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if any shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn clear(&self) {
+    for shard in &self.shards {
+      Self::lock_shard(shard).clear();
+    }
+  }
+  
+  /**
+   * Locks every shard of this `ShardedInterner`, in order, until the returned [`LockedShardedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * While it is locked, the current thread has exclusive access to all of this `ShardedInterner`'s strings
+   * (accessible from the [`LockedShardedInterner`]; any methods used directly on `self` may panic).
+   * This enables iterating over the interned strings, with [`LockedShardedInterner::iter`].
+   * 
+   * # Panics
+   * This method panics if any shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn lock_all(&self) -> LockedShardedInterner<'_, S> {
+    LockedShardedInterner::new(self.shards.iter().map(Self::lock_shard).collect(), &self.hasher)
+  }
+  
+}
+
+impl<S: BuildHasher> ShardedInterner<S> {
+  
+  fn shard(&self, string: &str) -> MutexGuard<'_, HashSet<InternedStr, S>> {
+    Self::lock_shard(&self.shards[shard_index(&self.hasher, self.shards.len(), string)])
+  }
+  
+  /**
+   * Locks the given string's shard, saves the string if it is not already saved,
+   * and returns a reference to the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the string's shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    intern_in_shard(&mut self.shard(string), string)
+  }
+  
+  /**
+   * Locks the given string's shard and returns whether the string has been saved, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the string's shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    let string = string.as_ref();
+    self.shard(string).contains(string)
+  }
+  
+  /**
+   * Locks the given string's shard, and if the string has been saved, returns a reference to the saved allocation,
+   * or `None` otherwise, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the string's shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.shard(string).get(string).cloned()
+  }
+  
+  /**
+   * Locks the given string's shard, removes the string, if it has been saved, and returns the saved allocation, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the string's shard has been poisoned, and it may panic if this `ShardedInterner` is already locked on this thread.
+   */
+  pub fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.shard(string).take(string)
+  }
+  
+}
+
+impl<S> Debug for ShardedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("ShardedInterner").field("shards", &self.shards.len()).finish_non_exhaustive()
+  }
+  
+}
+
+impl<S: BuildHasher + Clone + Default> Default for ShardedInterner<S> {
+  
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for ShardedInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      let string = string.as_ref();
+      let index = shard_index(&self.hasher, self.shards.len(), string);
+      intern_in_shard(self.shards[index].get_mut().expect(Self::POISON_MESSAGE), string);
+    }
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher + Clone + Default> FromIterator<T> for ShardedInterner<S> {
+  
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
+  }
+  
+}
+
+/**
+ * Returns the index of the shard that the given string belongs to, out of `shards`, which must be a power of two.
+ */
+fn shard_index<S: BuildHasher>(hasher: &S, shards: usize, string: &str) -> usize {
+  // `HashSet` uses the lowest and highest bits of the hash itself, so these come from the middle, to keep them independent.
+  (hasher.hash_one(string) >> 32) as usize & (shards - 1)
+}
+
+fn intern_in_shard<S: BuildHasher>(shard: &mut HashSet<InternedStr, S>, string: &str) -> InternedStr {
+  if let Some(saved) = shard.get(string) {
+    return InternedStr::clone(saved);
+  }
+  let saved = InternedStr::from(string);
+  shard.insert(InternedStr::clone(&saved));
+  saved
+}
+
+/**
+ * A [`ShardedInterner`] with all of its shards locked. This `struct` is created by [`ShardedInterner::lock_all`]; see its documentation for more details.
+ */
+pub struct LockedShardedInterner<'a, S = RandomState> {
+  
+  shards: Vec<MutexGuard<'a, HashSet<InternedStr, S>>>,
+  hasher: &'a S
+  
+}
+
+impl<'a, S> LockedShardedInterner<'a, S> {
+  
+  fn new(shards: Vec<MutexGuard<'a, HashSet<InternedStr, S>>>, hasher: &'a S) -> Self {
+    Self { shards, hasher }
+  }
+  
+  /**
+   * Returns the total number of strings that are interned in all of the shards.
+   */
+  pub fn len(&self) -> usize {
+    self.shards.iter().map(|shard| shard.len()).sum()
+  }
+  
+  /**
+   * Returns whether no strings are interned in any of the shards.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.shards.iter().all(|shard| shard.is_empty())
+  }
+  
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    for shard in &mut self.shards {
+      shard.clear();
+    }
+  }
+  
+  /**
+   * An iterator over all of the currently interned strings, which visits each shard in order.
+   */
+  pub fn iter(&self) -> ShardedIter<'_, S> {
+    ShardedIter::new(&self.shards)
+  }
+  
+}
+
+impl<'a, S: BuildHasher> LockedShardedInterner<'a, S> {
+  
+  fn shard(&mut self, string: &str) -> &mut HashSet<InternedStr, S> {
+    let index = shard_index(self.hasher, self.shards.len(), string);
+    &mut self.shards[index]
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    intern_in_shard(self.shard(string), string)
+  }
+  
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    let string = string.as_ref();
+    self.shards[shard_index(self.hasher, self.shards.len(), string)].contains(string)
+  }
+  
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.shards[shard_index(self.hasher, self.shards.len(), string)].get(string).cloned()
+  }
+  
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.shard(string).take(string)
+  }
+  
+}
+
+impl<'a, S> Debug for LockedShardedInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("LockedShardedInterner").field(&self.shards.iter().map(|shard| &**shard).collect::<Vec<_>>()).finish()
+  }
+  
+}
+
+impl<'a, 'b, S> IntoIterator for &'b LockedShardedInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = ShardedIter<'b, S>;
+  
+  fn into_iter(self) -> ShardedIter<'b, S> {
+    self.iter()
+  }
+  
+}
+
+impl<'a, T: AsRef<str>, S: BuildHasher> Extend<T> for LockedShardedInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
+  }
+  
+}
+
+/**
+ * An iterator over the strings in a [`ShardedInterner`], which visits each shard in order.
+ * 
+ * This `struct` is created by the [`iter`](LockedShardedInterner::iter) method on [`LockedShardedInterner`].
+ */
+pub struct ShardedIter<'a, S = RandomState> {
+  
+  shards: SliceIter<'a, MutexGuard<'a, HashSet<InternedStr, S>>>,
+  shard: Option<SetIter<'a, InternedStr>>,
+  
+  /**
+   * The number of strings left in `shard` and `shards`.
+   */
+  len: usize
+  
+}
+
+impl<'a, S> ShardedIter<'a, S> {
+  
+  fn new(shards: &'a [MutexGuard<'a, HashSet<InternedStr, S>>]) -> Self {
+    let len = shards.iter().map(|shard| shard.len()).sum();
+    Self { shards: shards.iter(), shard: None, len }
+  }
+  
+}
+
+impl<'a, S> Clone for ShardedIter<'a, S> {
+  
+  fn clone(&self) -> Self {
+    Self { shards: self.shards.clone(), shard: self.shard.clone(), len: self.len }
+  }
+  
+}
+
+impl<'a, S> Iterator for ShardedIter<'a, S> {
+  
+  type Item = &'a InternedStr;
+  
+  fn next(&mut self) -> Option<&'a InternedStr> {
+    loop {
+      if let Some(string) = self.shard.as_mut().and_then(Iterator::next) {
+        self.len -= 1;
+        return Some(string);
+      }
+      self.shard = Some(self.shards.next()?.iter());
+    }
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+  
+}
+
+impl<'a, S> ExactSizeIterator for ShardedIter<'a, S> {
+  
+  fn len(&self) -> usize {
+    self.len
+  }
+  
+}
+
+impl<'a, S> FusedIterator for ShardedIter<'a, S> {}
+
+impl<'a, S> Debug for ShardedIter<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+  
+}
+
+thread_local! {
+  
+  /**
+   * The local caches of every [`CachedInterner`] that has been used on this thread, by the address of its [`CachedInterner::owner`].
+   */
+  static LOCAL_CACHES: RefCell<HashMap<usize, LocalCache>> = RefCell::new(HashMap::new());
+  
+}
+
+/**
+ * A cache of strings that have been interned by a [`CachedInterner`] on one thread.
+ */
+struct LocalCache {
+  
+  /**
+   * Keeps the address of the [`CachedInterner::owner`] from being reused while this `LocalCache` exists, and tells when it can be dropped.
+   */
+  owner: Weak<()>,
+  
+  /**
+   * The [`Interner::removals`] as of the last time `strings` was cleared, so `strings` are all still saved as long as it has not changed.
+   */
+  removals: u64,
+  strings: HashSet<InternedStr>
+  
+}
+
+/**
+ * A shared [`Interner`] with a small cache on each thread in front of it, so that interning a string that has recently been interned on the same thread
+ * does not need to touch the shared `Interner` at all.
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use std::thread;
+ * # use str_intern::sync::{CachedInterner, Interner, InternedStr};
+ * let interner = CachedInterner::new(Arc::new(Interner::new()));
+ * let foo0 = interner.intern("foo");
+ * let foo1 = interner.intern("foo"); // Found in this thread's cache.
+ * let foo2 = thread::scope(|scope| scope.spawn(|| interner.intern("foo")).join().unwrap());
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * assert!(InternedStr::ptr_eq(&foo0, &foo2));
+ * ```
+ * 
+ * The cache only ever contains strings that were returned by the shared `Interner`, so they are always canonical.
+ * If any string is removed from the shared `Interner` (e.g., by [`Interner::remove`] or [`Interner::gc`]),
+ * each thread's cache is cleared the next time it is used, since it might contain that string.
+ * When a cache is full, it is also cleared, rather than keeping track of which strings were used least recently.
+ * 
+ * Clones of a `CachedInterner` share the same caches, while separate `CachedInterner`s for the same `Interner` each have their own.
+ * Each thread's cache lasts until the thread exits, or until it next uses a `CachedInterner` after every clone of this one has been dropped.
+ */
+pub struct CachedInterner<S = RandomState> {
   
-  type Item = &'b InternedStr;
-  type IntoIter = Iter<'b>;
+  interner: Arc<Interner<S>>,
   
-  fn into_iter(self) -> Iter<'b> {
-    Iter::new(self.strings.iter())
+  /**
+   * Identifies the caches of this `CachedInterner` (and its clones), and tells when they are no longer needed.
+   */
+  owner: Arc<()>,
+  
+  cache_capacity: usize
+  
+}
+
+impl<S> CachedInterner<S> {
+  
+  /**
+   * The number of strings each thread's cache holds by default.
+   */
+  pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+  
+  /**
+   * Constructs a new `CachedInterner` in front of the given shared [`Interner`], which caches up to [`DEFAULT_CACHE_CAPACITY`](Self::DEFAULT_CACHE_CAPACITY) strings on each thread.
+   */
+  pub fn new(interner: Arc<Interner<S>>) -> Self {
+    Self::with_cache_capacity(interner, Self::DEFAULT_CACHE_CAPACITY)
+  }
+  
+  /**
+   * Constructs a new `CachedInterner` in front of the given shared [`Interner`], which caches up to `cache_capacity` strings on each thread.
+   * 
+   * If `cache_capacity` is 0, nothing is cached, and every string is interned directly in the shared `Interner`.
+   */
+  pub fn with_cache_capacity(interner: Arc<Interner<S>>, cache_capacity: usize) -> Self {
+    Self { interner, owner: Arc::new(()), cache_capacity }
+  }
+  
+  /**
+   * Returns the shared [`Interner`] behind this `CachedInterner`.
+   */
+  pub fn interner(&self) -> &Arc<Interner<S>> {
+    &self.interner
+  }
+  
+  /**
+   * Returns the number of strings each thread's cache can hold.
+   */
+  pub fn cache_capacity(&self) -> usize {
+    self.cache_capacity
+  }
+  
+  /**
+   * Returns the number of strings in the current thread's cache.
+   */
+  pub fn local_cache_len(&self) -> usize {
+    self.with_local_cache(self.removals(), |cache| cache.map_or(0, |cache| cache.strings.len()))
+  }
+  
+  /**
+   * Clears the current thread's cache.
+   */
+  pub fn clear_local_cache(&self) {
+    LOCAL_CACHES.with(|caches| caches.borrow_mut().remove(&self.key()));
+  }
+  
+  fn key(&self) -> usize {
+    Arc::as_ptr(&self.owner) as usize
+  }
+  
+  fn removals(&self) -> u64 {
+    self.interner.removals.load(atomic::Ordering::Acquire)
+  }
+  
+  /**
+   * Calls `f` with the current thread's cache, if there is one and it is up to date as of the given [`Interner::removals`].
+   */
+  fn with_local_cache<T>(&self, removals: u64, f: impl FnOnce(Option<&LocalCache>) -> T) -> T {
+    LOCAL_CACHES.with(|caches| f(caches.borrow().get(&self.key()).filter(|cache| cache.removals == removals)))
+  }
+  
+}
+
+impl<S: BuildHasher> CachedInterner<S> {
+  
+  /**
+   * Returns the saved allocation for the given string from the current thread's cache, if it is there,
+   * or else interns it in the shared [`Interner`] and caches the result.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected, if the shared `Interner` has been [poisoned](Interner#poisoning),
+   * and it may panic if the shared `Interner` is already locked on this thread.
+   */
+  pub fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    // This has to be loaded before interning, so that a string which is removed in the meantime can't be cached as if it were still saved.
+    let removals = self.removals();
+    if let Some(saved) = self.with_local_cache(removals, |cache| cache?.strings.get(string).cloned()) {
+      return saved;
+    }
+    // The cache isn't borrowed while interning, in case a hook uses this CachedInterner.
+    let saved = self.interner.intern(string);
+    if self.cache_capacity > 0 {
+      LOCAL_CACHES.with(|caches| {
+        let mut caches = caches.borrow_mut();
+        if !caches.contains_key(&self.key()) {
+          caches.retain(|_, cache| cache.owner.strong_count() > 0);
+        }
+        let cache = caches.entry(self.key()).or_insert_with(|| LocalCache { owner: Arc::downgrade(&self.owner), removals, strings: HashSet::new() });
+        if cache.removals != removals || cache.strings.len() >= self.cache_capacity {
+          cache.removals = removals;
+          cache.strings.clear();
+        }
+        cache.strings.insert(InternedStr::clone(&saved));
+      });
+    }
+    saved
+  }
+  
+}
+
+impl<S> Clone for CachedInterner<S> {
+  
+  /**
+   * Returns a new handle to the same shared [`Interner`], which shares the same caches.
+   */
+  fn clone(&self) -> Self {
+    Self { interner: Arc::clone(&self.interner), owner: Arc::clone(&self.owner), cache_capacity: self.cache_capacity }
+  }
+  
+}
+
+impl<S> Debug for CachedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("CachedInterner").field("interner", &self.interner).field("cache_capacity", &self.cache_capacity).finish()
+  }
+  
+}
+
+/**
+ * A short-lived interner in front of a long-lived, shared parent [`Interner`], which saves new strings locally rather than in the parent.
+ * 
+ * For example:
+ * ```rust
+ * # use std::sync::Arc;
+ * # use str_intern::sync::{Interner, ScopedInterner, InternedStr};
+ * let parent = Arc::new(Interner::new());
+ * let id = parent.intern("id");
+ * let mut scoped = ScopedInterner::new(Arc::clone(&parent));
+ * assert!(InternedStr::ptr_eq(&scoped.intern("id"), &id));
+ * let local = scoped.intern("some request-local string");
+ * assert!(InternedStr::ptr_eq(&scoped.intern("some request-local string"), &local));
+ * drop(scoped);
+ * assert!(!parent.contains("some request-local string"));
+ * ```
+ * 
+ * [`intern`](Self::intern) only saves a string locally if the parent does not have it,
+ * so strings from the parent are shared, while the local strings are freed when the `ScopedInterner` is dropped (unless they are still referenced elsewhere).
+ * Unlike with [`crate::ScopedInterner`], the parent can still change in the meantime,
+ * so [`intern`](Self::intern) checks the local strings before the parent:
+ * that way, once a string has been saved locally, it keeps coming back as the same allocation, even if the parent saves it later.
+ * 
+ * The local strings are not shared, so this `ScopedInterner` needs exclusive access to intern them, but it never locks the parent for longer than one lookup.
+ */
+pub struct ScopedInterner<S = RandomState> {
+  
+  parent: Arc<Interner<S>>,
+  local: HashSet<InternedStr, S>
+  
+}
+
+impl<S: Default> ScopedInterner<S> {
+  
+  /**
+   * Constructs a new `ScopedInterner` in front of the given parent, with no local strings.
+   */
+  pub fn new(parent: Arc<Interner<S>>) -> Self {
+    Self::with_hasher(parent, S::default())
+  }
+  
+}
+
+impl<S> ScopedInterner<S> {
+  
+  /**
+   * Constructs a new `ScopedInterner` in front of the given parent, with no local strings, which uses the given hasher for them.
+   * See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(parent: Arc<Interner<S>>, hasher: S) -> Self {
+    Self { parent, local: HashSet::with_hasher(hasher) }
+  }
+  
+  /**
+   * Returns the parent [`Interner`].
+   */
+  pub fn parent(&self) -> &Arc<Interner<S>> {
+    &self.parent
+  }
+  
+  /**
+   * Consume this `ScopedInterner` and return a set containing the local strings.
+   */
+  pub fn into_local_set(self) -> HashSet<InternedStr, S> {
+    self.local
+  }
+  
+  /**
+   * Returns the number of strings that have been saved locally.
+   */
+  pub fn local_len(&self) -> usize {
+    self.local.len()
+  }
+  
+  /**
+   * Removes all of the local strings. The parent's strings are unaffected.
+   */
+  pub fn clear_local(&mut self) {
+    self.local.clear()
+  }
+  
+  /**
+   * An iterator over only the local strings.
+   */
+  pub fn iter_local(&self) -> Iter<'_> {
+    Iter::new(self.local.iter())
+  }
+  
+  /**
+   * Locks the parent and returns all of its strings, followed by the local ones, or blocks until it is able to do so.
+   * 
+   * # Panics
+   * This method panics if the parent has been [poisoned](Interner#poisoning), and it may panic if the parent is already locked on this thread.
+   */
+  pub fn iter_snapshot(&self) -> Vec<InternedStr> {
+    let parent = self.parent.lock();
+    parent.iter().chain(&self.local).cloned().collect()
+  }
+  
+}
+
+impl<S: BuildHasher> ScopedInterner<S> {
+  
+  /**
+   * Returns the saved allocation for the given string if it has been saved locally or by the parent,
+   * or otherwise saves it locally and returns a reference to the saved allocation.
+   * 
+   * # Panics
+   * This method panics if the parent has been [poisoned](Interner#poisoning), and it may panic if the parent is already locked on this thread.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    let string = string.as_ref();
+    if let Some(saved) = self.local.get(string) {
+      return InternedStr::clone(saved);
+    }
+    if let Some(saved) = self.parent.get(string) {
+      return saved;
+    }
+    let saved = InternedStr::from(string);
+    self.local.insert(InternedStr::clone(&saved));
+    saved
+  }
+  
+  /**
+   * Returns whether the given string has been saved, either locally or by the parent.
+   * 
+   * # Panics
+   * This method panics if the parent has been [poisoned](Interner#poisoning), and it may panic if the parent is already locked on this thread.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    let string = string.as_ref();
+    self.local.contains(string) || self.parent.contains(string)
+  }
+  
+  /**
+   * If the given string has been saved, either locally or by the parent, returns a reference to the saved allocation, or `None` otherwise.
+   * 
+   * # Panics
+   * This method panics if the parent has been [poisoned](Interner#poisoning), and it may panic if the parent is already locked on this thread.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    let string = string.as_ref();
+    self.local.get(string).cloned().or_else(|| self.parent.get(string))
+  }
+  
+  /**
+   * Returns whether the given string has been saved locally (rather than by the parent).
+   */
+  pub fn contains_local(&self, string: impl AsRef<str>) -> bool {
+    self.local.contains(string.as_ref())
+  }
+  
+}
+
+impl<S> Debug for ScopedInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("ScopedInterner").field("parent", &self.parent).field("local", &self.local).finish()
+  }
+  
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for ScopedInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for string in iter {
+      self.intern(string);
+    }
   }
   
 }
 
 /**
- * An iterator over the strings in a `LockedInterner`.
+ * A read-only interner, which can be shared between threads without locking.
+ * 
+ * This is useful when an [`Interner`] is filled in once (e.g., at startup) and never changes afterwards,
+ * since looking up a string in a `FrozenInterner` doesn't need to wait for, or contend on, a lock.
+ * A `FrozenInterner` is created by [`Interner::freeze`], and can be turned back into an `Interner` with [`thaw`](Self::thaw).
  * 
- * This `struct` is created by the [`iter`](LockedInterner::iter) method on `LockedInterner`.
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{Interner, InternedStr};
+ * let interner = Interner::new();
+ * let foo = interner.intern("foo");
+ * let frozen = interner.freeze();
+ * assert!(InternedStr::ptr_eq(&frozen.get("foo").unwrap(), &foo));
+ * assert_eq!(frozen.get("bar"), None);
+ * ```
  */
 #[repr(transparent)]
-#[derive(Clone, Debug)]
-pub struct Iter<'a> {
+pub struct FrozenInterner<S = RandomState> {
   
-  iter: SetIter<'a, InternedStr>
+  strings: HashSet<InternedStr, S>
   
 }
 
-impl<'a> Iter<'a> {
+impl<S> FrozenInterner<S> {
   
-  fn new(iter: SetIter<'a, InternedStr>) -> Self {
-    Self { iter }
+  /**
+   * Construct a new `FrozenInterner` with the given set's contents.
+   * The new `FrozenInterner` will also use the given set's hasher.
+   */
+  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
+    Self { strings }
+  }
+  
+  /**
+   * Consume this `FrozenInterner` and return a set containing all of its strings.
+   * The returned set also uses the same hasher.
+   */
+  pub fn into_set(self) -> HashSet<InternedStr, S> {
+    self.strings
+  }
+  
+  /**
+   * Consume this `FrozenInterner` and return an [`Interner`] with all of its strings already interned, so that more strings can be interned.
+   * The returned `Interner` also uses the same hasher.
+   */
+  pub fn thaw(self) -> Interner<S> {
+    Interner::from_set(self.strings)
+  }
+  
+  /**
+   * Returns the number of strings in this `FrozenInterner`.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+  
+  /**
+   * Returns whether this `FrozenInterner` contains no strings.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+  
+  /**
+   * An iterator over all of the strings in this `FrozenInterner`.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.strings.iter())
   }
   
 }
 
-impl<'a> Iterator for Iter<'a> {
+impl<S: BuildHasher> FrozenInterner<S> {
   
-  type Item =  &'a InternedStr;
+  /**
+   * Returns whether this `FrozenInterner` contains the given string.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains(string.as_ref())
+  }
   
-  fn next(&mut self) -> Option<Self::Item> {
-    self.iter.next()
+  /**
+   * If this `FrozenInterner` contains the given string, returns a reference to its saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.get(string.as_ref()).cloned()
+  }
+  
+}
+
+impl<S: Clone> Clone for FrozenInterner<S> {
+  
+  fn clone(&self) -> Self {
+    Self { strings: self.strings.clone() }
+  }
+  
+}
+
+impl<S: BuildHasher> PartialEq for FrozenInterner<S> {
+  
+  fn eq(&self, other: &Self) -> bool {
+    self.strings.eq(&other.strings)
+  }
+  
+}
+
+impl<S: BuildHasher> Eq for FrozenInterner<S> {}
+
+impl<S> Debug for FrozenInterner<S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("FrozenInterner").field(&self.strings).finish()
   }
   
-  fn size_hint(&self) -> (usize, Option<usize>) {
-    self.iter.size_hint()
+}
+
+impl<S> IntoIterator for FrozenInterner<S> {
+  
+  type Item = InternedStr;
+  type IntoIter = IntoIter;
+  
+  fn into_iter(self) -> IntoIter {
+    IntoIter::new(self.strings.into_iter())
   }
   
-  fn count(self) -> usize {
-    self.iter.count()
-  }
+}
+
+impl<'a, S> IntoIterator for &'a FrozenInterner<S> {
   
-  fn last(self) -> Option<Self::Item> {
-    self.iter.last()
-  }
+  type Item = &'a InternedStr;
+  type IntoIter = Iter<'a>;
   
-  fn nth(&mut self, n: usize) -> Option<Self::Item> {
-    self.iter.nth(n)
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
   }
   
-  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
-    self.iter.for_each(f)
-  }
+}
+
+/**
+ * An interner for asynchronous code, which waits for its lock asynchronously instead of blocking the thread.
+ * 
+ * Like an [`Interner`], an `AsyncInterner` ensures there is only one allocation for any given string contents,
+ * but it uses a [`tokio::sync::Mutex`], so a task waiting to intern a string yields to the runtime rather than blocking a worker thread.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{AsyncInterner, InternedStr};
+ * # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+ * let interner = AsyncInterner::new();
+ * let foo0 = interner.intern(String::from("foo")).await;
+ * let foo1 = interner.intern(String::from("foo")).await;
+ * assert!(InternedStr::ptr_eq(&foo0, &foo1));
+ * # });
+ * ```
+ * 
+ * This only has the basic features of an [`Interner`]. It is only available when the `tokio` feature is enabled.
+ * 
+ * # Cancellation safety
+ * Every `async` method of an `AsyncInterner` is cancellation safe.
+ * The only point at which they wait is while acquiring the lock, which does not change anything,
+ * so dropping their futures before they complete just means that they did nothing at all.
+ * 
+ * Unlike a [`Mutex`], a [`tokio::sync::Mutex`] is never poisoned, so none of these methods panic for that reason.
+ */
+#[cfg(feature = "tokio")]
+pub struct AsyncInterner<S = RandomState> {
   
-  fn collect<B: FromIterator<Self::Item>>(self) -> B {
-    self.iter.collect()
-  }
+  strings: AsyncMutex<HashSet<InternedStr, S>>
   
-  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
-    self.iter.partition(f)
-  }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncInterner {
   
-  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
-    self.iter.fold(init, f)
+  /**
+   * Constructs a new `AsyncInterner`.
+   */
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
   }
   
-  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
-    self.iter.reduce(f)
+  /**
+   * Constructs a new `AsyncInterner` with the given capacity. See [`HashSet`] for more information.
+   */
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
   }
   
-  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.all(f)
-  }
+}
+
+#[cfg(feature = "tokio")]
+impl<S> AsyncInterner<S> {
   
-  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.any(f)
+  /**
+   * Constructs a new `AsyncInterner` with the given hasher. See [`BuildHasher`] for more information.
+   */
+  pub fn with_hasher(hasher: S) -> Self {
+    Self::from_set(HashSet::with_hasher(hasher))
   }
   
-  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
-    self.iter.find(predicate)
+  /**
+   * Constructs a new `AsyncInterner` with the given capacity and hasher. See [`HashSet`] and [`BuildHasher`] for more information.
+   */
+  pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    Self::from_set(HashSet::with_capacity_and_hasher(capacity, hasher))
   }
   
-  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
-    self.iter.find_map(f)
+  /**
+   * Constructs an `AsyncInterner` which already has all of the strings in the given set.
+   */
+  pub fn from_set(strings: HashSet<InternedStr, S>) -> Self {
+    Self { strings: AsyncMutex::new(strings) }
   }
   
-  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
-    self.iter.position(predicate)
+  /**
+   * Consume this `AsyncInterner` and return a set containing all of the strings that were interned.
+   */
+  pub fn into_set(self) -> HashSet<InternedStr, S> {
+    self.strings.into_inner()
   }
   
-  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.max()
+  /**
+   * Returns the number of strings that are currently interned, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn len(&self) -> usize {
+    self.strings.lock().await.len()
   }
   
-  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.min()
+  /**
+   * Returns whether no strings are currently interned, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn is_empty(&self) -> bool {
+    self.strings.lock().await.is_empty()
   }
   
-  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.max_by_key(f)
+  /**
+   * Removes all of the interned strings, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn clear(&self) {
+    self.strings.lock().await.clear()
   }
   
-  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.max_by(compare)
+  /**
+   * Locks this `AsyncInterner` until the returned [`AsyncLockedInterner`] is dropped, once it is able to do so.
+   * 
+   * While it is locked, the current task has exclusive access to this `AsyncInterner`'s methods (accessible from the [`AsyncLockedInterner`]).
+   * This enables iterating over the interned strings, with [`AsyncLockedInterner::iter`].
+   * 
+   * Unlike a [`LockedInterner`], an [`AsyncLockedInterner`] can be held across an `.await`,
+   * but any other task using this `AsyncInterner` in the meantime will have to wait.
+   */
+  pub async fn lock(&self) -> AsyncLockedInterner<'_, S> {
+    AsyncLockedInterner::new(self.strings.lock().await)
   }
   
-  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.min_by_key(f)
+  /**
+   * Locks this `AsyncInterner` until the returned [`AsyncLockedInterner`] is dropped, or blocks until it is able to do so.
+   * 
+   * This is for synchronous code which needs to use an `AsyncInterner`; see [`lock`](Self::lock).
+   * 
+   * # Panics
+   * This method panics if it is called within an asynchronous execution context.
+   */
+  pub fn blocking_lock(&self) -> AsyncLockedInterner<'_, S> {
+    AsyncLockedInterner::new(self.strings.blocking_lock())
   }
   
-  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.min_by(compare)
-  }
+}
+
+#[cfg(feature = "tokio")]
+impl<S: BuildHasher> AsyncInterner<S> {
   
-  fn sum<S: Sum<Self::Item>>(self) -> S {
-    self.iter.sum()
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation, once it is able to lock this `AsyncInterner`.
+   * 
+   * `interner.intern(string).await` is equivalent to `interner.lock().await.intern(string)`.
+   */
+  pub async fn intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.lock().await.intern(string)
   }
   
-  fn product<P: Product<Self::Item>>(self) -> P {
-    self.iter.product()
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation, or blocks until it is able to lock this `AsyncInterner`.
+   * 
+   * This is for synchronous code which needs to use an `AsyncInterner`; see [`intern`](Self::intern).
+   * 
+   * # Panics
+   * This method panics if it is called within an asynchronous execution context.
+   */
+  pub fn blocking_intern(&self, string: impl AsRef<str>) -> InternedStr {
+    self.blocking_lock().intern(string)
   }
   
-  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
-    self.iter.cmp(other)
+  /**
+   * Returns whether the given string has been saved, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.lock().await.contains(string)
   }
   
-  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
-    self.iter.partial_cmp(other)
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.lock().await.get(string)
   }
   
-  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.eq(other)
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation, once it is able to lock this `AsyncInterner`.
+   */
+  pub async fn remove(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.lock().await.remove(string)
   }
   
-  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.ne(other)
-  }
+}
+
+#[cfg(feature = "tokio")]
+impl<S> Debug for AsyncInterner<S> {
   
-  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.lt(other)
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("AsyncInterner").field(&self.strings).finish()
   }
   
-  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.le(other)
-  }
+}
+
+#[cfg(feature = "tokio")]
+impl<S: Default> Default for AsyncInterner<S> {
   
-  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.gt(other)
+  fn default() -> Self {
+    Self::with_hasher(S::default())
   }
   
-  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.ge(other)
+}
+
+#[cfg(feature = "tokio")]
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for AsyncInterner<S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    AsyncLockedInterner::extend_set(self.strings.get_mut(), iter)
   }
   
 }
 
-impl<'a> ExactSizeIterator for Iter<'a> {
+#[cfg(feature = "tokio")]
+impl<T: AsRef<str>, S: BuildHasher + Default> FromIterator<T> for AsyncInterner<S> {
   
-  fn len(&self) -> usize {
-    self.iter.len()
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut interner = Self::default();
+    interner.extend(iter);
+    interner
   }
   
 }
 
-impl<'a> FusedIterator for Iter<'a> {}
-
 /**
- * An owning iterator over the strings that were in an `Interner`.
- * 
- * This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`Interner`]
- * (provided by the [`IntoIterator`] trait).
+ * A locked [`AsyncInterner`]. This `struct` is created by [`AsyncInterner::lock`] (or [`AsyncInterner::blocking_lock`]); see its documentation for more details.
  */
+#[cfg(feature = "tokio")]
 #[repr(transparent)]
-#[derive(Debug)]
-pub struct IntoIter {
+pub struct AsyncLockedInterner<'a, S = RandomState> {
   
-  iter: SetIntoIter<InternedStr>
+  strings: AsyncMutexGuard<'a, HashSet<InternedStr, S>>
   
 }
 
-impl IntoIter {
+#[cfg(feature = "tokio")]
+impl<'a, S> AsyncLockedInterner<'a, S> {
   
-  fn new(iter: SetIntoIter<InternedStr>) -> Self {
-    Self { iter }
+  fn new(strings: AsyncMutexGuard<'a, HashSet<InternedStr, S>>) -> Self {
+    Self { strings }
   }
   
-}
-
-impl Iterator for IntoIter {
-  
-  type Item = InternedStr;
+  /**
+   * Returns the number of strings that are currently interned.
+   */
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
   
-  fn next(&mut self) -> Option<Self::Item> {
-    self.iter.next()
+  /**
+   * Returns whether no strings are currently interned.
+   */
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
   }
   
-  fn size_hint(&self) -> (usize, Option<usize>) {
-    self.iter.size_hint()
+  /**
+   * Removes all of the interned strings.
+   */
+  pub fn clear(&mut self) {
+    self.strings.clear()
   }
   
-  fn count(self) -> usize {
-    self.iter.count()
+  /**
+   * An iterator over all of the currently interned strings.
+   */
+  pub fn iter(&self) -> Iter<'_> {
+    Iter::new(self.strings.iter())
   }
   
-  fn last(self) -> Option<Self::Item> {
-    self.iter.last()
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, S: BuildHasher> AsyncLockedInterner<'a, S> {
+  
+  fn intern_into(strings: &mut HashSet<InternedStr, S>, string: &str) -> InternedStr {
+    if let Some(saved) = strings.get(string) {
+      return InternedStr::clone(saved);
+    }
+    let saved = InternedStr::from(string);
+    strings.insert(InternedStr::clone(&saved));
+    saved
   }
   
-  fn nth(&mut self, n: usize) -> Option<Self::Item> {
-    self.iter.nth(n)
+  fn extend_set<T: AsRef<str>>(strings: &mut HashSet<InternedStr, S>, iter: impl IntoIterator<Item = T>) {
+    for string in iter {
+      Self::intern_into(strings, string.as_ref());
+    }
   }
   
-  fn for_each<F: FnMut(Self::Item)>(self, f: F) {
-    self.iter.for_each(f)
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation.
+   */
+  pub fn intern(&mut self, string: impl AsRef<str>) -> InternedStr {
+    Self::intern_into(&mut self.strings, string.as_ref())
   }
   
-  fn collect<B: FromIterator<Self::Item>>(self) -> B {
-    self.iter.collect()
+  /**
+   * Returns whether the given string has been saved.
+   */
+  pub fn contains(&self, string: impl AsRef<str>) -> bool {
+    self.strings.contains(string.as_ref())
   }
   
-  fn partition<B: Default + Extend<Self::Item>, F: FnMut(&Self::Item) -> bool>(self, f: F) -> (B, B) {
-    self.iter.partition(f)
+  /**
+   * If the given string has been saved, returns a reference to the saved allocation, or `None` otherwise.
+   */
+  pub fn get(&self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.get(string.as_ref()).cloned()
   }
   
-  fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
-    self.iter.fold(init, f)
+  /**
+   * Removes the given string, if it has been saved, and returns the saved allocation.
+   */
+  pub fn remove(&mut self, string: impl AsRef<str>) -> Option<InternedStr> {
+    self.strings.take(string.as_ref())
   }
   
-  fn reduce<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> Option<Self::Item> {
-    self.iter.reduce(f)
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, S> Debug for AsyncLockedInterner<'a, S> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_tuple("AsyncLockedInterner").field(&*self.strings).finish()
   }
   
-  fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.all(f)
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, 'b, S> IntoIterator for &'b AsyncLockedInterner<'a, S> {
+  
+  type Item = &'b InternedStr;
+  type IntoIter = Iter<'b>;
+  
+  fn into_iter(self) -> Iter<'b> {
+    self.iter()
   }
   
-  fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
-    self.iter.any(f)
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, T: AsRef<str>, S: BuildHasher> Extend<T> for AsyncLockedInterner<'a, S> {
+  
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    Self::extend_set(&mut self.strings, iter)
   }
   
-  fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
-    self.iter.find(predicate)
+}
+
+#[cfg(feature = "global")]
+static GLOBAL: OnceLock<Interner<GlobalState>> = OnceLock::new();
+
+/**
+ * A global [`Interner`], just for convenience.
+ * 
+ * `GlobalInterner` functions just like any other `Interner`,
+ * so a string interned in another interner will not be automatically interned into this one.
+ * 
+ * It is created with [`Interner::default`] the first time it is used, unless it has already been given a different `Interner` by [`init_global`].
+ * 
+ * For most purposes, [`intern`] will be sufficient.
+ */
+#[cfg(feature = "global")]
+pub struct GlobalInterner;
+
+#[cfg(feature = "global")]
+impl Deref for GlobalInterner {
+  
+  type Target = Interner<GlobalState>;
+  
+  fn deref(&self) -> &Interner<GlobalState> {
+    GLOBAL.get_or_init(Interner::default)
   }
   
-  fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
-    self.iter.find_map(f)
-  }
+}
+
+/**
+ * The [`BuildHasher`] used by the [`GlobalInterner`], which is either a [`RandomState`] (by default) or a [`FixedState`].
+ * 
+ * Since the `GlobalInterner` is a `static`, its hasher has to be a single type, but both of these build the standard library's [`DefaultHasher`],
+ * so choosing between them costs nothing while hashing.
+ */
+#[cfg(feature = "global")]
+#[derive(Clone, Debug)]
+pub enum GlobalState {
   
-  fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
-    self.iter.position(predicate)
-  }
+  /**
+   * Hashes strings differently in every run of the program, which is the default.
+   */
+  Random(RandomState),
   
-  fn max(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.max()
-  }
+  /**
+   * Hashes strings the same way in every run of the program with the same seed. (See [`FixedState`] for why that might be a problem.)
+   */
+  Fixed(FixedState)
   
-  fn min(self) -> Option<Self::Item> where Self::Item: Ord {
-    self.iter.min()
-  }
+}
+
+#[cfg(feature = "global")]
+impl BuildHasher for GlobalState {
   
-  fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.max_by_key(f)
-  }
+  type Hasher = DefaultHasher;
   
-  fn max_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.max_by(compare)
+  fn build_hasher(&self) -> DefaultHasher {
+    match self {
+      Self::Random(state) => state.build_hasher(),
+      Self::Fixed(state) => state.build_hasher()
+    }
   }
   
-  fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
-    self.iter.min_by_key(f)
-  }
+}
+
+#[cfg(feature = "global")]
+impl Default for GlobalState {
   
-  fn min_by<F: FnMut(&Self::Item, &Self::Item) -> Ordering>(self, compare: F) -> Option<Self::Item> {
-    self.iter.min_by(compare)
+  fn default() -> Self {
+    Self::Random(RandomState::new())
   }
   
-  fn sum<S: Sum<Self::Item>>(self) -> S {
-    self.iter.sum()
-  }
+}
+
+#[cfg(feature = "global")]
+impl From<RandomState> for GlobalState {
   
-  fn product<P: Product<Self::Item>>(self) -> P {
-    self.iter.product()
+  fn from(state: RandomState) -> Self {
+    Self::Random(state)
   }
   
-  fn cmp<I: IntoIterator<Item = Self::Item>>(self, other: I) -> Ordering where Self::Item: Ord {
-    self.iter.cmp(other)
-  }
+}
+
+#[cfg(feature = "global")]
+impl From<FixedState> for GlobalState {
   
-  fn partial_cmp<I: IntoIterator>(self, other: I) -> Option<Ordering> where Self::Item: PartialOrd<I::Item> {
-    self.iter.partial_cmp(other)
+  fn from(state: FixedState) -> Self {
+    Self::Fixed(state)
   }
   
-  fn eq<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.eq(other)
-  }
+}
+
+/**
+ * The error returned by [`init_global`] when the [`GlobalInterner`] has already been created.
+ */
+#[cfg(feature = "global")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AlreadyInitialized;
+
+#[cfg(feature = "global")]
+impl Display for AlreadyInitialized {
   
-  fn ne<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialEq<I::Item> {
-    self.iter.ne(other)
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "the global interner has already been initialized")
   }
   
-  fn lt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.lt(other)
-  }
+}
+
+#[cfg(feature = "global")]
+impl Error for AlreadyInitialized {}
+
+/**
+ * Makes the given `Interner` the [`GlobalInterner`], or returns an error if the `GlobalInterner` has already been created.
+ * 
+ * This has to be called before the `GlobalInterner` is used for anything (including by [`intern`] or [`InternExt::intern`]),
+ * since it is created with the default configuration the first time it is used.
+ * 
+ * For example, to make the `GlobalInterner` hash strings the same way in every run of the program, with space for plenty of strings up front:
+ * ```rust
+ * # use str_intern::FixedState;
+ * # use str_intern::sync::{init_global, intern, Interner, AlreadyInitialized};
+ * init_global(Interner::with_capacity_and_hasher(100_000, FixedState::with_seed(42).into())).unwrap();
+ * intern("foo");
+ * assert_eq!(init_global(Interner::default()), Err(AlreadyInitialized));
+ * ```
+ */
+#[cfg(feature = "global")]
+pub fn init_global(interner: Interner<GlobalState>) -> Result<(), AlreadyInitialized> {
+  GLOBAL.set(interner).map_err(|_| AlreadyInitialized)
+}
+
+/**
+ * Removes every string from the [`GlobalInterner`], and returns them in a new `Interner` with the same hasher,
+ * or returns `None` if the `GlobalInterner` has not been created yet (in which case it still isn't).
+ * 
+ * This is meant for tests which use the `GlobalInterner`, so that each one can start from an empty `GlobalInterner`
+ * (which is why it is only available with the `global-testing` feature, e.g., as a dev-dependency).
+ * Strings interned after this are new allocations, so they are never pointer-equal to strings interned before it, even if they have the same contents.
+ * 
+ * The strings are drained out of the `GlobalInterner` exactly as if it were [cleared](Interner::clear):
+ * its [removal hook](Interner::set_removal_hook), if any, is called with [`RemovalCause::Cleared`] for each of them,
+ * and its [version](Interner::version) is incremented (if it was not already empty).
+ * Everything else about the `GlobalInterner` (e.g., its hooks, its normalizer, or its limits) stays with it.
+ * The returned `Interner` only has the strings and a clone of the hasher, so none of that configuration carries over to it.
+ * 
+ * The strings are all removed while the `GlobalInterner` is locked, so nothing else can use it halfway through,
+ * but tests which run at the same time still share the same `GlobalInterner`, so they should not use this unless they are run one at a time.
+ * 
+ * For example:
+ * ```rust
+ * # use str_intern::sync::{intern, take_global, InternedStr};
+ * let foo0 = intern("foo");
+ * let old = take_global().unwrap();
+ * assert!(InternedStr::ptr_eq(&old.get("foo").unwrap(), &foo0));
+ * let foo1 = intern("foo");
+ * assert!(!InternedStr::ptr_eq(&foo0, &foo1));
+ * ```
+ * 
+ * # Panics
+ * This function panics if the `GlobalInterner` has been [poisoned](Interner#poisoning), and it may panic if the `GlobalInterner` is already locked on this thread.
+ */
+#[cfg(feature = "global-testing")]
+pub fn take_global() -> Option<Interner<GlobalState>> {
+  let mut table = GLOBAL.get()?.table();
+  let mut strings = HashSet::with_capacity_and_hasher(table.strings.len(), table.strings.hasher().clone());
+  strings.extend(table.drain());
+  Some(Interner::from_set(strings))
+}
+
+/**
+ * Removes every string from the [`GlobalInterner`], if it has been created. (See [`take_global`].)
+ * 
+ * # Panics
+ * This function panics if the `GlobalInterner` has been [poisoned](Interner#poisoning), and it may panic if the `GlobalInterner` is already locked on this thread.
+ */
+#[cfg(feature = "global-testing")]
+pub fn reset_global() {
+  take_global();
+}
+
+/**
+ * Locks the [`GlobalInterner`], saves the given string if it is not already saved, and returns the saved string, or blocks until it is able to do so.
+ * 
+ * `intern(string)` is equivalent to `GlobalInterner.intern(string)`, which is transitively equivalent to `GlobalInterner.lock().intern(string)`.
+ * (See [`Interner::intern`] and [`LockedInterner::intern`].)
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global")]
+#[inline]
+pub fn intern(string: impl AsRef<str>) -> InternedStr {
+  GlobalInterner.intern(string)
+}
+
+/**
+ * If the [`GlobalInterner`] is not currently locked, locks it, saves the given string if it is not already saved, and returns the saved string.
+ * Otherwise, returns `None` without blocking.
+ * 
+ * `try_intern_nonblocking(string)` is equivalent to `GlobalInterner.try_intern_nonblocking(string)`.
+ * (See [`Interner::try_intern_nonblocking`].)
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning).
+ */
+#[cfg(feature = "global")]
+#[inline]
+pub fn try_intern_nonblocking(string: impl AsRef<str>) -> Option<InternedStr> {
+  GlobalInterner.try_intern_nonblocking(string)
+}
+
+/**
+ * Locks the [`GlobalInterner`], saves each of the given strings if they are not already saved, and returns the saved strings, in the same order,
+ * or blocks until it is able to do so.
+ * 
+ * `intern_many(strings)` is equivalent to `GlobalInterner.intern_all(strings)`. (See [`Interner::intern_all`].)
+ * This only locks the `GlobalInterner` once, rather than once per string, like [`intern`] would.
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global")]
+#[inline]
+pub fn intern_many<I: IntoIterator>(strings: I) -> Vec<InternedStr> where I::Item: AsRef<str> {
+  GlobalInterner.intern_all(strings)
+}
+
+/**
+ * Locks the [`GlobalInterner`], saves both of the given strings if they are not already saved, and returns the saved strings, or blocks until it is able to do so.
+ * 
+ * This only locks the `GlobalInterner` once, rather than once per string, like [`intern`] would. (See also [`intern_many`].)
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global")]
+pub fn intern_pair(first: impl AsRef<str>, second: impl AsRef<str>) -> (InternedStr, InternedStr) {
+  let mut interner = GlobalInterner.lock();
+  (interner.intern(first), interner.intern(second))
+}
+
+/**
+ * Locks the [`GlobalInterner`], saves each of the given strings if they are not already saved, and returns the saved strings, in the same order,
+ * or blocks until it is able to do so.
+ * 
+ * This is like [`intern_many`], except that it returns an array instead of allocating a `Vec`.
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global")]
+pub fn intern_array<T: AsRef<str>, const N: usize>(strings: [T; N]) -> [InternedStr; N] {
+  let mut interner = GlobalInterner.lock();
+  strings.map(|string| interner.intern(string))
+}
+
+#[cfg(all(feature = "global", debug_assertions))]
+std::thread_local! {
   
-  fn le<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.le(other)
-  }
+  /**
+   * Whether [`with_global`] is currently running on this thread.
+   */
+  static IN_WITH_GLOBAL: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
   
-  fn gt<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.gt(other)
-  }
+}
+
+/**
+ * Resets [`IN_WITH_GLOBAL`] when dropped, even if the function given to [`with_global`] panics.
+ */
+#[cfg(all(feature = "global", debug_assertions))]
+struct WithGlobalGuard;
+
+#[cfg(all(feature = "global", debug_assertions))]
+impl WithGlobalGuard {
   
-  fn ge<I: IntoIterator>(self, other: I) -> bool where Self::Item: PartialOrd<I::Item> {
-    self.iter.ge(other)
+  fn new() -> Self {
+    if IN_WITH_GLOBAL.with(|in_with_global| in_with_global.replace(true)) {
+      panic!("with_global called re-entrantly; the GlobalInterner is already locked on this thread");
+    }
+    Self
   }
   
 }
 
-impl ExactSizeIterator for IntoIter {
+#[cfg(all(feature = "global", debug_assertions))]
+impl Drop for WithGlobalGuard {
   
-  fn len(&self) -> usize {
-    self.iter.len()
+  fn drop(&mut self) {
+    IN_WITH_GLOBAL.with(|in_with_global| in_with_global.set(false));
   }
   
 }
 
-impl FusedIterator for IntoIter {}
+/**
+ * Locks the [`GlobalInterner`], calls the given function with it, and returns its result, or blocks until it is able to do so.
+ * 
+ * This is the preferred way to do several operations atomically on the `GlobalInterner`, since the lock cannot outlive the function:
+ * ```rust
+ * # use str_intern::sync::with_global;
+ * let newly_saved = with_global(|locked| {
+ *   let newly_saved = !locked.contains("with_global");
+ *   locked.intern("with_global");
+ *   newly_saved
+ * });
+ * assert!(newly_saved);
+ * ```
+ * 
+ * `with_global(f)` is equivalent to `GlobalInterner.visit(f)`. (See [`Interner::visit`].)
+ * The function must only use the [`LockedInterner`] it is given, and not the `GlobalInterner` (including through [`intern`] and the other free functions),
+ * since it is already locked on this thread.
+ * 
+ * # Panics
+ * This function panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ * With debug assertions enabled, it always panics (rather than deadlocking) if it is called from within another call to `with_global` on the same thread.
+ * If the given function panics, the `GlobalInterner` becomes poisoned (unless the `parking_lot` feature is enabled).
+ */
+#[cfg(feature = "global")]
+pub fn with_global<R>(f: impl FnOnce(&mut LockedInterner<'_, GlobalState>) -> R) -> R {
+  #[cfg(debug_assertions)]
+  let _guard = WithGlobalGuard::new();
+  GlobalInterner.visit(f)
+}
+
+/**
+ * Locks the [`GlobalInterner`] and returns the number of strings that are currently interned, or blocks until it is able to do so.
+ * 
+ * `global_len()` is equivalent to `GlobalInterner.len()`. (See [`Interner::len`].)
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global")]
+#[inline]
+pub fn global_len() -> usize {
+  GlobalInterner.len()
+}
 
+/**
+ * Locks the [`GlobalInterner`] and returns how many times strings have been interned into it and found to be already saved or not, or blocks until it is able to do so.
+ * 
+ * `global_stats()` is equivalent to `GlobalInterner.stats()`. (See [`Interner::stats`].)
+ * Like [`global_len`] and [`global_memory_usage`], this doesn't need to look at every string, so it is cheap enough to call periodically (e.g., to log it).
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
 #[cfg(feature = "global")]
-static GLOBAL: OnceLock<Interner> = OnceLock::new();
+#[inline]
+pub fn global_stats() -> InternStats {
+  GlobalInterner.stats()
+}
 
 /**
- * A global [`Interner`], just for convenience.
+ * Locks the [`GlobalInterner`] and returns an estimate of how much memory it uses, including the interned strings themselves, or blocks until it is able to do so.
  * 
- * `GlobalInterner` functions just like any other `Interner`,
- * so a string interned in another interner will not be automatically interned into this one.
+ * `global_memory_usage()` is equivalent to `GlobalInterner.memory_usage()`. (See [`Interner::memory_usage`].)
  * 
- * For most purposes, [`intern`] will be sufficient.
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
  */
 #[cfg(feature = "global")]
-pub struct GlobalInterner;
+#[inline]
+pub fn global_memory_usage() -> MemoryUsage {
+  GlobalInterner.memory_usage()
+}
 
+/**
+ * Locks the [`GlobalInterner`], removes every interned string which is not referenced outside of it, and returns how many strings were removed,
+ * and how many bytes they contained, or blocks until it is able to do so.
+ * 
+ * `global_gc()` is equivalent to `GlobalInterner.gc()`. (See [`Interner::gc`].)
+ * Strings are never removed from the `GlobalInterner` otherwise, so a long-running program which interns many short-lived strings
+ * might want to call this periodically. It has to look at every string, so it keeps the `GlobalInterner` locked for longer than most methods.
+ * 
+ * # Panics
+ * This method panics if the [`GlobalInterner`] has been [poisoned](Interner#poisoning), and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ */
 #[cfg(feature = "global")]
-impl Deref for GlobalInterner {
+#[inline]
+pub fn global_gc() -> GcReport {
+  GlobalInterner.gc()
+}
+
+#[cfg(feature = "global-weak")]
+static GLOBAL_WEAK: OnceLock<WeakInterner> = OnceLock::new();
+
+/**
+ * A global [`WeakInterner`], just for convenience.
+ * 
+ * Unlike the [`GlobalInterner`], which keeps every string it has ever interned alive until the program exits (or until [`global_gc`] is called),
+ * a string in the `GlobalWeakInterner` is freed as soon as every reference to it is dropped, which suits long-running programs that intern many short-lived strings.
+ * The two are separate interners, so a string interned in one is not pointer-equal to the same string interned in the other.
+ * 
+ * This comes at a cost: interning a string has to upgrade a weak reference to each saved string with the same hash,
+ * and every so often (whenever the number of entries has doubled), interning a string purges the entries for every freed string at once,
+ * which keeps the `GlobalWeakInterner` locked for time proportional to its size.
+ * Purging it at a convenient time instead (with [`global_purge`]) makes that less likely to happen at an inconvenient one.
+ * 
+ * For most purposes, [`intern_weak`] will be sufficient.
+ */
+#[cfg(feature = "global-weak")]
+pub struct GlobalWeakInterner;
+
+#[cfg(feature = "global-weak")]
+impl Deref for GlobalWeakInterner {
   
-  type Target = Interner;
+  type Target = WeakInterner;
   
-  fn deref(&self) -> &Interner {
-    GLOBAL.get_or_init(Interner::new)
+  fn deref(&self) -> &WeakInterner {
+    GLOBAL_WEAK.get_or_init(WeakInterner::new)
   }
   
 }
 
 /**
- * Locks the [`GlobalInterner`], saves the given string if it is not already saved, and returns the saved string, or blocks until it is able to do so.
+ * Locks the [`GlobalWeakInterner`], and returns the saved string if it has not been freed, or saves the given string and returns that otherwise,
+ * or blocks until it is able to do so.
  * 
- * `intern(string)` is equivalent to `GlobalInterner.intern(string)`, which is transitively equivalent to `GlobalInterner.lock().intern(string)`.
- * (See [`Interner::intern`] and [`LockedInterner::intern`].)
+ * `intern_weak(string)` is equivalent to `GlobalWeakInterner.intern(string)`. (See [`WeakInterner::intern`].)
  * 
  * # Panics
- * This method panics if the [`GlobalInterner`] has been poisoned, and it may panic if the [`GlobalInterner`] is already locked on this thread.
+ * This method panics if the [`GlobalWeakInterner`] has been poisoned, and it may panic if the [`GlobalWeakInterner`] is already locked on this thread.
  */
-#[cfg(feature = "global")]
+#[cfg(feature = "global-weak")]
 #[inline]
-pub fn intern(string: impl AsRef<str>) -> InternedStr {
-  GlobalInterner.intern(string)
+pub fn intern_weak(string: impl AsRef<str>) -> InternedStr {
+  GlobalWeakInterner.intern(string)
+}
+
+/**
+ * Locks the [`GlobalWeakInterner`], removes the entries for every string that has been freed, and returns how many were removed,
+ * or blocks until it is able to do so.
+ * 
+ * `global_purge()` is equivalent to `GlobalWeakInterner.purge()`. (See [`WeakInterner::purge`].)
+ * 
+ * # Panics
+ * This method panics if the [`GlobalWeakInterner`] has been poisoned, and it may panic if the [`GlobalWeakInterner`] is already locked on this thread.
+ */
+#[cfg(feature = "global-weak")]
+#[inline]
+pub fn global_purge() -> usize {
+  GlobalWeakInterner.purge()
 }
 
 /**
- * An "extension trait" to add a the [`intern`](InternExt::intern) method to [`str`],
- * which effectively adds it to all types that directly or transitively implement [`Deref<Target = str>`](std::ops::Deref),
- * which includes [`String`], references, and  smart pointers to [`str`] or [`String`].
+ * An "extension trait" to add a the [`intern`](InternExt::intern) method to string types.
+ * 
+ * `InternExt` is implemented for [`&str`](prim@str), [`String`], [`Box<str>`], [`Cow<str>`], and [`Arc<str>`],
+ * and references to any of these also pick it up through auto-deref.
  * 
- * Ideally, I would like to ban [`Rc`](std::rc::Rc), but that would require auto traits or negative `impl`s or something.
- * My reasoning for this is that I suspect it will be a bit of a footgun,
- * or at least an unintuitive behavior if [`Rc`](std::rc::Rc) becomes an [`Arc`] when it gets interned.
+ * It is deliberately *not* implemented for [`Rc<str>`](std::rc::Rc),
+ * because an [`Rc`](std::rc::Rc) silently becoming an [`Arc`] copy when it gets interned would be a footgun.
+ * To make sure that deref-based method resolution cannot reintroduce it, the `impl` is for `&str` rather than `str`,
+ * and the trait is sealed, so no other `impl`s can be added:
+ * ```rust,compile_fail
+ * # use std::rc::Rc;
+ * # use str_intern::sync::InternExt;
+ * let rc: Rc<str> = Rc::from("rc");
+ * rc.intern();
+ * ```
+ * Use [`intern`] directly (e.g., `intern(&*rc)`) to make the conversion explicit.
  */
 #[cfg(feature = "global")]
-pub trait InternExt: AsRef<str> {
+pub trait InternExt: AsRef<str> + sealed::Sealed {
   
   /**
    * Equivalent to `intern(self)`.
@@ -703,4 +6633,32 @@ pub trait InternExt: AsRef<str> {
 }
 
 #[cfg(feature = "global")]
-impl InternExt for str {}
\ No newline at end of file
+mod sealed {
+  
+  use std::borrow::Cow;
+  use std::sync::Arc;
+  
+  pub trait Sealed {}
+  
+  impl Sealed for &str {}
+  impl Sealed for String {}
+  impl Sealed for Box<str> {}
+  impl Sealed for Cow<'_, str> {}
+  impl Sealed for Arc<str> {}
+  
+}
+
+#[cfg(feature = "global")]
+impl InternExt for &str {}
+
+#[cfg(feature = "global")]
+impl InternExt for String {}
+
+#[cfg(feature = "global")]
+impl InternExt for Box<str> {}
+
+#[cfg(feature = "global")]
+impl InternExt for Cow<'_, str> {}
+
+#[cfg(feature = "global")]
+impl InternExt for Arc<str> {}
\ No newline at end of file
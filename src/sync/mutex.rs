@@ -0,0 +1,202 @@
+/*!
+ * The mutex behind [`Interner`](super::Interner): [`std::sync::Mutex`] by default, or `parking_lot::Mutex` when the `parking_lot` feature is enabled.
+ * When compiled with `--cfg str_intern_loom`, it is instead a lock built from `loom` primitives, so that `loom` can model-check the `Interner`.
+ * 
+ * Either way, it has the same API as [`std::sync::Mutex`], so that the `Interner` does not need to care which one it is using;
+ * a `parking_lot::Mutex` is just never poisoned.
+ */
+
+#[cfg(not(any(feature = "parking_lot", str_intern_loom)))]
+pub(crate) use std::sync::{Mutex, MutexGuard};
+
+#[cfg(all(feature = "parking_lot", not(str_intern_loom)))]
+pub(crate) use self::parking::{Mutex, MutexGuard};
+
+#[cfg(str_intern_loom)]
+pub(crate) use self::model::{Mutex, MutexGuard};
+
+#[cfg(all(feature = "parking_lot", not(str_intern_loom)))]
+mod parking {
+  
+  use std::panic::{RefUnwindSafe, UnwindSafe};
+  use std::sync::{LockResult, TryLockError, TryLockResult};
+  
+  pub(crate) type MutexGuard<'a, T> = parking_lot::MutexGuard<'a, T>;
+  
+  /**
+   * A `parking_lot::Mutex` with the API of a [`std::sync::Mutex`] which is never poisoned.
+   */
+  #[repr(transparent)]
+  pub(crate) struct Mutex<T>(parking_lot::Mutex<T>);
+  
+  impl<T> Mutex<T> {
+    
+    pub(crate) const fn new(value: T) -> Self {
+      Self(parking_lot::Mutex::new(value))
+    }
+    
+    pub(crate) fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+      Ok(self.0.lock())
+    }
+    
+    pub(crate) fn try_lock(&self) -> TryLockResult<MutexGuard<'_, T>> {
+      self.0.try_lock().ok_or(TryLockError::WouldBlock)
+    }
+    
+    pub(crate) fn into_inner(self) -> LockResult<T> {
+      Ok(self.0.into_inner())
+    }
+    
+    pub(crate) fn get_mut(&mut self) -> LockResult<&mut T> {
+      Ok(self.0.get_mut())
+    }
+    
+    pub(crate) fn clear_poison(&self) {}
+    
+  }
+  
+  // Like a std::sync::Mutex, which is only unwind safe because of poisoning; an Interner is unwind safe anyway (see Interner::clear_poison).
+  impl<T> UnwindSafe for Mutex<T> {}
+  
+  impl<T> RefUnwindSafe for Mutex<T> {}
+  
+}
+
+#[cfg(str_intern_loom)]
+mod model {
+  
+  use std::mem::ManuallyDrop;
+  use std::ops::{Deref, DerefMut};
+  use std::panic::{RefUnwindSafe, UnwindSafe};
+  use std::sync::{LockResult, PoisonError, TryLockError, TryLockResult};
+  use std::thread;
+  
+  use loom::cell::{MutPtr, UnsafeCell};
+  use loom::sync::atomic::{AtomicBool, Ordering};
+  
+  /**
+   * A spin lock with the API of a [`std::sync::Mutex`], including poisoning, whose every step is visible to `loom`.
+   * 
+   * This isn't just a `loom::sync::Mutex`, since one of those can never be locked again after a thread panics while holding it,
+   * so it couldn't be used to check how an `Interner` recovers from being poisoned.
+   */
+  pub(crate) struct Mutex<T> {
+    
+    locked: AtomicBool,
+    poisoned: AtomicBool,
+    value: UnsafeCell<T>
+    
+  }
+  
+  impl<T> Mutex<T> {
+    
+    pub(crate) fn new(value: T) -> Self {
+      Self { locked: AtomicBool::new(false), poisoned: AtomicBool::new(false), value: UnsafeCell::new(value) }
+    }
+    
+    pub(crate) fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+      while self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+        loom::thread::yield_now();
+      }
+      self.guard()
+    }
+    
+    pub(crate) fn try_lock(&self) -> TryLockResult<MutexGuard<'_, T>> {
+      match self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed) {
+        Ok(_) => Ok(self.guard()?),
+        Err(_) => Err(TryLockError::WouldBlock)
+      }
+    }
+    
+    /**
+     * Wraps the value in a guard once this has been locked.
+     */
+    fn guard(&self) -> LockResult<MutexGuard<'_, T>> {
+      let guard = MutexGuard { mutex: self, value: ManuallyDrop::new(self.value.get_mut()), panicking: thread::panicking() };
+      if self.poisoned.load(Ordering::Relaxed) {
+        Err(PoisonError::new(guard))
+      } else {
+        Ok(guard)
+      }
+    }
+    
+    pub(crate) fn into_inner(self) -> LockResult<T> {
+      let poisoned = self.poisoned.load(Ordering::Relaxed);
+      let value = self.value.into_inner();
+      if poisoned {
+        Err(PoisonError::new(value))
+      } else {
+        Ok(value)
+      }
+    }
+    
+    pub(crate) fn get_mut(&mut self) -> LockResult<&mut T> {
+      // Having `&mut self` means that nothing else can be accessing the value.
+      let value = unsafe { &mut *self.value.with_mut(|value| value) };
+      if self.poisoned.load(Ordering::Relaxed) {
+        Err(PoisonError::new(value))
+      } else {
+        Ok(value)
+      }
+    }
+    
+    pub(crate) fn clear_poison(&self) {
+      self.poisoned.store(false, Ordering::Relaxed);
+    }
+    
+  }
+  
+  // Only one guard can exist at a time, so this is as safe as a std::sync::Mutex.
+  unsafe impl<T: Send> Sync for Mutex<T> {}
+  
+  // Like a std::sync::Mutex, which is only unwind safe because of poisoning.
+  impl<T> UnwindSafe for Mutex<T> {}
+  
+  impl<T> RefUnwindSafe for Mutex<T> {}
+  
+  pub(crate) struct MutexGuard<'a, T> {
+    
+    mutex: &'a Mutex<T>,
+    value: ManuallyDrop<MutPtr<T>>,
+    
+    /**
+     * Whether this thread was already panicking when this was locked, in which case it doesn't poison the `Mutex`.
+     */
+    panicking: bool
+    
+  }
+  
+  impl<'a, T> Deref for MutexGuard<'a, T> {
+    
+    type Target = T;
+    
+    fn deref(&self) -> &T {
+      // The lock is held, so nothing else can be accessing the value.
+      unsafe { MutPtr::deref(&self.value) }
+    }
+    
+  }
+  
+  impl<'a, T> DerefMut for MutexGuard<'a, T> {
+    
+    fn deref_mut(&mut self) -> &mut T {
+      // The lock is held, so nothing else can be accessing the value.
+      unsafe { MutPtr::deref(&self.value) }
+    }
+    
+  }
+  
+  impl<'a, T> Drop for MutexGuard<'a, T> {
+    
+    fn drop(&mut self) {
+      // The access has to end before the lock is released, or `loom` would report a race with the next thread to lock it.
+      unsafe { ManuallyDrop::drop(&mut self.value) };
+      if !self.panicking && thread::panicking() {
+        self.mutex.poisoned.store(true, Ordering::Relaxed);
+      }
+      self.mutex.locked.store(false, Ordering::Release);
+    }
+    
+  }
+  
+}
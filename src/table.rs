@@ -0,0 +1,1057 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_set::{Drain, ExtractIf as SetExtractIf, Iter as SetIter};
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Deref, Range};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{ValidationError, TryInternError, GcReport, MemoryUsage, InternStats, RemovalCause};
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{IsNormalized, UnicodeNormalization};
+
+/**
+ * The differences between the two kinds of `Interner` that a `Table` needs to know about.
+ */
+pub(crate) trait Kind {
+  
+  /**
+   * A saved allocation, i.e., [`InternedStr`](crate::InternedStr) or [`sync::InternedStr`](crate::sync::InternedStr).
+   */
+  type Str: Clone + Deref<Target = str> + Borrow<str> + Hash + Eq + for<'s> From<&'s str> + From<String> + Debug;
+  
+  /**
+   * A function that is applied to every string before it is looked up or saved.
+   */
+  type Normalizer: Clone;
+  
+  /**
+   * A function that decides whether a string may be saved.
+   */
+  type Validator: Clone;
+  
+  /**
+   * A function that is called with every string that is removed.
+   */
+  type RemovalHook: FnMut(&Self::Str, RemovalCause);
+  
+  /**
+   * A function that is called with every string that is newly saved.
+   */
+  type InsertHook: FnMut(&Self::Str);
+  
+  fn normalize<'s>(normalizer: &Self::Normalizer, string: &'s str) -> Cow<'s, str>;
+  
+  fn validate(validator: &Self::Validator, string: &str) -> Result<(), ValidationError>;
+  
+  fn strong_count(string: &Self::Str) -> usize;
+  
+}
+
+/**
+ * A string that can be saved as a `T`, i.e., [`IntoInterned`](crate::IntoInterned) or [`sync::IntoInterned`](crate::sync::IntoInterned).
+ */
+pub(crate) trait Source<T> {
+  
+  fn as_str(&self) -> &str;
+  
+  fn into_saved(self) -> T;
+  
+}
+
+/**
+ * The storage behind both kinds of `Interner`, which keeps track of the saved allocations,
+ * along with anything else that is needed to look them up.
+ * 
+ * Everything that adds or removes a saved allocation should go through a `Table`, so that it stays consistent.
+ */
+pub(crate) struct Table<K: Kind, S> {
+  
+  pub(crate) strings: HashSet<K::Str, S>,
+  
+  /**
+   * The total length of `strings`.
+   */
+  bytes: usize,
+  
+  extras: Extras<K>,
+  config: Config<K>,
+  auto_gc: AutoGc,
+  stats: InternStats,
+  
+  /**
+   * How many times `strings` has changed.
+   */
+  version: u64,
+  
+  /**
+   * How many times a saved allocation has been removed from `strings`, so that caches of saved allocations can tell when they might be out of date.
+   */
+  removals: u64
+  
+}
+
+/**
+ * Everything besides [`Table::strings`] that keeps track of the saved allocations, each of which is only present if it is needed.
+ */
+struct Extras<K: Kind> {
+  
+  /**
+   * Each saved allocation, keyed by its contents with ASCII letters lowercased, when ignoring ASCII case.
+   */
+  folded: Option<HashMap<Box<str>, K::Str>>,
+  
+  recency: Option<Recency<K>>,
+  
+  /**
+   * How many times each saved allocation has been interned, when counting.
+   */
+  counts: Option<HashMap<K::Str, u64>>,
+  
+  /**
+   * This is never cloned, since whatever it keeps track of belongs to the original `Table`.
+   */
+  removal_hook: Option<K::RemovalHook>,
+  
+  /**
+   * This is never cloned, for the same reason as `removal_hook`.
+   */
+  insert_hook: Option<K::InsertHook>
+  
+}
+
+impl<K: Kind> Extras<K> {
+  
+  const fn new() -> Self {
+    Self { folded: None, recency: None, counts: None, removal_hook: None, insert_hook: None }
+  }
+  
+  /**
+   * Returns how many references these `Extras` hold to each saved allocation.
+   */
+  fn refs(&self) -> usize {
+    // `recency` references each allocation twice.
+    usize::from(self.folded.is_some()) + 2 * usize::from(self.recency.is_some()) + usize::from(self.counts.is_some())
+  }
+  
+  /**
+   * Starts keeping track of the given allocation, which has just been saved.
+   */
+  fn remember(&mut self, string: &K::Str) {
+    if let Some(insert_hook) = &mut self.insert_hook {
+      insert_hook(string);
+    }
+    if let Some(folded) = &mut self.folded {
+      folded.insert(fold_ascii_case(string).into_owned().into_boxed_str(), K::Str::clone(string));
+    }
+    if let Some(recency) = &mut self.recency {
+      recency.insert(K::Str::clone(string));
+    }
+    if let Some(counts) = &mut self.counts {
+      counts.insert(K::Str::clone(string), 0);
+    }
+  }
+  
+  /**
+   * Stops keeping track of the given allocation, which has just been removed for the given reason.
+   */
+  fn forget(&mut self, string: &K::Str, cause: RemovalCause) {
+    if let Some(removal_hook) = &mut self.removal_hook {
+      removal_hook(string, cause);
+    }
+    if let Some(folded) = &mut self.folded {
+      folded.remove(&*fold_ascii_case(string));
+    }
+    if let Some(recency) = &mut self.recency {
+      recency.remove(string);
+    }
+    if let Some(counts) = &mut self.counts {
+      counts.remove(&**string);
+    }
+  }
+  
+  fn clear(&mut self) {
+    if let Some(folded) = &mut self.folded {
+      folded.clear();
+    }
+    if let Some(recency) = &mut self.recency {
+      recency.clear();
+    }
+    if let Some(counts) = &mut self.counts {
+      counts.clear();
+    }
+  }
+  
+  fn shrink_to(&mut self, min_capacity: usize) {
+    if let Some(folded) = &mut self.folded {
+      folded.shrink_to(min_capacity);
+    }
+    if let Some(recency) = &mut self.recency {
+      recency.used.shrink_to(min_capacity);
+    }
+    if let Some(counts) = &mut self.counts {
+      counts.shrink_to(min_capacity);
+    }
+  }
+  
+  /**
+   * Estimates how much memory these `Extras` use, given the total length of the saved allocations.
+   */
+  fn memory_usage(&self, bytes: usize) -> usize {
+    let mut usage = 0;
+    if let Some(folded) = &self.folded {
+      // Every key is a copy of a saved string.
+      usage += folded.capacity() * (mem::size_of::<(Box<str>, K::Str)>() + 1) + bytes;
+    }
+    if let Some(recency) = &self.recency {
+      usage += recency.used.capacity() * (mem::size_of::<(K::Str, Used)>() + 1) + recency.order.len() * mem::size_of::<(u64, K::Str)>();
+    }
+    if let Some(counts) = &self.counts {
+      usage += counts.capacity() * (mem::size_of::<(K::Str, u64)>() + 1);
+    }
+    usage
+  }
+  
+}
+
+impl<K: Kind> Clone for Extras<K> {
+  
+  fn clone(&self) -> Self {
+    Self { folded: self.folded.clone(), recency: self.recency.clone(), counts: self.counts.clone(), removal_hook: None, insert_hook: None }
+  }
+  
+  fn clone_from(&mut self, source: &Self) {
+    self.folded.clone_from(&source.folded);
+    self.recency.clone_from(&source.recency);
+    self.counts.clone_from(&source.counts);
+    self.removal_hook = None;
+    self.insert_hook = None;
+  }
+  
+}
+
+/**
+ * How often a `Table` should [`gc`](Table::gc) by itself.
+ */
+#[derive(Clone, Copy, Default)]
+struct AutoGc {
+  
+  /**
+   * How many strings to save between each sweep, or 0 to never sweep automatically.
+   */
+  threshold: usize,
+  
+  /**
+   * How many strings have been saved since the last sweep.
+   */
+  inserts: usize
+  
+}
+
+/**
+ * How much a `Table` may hold before it has to evict strings.
+ */
+#[derive(Clone, Copy)]
+struct Limit {
+  
+  entries: usize,
+  bytes: usize
+  
+}
+
+impl Limit {
+  
+  fn allows(self, entries: usize, bytes: usize) -> bool {
+    entries <= self.entries && bytes <= self.bytes
+  }
+  
+}
+
+/**
+ * The order in which the strings in a `Table` were last used, so that the least recently used one can be evicted when it is full.
+ * 
+ * Strings can be looked up through a shared reference, so the time each one was last used is atomic,
+ * and `order` is only brought up to date when something actually needs to be evicted.
+ */
+struct Recency<K: Kind> {
+  
+  limit: Limit,
+  clock: AtomicU64,
+  used: HashMap<K::Str, Used>,
+  
+  /**
+   * Each string, keyed by the time it was last used as of the last time it was put in order.
+   */
+  order: BTreeMap<u64, K::Str>
+  
+}
+
+struct Used {
+  
+  /**
+   * This string's key in [`Recency::order`].
+   */
+  ordered: u64,
+  
+  last: AtomicU64
+  
+}
+
+/**
+ * The parts of a `Table` which are set when it is constructed, and never change afterward.
+ */
+struct Config<K: Kind> {
+  
+  normalizer: Option<K::Normalizer>,
+  validator: Option<K::Validator>,
+  max_len: Option<usize>
+  
+}
+
+impl<K: Kind> Config<K> {
+  
+  const fn new() -> Self {
+    Self { normalizer: None, validator: None, max_len: None }
+  }
+  
+}
+
+impl<K: Kind> Recency<K> {
+  
+  fn new(limit: Limit) -> Self {
+    Self { limit, clock: AtomicU64::new(0), used: HashMap::new(), order: BTreeMap::new() }
+  }
+  
+  fn tick(&self) -> u64 {
+    self.clock.fetch_add(1, Ordering::Relaxed)
+  }
+  
+  fn touch(&self, string: &str) {
+    if let Some(used) = self.used.get(string) {
+      used.last.store(self.tick(), Ordering::Relaxed);
+    }
+  }
+  
+  fn insert(&mut self, string: K::Str) {
+    let now = self.tick();
+    self.used.insert(K::Str::clone(&string), Used { ordered: now, last: AtomicU64::new(now) });
+    self.order.insert(now, string);
+  }
+  
+  fn remove(&mut self, string: &str) {
+    if let Some(used) = self.used.remove(string) {
+      self.order.remove(&used.ordered);
+    }
+  }
+  
+  fn clear(&mut self) {
+    self.used.clear();
+    self.order.clear();
+  }
+  
+  /**
+   * Forgets the least recently used string and returns it, or returns `None` if there are no strings.
+   */
+  fn pop_least_recent(&mut self) -> Option<K::Str> {
+    loop {
+      let (ordered, string) = self.order.pop_first()?;
+      let used = self.used.get_mut(&*string).expect("every ordered string should have a use time");
+      let last = *used.last.get_mut();
+      if last == ordered {
+        self.used.remove(&*string);
+        return Some(string);
+      }
+      // It has been used since it was last put in order, so put it back where it belongs.
+      used.ordered = last;
+      self.order.insert(last, string);
+    }
+  }
+  
+}
+
+impl<K: Kind> Clone for Recency<K> {
+  
+  fn clone(&self) -> Self {
+    Self {
+      limit: self.limit,
+      clock: AtomicU64::new(self.clock.load(Ordering::Relaxed)),
+      used: self.used.clone(),
+      order: self.order.clone()
+    }
+  }
+  
+}
+
+impl Clone for Used {
+  
+  fn clone(&self) -> Self {
+    Self { ordered: self.ordered, last: AtomicU64::new(self.last.load(Ordering::Relaxed)) }
+  }
+  
+}
+
+impl<K: Kind> Clone for Config<K> {
+  
+  fn clone(&self) -> Self {
+    Self { normalizer: self.normalizer.clone(), validator: self.validator.clone(), max_len: self.max_len }
+  }
+  
+}
+
+/**
+ * Everything about how a `Table` behaves that can be chosen when it is constructed, any of which can be combined.
+ */
+pub(crate) struct Options<K: Kind> {
+  
+  /**
+   * A function to apply to strings before looking them up or saving them.
+   */
+  pub(crate) normalizer: Option<K::Normalizer>,
+  
+  /**
+   * A function that decides whether a string may be saved.
+   */
+  pub(crate) validator: Option<K::Validator>,
+  
+  /**
+   * The longest string that may be saved, in bytes.
+   */
+  pub(crate) max_len: Option<usize>,
+  
+  /**
+   * The most strings that may be saved at once, which must not be 0, before the least recently used ones are evicted.
+   */
+  pub(crate) max_entries: Option<usize>,
+  
+  /**
+   * The most bytes that may be saved at once, in total, before the least recently used strings are evicted.
+   */
+  pub(crate) max_bytes: Option<usize>,
+  
+  /**
+   * Whether to look strings up without regard to ASCII case, keeping whichever casing was saved first.
+   */
+  pub(crate) ascii_case_insensitive: bool,
+  
+  /**
+   * Whether to count how many times each string is interned.
+   */
+  pub(crate) counting: bool
+  
+}
+
+impl<K: Kind> Options<K> {
+  
+  pub(crate) const fn new() -> Self {
+    Self { normalizer: None, validator: None, max_len: None, max_entries: None, max_bytes: None, ascii_case_insensitive: false, counting: false }
+  }
+  
+}
+
+impl<K: Kind> Clone for Options<K> {
+  
+  fn clone(&self) -> Self {
+    Self {
+      normalizer: self.normalizer.clone(),
+      validator: self.validator.clone(),
+      max_len: self.max_len,
+      max_entries: self.max_entries,
+      max_bytes: self.max_bytes,
+      ascii_case_insensitive: self.ascii_case_insensitive,
+      counting: self.counting
+    }
+  }
+  
+}
+
+impl<K: Kind> Debug for Options<K> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_struct("Options")
+      .field("normalizer", &self.normalizer.is_some())
+      .field("validator", &self.validator.is_some())
+      .field("max_len", &self.max_len)
+      .field("max_entries", &self.max_entries)
+      .field("max_bytes", &self.max_bytes)
+      .field("ascii_case_insensitive", &self.ascii_case_insensitive)
+      .field("counting", &self.counting)
+      .finish()
+  }
+  
+}
+
+impl<K: Kind, S> Table<K, S> {
+  
+  pub(crate) fn new(strings: HashSet<K::Str, S>) -> Self {
+    let bytes = strings.iter().map(|string| string.len()).sum();
+    Self { strings, bytes, extras: Extras::new(), config: Config::new(), auto_gc: AutoGc::default(), stats: InternStats::default(), version: 0, removals: 0 }
+  }
+  
+  /**
+   * Constructs a new, empty `Table` with the given hasher, in a `const` context.
+   */
+  pub(crate) const fn with_hasher(hasher: S) -> Self {
+    Self {
+      strings: HashSet::with_hasher(hasher),
+      bytes: 0,
+      extras: Extras::new(),
+      config: Config::new(),
+      auto_gc: AutoGc { threshold: 0, inserts: 0 },
+      stats: InternStats { hits: 0, misses: 0, inserts: 0, bytes_saved_estimate: 0 },
+      version: 0,
+      removals: 0
+    }
+  }
+  
+  /**
+   * Constructs a new `Table` which behaves as the given options say.
+   * The given set must already be consistent with them (e.g., it must not contain any strings which the normalizer would change,
+   * or which the validator would reject, or more strings than the limits allow).
+   */
+  pub(crate) fn with_options(strings: HashSet<K::Str, S>, options: Options<K>) -> Self {
+    let folded = options.ascii_case_insensitive.then(|| {
+      let mut folded = HashMap::with_capacity(strings.len());
+      for string in &strings {
+        folded.entry(fold_ascii_case(string).into_owned().into_boxed_str()).or_insert_with(|| K::Str::clone(string));
+      }
+      folded
+    });
+    let recency = (options.max_entries.is_some() || options.max_bytes.is_some()).then(|| {
+      assert!(options.max_entries != Some(0), "an interner must be able to hold at least one string");
+      let mut recency = Recency::new(Limit { entries: options.max_entries.unwrap_or(usize::MAX), bytes: options.max_bytes.unwrap_or(usize::MAX) });
+      for string in &strings {
+        recency.insert(K::Str::clone(string));
+      }
+      recency
+    });
+    let counts = options.counting.then(|| strings.iter().map(|string| (K::Str::clone(string), 0)).collect());
+    Self {
+      extras: Extras { folded, recency, counts, ..Extras::new() },
+      config: Config { normalizer: options.normalizer, validator: options.validator, max_len: options.max_len },
+      ..Self::new(strings)
+    }
+  }
+  
+  pub(crate) fn into_set(self) -> HashSet<K::Str, S> {
+    self.strings
+  }
+  
+  pub(crate) fn bytes(&self) -> usize {
+    self.bytes
+  }
+  
+  /**
+   * Estimates how much memory this `Table` uses, without looking at every string.
+   */
+  pub(crate) fn memory_usage(&self) -> MemoryUsage {
+    // Every bucket of a `HashSet` or `HashMap` also has a control byte.
+    let table = self.strings.capacity() * (mem::size_of::<K::Str>() + 1) + self.extras.memory_usage(self.bytes);
+    // The strong and weak counts are stored in the same allocation as each string.
+    MemoryUsage { strings: self.bytes, headers: self.strings.len() * 2 * mem::size_of::<usize>(), table }
+  }
+  
+  pub(crate) fn set_removal_hook(&mut self, removal_hook: K::RemovalHook) {
+    self.extras.removal_hook = Some(removal_hook);
+  }
+  
+  pub(crate) fn set_insert_hook(&mut self, insert_hook: K::InsertHook) {
+    self.extras.insert_hook = Some(insert_hook);
+  }
+  
+  pub(crate) fn clear(&mut self) {
+    self.clear_extras();
+    self.strings.clear();
+  }
+  
+  pub(crate) fn drain(&mut self) -> Drain<'_, K::Str> {
+    // Every string is removed even if the `Drain` is dropped early, so they can all be reported right away.
+    self.clear_extras();
+    self.strings.drain()
+  }
+  
+  /**
+   * Clears everything besides `strings` that keeps track of the saved allocations, as if `strings` were cleared.
+   */
+  fn clear_extras(&mut self) {
+    if !self.strings.is_empty() {
+      self.version += 1;
+      self.removals += 1;
+    }
+    if let Some(removal_hook) = &mut self.extras.removal_hook {
+      for string in &self.strings {
+        removal_hook(string, RemovalCause::Cleared);
+      }
+    }
+    self.bytes = 0;
+    self.extras.clear();
+  }
+  
+  /**
+   * Returns an iterator which removes the saved allocations which match the given predicate for the given reason.
+   */
+  pub(crate) fn extract_if<F: FnMut(&K::Str) -> bool>(&mut self, predicate: F, cause: RemovalCause) -> ExtractIf<'_, K, F> {
+    ExtractIf {
+      iter: self.strings.extract_if(predicate),
+      cause,
+      bytes: &mut self.bytes,
+      version: &mut self.version,
+      removals: &mut self.removals,
+      extras: &mut self.extras
+    }
+  }
+  
+  /**
+   * Removes the saved allocations which do not match the given predicate.
+   */
+  pub(crate) fn retain(&mut self, mut predicate: impl FnMut(&K::Str) -> bool) {
+    self.extract_if(|string| !predicate(string), RemovalCause::Retain).for_each(drop);
+  }
+  
+  pub(crate) fn stats(&self) -> InternStats {
+    self.stats
+  }
+  
+  pub(crate) fn reset_stats(&mut self) {
+    self.stats = InternStats::default();
+  }
+  
+  pub(crate) fn version(&self) -> u64 {
+    self.version
+  }
+  
+  pub(crate) fn removals(&self) -> u64 {
+    self.removals
+  }
+  
+  pub(crate) fn gc_threshold(&self) -> usize {
+    self.auto_gc.threshold
+  }
+  
+  pub(crate) fn set_gc_threshold(&mut self, threshold: usize) {
+    self.auto_gc = AutoGc { threshold, inserts: 0 };
+  }
+  
+  /**
+   * Returns how many references this `Table` holds to each saved allocation.
+   */
+  fn internal_refs(&self) -> usize {
+    1 + self.extras.refs()
+  }
+  
+  /**
+   * Returns an iterator over the saved allocations which are referenced at least `min_refs` times outside of this `Table`,
+   * along with how many times each of them is referenced.
+   */
+  pub(crate) fn ref_counts(&self, min_refs: usize) -> RefCounts<'_, K> {
+    RefCounts { iter: self.strings.iter(), internal: self.internal_refs(), min_refs }
+  }
+  
+  /**
+   * Removes every saved allocation which is not referenced outside of this `Table`.
+   */
+  pub(crate) fn gc(&mut self) -> GcReport {
+    let internal = self.internal_refs();
+    self.auto_gc.inserts = 0;
+    let mut report = GcReport::default();
+    for string in self.extract_if(|string| K::strong_count(string) <= internal, RemovalCause::Gc) {
+      report.entries += 1;
+      report.bytes += string.len();
+    }
+    report
+  }
+  
+}
+
+impl<K: Kind, S: BuildHasher> Table<K, S> {
+  
+  /**
+   * Shrinks the capacity of `strings` and everything else that keeps track of them as much as possible, but not below `min_capacity`,
+   * and returns how much the capacity of `strings` decreased.
+   */
+  pub(crate) fn shrink_to(&mut self, min_capacity: usize) -> usize {
+    let capacity = self.strings.capacity();
+    self.strings.shrink_to(min_capacity);
+    self.extras.shrink_to(min_capacity);
+    capacity - self.strings.capacity()
+  }
+  
+  /**
+   * Returns the form of the given string that is actually saved, which is only different if this `Table` normalizes strings.
+   */
+  pub(crate) fn normalize<'s>(&self, string: &'s str) -> Cow<'s, str> {
+    match &self.config.normalizer {
+      Some(normalizer) => K::normalize(normalizer, string),
+      None => Cow::Borrowed(string)
+    }
+  }
+  
+  /**
+   * Returns the saved allocation for the given string, if there is one.
+   */
+  pub(crate) fn get(&self, string: &str) -> Option<&K::Str> {
+    self.get_normalized(&self.normalize(string))
+  }
+  
+  /**
+   * Saves the given string if it is not already saved, and returns a reference to the saved allocation,
+   * along with whether it was newly saved.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected.
+   */
+  pub(crate) fn intern<I: Source<K::Str>>(&mut self, string: I) -> (K::Str, bool) {
+    self.try_intern(string).unwrap_or_else(|error| panic!("string was rejected by the interner: {error}"))
+  }
+  
+  /**
+   * Saves the given string if it is not already saved and it is not rejected, and returns a reference to the saved allocation,
+   * along with whether it was newly saved.
+   * A string which can never fit in this `Table` is returned without being saved, so it is not newly saved either.
+   */
+  pub(crate) fn try_intern<I: Source<K::Str>>(&mut self, string: I) -> Result<(K::Str, bool), TryInternError> {
+    let normalized = self.prepare(&string)?;
+    // Sorrow abounds, for behold: HashSet::get_or_insert_with doesn't exist yet.
+    match self.get_normalized(normalized.as_str(string.as_str())).cloned() {
+      Some(string) => {
+        self.hit(&string);
+        Ok((string, false))
+      }
+      None => self.save(string, normalized)
+    }
+  }
+  
+  /**
+   * Records that the given saved allocation was found when interning a string, which the caller has already looked up.
+   */
+  pub(crate) fn hit(&mut self, string: &str) {
+    self.count(string);
+    self.stats.hits += 1;
+    self.stats.bytes_saved_estimate += string.len();
+  }
+  
+  /**
+   * Saves the given string, which the caller has just found is not already saved, without looking it up again,
+   * and returns a reference to the saved allocation, along with whether it was newly saved.
+   * 
+   * # Panics
+   * This method panics if the given string is rejected.
+   */
+  pub(crate) fn intern_vacant<I: Source<K::Str>>(&mut self, string: I) -> (K::Str, bool) {
+    self.prepare(&string)
+      .and_then(|normalized| self.save(string, normalized))
+      .unwrap_or_else(|error| panic!("string was rejected by the interner: {error}"))
+  }
+  
+  /**
+   * Checks that the given string is not too long, and normalizes it.
+   */
+  fn prepare<I: Source<K::Str>>(&self, string: &I) -> Result<Normalized, TryInternError> {
+    // Check this before normalizing, since that could allocate.
+    if let Some(max) = self.config.max_len {
+      let len = string.as_str().len();
+      if len > max {
+        return Err(TryInternError::TooLong { len, max });
+      }
+    }
+    // Don't hang on to a borrow of `string`, so that it can still be saved as is if normalizing it doesn't change anything.
+    Ok(Normalized::new(string.as_str(), self.normalize(string.as_str())))
+  }
+  
+  /**
+   * Validates and saves the given string, which must not already be saved, in its normalized form.
+   */
+  fn save<I: Source<K::Str>>(&mut self, string: I, normalized: Normalized) -> Result<(K::Str, bool), TryInternError> {
+    self.stats.misses += 1;
+    if let Some(validator) = &self.config.validator {
+      K::validate(validator, normalized.as_str(string.as_str())).map_err(TryInternError::Invalid)?;
+    }
+    let string = match normalized {
+      Normalized::Unchanged => string.into_saved(),
+      Normalized::Slice(range) => K::Str::from(&string.as_str()[range]),
+      Normalized::Owned(normalized) => K::Str::from(normalized)
+    };
+    if !self.insert(K::Str::clone(&string)) {
+      // It can never fit, so just hand it back, without claiming that it was saved.
+      return Ok((string, false));
+    }
+    self.count(&string);
+    self.stats.inserts += 1;
+    self.auto_gc.inserts += 1;
+    if self.auto_gc.inserts == self.auto_gc.threshold {
+      // The new string can't be swept, since it is about to be returned.
+      self.gc();
+    }
+    Ok((string, true))
+  }
+  
+  /**
+   * Returns the saved allocation for the given string, which must already be normalized, if there is one.
+   * This counts as using it.
+   */
+  fn get_normalized(&self, string: &str) -> Option<&K::Str> {
+    let saved = self.find_normalized(string)?;
+    if let Some(recency) = &self.extras.recency {
+      recency.touch(saved);
+    }
+    Some(saved)
+  }
+  
+  /**
+   * Returns the saved allocation for the given string, which must already be normalized, if there is one, without counting it as being used.
+   */
+  fn find_normalized(&self, string: &str) -> Option<&K::Str> {
+    match &self.extras.folded {
+      Some(folded) => folded.get(&*fold_ascii_case(string)),
+      None => self.strings.get(string)
+    }
+  }
+  
+  /**
+   * Returns how many times the given string has been interned, if this `Table` is counting and the string is saved.
+   */
+  pub(crate) fn count_of(&self, string: &str) -> Option<u64> {
+    let counts = self.extras.counts.as_ref()?;
+    counts.get(&**self.find_normalized(&self.normalize(string))?).copied()
+  }
+  
+  /**
+   * Returns up to `n` of the saved allocations which have been interned the most times, along with their counts,
+   * sorted by count in descending order, with ties broken by their contents in ascending order.
+   */
+  pub(crate) fn most_common(&self, n: usize) -> Vec<(K::Str, u64)> {
+    let Some(counts) = &self.extras.counts else { return Vec::new() };
+    let mut most_common: Vec<_> = counts.iter().map(|(string, &count)| (K::Str::clone(string), count)).collect();
+    most_common.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    most_common.truncate(n);
+    most_common
+  }
+  
+  /**
+   * Increments how many times the given saved allocation has been interned, if this `Table` is counting.
+   */
+  fn count(&mut self, string: &str) {
+    if let Some(count) = self.extras.counts.as_mut().and_then(|counts| counts.get_mut(string)) {
+      *count += 1;
+    }
+  }
+  
+  /**
+   * Saves the given allocation, which must already be normalized, and must not already have been saved, and returns whether it was saved.
+   * If this `Table` is full, the least recently used strings are evicted first,
+   * unless the given string would not fit even if this `Table` were empty, in which case nothing happens.
+   */
+  fn insert(&mut self, string: K::Str) -> bool {
+    if let Some(limit) = self.extras.recency.as_ref().map(|recency| recency.limit) {
+      if !limit.allows(1, string.len()) {
+        return false;
+      }
+      while !limit.allows(self.strings.len() + 1, self.bytes + string.len()) {
+        let Some(evicted) = self.extras.recency.as_mut().and_then(Recency::pop_least_recent) else { break };
+        self.extras.forget(&evicted, RemovalCause::Evicted);
+        self.bytes -= evicted.len();
+        self.removals += 1;
+        self.strings.remove(&evicted);
+      }
+    }
+    self.extras.remember(&string);
+    self.bytes += string.len();
+    self.version += 1;
+    self.strings.insert(string);
+    true
+  }
+  
+  /**
+   * Removes the saved allocation for the given string, if there is one.
+   */
+  pub(crate) fn remove(&mut self, string: &str) -> Option<K::Str> {
+    let string = K::Str::clone(self.find_normalized(&self.normalize(string))?);
+    self.strings.remove(&string);
+    self.extras.forget(&string, RemovalCause::Explicit);
+    self.bytes -= string.len();
+    self.version += 1;
+    self.removals += 1;
+    Some(string)
+  }
+  
+}
+
+impl<K: Kind, S: Clone> Clone for Table<K, S> {
+  
+  fn clone(&self) -> Self {
+    Self {
+      strings: self.strings.clone(),
+      bytes: self.bytes,
+      extras: self.extras.clone(),
+      config: self.config.clone(),
+      auto_gc: self.auto_gc,
+      stats: self.stats,
+      version: self.version,
+      removals: self.removals
+    }
+  }
+  
+  fn clone_from(&mut self, source: &Self) {
+    self.strings.clone_from(&source.strings);
+    self.bytes = source.bytes;
+    self.extras.clone_from(&source.extras);
+    self.config.clone_from(&source.config);
+    self.auto_gc = source.auto_gc;
+    self.stats = source.stats;
+    // Anything keeping track of this `Table` has to see a change, so these can never go backwards, even if `source`'s are lower.
+    self.version = self.version.max(source.version) + 1;
+    self.removals = self.removals.max(source.removals) + 1;
+  }
+  
+}
+
+impl<K: Kind, S: Default> Default for Table<K, S> {
+  
+  fn default() -> Self {
+    Self::new(HashSet::default())
+  }
+  
+}
+
+/**
+ * The part of [`extract_if`](Table::extract_if) that each kind of `ExtractIf` wraps.
+ */
+pub(crate) struct ExtractIf<'a, K: Kind, F: FnMut(&K::Str) -> bool> {
+  
+  iter: SetExtractIf<'a, K::Str, F>,
+  cause: RemovalCause,
+  bytes: &'a mut usize,
+  version: &'a mut u64,
+  removals: &'a mut u64,
+  extras: &'a mut Extras<K>
+  
+}
+
+impl<'a, K: Kind, F: FnMut(&K::Str) -> bool> Iterator for ExtractIf<'a, K, F> {
+  
+  type Item = K::Str;
+  
+  fn next(&mut self) -> Option<K::Str> {
+    let string = self.iter.next()?;
+    *self.bytes -= string.len();
+    *self.version += 1;
+    *self.removals += 1;
+    self.extras.forget(&string, self.cause);
+    Some(string)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+  
+}
+
+impl<'a, K: Kind, F: FnMut(&K::Str) -> bool> Debug for ExtractIf<'a, K, F> {
+  
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    self.iter.fmt(f)
+  }
+  
+}
+
+/**
+ * The part of [`ref_counts`](Table::ref_counts) that each kind of `RefCounts` wraps.
+ */
+pub(crate) struct RefCounts<'a, K: Kind> {
+  
+  iter: SetIter<'a, K::Str>,
+  internal: usize,
+  min_refs: usize
+  
+}
+
+impl<'a, K: Kind> Iterator for RefCounts<'a, K> {
+  
+  type Item = (&'a K::Str, usize);
+  
+  fn next(&mut self) -> Option<Self::Item> {
+    let (internal, min_refs) = (self.internal, self.min_refs);
+    self.iter.by_ref().map(|string| (string, K::strong_count(string) - internal)).find(|&(_, refs)| refs >= min_refs)
+  }
+  
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (min, max) = self.iter.size_hint();
+    (if self.min_refs == 0 { min } else { 0 }, max)
+  }
+  
+}
+
+impl<'a, K: Kind> Clone for RefCounts<'a, K> {
+  
+  fn clone(&self) -> Self {
+    Self { iter: self.iter.clone(), internal: self.internal, min_refs: self.min_refs }
+  }
+  
+}
+
+/**
+ * The normalized form of a string, without borrowing the original string.
+ */
+enum Normalized {
+  
+  Unchanged,
+  Slice(Range<usize>),
+  Owned(String)
+  
+}
+
+impl Normalized {
+  
+  fn new(string: &str, normalized: Cow<'_, str>) -> Self {
+    match normalized {
+      Cow::Borrowed(normalized) => {
+        // Normalizers like str::trim return part of the original string, which can be remembered by its position instead.
+        let start = (normalized.as_ptr() as usize).wrapping_sub(string.as_ptr() as usize);
+        match start.checked_add(normalized.len()) {
+          Some(end) if start == 0 && end == string.len() => Normalized::Unchanged,
+          Some(end) if end <= string.len() => Normalized::Slice(start..end),
+          _ => Normalized::Owned(String::from(normalized))
+        }
+      }
+      Cow::Owned(normalized) => Normalized::Owned(normalized)
+    }
+  }
+  
+  fn as_str<'a>(&'a self, string: &'a str) -> &'a str {
+    match self {
+      Normalized::Unchanged => string,
+      Normalized::Slice(range) => &string[range.clone()],
+      Normalized::Owned(normalized) => normalized
+    }
+  }
+  
+}
+
+/**
+ * Lowercases any ASCII letters in the given string, only allocating if there are any uppercase ones.
+ */
+fn fold_ascii_case(string: &str) -> Cow<'_, str> {
+  if string.bytes().any(|byte| byte.is_ascii_uppercase()) {
+    Cow::Owned(string.to_ascii_lowercase())
+  } else {
+    Cow::Borrowed(string)
+  }
+}
+
+/**
+ * Normalizes the given string to NFC, only allocating if it is not already in NFC.
+ */
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn nfc(string: &str) -> Cow<'_, str> {
+  let is_nfc = match unicode_normalization::is_nfc_quick(string.chars()) {
+    IsNormalized::Yes => true,
+    IsNormalized::No => false,
+    IsNormalized::Maybe => unicode_normalization::is_nfc(string)
+  };
+  if is_nfc {
+    Cow::Borrowed(string)
+  } else {
+    Cow::Owned(string.nfc().collect())
+  }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::ops::Deref;
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+/**
+ * A reference-counted string which can be downgraded, i.e., [`InternedStr`](crate::InternedStr) or [`sync::InternedStr`](crate::sync::InternedStr).
+ */
+pub(crate) trait Strong: Clone + Deref<Target = str> + for<'a> From<&'a str> {
+  
+  type Weak;
+  
+  fn downgrade(&self) -> Self::Weak;
+  
+  fn upgrade(weak: &Self::Weak) -> Option<Self>;
+  
+  fn is_live(weak: &Self::Weak) -> bool;
+  
+}
+
+impl Strong for Rc<str> {
+  
+  type Weak = rc::Weak<str>;
+  
+  fn downgrade(&self) -> rc::Weak<str> {
+    Rc::downgrade(self)
+  }
+  
+  fn upgrade(weak: &rc::Weak<str>) -> Option<Self> {
+    weak.upgrade()
+  }
+  
+  fn is_live(weak: &rc::Weak<str>) -> bool {
+    weak.strong_count() != 0
+  }
+  
+}
+
+impl Strong for Arc<str> {
+  
+  type Weak = sync::Weak<str>;
+  
+  fn downgrade(&self) -> sync::Weak<str> {
+    Arc::downgrade(self)
+  }
+  
+  fn upgrade(weak: &sync::Weak<str>) -> Option<Self> {
+    weak.upgrade()
+  }
+  
+  fn is_live(weak: &sync::Weak<str>) -> bool {
+    weak.strong_count() != 0
+  }
+  
+}
+
+/**
+ * The storage behind both kinds of `WeakInterner`.
+ * 
+ * Since the contents of a weak reference can't be borrowed, they can't be used as keys directly;
+ * instead, each string is stored under the hash of its contents, and compared after upgrading it.
+ */
+pub(crate) struct WeakTable<T: Strong, S> {
+  
+  buckets: HashMap<u64, Vec<T::Weak>>,
+  hasher: S,
+  
+  /**
+   * The total length of `buckets`, including entries whose strings have been dropped.
+   */
+  entries: usize,
+  
+  /**
+   * How many entries there can be before every bucket is purged at once, so that entries with unique hashes can't pile up forever.
+   */
+  purge_at: usize
+  
+}
+
+impl<T: Strong, S> WeakTable<T, S> {
+  
+  const MIN_PURGE_AT: usize = 64;
+  
+  pub(crate) fn new(hasher: S) -> Self {
+    Self { buckets: HashMap::new(), hasher, entries: 0, purge_at: Self::MIN_PURGE_AT }
+  }
+  
+  /**
+   * Returns the number of entries, including any whose strings have been dropped but which have not been purged yet.
+   */
+  pub(crate) fn entries(&self) -> usize {
+    self.entries
+  }
+  
+  pub(crate) fn len(&self) -> usize {
+    self.buckets.values().flatten().filter(|weak| T::is_live(weak)).count()
+  }
+  
+  pub(crate) fn is_empty(&self) -> bool {
+    !self.buckets.values().flatten().any(T::is_live)
+  }
+  
+  pub(crate) fn clear(&mut self) {
+    self.buckets.clear();
+    self.entries = 0;
+  }
+  
+  /**
+   * Returns an iterator over the strings which have not been dropped.
+   */
+  pub(crate) fn live(&self) -> impl Iterator<Item = T> + '_ {
+    self.buckets.values().flatten().filter_map(T::upgrade)
+  }
+  
+  /**
+   * Removes every entry whose string has been dropped, and returns how many were removed.
+   */
+  pub(crate) fn purge(&mut self) -> usize {
+    let mut purged = 0;
+    self.buckets.retain(|_, bucket| {
+      purged += purge_bucket::<T>(bucket);
+      !bucket.is_empty()
+    });
+    self.entries -= purged;
+    purged
+  }
+  
+}
+
+impl<T: Strong, S: BuildHasher> WeakTable<T, S> {
+  
+  pub(crate) fn get(&self, string: &str) -> Option<T> {
+    self.buckets.get(&self.hasher.hash_one(string))?.iter().filter_map(T::upgrade).find(|saved| &**saved == string)
+  }
+  
+  /**
+   * Returns the saved allocation for the given string if there is a live one, or saves a new one otherwise.
+   * Any entries with the same hash whose strings have been dropped are removed along the way,
+   * and if the number of entries has doubled since every bucket was last purged, they are all purged again.
+   */
+  pub(crate) fn intern(&mut self, string: &str) -> T {
+    let bucket = self.buckets.entry(self.hasher.hash_one(string)).or_default();
+    self.entries -= purge_bucket::<T>(bucket);
+    if let Some(saved) = bucket.iter().filter_map(T::upgrade).find(|saved| &**saved == string) {
+      return saved;
+    }
+    let saved = T::from(string);
+    bucket.push(saved.downgrade());
+    self.entries += 1;
+    if self.entries >= self.purge_at {
+      self.purge();
+      self.purge_at = (self.entries * 2).max(Self::MIN_PURGE_AT);
+    }
+    saved
+  }
+  
+}
+
+fn purge_bucket<T: Strong>(bucket: &mut Vec<T::Weak>) -> usize {
+  let len = bucket.len();
+  bucket.retain(T::is_live);
+  len - bucket.len()
+}
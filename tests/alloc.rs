@@ -0,0 +1,106 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+#[cfg(feature = "bumpalo")]
+use std::fmt::Write;
+
+use str_intern::{Interner, InternedStr};
+#[cfg(feature = "bumpalo")]
+use str_intern::arena::{ArenaInterner, Bump};
+
+struct CountingAlloc;
+
+thread_local! {
+  // Counted per thread, since tests run in parallel.
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+    unsafe { System.alloc(layout) }
+  }
+  
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+  
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+  let before = ALLOCATIONS.get();
+  let result = f();
+  (result, ALLOCATIONS.get() - before)
+}
+
+#[test]
+fn intern_cow() {
+  let mut interner = Interner::with_capacity(16);
+  let string = String::from("foo");
+  let (foo0, count) = allocations(|| interner.intern_cow(Cow::Owned(string)));
+  assert_eq!(count, 1);
+  let string = String::from("foo");
+  let (foo1, count) = allocations(|| interner.intern_cow(Cow::Owned(string)));
+  assert_eq!(count, 0);
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  let (foo2, count) = allocations(|| interner.intern_cow(Cow::Borrowed("foo")));
+  assert_eq!(count, 0);
+  assert!(InternedStr::ptr_eq(&foo0, &foo2));
+}
+
+#[test]
+fn extend_string() {
+  let mut interner = Interner::with_capacity(16);
+  let string = String::from("foo");
+  // Converting a `String` into an `InternedStr` always allocates and copies, but that new `Rc<str>` should be the only allocation, with no intermediate copy.
+  let ((), count) = allocations(|| interner.extend([string]));
+  assert_eq!(count, 1);
+  let foo = interner.get("foo").unwrap();
+  let string = String::from("foo");
+  let ((), count) = allocations(|| interner.extend([string]));
+  assert_eq!(count, 0);
+  assert!(InternedStr::ptr_eq(&interner.get("foo").unwrap(), &foo));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn intern_fmt() {
+  let mut interner = Interner::with_capacity(16);
+  let (module, name) = ("std", "mem");
+  let (path0, count) = allocations(|| interner.intern_fmt(format_args!("{module}::{name}")));
+  assert_eq!(count, 1);
+  let (path1, count) = allocations(|| interner.intern_fmt(format_args!("{module}::{name}")));
+  assert_eq!(count, 0);
+  assert!(InternedStr::ptr_eq(&path0, &path1));
+}
+
+#[test]
+fn with_max_len() {
+  let mut interner = Interner::with_max_len(3);
+  let body = "x".repeat(1 << 20);
+  let (result, count) = allocations(|| interner.try_intern(&body));
+  assert!(result.is_err());
+  assert_eq!(count, 0);
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn arena_interner() {
+  let arena = Bump::new();
+  let mut interner = ArenaInterner::with_capacity(&arena, 1_000_000);
+  let mut buffer = String::with_capacity(16);
+  let (_, count) = allocations(|| {
+    for i in 0..1_000_000 {
+      buffer.clear();
+      write!(buffer, "s{i}").unwrap();
+      interner.intern(&buffer);
+    }
+  });
+  assert_eq!(interner.len(), 1_000_000);
+  // The arena grows by doubling, so it only needs a few dozen chunks.
+  assert!(count < 100, "{count} allocations");
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "bumpalo")]
+
+use std::ptr;
+
+use str_intern::arena::{ArenaInterner, Bump};
+
+#[test]
+fn intern() {
+  let arena = Bump::new();
+  let mut interner = ArenaInterner::new(&arena);
+  assert!(interner.is_empty());
+  let foo0 = interner.intern(&String::from("foo"));
+  let foo1 = interner.intern("foo");
+  assert!(ptr::eq(foo0, foo1));
+  let bar = interner.intern("bar");
+  assert!(!ptr::eq(foo0, bar));
+  assert_eq!(interner.len(), 2);
+  assert!(ptr::eq(interner.get("bar").unwrap(), bar));
+  assert!(interner.contains("foo"));
+  assert!(!interner.contains("baz"));
+  assert_eq!(interner.get("baz"), None);
+  let mut strings: Vec<_> = interner.iter().collect();
+  strings.sort();
+  assert_eq!(strings, ["bar", "foo"]);
+  let mut clone = interner.clone();
+  assert!(ptr::eq(clone.intern("foo"), foo0));
+  drop((interner, clone));
+  assert_eq!((foo0, bar), ("foo", "bar"));
+}
+
+#[test]
+fn shared_arena() {
+  let arena = Bump::new();
+  let mut first = ArenaInterner::new(&arena);
+  let mut second = ArenaInterner::with_capacity(&arena, 4);
+  let foo0 = first.intern("foo");
+  let foo1 = second.intern("foo");
+  assert_eq!(foo0, foo1);
+  assert!(!ptr::eq(foo0, foo1));
+  assert!(ptr::eq(first.arena(), second.arena()));
+  second.extend(["foo", "bar", "baz"]);
+  assert_eq!(second.len(), 3);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "tokio")]
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use str_intern::sync::{AsyncInterner, InternedStr};
+
+#[tokio::test]
+async fn intern() {
+  let interner = AsyncInterner::new();
+  let foo0 = interner.intern("foo").await;
+  let foo1 = interner.intern(String::from("foo")).await;
+  let bar = interner.intern("bar").await;
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  assert!(!InternedStr::ptr_eq(&foo0, &bar));
+  assert_eq!(interner.len().await, 2);
+  assert!(interner.contains("foo").await);
+  assert!(!interner.contains("baz").await);
+  assert!(InternedStr::ptr_eq(&interner.get("bar").await.unwrap(), &bar));
+  assert!(InternedStr::ptr_eq(&interner.remove("bar").await.unwrap(), &bar));
+  assert_eq!(interner.get("bar").await, None);
+  interner.clear().await;
+  assert!(interner.is_empty().await);
+}
+
+#[tokio::test]
+async fn lock() {
+  let interner: AsyncInterner = ["foo", "bar"].into_iter().collect();
+  let mut locked = interner.lock().await;
+  let baz = locked.intern("baz");
+  tokio::task::yield_now().await;
+  assert!(InternedStr::ptr_eq(&locked.intern("baz"), &baz));
+  let strings: HashSet<&str> = locked.iter().map(|string| &**string).collect();
+  assert_eq!(strings, HashSet::from(["foo", "bar", "baz"]));
+  drop(locked);
+  assert_eq!(interner.into_set().len(), 3);
+}
+
+#[test]
+fn blocking_intern() {
+  let interner = AsyncInterner::new();
+  let foo = interner.blocking_intern("foo");
+  assert!(InternedStr::ptr_eq(&interner.blocking_intern("foo"), &foo));
+  assert_eq!(interner.blocking_lock().len(), 1);
+}
+
+#[tokio::test]
+async fn cancelled_intern() {
+  let interner = AsyncInterner::new();
+  let locked = interner.lock().await;
+  // This future is waiting for the lock when it is dropped, so it does nothing.
+  assert!(tokio::time::timeout(Duration::from_millis(10), interner.intern("foo")).await.is_err());
+  drop(locked);
+  assert!(!interner.contains("foo").await);
+  interner.intern("foo").await;
+  assert_eq!(interner.len().await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn many_tasks() {
+  let interner = Arc::new(AsyncInterner::new());
+  let tasks: Vec<_> = (0..64).map(|task| {
+    let interner = Arc::clone(&interner);
+    tokio::spawn(async move {
+      let mut strings = Vec::new();
+      for i in 0..100 {
+        strings.push(interner.intern(format!("string {}", (task + i) % 100)).await);
+        tokio::task::yield_now().await;
+      }
+      strings
+    })
+  }).collect();
+  let mut canonical = Vec::new();
+  for task in tasks {
+    canonical.extend(task.await.unwrap());
+  }
+  assert_eq!(interner.len().await, 100);
+  for string in canonical {
+    assert!(InternedStr::ptr_eq(&interner.get(&string).await.unwrap(), &string));
+  }
+}
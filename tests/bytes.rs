@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+use std::thread;
+
+use str_intern::bytes::{Interner, InternedBytes};
+use str_intern::bytes::sync::{Interner as SyncInterner, InternedBytes as SyncInternedBytes};
+
+#[test]
+fn len() {
+  let mut interner = Interner::new();
+  assert!(interner.is_empty());
+  interner.intern(b"foo");
+  interner.intern(b"foo");
+  interner.intern([0xff, 0xfe]);
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.len(), interner.iter().len());
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn ptr_eq() {
+  let mut interner = Interner::new();
+  let tag0 = interner.intern(vec![0x08, 0x96, 0x01]);
+  let tag1 = interner.intern([0x08, 0x96, 0x01]);
+  let other = interner.intern([0x08, 0x96]);
+  assert!(InternedBytes::ptr_eq(&tag0, &tag1));
+  assert!(!InternedBytes::ptr_eq(&tag0, &other));
+}
+
+#[test]
+fn lookup() {
+  let mut interner = Interner::new();
+  let invalid = interner.intern([0xc3, 0x28]);
+  assert!(interner.contains([0xc3, 0x28]));
+  assert!(InternedBytes::ptr_eq(&interner.get([0xc3, 0x28]).unwrap(), &invalid));
+  assert!(!interner.contains(b"bar"));
+  assert_eq!(interner.get(b"bar"), None);
+  assert!(InternedBytes::ptr_eq(&interner.remove([0xc3, 0x28]).unwrap(), &invalid));
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn gc() {
+  let mut interner: Interner = [b"foo", b"bar"].into_iter().collect();
+  let foo = interner.intern(b"foo");
+  assert_eq!(interner.gc(), 1);
+  assert!(interner.contains(b"foo"));
+  assert!(!interner.contains(b"bar"));
+  drop(foo);
+  let strings: HashSet<InternedBytes> = interner.into_iter().collect();
+  assert_eq!(strings, HashSet::from([InternedBytes::from(&b"foo"[..])]));
+}
+
+#[test]
+fn sync_ptr_eq() {
+  let interner = SyncInterner::new();
+  let tags: Vec<SyncInternedBytes> = thread::scope(|scope| {
+    let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| interner.intern([0x08, 0x96, 0x01]))).collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+  });
+  assert!(tags.iter().all(|tag| SyncInternedBytes::ptr_eq(tag, &tags[0])));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn sync_locked() {
+  let interner = SyncInterner::new();
+  let foo = interner.intern(b"foo");
+  interner.intern(b"bar");
+  let mut locked = interner.lock();
+  assert_eq!(locked.len(), 2);
+  assert_eq!(locked.len(), locked.iter().len());
+  assert!(SyncInternedBytes::ptr_eq(&locked.intern(b"foo"), &foo));
+  assert!(locked.contains(b"bar"));
+  assert_eq!(locked.gc(), 1);
+  assert!(!locked.contains(b"bar"));
+  assert!(SyncInternedBytes::ptr_eq(&locked.remove(b"foo").unwrap(), &foo));
+  assert!(locked.is_empty());
+}
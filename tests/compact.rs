@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::mem::size_of;
+
+use str_intern::InternedStr;
+use str_intern::compact::{CompactInterner, CompactStr, INLINE_LEN};
+
+#[test]
+fn boundary() {
+  let mut interner = CompactInterner::new();
+  let short = "a".repeat(INLINE_LEN);
+  let long = "a".repeat(INLINE_LEN + 1);
+  let short0 = interner.intern(&short);
+  let long0 = interner.intern(&long);
+  assert!(short0.is_inline());
+  assert!(!long0.is_inline());
+  assert_eq!(&*short0, short);
+  assert_eq!(&*long0, long);
+  let short1 = interner.intern(short.clone());
+  let long1 = interner.intern(long.clone());
+  assert_eq!(short0, short1);
+  assert_eq!(long0, long1);
+  assert!(!CompactStr::ptr_eq(&short0, &short1));
+  assert!(CompactStr::ptr_eq(&long0, &long1));
+  assert_ne!(short0, long0);
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn empty_and_multibyte() {
+  let mut interner = CompactInterner::new();
+  let empty = interner.intern("");
+  assert!(empty.is_inline());
+  assert_eq!(&*empty, "");
+  let snowmen = "☃".repeat(INLINE_LEN / 3);
+  assert!(interner.intern(&snowmen).is_inline());
+  assert_eq!(&*interner.intern(&snowmen), snowmen);
+  let more_snowmen = "☃".repeat(INLINE_LEN / 3 + 1);
+  assert!(!interner.intern(&more_snowmen).is_inline());
+  assert_eq!(&*interner.intern(&more_snowmen), more_snowmen);
+}
+
+#[test]
+fn lookup() {
+  let mut interner = CompactInterner::new();
+  interner.extend(["foo", "a string that is too long to store inline"]);
+  assert!(interner.contains("foo"));
+  assert!(interner.contains("a string that is too long to store inline"));
+  assert!(!interner.contains("bar"));
+  assert_eq!(interner.get("foo").as_deref(), Some("foo"));
+  assert_eq!(interner.get("bar"), None);
+  let strings: HashSet<&str> = interner.iter().map(|string| &**string).collect();
+  assert_eq!(strings, HashSet::from(["foo", "a string that is too long to store inline"]));
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn hash_matches_str() {
+  let mut interner = CompactInterner::new();
+  let set = HashSet::from([interner.intern("foo"), interner.intern("a string that is too long to store inline")]);
+  assert!(set.contains("foo"));
+  assert!(set.contains("a string that is too long to store inline"));
+  assert!(!set.contains("bar"));
+}
+
+#[test]
+fn different_interners() {
+  let long = "a string that is too long to store inline";
+  let string0 = CompactInterner::new().intern(long);
+  let string1 = CompactInterner::new().intern(long);
+  assert!(!CompactStr::ptr_eq(&string0, &string1));
+  assert_eq!(string0, string1);
+}
+
+#[test]
+fn intern_static() {
+  static LONG: &str = "a keyword that is too long to store inline";
+  let mut interner = CompactInterner::new();
+  let keyword0 = interner.intern_static("fn");
+  let long0 = interner.intern_static(LONG);
+  assert!(keyword0.is_static());
+  assert!(!keyword0.is_inline());
+  assert!(long0.is_static());
+  assert!(std::ptr::eq(&*long0, LONG));
+  let keyword1 = interner.intern(String::from("fn"));
+  let long1 = interner.intern(String::from(LONG));
+  assert!(CompactStr::ptr_eq(&keyword0, &keyword1));
+  assert!(CompactStr::ptr_eq(&long0, &long1));
+  assert!(CompactStr::ptr_eq(&interner.intern_static("fn"), &keyword0));
+  assert_eq!(interner.len(), 2);
+  let statics: HashSet<&str> = interner.iter().filter(|string| string.is_static()).map(|string| &**string).collect();
+  assert_eq!(statics, HashSet::from(["fn", LONG]));
+  let set = interner.into_set();
+  assert!(set.get("fn").unwrap().is_static());
+}
+
+#[test]
+fn intern_static_after_intern() {
+  let mut interner = CompactInterner::new();
+  let short0 = interner.intern("let");
+  let long0 = interner.intern(String::from("a keyword that is too long to store inline"));
+  let short1 = interner.intern_static("let");
+  let long1 = interner.intern_static("a keyword that is too long to store inline");
+  assert!(short1.is_inline());
+  assert!(!long1.is_static());
+  assert!(CompactStr::ptr_eq(&long0, &long1));
+  assert_eq!(short0, short1);
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn static_different_interners() {
+  let mut interner = CompactInterner::new();
+  let keyword0 = interner.intern_static("fn");
+  let keyword1 = CompactInterner::new().intern("fn");
+  assert!(!CompactStr::ptr_eq(&keyword0, &keyword1));
+  assert_eq!(keyword0, keyword1);
+  assert_eq!(keyword1, keyword0);
+  let long = "a keyword that is too long to store inline";
+  assert_eq!(CompactInterner::new().intern_static(long), CompactInterner::new().intern(long));
+}
+
+#[test]
+fn size() {
+  assert!(size_of::<CompactStr>() <= size_of::<InternedStr>() + size_of::<usize>());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "dashmap")]
+
+use std::collections::HashSet;
+use std::sync::Barrier;
+use std::thread;
+
+use str_intern::concurrent::ConcurrentInterner;
+use str_intern::sync::InternedStr;
+
+#[test]
+fn intern() {
+  let interner = ConcurrentInterner::new();
+  assert!(interner.is_empty());
+  let foo0 = interner.intern("foo");
+  let foo1 = interner.intern(String::from("foo"));
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  interner.intern("bar");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("bar"));
+  assert!(InternedStr::ptr_eq(&interner.get("foo").unwrap(), &foo0));
+  assert_eq!(interner.get("baz"), None);
+  let strings: HashSet<InternedStr> = interner.iter().collect();
+  assert_eq!(strings, HashSet::from(["foo", "bar"].map(InternedStr::from)));
+  assert!(InternedStr::ptr_eq(&interner.remove("foo").unwrap(), &foo0));
+  assert!(!interner.contains("foo"));
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn racing_insertions() {
+  const THREADS: usize = 32;
+  let interner = ConcurrentInterner::new();
+  for round in 0..20 {
+    let string = format!("fresh string {round}");
+    let barrier = Barrier::new(THREADS);
+    let saved: Vec<InternedStr> = thread::scope(|scope| {
+      let threads: Vec<_> = (0..THREADS).map(|_| scope.spawn(|| {
+        barrier.wait();
+        interner.intern(&string)
+      })).collect();
+      threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+    });
+    let winner = interner.get(&string).unwrap();
+    assert!(saved.iter().all(|saved| InternedStr::ptr_eq(saved, &winner)));
+  }
+  assert_eq!(interner.len(), 20);
+}
+
+#[test]
+fn survives_panics() {
+  let interner = ConcurrentInterner::new();
+  let foo = interner.intern("foo");
+  let _ = thread::scope(|scope| scope.spawn(|| {
+    interner.intern("bar");
+    panic!("panicking while using the interner");
+  }).join());
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert!(interner.contains("bar"));
+}
@@ -0,0 +1,80 @@
+use std::ffi::{CStr, CString, OsStr};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+use str_intern::generic::Interner;
+use str_intern::generic::sync::Interner as SyncInterner;
+
+#[test]
+fn str() {
+  let mut interner = Interner::<str>::new();
+  let foo0 = interner.intern("foo");
+  let foo1 = interner.intern(String::from("foo"));
+  assert!(Rc::ptr_eq(&foo0, &foo1));
+  assert_eq!(&*foo0, "foo");
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn code_points() {
+  let mut interner = Interner::<[u32]>::new();
+  let foo0 = interner.intern("foo".chars().map(u32::from).collect::<Vec<_>>());
+  let foo1 = interner.intern([0x66, 0x6f, 0x6f]);
+  let bar = interner.intern([0x62, 0x61, 0x72]);
+  assert!(Rc::ptr_eq(&foo0, &foo1));
+  assert!(!Rc::ptr_eq(&foo0, &bar));
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn os_str_and_path() {
+  let mut os_strs = Interner::<OsStr>::new();
+  assert!(Rc::ptr_eq(&os_strs.intern("a/b"), &os_strs.intern(OsStr::new("a/b"))));
+  assert!(!os_strs.contains("a//b"));
+  let mut paths = Interner::<Path>::new();
+  let path = paths.intern("a/b");
+  // Unlike the `path` module, this compares paths as `Path`s.
+  assert!(Rc::ptr_eq(&paths.intern("a//b"), &path));
+  assert_eq!(paths.len(), 1);
+}
+
+#[test]
+fn c_str() {
+  let mut interner = Interner::<CStr>::new();
+  let foo0 = interner.intern(c"foo");
+  let foo1 = interner.intern(CString::new("foo").unwrap());
+  assert!(Rc::ptr_eq(&foo0, &foo1));
+  assert_eq!(foo0.to_bytes(), b"foo");
+}
+
+#[test]
+fn lookup() {
+  let mut interner: Interner<[u8]> = [b"foo", b"bar"].into_iter().collect();
+  let foo = interner.get(b"foo").unwrap();
+  assert!(interner.contains(b"bar"));
+  assert!(!interner.contains(b"baz"));
+  assert_eq!(interner.get(b"baz"), None);
+  assert_eq!(interner.gc(), 1);
+  assert!(!interner.contains(b"bar"));
+  assert!(Rc::ptr_eq(&interner.remove(b"foo").unwrap(), &foo));
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn sync() {
+  let interner = SyncInterner::<str>::new();
+  let strings: Vec<Arc<str>> = thread::scope(|scope| {
+    let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| interner.intern("foo"))).collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+  });
+  assert!(strings.iter().all(|string| Arc::ptr_eq(string, &strings[0])));
+  let mut locked = interner.lock();
+  locked.extend(["bar", "baz"]);
+  assert_eq!(locked.len(), 3);
+  assert_eq!(locked.len(), locked.iter().len());
+  assert_eq!(locked.gc(), 2);
+  assert!(Arc::ptr_eq(&locked.remove("foo").unwrap(), &strings[0]));
+  assert!(locked.is_empty());
+}
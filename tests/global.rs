@@ -0,0 +1,29 @@
+#![cfg(any(feature = "global", feature = "global-unsync"))]
+
+use str_intern::global::{self, InternedStr};
+
+#[test]
+fn global_intern() {
+  assert!(!global::contains("global::intern"));
+  assert_eq!(global::get("global::intern"), None);
+  let s0 = global::intern("global::intern");
+  let s1 = global::intern(String::from("global::intern"));
+  assert!(InternedStr::ptr_eq(&s0, &s1));
+  assert!(global::contains("global::intern"));
+  assert!(InternedStr::ptr_eq(&global::get("global::intern").unwrap(), &s0));
+}
+
+#[cfg(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics"))))]
+#[test]
+fn global_unsync() {
+  let s0: std::rc::Rc<str> = global::intern("global::unsync");
+  assert!(!str_intern::sync::GlobalInterner.contains("global::unsync"));
+  assert_eq!(&*s0, "global::unsync");
+}
+
+#[cfg(not(any(feature = "global-unsync", all(target_family = "wasm", not(target_feature = "atomics")))))]
+#[test]
+fn global_sync() {
+  let s0: std::sync::Arc<str> = global::intern("global::sync");
+  assert!(std::sync::Arc::ptr_eq(&s0, &str_intern::sync::intern("global::sync")));
+}
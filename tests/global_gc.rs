@@ -0,0 +1,33 @@
+#![cfg(feature = "global")]
+
+use std::sync::Arc;
+
+use str_intern::sync::{intern, global_gc, global_len, global_stats, global_memory_usage, InternedStr, GcReport};
+
+// This is its own test binary, so that no other tests share the global interner with it.
+#[test]
+fn global_gc_and_stats() {
+  let held = intern("held");
+  let dropped = intern("dropped");
+  let weak = Arc::downgrade(&dropped);
+  intern("held");
+  assert_eq!(global_len(), 2);
+  let stats = global_stats();
+  assert_eq!((stats.hits, stats.misses), (1, 2));
+  assert_eq!(global_memory_usage().strings, "held".len() + "dropped".len());
+  
+  drop(dropped);
+  assert!(weak.upgrade().is_some());
+  assert_eq!(global_gc(), GcReport { entries: 1, bytes: "dropped".len() });
+  assert!(weak.upgrade().is_none());
+  assert_eq!(global_len(), 1);
+  assert_eq!(global_memory_usage().strings, "held".len());
+  // The string that was still held survived, and the dropped one is saved again from scratch.
+  assert!(InternedStr::ptr_eq(&intern("held"), &held));
+  let dropped = intern("dropped");
+  assert!(weak.upgrade().is_none());
+  assert_eq!(&*dropped, "dropped");
+  assert_eq!(global_stats().misses, 3);
+  assert_eq!(global_len(), 2);
+  assert_eq!(global_gc(), GcReport { entries: 0, bytes: 0 });
+}
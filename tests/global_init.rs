@@ -0,0 +1,31 @@
+#![cfg(feature = "global")]
+
+use std::hash::BuildHasher;
+
+use str_intern::FixedState;
+use str_intern::sync::{init_global, intern, Interner, GlobalInterner, GlobalState, InternedStr, AlreadyInitialized};
+
+// This is its own test binary, so that nothing else can use the global interner before it is initialized.
+#[test]
+fn init_global_fixed() {
+  let state = GlobalState::from(FixedState::with_seed(42));
+  assert_eq!(state.hash_one("foo"), FixedState::with_seed(42).hash_one("foo"));
+  assert_eq!(init_global(Interner::with_capacity_and_hasher(100, state.clone())), Ok(()));
+  let local = Interner::with_capacity_and_hasher(100, state);
+  let strings = (0..50).map(|i| i.to_string()).collect::<Vec<_>>();
+  for string in &strings {
+    intern(string);
+    local.intern(string);
+  }
+  let mut global = GlobalInterner.lock().iter().cloned().collect::<Vec<_>>();
+  let mut expected = local.lock().iter().cloned().collect::<Vec<_>>();
+  global.sort();
+  expected.sort();
+  assert_eq!(global, expected);
+  assert!(GlobalInterner.lock().capacity() >= 100);
+  let foo = intern("foo");
+  assert_eq!(init_global(Interner::default()), Err(AlreadyInitialized));
+  assert_eq!(AlreadyInitialized.to_string(), "the global interner has already been initialized");
+  assert!(InternedStr::ptr_eq(&intern("foo"), &foo));
+  assert_eq!(GlobalInterner.len(), 51);
+}
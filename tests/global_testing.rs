@@ -0,0 +1,47 @@
+#![cfg(feature = "global-testing")]
+
+use std::sync::{Arc, Mutex};
+
+use str_intern::RemovalCause;
+use str_intern::sync::{intern, take_global, reset_global, GlobalInterner, InternedStr};
+
+// This is its own test binary, so that no other tests share the global interner with it.
+#[test]
+fn sessions() {
+  // Taking or resetting the global interner before it exists doesn't create it.
+  reset_global();
+  assert!(take_global().is_none());
+  
+  let foo0 = intern("foo");
+  let bar0 = intern("bar");
+  let first = take_global().unwrap();
+  assert_eq!(first.len(), 2);
+  assert!(InternedStr::ptr_eq(&first.get("foo").unwrap(), &foo0));
+  assert!(GlobalInterner.is_empty());
+  
+  let foo1 = intern("foo");
+  assert!(!InternedStr::ptr_eq(&foo0, &foo1));
+  assert!(InternedStr::ptr_eq(&intern("foo"), &foo1));
+  assert!(!GlobalInterner.contains("bar"));
+  assert!(!InternedStr::ptr_eq(&intern("bar"), &bar0));
+  
+  reset_global();
+  assert!(GlobalInterner.is_empty());
+  assert!(!InternedStr::ptr_eq(&intern("foo"), &foo1));
+  assert!(InternedStr::ptr_eq(&first.intern("foo"), &foo0));
+  
+  // Taking the global interner drains it like clearing it would, and its removal hook stays installed.
+  let removed = Arc::new(Mutex::new(Vec::new()));
+  let log = Arc::clone(&removed);
+  GlobalInterner.set_removal_hook(move |string, cause| log.lock().unwrap().push((String::from(&**string), cause)));
+  let version = GlobalInterner.version();
+  let taken = take_global().unwrap();
+  assert_eq!(*removed.lock().unwrap(), [(String::from("foo"), RemovalCause::Cleared)]);
+  assert!(GlobalInterner.version() > version);
+  assert_eq!(taken.len(), 1);
+  taken.remove("foo");
+  assert_eq!(removed.lock().unwrap().len(), 1);
+  intern("bar");
+  reset_global();
+  assert_eq!(removed.lock().unwrap()[1], (String::from("bar"), RemovalCause::Cleared));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "global-weak")]
+
+use std::sync::Arc;
+
+use str_intern::sync::{intern_weak, global_purge, GlobalWeakInterner, InternedStr};
+
+// This is its own test binary, so that no other tests share the global weak interner with it.
+#[test]
+fn global_weak() {
+  let foo0 = intern_weak("foo");
+  let foo1 = intern_weak(String::from("foo"));
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  let bar = intern_weak("bar");
+  assert_eq!(GlobalWeakInterner.len(), 2);
+  let weak = Arc::downgrade(&foo0);
+  
+  drop((foo0, foo1, bar));
+  assert!(weak.upgrade().is_none());
+  assert!(GlobalWeakInterner.is_empty());
+  assert_eq!(GlobalWeakInterner.entries(), 2);
+  assert_eq!(global_purge(), 2);
+  assert_eq!(GlobalWeakInterner.entries(), 0);
+  
+  // The old allocation is gone, so interning the same contents again saves a new one.
+  let foo = intern_weak("foo");
+  assert_eq!(Arc::strong_count(&foo), 1);
+  assert!(weak.upgrade().is_none());
+  assert_eq!(GlobalWeakInterner.entries(), 1);
+}
@@ -0,0 +1,1189 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::fmt::{self, Display, Formatter};
+use std::hash::BuildHasher;
+use std::ops::{Bound, RangeFull};
+use std::mem;
+use std::rc::Rc;
+
+use str_intern::{Interner, SharedInterner, ScopedInterner, WeakInterner, OrderedInterner, OrdInterner, FixedState, InternedStr, Entry, TryInternError, ValidationError, GcReport, MemoryUsage, InternStats, LenStats, RemovalCause};
+
+#[test]
+fn len() {
+  let mut interner = Interner::new();
+  assert_eq!(interner.len(), 0);
+  assert!(interner.is_empty());
+  interner.intern("foo");
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.len(), interner.iter().len());
+  assert!(!interner.is_empty());
+  interner.clear();
+  assert_eq!(interner.len(), 0);
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn lookup() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  assert!(interner.contains("foo"));
+  assert!(InternedStr::ptr_eq(&interner.get("foo").unwrap(), &foo));
+  assert!(!interner.contains("bar"));
+  assert_eq!(interner.get("bar"), None);
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn remove() {
+  let mut interner = Interner::new();
+  let foo0 = interner.intern("foo");
+  let removed = interner.remove("foo").unwrap();
+  assert!(InternedStr::ptr_eq(&foo0, &removed));
+  assert!(!interner.contains("foo"));
+  assert_eq!(interner.remove("foo"), None);
+  assert_eq!(interner.remove("bar"), None);
+  let foo1 = interner.intern("foo");
+  assert!(!InternedStr::ptr_eq(&foo0, &foo1));
+  assert_eq!(&*foo0, "foo");
+}
+
+#[test]
+fn drain() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  let mut drained: Vec<_> = interner.drain().collect();
+  drained.sort();
+  assert_eq!(drained, ["bar".into(), "foo".into()]);
+  assert!(drained.iter().any(|string| InternedStr::ptr_eq(string, &foo)));
+  assert!(interner.is_empty());
+  interner.intern("foo");
+  interner.intern("bar");
+  let mut drain = interner.drain();
+  assert_eq!(drain.len(), 2);
+  drain.next();
+  drop(drain);
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn extract_if() {
+  let mut interner: Interner = Interner::from_iter(["a0", "a1", "a2", "b0", "b1"].map(InternedStr::from));
+  let mut extracted: Vec<_> = interner.extract_if(|string| string.starts_with('a')).collect();
+  extracted.sort();
+  assert_eq!(extracted, ["a0".into(), "a1".into(), "a2".into()]);
+  assert_eq!(interner.len(), 2);
+  let extracted = interner.extract_if(|_| true).next().unwrap();
+  assert_eq!(interner.len(), 1);
+  assert!(!interner.contains(&extracted));
+  assert!(!InternedStr::ptr_eq(&interner.intern(&extracted), &extracted));
+}
+
+#[test]
+fn capacity() {
+  let mut interner = Interner::with_capacity(100);
+  assert!(interner.capacity() >= 100);
+  interner.reserve(1000);
+  assert!(interner.capacity() >= 1000);
+  for i in 0..1000 {
+    interner.intern(i.to_string());
+  }
+  let capacity = interner.capacity();
+  interner.clear();
+  assert_eq!(interner.capacity(), capacity);
+  interner.shrink_to_fit();
+  assert!(interner.capacity() < capacity);
+}
+
+#[test]
+fn try_reserve() {
+  let mut interner = Interner::with_capacity_and_hasher(10, RandomState::new());
+  assert!(interner.capacity() >= 10);
+  assert!(interner.try_reserve(100).is_ok());
+  assert!(interner.capacity() >= 100);
+  assert!(interner.try_reserve(usize::MAX).is_err());
+}
+
+#[test]
+fn entry() {
+  let mut interner = Interner::new();
+  let mut calls = 0;
+  let foo0 = interner.entry("foo").or_insert_with(|| { calls += 1; String::from("foo") });
+  let foo1 = interner.entry("foo").or_insert_with(|| { calls += 1; String::from("foo") });
+  assert_eq!(calls, 1);
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  match interner.entry("foo") {
+    Entry::Occupied(entry) => assert!(InternedStr::ptr_eq(&entry.remove(), &foo0)),
+    Entry::Vacant(_) => panic!("foo should be occupied")
+  }
+  assert!(matches!(interner.entry("foo"), Entry::Vacant(_)));
+  assert_eq!(&*interner.entry("bar").or_insert(), "bar");
+  assert!(interner.contains("bar"));
+}
+
+#[derive(Clone, Default)]
+struct CountingState(Rc<Cell<usize>>);
+
+impl BuildHasher for CountingState {
+  
+  type Hasher = DefaultHasher;
+  
+  fn build_hasher(&self) -> DefaultHasher {
+    self.0.set(self.0.get() + 1);
+    DefaultHasher::new()
+  }
+  
+}
+
+#[test]
+fn entry_hashes_once_per_probe() {
+  let state = CountingState::default();
+  let mut interner = Interner::with_capacity_and_hasher(8, state.clone());
+  interner.intern("foo");
+  state.0.set(0);
+  interner.entry("bar").or_insert();
+  // One hash to find that "bar" is vacant, and one to insert it, but none to look it up again.
+  assert_eq!(state.0.get(), 2);
+  state.0.set(0);
+  interner.entry("bar").or_insert();
+  assert_eq!(state.0.get(), 1);
+}
+
+#[test]
+fn index() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  assert!(InternedStr::ptr_eq(&interner["foo"], &foo));
+}
+
+#[test]
+#[should_panic(expected = "\"bar\" has not been interned")]
+fn index_missing() {
+  let interner = Interner::new();
+  let _ = &interner["bar"];
+}
+
+#[test]
+fn intern_from() {
+  let mut interner = Interner::new();
+  let foo = InternedStr::from("foo");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&foo)), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern_from(String::from("foo")), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern_from(Box::<str>::from("foo")), &foo));
+  let bar = interner.intern("bar");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::from("bar")), &bar));
+  assert!(InternedStr::ptr_eq(&interner.intern(String::from("bar")), &bar));
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn intern_utf8() {
+  let mut interner = Interner::new();
+  let foo = interner.intern_utf8(b"foo").unwrap();
+  assert!(InternedStr::ptr_eq(&foo, &interner.intern("foo")));
+  let grin = interner.intern_utf8("😁 grin".as_bytes()).unwrap();
+  assert_eq!(&*grin, "😁 grin");
+  let error = interner.intern_utf8(b"ok\xF0\x9F\x98").unwrap_err();
+  assert_eq!(error.valid_up_to(), 2);
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn intern_utf8_lossy() {
+  let mut interner = Interner::new();
+  let invalid0 = interner.intern_utf8_lossy(b"foo\xFFbar");
+  let invalid1 = interner.intern_utf8_lossy(b"foo\xFFbar");
+  assert_eq!(&*invalid0, "foo\u{FFFD}bar");
+  assert!(InternedStr::ptr_eq(&invalid0, &invalid1));
+  assert!(InternedStr::ptr_eq(&interner.intern_utf8_lossy(b"foo"), &interner.intern("foo")));
+}
+
+#[test]
+fn intern_fmt() {
+  let mut interner = Interner::new();
+  let (module, name) = ("std", "mem");
+  let path = interner.intern("std::mem");
+  assert!(InternedStr::ptr_eq(&interner.intern_fmt(format_args!("{module}::{name}")), &path));
+  assert!(InternedStr::ptr_eq(&interner.intern_fmt(format_args!("std::mem")), &path));
+  let long = "long".repeat(100);
+  let long0 = interner.intern_fmt(format_args!("{long}/{long}"));
+  let long1 = interner.intern(format!("{long}/{long}"));
+  assert_eq!(long0.len(), 801);
+  assert!(InternedStr::ptr_eq(&long0, &long1));
+}
+
+#[test]
+fn intern_display() {
+  struct Point(i32, i32);
+  impl Display for Point {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+      write!(f, "({}, {})", self.0, self.1)
+    }
+  }
+  let mut interner = Interner::new();
+  let answer = interner.intern_display(&42);
+  assert!(InternedStr::ptr_eq(&answer, &interner.intern("42")));
+  let origin = interner.intern_display(&Point(0, 0));
+  assert!(InternedStr::ptr_eq(&origin, &interner.intern_display(&Point(0, 0))));
+  assert_eq!(&*origin, "(0, 0)");
+}
+
+#[test]
+fn intern_join() {
+  let mut interner = Interner::new();
+  let (a, b, c) = ("a", "b", "c");
+  let path = interner.intern(format!("{a}.{b}.{c}"));
+  assert!(InternedStr::ptr_eq(&interner.intern_join(".", [a, b, c]), &path));
+  assert!(InternedStr::ptr_eq(&interner.intern_join(".", vec![String::from("a"), String::from("b"), String::from("c")]), &path));
+  assert_eq!(&*interner.intern_join(".", ["a"]), "a");
+  assert_eq!(&*interner.intern_join(".", [""; 0]), "");
+  assert_eq!(interner.intern_join("/", ["segment"; 100]).len(), 799);
+}
+
+#[test]
+fn intern_split() {
+  let mut interner = Interner::new();
+  let fields: Vec<_> = interner.intern_split("a,,b,a,", ',').collect();
+  assert_eq!(fields, ["a", "", "b", "a", ""].map(InternedStr::from));
+  assert!(InternedStr::ptr_eq(&fields[0], &fields[3]));
+  assert!(InternedStr::ptr_eq(&fields[1], &fields[4]));
+  assert_eq!(interner.len(), 3);
+  let line = ["red", "green", "blue"].repeat(1000).join(",");
+  assert_eq!(interner.intern_split(&line, ',').count(), 3000);
+  assert_eq!(interner.len(), 6);
+}
+
+#[test]
+fn intern_lines() {
+  let mut interner = Interner::from_lines("foo\r\nbar\n\nfoo\n");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("foo") && interner.contains("bar"));
+  assert_eq!(interner.intern_lines("bar\r\nbaz\r\n\r\nqux".as_bytes()).unwrap(), 2);
+  assert_eq!(interner.len(), 4);
+  assert!(interner.contains("qux"));
+  assert!(!interner.contains(""));
+}
+
+#[test]
+fn intern_lines_shrinking() {
+  let mut interner = Interner::new();
+  interner.set_gc_threshold(1);
+  assert_eq!(interner.intern_lines("foo\nbar\nbaz\n".as_bytes()).unwrap(), 3);
+  let mut interner = Interner::with_max_entries(2);
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.intern_lines("baz\nqux\n".as_bytes()).unwrap(), 2);
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("baz") && interner.contains("qux"));
+}
+
+#[test]
+fn intern_all() {
+  let mut interner = Interner::new();
+  let bar = interner.intern("bar");
+  let strings = interner.intern_all(["foo", "bar", "foo", "baz"]);
+  assert_eq!(strings, ["foo", "bar", "foo", "baz"].map(InternedStr::from));
+  assert!(InternedStr::ptr_eq(&strings[0], &strings[2]));
+  assert!(InternedStr::ptr_eq(&strings[1], &bar));
+  assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn extend() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  interner.extend(["foo", "bar"]);
+  interner.extend([String::from("bar"), String::from("baz")]);
+  interner.extend([Box::<str>::from("baz")]);
+  assert_eq!(interner.len(), 3);
+  assert!(InternedStr::ptr_eq(&interner["foo"], &foo));
+}
+
+#[test]
+fn with_seeds() {
+  let (mut interner, seeds) = Interner::with_seeds(["if", "else", "while", "if"]);
+  assert_eq!(interner.len(), 3);
+  assert_eq!(seeds, ["if", "else", "while", "if"].map(InternedStr::from));
+  assert!(InternedStr::ptr_eq(&seeds[0], &seeds[3]));
+  assert!(InternedStr::ptr_eq(&interner.intern("if"), &seeds[0]));
+  assert!(InternedStr::ptr_eq(&interner.intern(String::from("while")), &seeds[2]));
+  assert!(interner.capacity() >= 3);
+  let (interner, seeds) = Interner::with_seeds(Vec::<String>::new());
+  assert!(interner.is_empty() && seeds.is_empty());
+}
+
+#[test]
+fn from() {
+  assert_eq!(Interner::from(["if", "else", "while", "if"]).len(), 3);
+  assert_eq!(Interner::from([String::from("if"), String::from("if")]).len(), 1);
+  assert_eq!(Interner::from(&["if", "else", "else"][..]).len(), 2);
+  assert_eq!(Interner::from(&[String::from("if"), String::from("else")][..]).len(), 2);
+}
+
+#[test]
+fn get_or_intern_with() {
+  let mut interner = Interner::new();
+  let foo = interner.get_or_intern_with("foo", || String::from("foo"));
+  for _ in 0..10 {
+    let hit = interner.get_or_intern_with("foo", || -> String { panic!("foo should already be interned") });
+    assert!(InternedStr::ptr_eq(&hit, &foo));
+  }
+}
+
+#[test]
+#[should_panic]
+fn get_or_intern_with_mismatch() {
+  Interner::new().get_or_intern_with("foo", || String::from("bar"));
+}
+
+#[test]
+fn intern_full() {
+  let mut interner = Interner::new();
+  let (foo0, new) = interner.intern_full("foo");
+  assert!(new);
+  let (foo1, new) = interner.intern_full("foo");
+  assert!(!new);
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  interner.remove("foo");
+  let (foo2, new) = interner.intern_full("foo");
+  assert!(new);
+  assert!(!InternedStr::ptr_eq(&foo0, &foo2));
+}
+
+#[test]
+fn canonicalize() {
+  let mut interner = Interner::new();
+  let foo0 = InternedStr::from("foo");
+  let foo1 = InternedStr::from("foo");
+  assert!(InternedStr::ptr_eq(&interner.canonicalize(InternedStr::clone(&foo0)), &foo0));
+  assert!(InternedStr::ptr_eq(&interner.canonicalize(foo1), &foo0));
+}
+
+#[test]
+fn is_canonical() {
+  let mut interner = Interner::new();
+  let mut other = Interner::new();
+  let foo = interner.intern("foo");
+  let other_foo = other.intern("foo");
+  assert!(interner.is_canonical(&foo));
+  assert!(interner.contains_ptr(&foo));
+  assert!(!interner.is_canonical(&other_foo));
+  assert!(!interner.contains_ptr(&other_foo));
+  assert!(!interner.is_canonical(&InternedStr::from("bar")));
+}
+
+#[test]
+fn dedupe_in_place() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  let mut strings = ["foo", "bar", "foo", "bar", "baz"].map(InternedStr::from);
+  let bar = InternedStr::clone(&strings[1]);
+  assert_eq!(interner.dedupe_in_place(&mut strings), 3);
+  assert!(InternedStr::ptr_eq(&strings[0], &foo));
+  assert!(InternedStr::ptr_eq(&strings[2], &foo));
+  assert!(InternedStr::ptr_eq(&strings[1], &bar));
+  assert!(InternedStr::ptr_eq(&strings[3], &bar));
+  assert_eq!(interner.len(), 3);
+  assert_eq!(interner.dedupe_in_place(&mut strings), 0);
+}
+
+#[test]
+fn new_ascii_case_insensitive() {
+  let mut interner = Interner::new_ascii_case_insensitive();
+  let content_type = interner.intern("Content-Type");
+  assert!(InternedStr::ptr_eq(&interner.intern("content-type"), &content_type));
+  assert!(InternedStr::ptr_eq(&interner.intern("CONTENT-TYPE"), &content_type));
+  assert_eq!(&*content_type, "Content-Type");
+  assert!(interner.contains("cOnTeNt-TyPe"));
+  assert_eq!(interner.get("content-TYPE"), Some(InternedStr::clone(&content_type)));
+  let lower = interner.intern("straße");
+  let upper = interner.intern("STRASSE");
+  assert!(!InternedStr::ptr_eq(&lower, &upper));
+  assert!(!InternedStr::ptr_eq(&interner.intern("É"), &interner.intern("é")));
+  assert_eq!(interner.iter().filter(|string| string.eq_ignore_ascii_case("content-type")).count(), 1);
+  assert_eq!(interner.len(), 5);
+  assert_eq!(interner.remove("CONTENT-type"), Some(content_type));
+  assert!(!interner.contains("Content-Type"));
+  assert_eq!(&*interner.intern("content-TYPE"), "content-TYPE");
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn new_nfc() {
+  let mut interner = Interner::new_nfc();
+  let decomposed = interner.intern("cafe\u{301}");
+  let composed = interner.intern("caf\u{e9}");
+  assert!(InternedStr::ptr_eq(&decomposed, &composed));
+  assert_eq!(&*decomposed, "caf\u{e9}");
+  assert!(interner.contains("cafe\u{301}"));
+  assert!(interner.contains("caf\u{e9}"));
+  let ascii = InternedStr::from("cafe");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&ascii)), &ascii));
+  assert!(interner.iter().any(|string| &**string == "caf\u{e9}"));
+  assert!(!interner.iter().any(|string| &**string == "cafe\u{301}"));
+  assert_eq!(interner.remove("cafe\u{301}"), Some(composed));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn with_normalizer() {
+  let mut interner = Interner::with_normalizer(RandomState::new(), |string| {
+    let trimmed = string.trim();
+    if trimmed.contains("  ") {
+      Cow::Owned(trimmed.split(' ').filter(|word| !word.is_empty()).collect::<Vec<_>>().join(" "))
+    } else {
+      Cow::Borrowed(trimmed)
+    }
+  });
+  let foo = interner.intern(" foo ");
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(&*foo, "foo");
+  let foo_bar = interner.intern("foo   bar");
+  assert!(InternedStr::ptr_eq(&interner.intern(" foo bar"), &foo_bar));
+  assert_eq!(&*foo_bar, "foo bar");
+  assert!(interner.contains("foo  "));
+  assert_eq!(interner.get("  foo  bar "), Some(InternedStr::clone(&foo_bar)));
+  assert!(interner.iter().all(|string| string.trim() == &**string));
+  assert_eq!(format!("{interner:?}").matches("foo").count(), 2);
+  assert_eq!(interner.remove(" foo"), Some(foo));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn with_validator() {
+  let mut interner = Interner::with_validator(|string| {
+    if string.len() > 8 {
+      Err(ValidationError::new("too long"))
+    } else if !string.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+      Err(ValidationError::new(format!("{string:?} is not an identifier")))
+    } else {
+      Ok(())
+    }
+  });
+  let foo = interner.try_intern("foo").unwrap();
+  assert!(InternedStr::ptr_eq(&interner.try_intern("foo").unwrap(), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(interner.try_intern("foo_bar_baz"), Err(TryInternError::Invalid(ValidationError::new("too long"))));
+  assert_eq!(interner.try_intern("foo bar").unwrap_err().to_string(), "\"foo bar\" is not an identifier");
+  assert!(!interner.contains("foo_bar_baz"));
+  assert!(!interner.contains("foo bar"));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn with_max_len() {
+  let mut interner = Interner::with_max_len(3);
+  let foo = interner.try_intern("foo").unwrap();
+  assert!(InternedStr::ptr_eq(&interner.try_intern("foo").unwrap(), &foo));
+  assert!(interner.try_intern("").is_ok());
+  assert_eq!(interner.try_intern("fooo"), Err(TryInternError::TooLong { len: 4, max: 3 }));
+  assert_eq!(interner.try_intern("\u{e9}\u{e9}"), Err(TryInternError::TooLong { len: 4, max: 3 }));
+  assert!(!interner.contains("fooo"));
+  assert_eq!(interner.len(), 2);
+  assert_eq!(Interner::new().try_intern("a very long string indeed").as_deref(), Ok("a very long string indeed"));
+}
+
+#[test]
+fn gc() {
+  let mut interner = Interner::new();
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("bazz");
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 7 });
+  assert_eq!(interner.len(), 1);
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  let bar = InternedStr::from("bar");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&bar)), &bar));
+  assert_eq!(interner.gc(), GcReport::default());
+  drop(bar);
+  assert_eq!(interner.gc(), GcReport { entries: 1, bytes: 3 });
+  let mut interner = Interner::new_ascii_case_insensitive();
+  let foo = interner.intern("Foo");
+  interner.intern("Bar");
+  assert_eq!(interner.gc(), GcReport { entries: 1, bytes: 3 });
+  assert!(InternedStr::ptr_eq(&interner.intern("FOO"), &foo));
+  assert!(!interner.contains("bar"));
+}
+
+#[test]
+fn set_gc_threshold() {
+  let mut interner = Interner::new();
+  assert_eq!(interner.gc_threshold(), 0);
+  interner.set_gc_threshold(3);
+  assert_eq!(interner.gc_threshold(), 3);
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("foo");
+  assert_eq!(interner.len(), 2);
+  interner.intern("baz");
+  assert_eq!(interner.len(), 2);
+  assert!(!interner.contains("bar"));
+  assert!(interner.contains("baz"));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  interner.intern("qux");
+  interner.intern("quux");
+  assert_eq!(interner.len(), 4);
+  interner.intern("corge");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("foo"));
+  assert!(interner.contains("corge"));
+}
+
+#[test]
+fn with_max_entries() {
+  let mut interner = Interner::with_max_entries(3);
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("baz");
+  interner.intern("foo");
+  assert!(interner.get("bar").is_some());
+  interner.intern("qux");
+  assert_eq!(interner.len(), 3);
+  assert!(!interner.contains("baz"));
+  interner.intern("quux");
+  assert!(!interner.contains("foo"));
+  assert!(interner.contains("bar"));
+  let new_foo = interner.intern("foo");
+  assert_eq!(new_foo, foo);
+  assert!(!InternedStr::ptr_eq(&new_foo, &foo));
+  assert!(!interner.contains("qux"));
+  assert_eq!(interner.remove("bar").as_deref(), Some("bar"));
+  interner.intern("corge");
+  assert_eq!(interner.len(), 3);
+  assert!(interner.contains("quux"));
+  drop(foo);
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 9 });
+  interner.intern("grault");
+  interner.intern("garply");
+  interner.intern("waldo");
+  assert!(!interner.contains("foo"));
+  let mut interner = Interner::with_max_entries(1);
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.len(), 1);
+  assert!(interner.contains("bar"));
+}
+
+#[test]
+fn builder() {
+  let mut interner = Interner::builder().hasher(FixedState::with_seed(7)).ascii_case_insensitive().max_entries(2).build();
+  let foo = interner.intern("Foo");
+  assert!(InternedStr::ptr_eq(&interner.intern("FOO"), &foo));
+  interner.intern("bar");
+  interner.intern("foo");
+  interner.intern("baz");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("FOO"));
+  assert!(!interner.contains("BAR"));
+  let mut interner = Interner::builder().max_entries(3).max_bytes(6).build();
+  interner.intern("ab");
+  interner.intern("cd");
+  interner.intern("ef");
+  interner.intern("g");
+  assert_eq!(interner.len(), 3);
+  assert!(!interner.contains("ab"));
+  interner.intern("hijk");
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.interned_bytes(), 5);
+  let mut interner = Interner::builder().validator(|string| {
+    if string.is_empty() {
+      Err(ValidationError::new("empty"))
+    } else {
+      Ok(())
+    }
+  }).counting().build();
+  interner.intern("foo");
+  interner.intern("foo");
+  assert!(interner.try_intern("").is_err());
+  assert_eq!(interner.count_of("foo"), Some(2));
+  assert_eq!(interner.count_of(""), None);
+}
+
+#[test]
+#[should_panic(expected = "at least one string")]
+fn builder_max_entries_zero() {
+  let _ = Interner::builder().max_entries(0);
+}
+
+#[test]
+fn interned_bytes() {
+  let mut interner = Interner::new();
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  interner.intern("barbaz");
+  interner.intern("foo");
+  assert_eq!(interner.interned_bytes(), 9);
+  interner.remove("foo");
+  interner.remove("qux");
+  assert_eq!(interner.interned_bytes(), 6);
+  interner.intern("abc");
+  interner.intern("abcd");
+  assert_eq!(interner.extract_if(|string| string.starts_with('a')).count(), 2);
+  assert_eq!(interner.interned_bytes(), 6);
+  interner.intern("quux");
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 10 });
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  interner.clear();
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  assert_eq!(interner.drain().count(), 1);
+  assert_eq!(interner.interned_bytes(), 0);
+  let foo = InternedStr::from("foo");
+  let set = HashSet::from([InternedStr::clone(&foo), InternedStr::from("quux")]);
+  assert_eq!(Interner::from_set(set).interned_bytes(), 7);
+}
+
+#[test]
+fn with_max_bytes() {
+  let mut interner = Interner::with_max_bytes(10);
+  interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("baz");
+  interner.intern("foo");
+  assert_eq!(interner.interned_bytes(), 9);
+  interner.intern("quux");
+  assert_eq!(interner.interned_bytes(), 10);
+  assert!(!interner.contains("bar"));
+  interner.remove("foo");
+  assert_eq!(interner.interned_bytes(), 7);
+  interner.intern("abcdef");
+  assert_eq!(interner.interned_bytes(), 10);
+  assert!(!interner.contains("baz"));
+  interner.intern("a");
+  assert_eq!(interner.interned_bytes(), 7);
+  assert!(!interner.contains("quux"));
+  let long = interner.intern("abcdefghijk");
+  assert_eq!(&*long, "abcdefghijk");
+  assert!(!interner.contains("abcdefghijk"));
+  assert_eq!(interner.interned_bytes(), 7);
+  assert_eq!(interner.len(), 2);
+  // It was never saved, so it isn't reported as newly saved either.
+  assert!(!interner.intern_full("abcdefghijk").1);
+  assert_eq!(interner.len(), 2);
+  interner.clear();
+  assert_eq!(interner.interned_bytes(), 0);
+  assert!(interner.intern_full("abcdefghij").1);
+  assert_eq!(interner.interned_bytes(), 10);
+}
+
+#[test]
+fn interned_bytes_random() {
+  const WORDS: [&str; 8] = ["", "a", "foo", "Foo", "barbaz", "quux", "\u{e9}t\u{e9}", "abcdefghij"];
+  let interners = [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(3), Interner::with_max_bytes(12)];
+  for mut interner in interners {
+    // A fixed linear congruential generator, so that failures are reproducible.
+    let mut state = 12345u64;
+    let mut next = || {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) as usize
+    };
+    let mut held = Vec::new();
+    for _ in 0..1000 {
+      let word = WORDS[next() % WORDS.len()];
+      match next() % 16 {
+        0 => interner.clear(),
+        1 => {
+          interner.gc();
+        }
+        2 => {
+          interner.extract_if(|string| string.len() > 3).for_each(drop);
+        }
+        3..=5 => {
+          interner.remove(word);
+        }
+        6..=8 => held.push(interner.intern(word)),
+        _ => {
+          interner.intern(word);
+        }
+      }
+      if held.len() > 4 {
+        held.remove(next() % held.len());
+      }
+      assert_eq!(interner.interned_bytes(), interner.iter().map(|string| string.len()).sum::<usize>());
+    }
+  }
+}
+
+#[test]
+fn memory_usage() {
+  let mut interner = Interner::new();
+  assert_eq!(interner.memory_usage().strings, 0);
+  interner.intern("foo");
+  interner.intern("barbaz");
+  let usage = interner.memory_usage();
+  assert_eq!(usage.strings, 9);
+  assert_eq!(usage.headers, 2 * 2 * std::mem::size_of::<usize>());
+  assert!(usage.table >= 2 * std::mem::size_of::<InternedStr>());
+  assert_eq!(usage.total(), usage.strings + usage.headers + usage.table);
+  assert_eq!(MemoryUsage::default().total(), 0);
+  let mut interner = Interner::new_ascii_case_insensitive();
+  interner.intern("foo");
+  assert!(interner.memory_usage().table > Interner::new().memory_usage().table);
+}
+
+#[test]
+fn compact() {
+  for mut interner in [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(1000)] {
+    let held: Vec<_> = (0..1000).map(|i| interner.intern(i.to_string())).filter(|string| string.len() == 1).collect();
+    assert_eq!(interner.gc().entries, 990);
+    let capacity = interner.capacity();
+    assert!(capacity >= 1000);
+    let usage = interner.memory_usage();
+    let released = interner.compact(100);
+    assert_eq!(released, capacity - interner.capacity());
+    assert!(interner.capacity() >= 100);
+    assert!(interner.capacity() < capacity);
+    assert!(interner.memory_usage().table < usage.table);
+    let capacity = interner.capacity();
+    let released = interner.compact(0);
+    assert_eq!(released, capacity - interner.capacity());
+    assert!(interner.capacity() < 100);
+    assert_eq!(interner.compact(0), 0);
+    assert_eq!(interner.len(), 10);
+    for string in &held {
+      assert!(InternedStr::ptr_eq(&interner.intern(&**string), string));
+    }
+    interner.intern("foo");
+    assert!(interner.contains("foo"));
+    assert_eq!(interner.len(), 11);
+  }
+}
+
+#[test]
+fn ref_counts() {
+  for mut interner in [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(10)] {
+    let foo = interner.intern("foo");
+    interner.intern("bar");
+    let counts = |interner: &Interner| {
+      let mut counts: Vec<_> = interner.ref_counts().map(|(string, count)| (String::from(&**string), count)).collect();
+      counts.sort();
+      counts
+    };
+    assert_eq!(counts(&interner), [(String::from("bar"), 0), (String::from("foo"), 1)]);
+    let foos = vec![foo.clone(), foo.clone()];
+    assert_eq!(counts(&interner)[1].1, 3);
+    assert_eq!(interner.leaked(2).map(|(string, _)| string).collect::<Vec<_>>(), [&foo]);
+    assert_eq!(interner.leaked(4).count(), 0);
+    assert_eq!(format!("{:?}", interner.leaked(1)), "{\"foo\": 3}");
+    drop(foos);
+    drop(foo);
+    assert_eq!(counts(&interner), [(String::from("bar"), 0), (String::from("foo"), 0)]);
+    assert_eq!(interner.leaked(1).count(), 0);
+  }
+}
+
+#[test]
+fn len_stats() {
+  let mut interner = Interner::new();
+  assert_eq!(interner.len_stats(), LenStats::default());
+  assert_eq!(interner.len_stats().mean(), 0.0);
+  for string in ["", "a", "ab", "abc", "abcd", "abcdefg", "abcdefgh", "abcdefghijklmnopq", "ab"] {
+    interner.intern(string);
+  }
+  let stats = interner.len_stats();
+  assert_eq!(stats.count, 8);
+  assert_eq!(stats.min, 0);
+  assert_eq!(stats.max, 17);
+  assert_eq!(stats.total, 42);
+  assert_eq!(stats.mean(), 5.25);
+  assert_eq!(stats.histogram, [1, 1, 2, 2, 1, 1]);
+  assert_eq!(LenStats::bucket_range(0), 0..=0);
+  assert_eq!(LenStats::bucket_range(3), 4..=7);
+  assert_eq!(LenStats::bucket_range(64), 1 << 63..=usize::MAX);
+  assert_eq!(stats.to_string(), "\
+count: 8, total: 42, min: 0, max: 17, mean: 5.25
+                  length | count
+                       0 | 1
+                       1 | 1
+                   2..=3 | 2
+                   4..=7 | 2
+                  8..=15 | 1
+                 16..=31 | 1");
+}
+
+#[test]
+fn with_counting() {
+  let mut interner = Interner::with_counting();
+  assert_eq!(interner.count_of("foo"), None);
+  for string in ["foo", "bar", "foo", "baz", "bar", "qux", "foo", "quux"] {
+    interner.intern(string);
+  }
+  interner.intern_from(String::from("qux"));
+  assert!(interner.get("baz").is_some());
+  assert_eq!(interner.count_of("foo"), Some(3));
+  assert_eq!(interner.count_of("baz"), Some(1));
+  assert_eq!(interner.count_of("corge"), None);
+  let most_common: Vec<_> = interner.most_common(4).into_iter().map(|(string, count)| (String::from(&*string), count)).collect();
+  assert_eq!(most_common, [(String::from("foo"), 3), (String::from("bar"), 2), (String::from("qux"), 2), (String::from("baz"), 1)]);
+  assert_eq!(interner.most_common(100).len(), 5);
+  assert!(interner.most_common(0).is_empty());
+  let foo = interner.intern("foo");
+  assert_eq!(interner.gc().entries, 4);
+  assert_eq!(interner.count_of("foo"), Some(4));
+  assert_eq!(interner.count_of("bar"), None);
+  interner.remove("foo");
+  interner.intern("foo");
+  assert_eq!(interner.count_of("foo"), Some(1));
+  drop(foo);
+  assert_eq!(Interner::new().count_of("foo"), None);
+  assert!(Interner::new().most_common(1).is_empty());
+}
+
+#[test]
+fn counting_entry() {
+  let mut interner = Interner::with_counting();
+  interner.intern("a");
+  interner.get_or_intern_with("a", || -> String { panic!("a should be occupied") });
+  interner.entry("a").or_insert();
+  interner.intern_split("a,a", ',').for_each(drop);
+  assert_eq!(interner.count_of("a"), Some(5));
+  let mut interner = Interner::with_counting();
+  interner.intern_split("a,a", ',').for_each(drop);
+  assert_eq!(interner.count_of("a"), Some(2));
+}
+
+#[test]
+fn stats() {
+  let mut interner = Interner::with_max_bytes(10);
+  assert_eq!(interner.stats(), InternStats::default());
+  for string in ["foo", "bar", "foo", "foo", "bazz", "bar", "too long!!!"] {
+    interner.intern(string);
+  }
+  interner.get("foo");
+  assert!(interner.contains("bar"));
+  assert_eq!(interner.stats(), InternStats { hits: 3, misses: 4, inserts: 3, bytes_saved_estimate: 9 });
+  interner.reset_stats();
+  assert_eq!(interner.stats(), InternStats::default());
+  interner.intern_from(String::from("bazz"));
+  interner.intern("qux");
+  assert_eq!(interner.stats(), InternStats { hits: 1, misses: 1, inserts: 1, bytes_saved_estimate: 4 });
+  let mut interner = Interner::with_validator(|string| if string.is_empty() { Err(ValidationError::new("empty")) } else { Ok(()) });
+  assert!(interner.try_intern("").is_err());
+  interner.intern("foo");
+  interner.intern("foo");
+  assert_eq!(interner.stats(), InternStats { hits: 1, misses: 2, inserts: 1, bytes_saved_estimate: 3 });
+}
+
+#[test]
+fn stats_entry() {
+  let mut interner = Interner::new();
+  interner.intern("a");
+  interner.get_or_intern_with("a", || -> String { panic!("a should be occupied") });
+  interner.entry("a").or_insert();
+  interner.intern_split("a,a", ',').for_each(drop);
+  assert_eq!(interner.stats(), InternStats { hits: 4, misses: 1, inserts: 1, bytes_saved_estimate: 4 });
+}
+
+#[test]
+fn removal_hook() {
+  let removed = Rc::new(RefCell::new(Vec::new()));
+  let mut interner = Interner::with_max_entries(3);
+  interner.set_removal_hook({
+    let removed = Rc::clone(&removed);
+    move |string, cause| removed.borrow_mut().push((String::from(&**string), cause))
+  });
+  let take = || mem::take(&mut *removed.borrow_mut());
+  for string in ["foo", "bar", "baz", "qux"] {
+    interner.intern(string);
+  }
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Evicted)]);
+  interner.remove("bar");
+  assert_eq!(interner.remove("bar"), None);
+  assert_eq!(take(), [(String::from("bar"), RemovalCause::Explicit)]);
+  interner.retain(|string| &**string != "baz");
+  assert_eq!(take(), [(String::from("baz"), RemovalCause::Retain)]);
+  let kept = interner.intern("kept");
+  interner.intern("swept");
+  assert_eq!(interner.gc().entries, 2);
+  let mut swept = take();
+  swept.sort_by(|(a, _), (b, _)| a.cmp(b));
+  assert_eq!(swept, [(String::from("qux"), RemovalCause::Gc), (String::from("swept"), RemovalCause::Gc)]);
+  interner.intern("foo");
+  assert_eq!(interner.extract_if(|string| &**string == "foo").count(), 1);
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Retain)]);
+  interner.intern("bar");
+  drop(interner.drain());
+  let mut cleared = take();
+  cleared.sort_by(|(a, _), (b, _)| a.cmp(b));
+  assert_eq!(cleared, [(String::from("bar"), RemovalCause::Cleared), (String::from("kept"), RemovalCause::Cleared)]);
+  interner.intern("foo");
+  interner.clone().clear();
+  assert!(take().is_empty());
+  interner.clear();
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Cleared)]);
+  drop(kept);
+}
+
+#[test]
+fn insert_hook() {
+  let inserted = Rc::new(RefCell::new(Vec::new()));
+  let mut interner = Interner::with_max_len(10);
+  interner.set_insert_hook({
+    let inserted = Rc::clone(&inserted);
+    move |string| inserted.borrow_mut().push(InternedStr::clone(string))
+  });
+  let foo = interner.intern("foo");
+  interner.intern("foo");
+  assert!(interner.try_intern("too long!!!").is_err());
+  interner.intern_all(["bar", "foo", "bar", "baz"]);
+  interner.extend(["qux", "baz"]);
+  interner.intern_lines("foo\nquux\nquux\n".as_bytes()).unwrap();
+  assert_eq!(inserted.borrow().len(), 5);
+  assert!(InternedStr::ptr_eq(&inserted.borrow()[0], &foo));
+  assert_eq!(inserted.borrow().iter().map(|string| &**string).collect::<Vec<_>>(), ["foo", "bar", "baz", "qux", "quux"]);
+  interner.clone().intern("corge");
+  assert_eq!(inserted.borrow().len(), 5);
+}
+
+#[test]
+fn version() {
+  let mut interner = Interner::with_max_entries(2);
+  let mut version = interner.version();
+  let mut changed = |interner: &Interner| {
+    let changed = interner.version() > version;
+    version = interner.version();
+    changed
+  };
+  interner.clear();
+  assert!(!changed(&interner));
+  interner.intern("foo");
+  assert!(changed(&interner));
+  interner.intern("foo");
+  interner.get("foo");
+  assert!(!interner.contains("bar"));
+  assert!(!changed(&interner));
+  interner.intern_all(["bar", "baz"]);
+  assert!(changed(&interner));
+  assert!(interner.remove("bar").is_some());
+  assert!(changed(&interner));
+  assert!(interner.remove("bar").is_none());
+  interner.retain(|_| true);
+  assert!(!changed(&interner));
+  interner.retain(|string| &**string != "baz");
+  assert!(changed(&interner));
+  interner.intern("qux");
+  assert!(changed(&interner));
+  interner.retain(|_| false);
+  assert!(changed(&interner));
+  let foo = interner.intern("foo");
+  assert!(changed(&interner));
+  assert_eq!(interner.gc().entries, 0);
+  assert!(!changed(&interner));
+  drop(foo);
+  assert_eq!(interner.gc().entries, 1);
+  assert!(changed(&interner));
+  interner.intern("foo");
+  changed(&interner);
+  interner.clear();
+  assert!(changed(&interner));
+  assert_eq!(interner.clone().version(), interner.version());
+}
+
+#[test]
+fn version_clone_from() {
+  let mut interner = Interner::from(["foo", "bar", "baz"]);
+  interner.remove("foo");
+  let version = interner.version();
+  // The version never goes backwards, even when cloning from an interner with a lower version.
+  interner.clone_from(&Interner::new());
+  assert!(interner.version() > version);
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn weak_interner() {
+  let mut interner = WeakInterner::new();
+  assert!(interner.is_empty());
+  let foo = interner.intern("foo");
+  assert_eq!(Rc::strong_count(&foo), 1);
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  let bar = interner.intern(String::from("bar"));
+  assert_eq!(interner.len(), 2);
+  assert!(InternedStr::ptr_eq(&interner.get("bar").unwrap(), &bar));
+  let weak = Rc::downgrade(&foo);
+  drop(foo);
+  assert!(weak.upgrade().is_none());
+  assert_eq!(interner.len(), 1);
+  assert!(!interner.contains("foo"));
+  assert!(interner.get("foo").is_none());
+  let foo = interner.intern("foo");
+  assert_eq!(Rc::strong_count(&foo), 1);
+  assert_eq!(interner.len(), 2);
+  drop((foo, bar));
+  assert!(interner.is_empty());
+  assert_eq!(interner.purge(), 2);
+  assert_eq!(interner.purge(), 0);
+}
+
+#[test]
+fn ordered_interner() {
+  let mut interner = OrderedInterner::new();
+  let words = ["the", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog", "quick", "the"];
+  for &word in &words {
+    let len = interner.len();
+    let hit = interner.contains(word);
+    interner.intern(word);
+    assert_eq!(interner.len(), if hit { len } else { len + 1 });
+  }
+  let order = ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog"];
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), order);
+  for (index, word) in order.into_iter().enumerate() {
+    assert_eq!(interner.index_of(word), Some(index));
+    assert_eq!(interner.get_index(index).map(|string| &**string), Some(word));
+  }
+  assert_eq!(interner.get_index(order.len()), None);
+  assert_eq!(interner.index_of("cat"), None);
+  let fox = interner.get("fox").unwrap();
+  assert!(InternedStr::ptr_eq(&interner.intern("fox"), &fox));
+  assert_eq!(interner.shift_remove("quick").as_deref(), Some("quick"));
+  assert_eq!(interner.shift_remove("quick"), None);
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "brown", "fox", "jumps", "over", "lazy", "dog"]);
+  assert_eq!(interner.index_of("dog"), Some(6));
+  assert_eq!(interner.swap_remove("brown").as_deref(), Some("brown"));
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "dog", "fox", "jumps", "over", "lazy"]);
+  assert_eq!(interner.index_of("dog"), Some(1));
+  assert_eq!(interner.swap_remove("lazy").as_deref(), Some("lazy"));
+  interner.intern("quick");
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "dog", "fox", "jumps", "over", "quick"]);
+  assert_eq!(interner.len(), 6);
+  let clone: OrderedInterner = interner.iter().collect();
+  assert_eq!(clone.iter().map(|string| &**string).collect::<Vec<_>>(), interner.iter().map(|string| &**string).collect::<Vec<_>>());
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn ord_interner() {
+  let mut interner = OrdInterner::new();
+  assert_eq!(interner.first(), None);
+  let name = interner.intern("user:1000:name");
+  assert!(InternedStr::ptr_eq(&interner.intern(String::from("user:1000:name")), &name));
+  for string in ["user:1001:name", "user:1000:name", "group:7:name", "user:1000:email", "user:1000:", "user:1001:", "user:1000:name"] {
+    interner.intern(string);
+  }
+  let range = |range: (Bound<&str>, Bound<&str>)| interner.range(range).map(|string| &**string).collect::<Vec<_>>();
+  assert_eq!(range((Bound::Included("user:1000:"), Bound::Excluded("user:1001:"))), ["user:1000:", "user:1000:email", "user:1000:name"]);
+  assert_eq!(range((Bound::Excluded("user:1000:"), Bound::Included("user:1001:"))), ["user:1000:email", "user:1000:name", "user:1001:"]);
+  assert_eq!(range((Bound::Unbounded, Bound::Excluded("user:"))), ["group:7:name"]);
+  assert_eq!(range((Bound::Included("user:1001:"), Bound::Unbounded)), ["user:1001:", "user:1001:name"]);
+  assert_eq!(range((Bound::Unbounded, Bound::Unbounded)).len(), 6);
+  assert!(range((Bound::Included("x"), Bound::Included("y"))).is_empty());
+  assert_eq!(interner.range("user:1000:".."user:1000:name").count(), 2);
+  assert_eq!(interner.range("user:1000:"..="user:1000:name").count(), 3);
+  assert_eq!(interner.range(.."user:1000:").count(), 1);
+  assert_eq!(interner.range("user:1000:email"..).rev().map(|string| &**string).collect::<Vec<_>>(), ["user:1001:name", "user:1001:", "user:1000:name", "user:1000:email"]);
+  assert_eq!(interner.range::<RangeFull>(..).count(), 6);
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), range((Bound::Unbounded, Bound::Unbounded)));
+  assert_eq!(interner.first().map(|string| &**string), Some("group:7:name"));
+  assert_eq!(interner.last().map(|string| &**string), Some("user:1001:name"));
+  assert!(InternedStr::ptr_eq(&interner.get("user:1000:name").unwrap(), &name));
+  assert_eq!(interner.remove("group:7:name").as_deref(), Some("group:7:name"));
+  assert!(!interner.contains("group:7:name"));
+  assert_eq!(interner.len(), 5);
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn iter_sorted() {
+  let mut interner = Interner::new();
+  let strings = ["zebra", "Zebra", "apple", "Äpfel", "äpfel", "apfel", "", "日本", "z", "apple", "é", "e\u{301}"];
+  for string in strings {
+    interner.intern(string);
+  }
+  // By UTF-8 bytes, every uppercase ASCII letter comes before every lowercase one, and everything non-ASCII comes after both.
+  let sorted = ["", "Zebra", "apfel", "apple", "e\u{301}", "z", "zebra", "Äpfel", "äpfel", "é", "日本"];
+  let mut expected = strings.to_vec();
+  expected.sort();
+  expected.dedup();
+  assert_eq!(expected, sorted);
+  assert_eq!(interner.iter_sorted().map(|string| &**string).collect::<Vec<_>>(), sorted);
+  assert_eq!(interner.iter_sorted().len(), sorted.len());
+  assert_eq!(interner.iter_sorted().next_back().map(|string| &**string), Some("日本"));
+  assert_eq!(interner.into_sorted_vec().iter().map(|string| &**string).collect::<Vec<_>>(), sorted);
+  assert_eq!(Interner::new().into_sorted_vec(), Vec::<InternedStr>::new());
+}
+
+#[test]
+fn iter_prefix() {
+  let strings = ["foo", "foo.bar", "foo.baz", "foo.bar.qux", "foobar", "fo", "bar.foo", "", "\u{D7FF}a", "\u{D7FF}", "\u{E000}", "\u{10FFFF}", "\u{10FFFF}\u{10FFFF}"];
+  let mut interner = Interner::new();
+  let mut ord_interner = OrdInterner::new();
+  for string in strings {
+    interner.intern(string);
+    ord_interner.intern(string);
+  }
+  for prefix in ["", "foo", "foo.", "foo.bar", "foo.bar.qux.", "f", "bar", "x", "\u{D7FF}", "\u{10FFFF}", "\u{10FFFF}\u{10FFFF}"] {
+    let mut expected: Vec<_> = strings.into_iter().filter(|string| string.starts_with(prefix)).collect();
+    expected.sort();
+    let mut actual: Vec<_> = interner.iter_prefix(prefix).map(|string| &**string).collect();
+    actual.sort();
+    assert_eq!(actual, expected, "prefix {prefix:?}");
+    assert_eq!(ord_interner.iter_prefix(prefix).map(|string| &**string).collect::<Vec<_>>(), expected, "prefix {prefix:?}");
+  }
+  assert_eq!(interner.iter_prefix("").count(), strings.len());
+  assert_eq!(interner.iter_prefix("foo.").count(), 3);
+}
+
+#[test]
+fn shared_interner() {
+  let interner = SharedInterner::new();
+  let clone = interner.clone();
+  assert!(interner.ptr_eq(&clone));
+  let foo0 = interner.intern("foo");
+  let foo1 = clone.intern(String::from("foo"));
+  assert!(Rc::ptr_eq(&foo0, &foo1));
+  clone.intern("bar");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("bar"));
+  assert!(Rc::ptr_eq(&interner.get("foo").unwrap(), &foo0));
+  assert_eq!(interner.get("baz"), None);
+  for string in interner.iter_snapshot() {
+    // The snapshot does not keep the interner borrowed.
+    let suffixed = clone.intern(format!("{string}!"));
+    assert!(Rc::ptr_eq(&suffixed, &interner.intern(&*suffixed)));
+  }
+  assert_eq!(clone.len(), 4);
+  assert!(!interner.ptr_eq(&SharedInterner::from(Interner::new())));
+  clone.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn shared_interner_reentrant() {
+  let interner = SharedInterner::new();
+  let _borrowed = interner.borrow();
+  interner.intern("foo");
+}
+
+#[test]
+fn scoped_interner() {
+  let parent: Interner = ["id", "name"].into_iter().collect();
+  let id = parent.get("id").unwrap();
+  let mut scoped = ScopedInterner::new(&parent);
+  assert!(Rc::ptr_eq(&scoped.intern("id"), &id));
+  assert!(!scoped.contains_local("id"));
+  let local = scoped.intern("local");
+  assert!(Rc::ptr_eq(&scoped.intern(String::from("local")), &local));
+  assert!(scoped.contains_local("local"));
+  assert!(scoped.contains("name") && scoped.contains("local"));
+  assert!(Rc::ptr_eq(&scoped.get("local").unwrap(), &local));
+  assert_eq!(scoped.len(), 3);
+  assert_eq!(scoped.iter().len(), 3);
+  let all: HashSet<&str> = scoped.iter().map(|string| &**string).collect();
+  assert_eq!(all, HashSet::from(["id", "name", "local"]));
+  let local_only: Vec<&str> = scoped.iter_local().map(|string| &**string).collect();
+  assert_eq!(local_only, ["local"]);
+  drop(scoped);
+  assert!(!parent.contains("local"));
+  assert_eq!(parent.len(), 2);
+  // Nothing else refers to the local string now.
+  assert_eq!(Rc::strong_count(&local), 1);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "local")]
+
+use std::panic;
+use std::thread;
+
+use str_intern::{local, InternedStr};
+
+#[test]
+fn intern() {
+  let foo = local::intern("foo");
+  assert!(InternedStr::ptr_eq(&local::intern(String::from("foo")), &foo));
+  assert!(InternedStr::ptr_eq(&local::get("foo").unwrap(), &foo));
+  assert!(local::contains("foo"));
+  assert!(!local::contains("bar"));
+  local::intern("bar");
+  assert_eq!(local::with(|interner| interner.len()), 2);
+  local::clear();
+  assert!(!local::contains("foo"));
+  assert!(!InternedStr::ptr_eq(&local::intern("foo"), &foo));
+}
+
+#[test]
+fn threads() {
+  local::intern("foo");
+  // Every thread has its own interner, which starts out empty.
+  thread::spawn(|| {
+    assert!(!local::contains("foo"));
+    local::intern("bar");
+    assert_eq!(local::with(|interner| interner.len()), 1);
+  }).join().unwrap();
+  assert!(local::contains("foo"));
+  assert!(!local::contains("bar"));
+}
+
+#[test]
+fn reentrant_with() {
+  let result = panic::catch_unwind(|| local::with(|_| local::intern("foo")));
+  assert!(result.is_err());
+  // The interner isn't left borrowed afterwards.
+  local::intern("foo");
+}
@@ -0,0 +1,98 @@
+//! Model checks for the thread-safe interners, which only run when compiled with `--cfg str_intern_loom`, e.g.:
+//! `RUSTFLAGS="--cfg str_intern_loom" cargo test --release --test loom`
+
+#![cfg(str_intern_loom)]
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use loom::thread;
+use str_intern::sync::{Interner, CachedInterner, InternedStr};
+
+#[test]
+fn intern_same_new_string() {
+  loom::model(|| {
+    let interner = Arc::new(Interner::new());
+    let version = interner.version();
+    let handles: Vec<_> = (0..2).map(|_| {
+      let interner = Arc::clone(&interner);
+      thread::spawn(move || {
+        let foo = interner.intern("foo");
+        assert!(interner.version() > version);
+        foo
+      })
+    }).collect();
+    let [foo0, foo1]: [InternedStr; 2] = handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>().try_into().unwrap();
+    assert!(InternedStr::ptr_eq(&foo0, &foo1));
+    assert!(InternedStr::ptr_eq(&interner.get("foo").unwrap(), &foo0));
+    assert_eq!(interner.len(), 1);
+    assert_eq!(interner.version(), interner.lock().version());
+  });
+}
+
+#[test]
+fn intern_racing_clear() {
+  loom::model(|| {
+    let interner = Arc::new(Interner::new());
+    interner.intern("bar");
+    let handle = thread::spawn({
+      let interner = Arc::clone(&interner);
+      move || interner.intern("foo")
+    });
+    interner.clear();
+    let foo = handle.join().unwrap();
+    assert!(!interner.contains("bar"));
+    match interner.get("foo") {
+      Some(saved) => {
+        assert!(InternedStr::ptr_eq(&saved, &foo));
+        assert_eq!(interner.len(), 1);
+      }
+      None => assert!(interner.is_empty())
+    }
+    assert_eq!(interner.version(), interner.lock().version());
+  });
+}
+
+#[test]
+fn poison_recovery() {
+  loom::model(|| {
+    let interner = Arc::new(Interner::new());
+    let handle = thread::spawn({
+      let interner = Arc::clone(&interner);
+      move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| interner.visit(|locked| {
+          locked.intern("foo");
+          panic!("poisoning the interner");
+        })));
+        assert!(result.is_err());
+      }
+    });
+    let bar = interner.recover().intern("bar");
+    handle.join().unwrap();
+    // The panic may have happened before or after the interner was recovered.
+    interner.clear_poison();
+    assert!(interner.lock_checked().is_ok());
+    assert!(interner.contains("foo"));
+    assert!(InternedStr::ptr_eq(&interner.intern("bar"), &bar));
+    assert_eq!(interner.len(), 2);
+  });
+}
+
+#[test]
+fn cached_intern_racing_remove() {
+  loom::model(|| {
+    let interner = CachedInterner::new(Arc::new(Interner::new()));
+    let foo = interner.intern("foo");
+    let handle = thread::spawn({
+      let interner = interner.clone();
+      move || interner.interner().remove("foo")
+    });
+    interner.intern("foo");
+    let removed = handle.join().unwrap();
+    assert!(InternedStr::ptr_eq(&removed.unwrap(), &foo));
+    // Once the removal is visible, the cache must not hand out the removed allocation anymore.
+    let saved = interner.intern("foo");
+    assert!(!InternedStr::ptr_eq(&saved, &foo));
+    assert!(InternedStr::ptr_eq(&saved, &interner.interner().get("foo").unwrap()));
+  });
+}
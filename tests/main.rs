@@ -1,10 +1,81 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use str_intern::sync::*;
 
 #[test]
+// This deliberately interns an owned `String`.
+#[allow(clippy::unnecessary_to_owned)]
 fn main() {
   let s0 = intern("Hello World!".to_string());
   let s1 = "Hello World!".intern();
   assert!(Arc::ptr_eq(&s0, &s1));
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "global")]
+#[test]
+fn intern_ext() {
+  let s0 = "InternExt".intern();
+  assert!(Arc::ptr_eq(&s0, &String::from("InternExt").intern()));
+  assert!(Arc::ptr_eq(&s0, &Box::<str>::from("InternExt").intern()));
+  assert!(Arc::ptr_eq(&s0, &Cow::Borrowed("InternExt").intern()));
+  assert!(Arc::ptr_eq(&s0, &Cow::<str>::Owned(String::from("InternExt")).intern()));
+  assert!(Arc::ptr_eq(&s0, &Arc::<str>::from("InternExt").intern()));
+  let owned = String::from("InternExt");
+  let borrowed: &&String = &&owned;
+  assert!(Arc::ptr_eq(&s0, &borrowed.intern()));
+}
+
+#[cfg(feature = "global")]
+#[test]
+fn global_try_intern_nonblocking() {
+  let s0 = try_intern_nonblocking("Hello try_intern_nonblocking!").unwrap();
+  let s1 = intern("Hello try_intern_nonblocking!");
+  assert!(Arc::ptr_eq(&s0, &s1));
+  let locked = GlobalInterner.lock();
+  assert_eq!(try_intern_nonblocking("Hello try_intern_nonblocking!"), None);
+  drop(locked);
+}
+
+#[cfg(feature = "global")]
+#[test]
+fn global_intern_many() {
+  let strings = ["intern_many 0", "intern_many 1", "intern_many 0", "intern_many 2"];
+  let many = intern_many(strings);
+  assert_eq!(many, strings.map(InternedStr::from));
+  for (saved, string) in many.iter().zip(strings) {
+    assert!(Arc::ptr_eq(saved, &intern(string)));
+  }
+  assert!(Arc::ptr_eq(&many[0], &many[2]));
+  let (first, second) = intern_pair("intern_many 1", String::from("intern_many 3"));
+  assert!(Arc::ptr_eq(&first, &many[1]));
+  assert!(Arc::ptr_eq(&second, &intern("intern_many 3")));
+  let array = intern_array(strings);
+  for (saved, array) in many.iter().zip(&array) {
+    assert!(Arc::ptr_eq(saved, array));
+  }
+  assert!(intern_many(Vec::<String>::new()).is_empty());
+}
+
+#[cfg(feature = "global")]
+#[test]
+fn intern_in() {
+  let interner = Interner::new();
+  let foo0 = "intern_in foo".intern_in(&interner);
+  let foo1 = String::from("intern_in foo").intern_in(&interner);
+  let foo2 = Box::<str>::from("intern_in foo").intern_in(&interner);
+  assert!(Arc::ptr_eq(&foo0, &foo1));
+  assert!(Arc::ptr_eq(&foo0, &foo2));
+  assert_eq!(interner.len(), 1);
+  
+  let mut local = str_intern::Interner::new();
+  let bar0 = "intern_in bar".intern_in_local(&mut local);
+  let bar1 = String::from("intern_in bar").intern_in_local(&mut local);
+  assert!(str_intern::InternedStr::ptr_eq(&bar0, &bar1));
+  assert_eq!(local.len(), 1);
+  
+  // Neither of them went through the global interner.
+  assert!(!GlobalInterner.contains("intern_in foo"));
+  assert!(!GlobalInterner.contains("intern_in bar"));
+  assert!(!Arc::ptr_eq(&foo0, &"intern_in foo".intern()));
+}
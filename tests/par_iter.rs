@@ -0,0 +1,62 @@
+#![cfg(feature = "rayon")]
+
+use std::collections::HashSet;
+
+use rayon::prelude::*;
+use str_intern::sync::{Interner, InternedStr};
+
+fn interner() -> Interner {
+  (0..1000).map(|i| i.to_string()).collect()
+}
+
+#[test]
+fn par_snapshot() {
+  let interner = interner();
+  let sequential = interner.snapshot().iter().map(|string| string.len()).sum::<usize>();
+  assert_eq!(interner.par_snapshot().len(), interner.len());
+  assert_eq!(interner.par_snapshot().map(|string| string.len()).sum::<usize>(), sequential);
+  assert_eq!(interner.par_iter().fold(|| 0, |len, string| len + string.len()).sum::<usize>(), sequential);
+  // The snapshot doesn't keep the interner locked.
+  let strings = interner.par_snapshot();
+  interner.intern("foo");
+  assert_eq!(strings.count(), 1000);
+}
+
+#[test]
+fn locked_par_iter() {
+  let interner = interner();
+  let locked = interner.lock();
+  let sequential = locked.iter().map(|string| string.len()).sum::<usize>();
+  assert_eq!(locked.par_iter().map(|string| string.len()).sum::<usize>(), sequential);
+  let strings = locked.par_iter().collect::<Vec<_>>();
+  assert_eq!(strings.len(), 1000);
+  for string in strings {
+    assert!(InternedStr::ptr_eq(string, &locked.get(string).unwrap()));
+  }
+}
+
+#[test]
+fn into_par_iter() {
+  let interner = interner();
+  let sequential = interner.snapshot().into_iter().collect::<HashSet<_>>();
+  let parallel = interner.into_par_iter().collect::<HashSet<_>>();
+  assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn par_intern_all() {
+  let strings = (0..10_000).map(|i| (i % 1234).to_string()).collect::<Vec<_>>();
+  let interner = Interner::from(["5", "foo"]);
+  let five = interner.get("5").unwrap();
+  let parallel = interner.par_intern_all(&strings);
+  assert_eq!(interner.len(), 1235);
+  assert!(InternedStr::ptr_eq(&parallel[5], &five));
+  let sequential = interner.intern_all(&strings);
+  assert_eq!(parallel.len(), sequential.len());
+  for ((parallel, sequential), string) in parallel.iter().zip(&sequential).zip(&strings) {
+    assert_eq!(**parallel, **string);
+    assert!(InternedStr::ptr_eq(parallel, sequential));
+  }
+  assert_eq!(interner.len(), 1235);
+  assert!(interner.par_intern_all::<&str>(&[]).is_empty());
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use str_intern::path::{Interner, InternedPath};
+use str_intern::path::sync::{Interner as SyncInterner, InternedPath as SyncInternedPath};
+
+#[test]
+fn dedup() {
+  let mut interner = Interner::new();
+  let main0 = interner.intern("src/main.rs");
+  let main1 = interner.intern(PathBuf::from("src").join("main.rs"));
+  let lib = interner.intern(Path::new("src/lib.rs"));
+  assert!(InternedPath::ptr_eq(&main0, &main1));
+  assert!(!InternedPath::ptr_eq(&main0, &lib));
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.len(), interner.iter().len());
+}
+
+#[test]
+fn no_normalization() {
+  let mut interner = Interner::new();
+  let plain = interner.intern("a/b");
+  let doubled = interner.intern("a//b");
+  let trailing = interner.intern("a/b/");
+  assert_eq!(plain, doubled);
+  assert!(!InternedPath::ptr_eq(&plain, &doubled));
+  assert!(!InternedPath::ptr_eq(&plain, &trailing));
+  assert_eq!(doubled.as_os_str(), "a//b");
+  assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn lookup() {
+  let mut interner = Interner::new();
+  let main = interner.intern("src/main.rs");
+  assert!(interner.contains("src/main.rs"));
+  assert!(InternedPath::ptr_eq(&interner.get("src/main.rs").unwrap(), &main));
+  assert!(!interner.contains("src//main.rs"));
+  assert_eq!(interner.get("src/lib.rs"), None);
+  assert!(InternedPath::ptr_eq(&interner.remove("src/main.rs").unwrap(), &main));
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn gc() {
+  let mut interner: Interner = ["src/main.rs", "src/lib.rs"].into_iter().collect();
+  let main = interner.intern("src/main.rs");
+  assert_eq!(interner.gc(), 1);
+  assert!(interner.contains(&*main));
+  assert!(!interner.contains("src/lib.rs"));
+  let paths: Vec<InternedPath> = interner.into_iter().collect();
+  assert_eq!(paths, [main]);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8() {
+  use std::ffi::OsStr;
+  use std::os::unix::ffi::OsStrExt;
+  
+  let mut interner = Interner::new();
+  let path = Path::new(OsStr::from_bytes(b"data/\xff\xfe.bin"));
+  assert_eq!(path.to_str(), None);
+  let saved0 = interner.intern(path);
+  let saved1 = interner.intern(OsStr::from_bytes(b"data/\xff\xfe.bin"));
+  assert!(InternedPath::ptr_eq(&saved0, &saved1));
+  assert_eq!(saved0.as_os_str().as_bytes(), b"data/\xff\xfe.bin");
+  assert!(interner.contains(path));
+  assert!(!interner.contains("data/\u{fffd}\u{fffd}.bin"));
+}
+
+#[test]
+fn sync_dedup() {
+  let interner = SyncInterner::new();
+  let paths: Vec<SyncInternedPath> = thread::scope(|scope| {
+    let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| interner.intern("src/main.rs"))).collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+  });
+  assert!(paths.iter().all(|path| SyncInternedPath::ptr_eq(path, &paths[0])));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn sync_locked() {
+  let interner = SyncInterner::new();
+  let main = interner.intern("src/main.rs");
+  interner.intern("src/lib.rs");
+  let mut locked = interner.lock();
+  assert_eq!(locked.len(), 2);
+  assert_eq!(locked.len(), locked.iter().len());
+  assert!(SyncInternedPath::ptr_eq(&locked.intern("src/main.rs"), &main));
+  assert!(locked.contains("src/lib.rs"));
+  assert_eq!(locked.gc(), 1);
+  assert!(!locked.contains("src/lib.rs"));
+  assert!(SyncInternedPath::ptr_eq(&locked.remove("src/main.rs").unwrap(), &main));
+  assert!(locked.is_empty());
+}
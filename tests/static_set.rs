@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use str_intern::static_interner;
+use str_intern::static_set::{StaticInterner, FallbackInterner};
+use str_intern::sync::Interner;
+
+static_interner! {
+  static TAGS = ["span", "div", "p", "a", "table"];
+  pub static EMPTY = [];
+}
+
+#[test]
+fn sorted() {
+  assert_eq!(TAGS.as_slice(), ["a", "div", "p", "span", "table"]);
+  assert_eq!(TAGS.len(), 5);
+  assert!(EMPTY.is_empty());
+  assert_eq!(EMPTY.get("div"), None);
+}
+
+#[test]
+fn static_hits() {
+  for string in ["a", "div", "p", "span", "table"] {
+    let saved0 = TAGS.get(string).unwrap();
+    let saved1 = TAGS.get(&String::from(string)).unwrap();
+    assert_eq!(&*saved0, string);
+    assert!(Arc::ptr_eq(&saved0, &saved1));
+    assert!(TAGS.contains(string));
+    assert_eq!(TAGS.get_static(string), Some(string));
+  }
+  assert_eq!(TAGS.index_of("div"), Some(1));
+  assert!(Arc::ptr_eq(&TAGS.get_index(1), &TAGS.get("div").unwrap()));
+  for string in ["", "b", "dive", "di", "tables", "zzz"] {
+    assert_eq!(TAGS.get(string), None);
+    assert!(!TAGS.contains(string));
+    assert_eq!(TAGS.get_static(string), None);
+  }
+}
+
+#[test]
+fn duplicates() {
+  assert!(std::panic::catch_unwind(|| StaticInterner::new(["a", "b", "a"])).is_err());
+}
+
+#[test]
+fn dynamic_fallback() {
+  let interner = FallbackInterner::new(&TAGS);
+  assert!(Arc::ptr_eq(&interner.intern("div"), &TAGS.get("div").unwrap()));
+  assert!(interner.dynamic().is_empty());
+  let custom0 = interner.intern("my-element");
+  let custom1 = interner.intern(String::from("my-element"));
+  assert!(Arc::ptr_eq(&custom0, &custom1));
+  assert!(interner.contains("my-element"));
+  assert!(interner.contains("p"));
+  assert!(!interner.contains("other-element"));
+  assert!(Arc::ptr_eq(&interner.get("my-element").unwrap(), &custom0));
+  assert_eq!(interner.get("other-element"), None);
+  assert_eq!(interner.into_dynamic().len(), 1);
+}
+
+#[test]
+fn existing_interner() {
+  let dynamic = Interner::new();
+  dynamic.intern("div");
+  let interner = FallbackInterner::with_interner(&TAGS, dynamic);
+  // The static set always takes priority.
+  assert!(Arc::ptr_eq(&interner.intern("div"), &TAGS.get("div").unwrap()));
+}
@@ -0,0 +1,61 @@
+use std::panic;
+use std::sync::Arc;
+
+use str_intern::substr::{SubstrInterner, InternedSubstr};
+
+#[test]
+fn dedupes_across_parents() {
+  let mut interner = SubstrInterner::new();
+  let parent0: Arc<str> = Arc::from("fn main() {}");
+  let parent1: Arc<str> = Arc::from("pub fn main() {}");
+  let main0 = interner.intern_substr(&parent0, 3..7);
+  let main1 = interner.intern_substr(&parent1, 7..11);
+  assert_eq!(&*main0, "main");
+  assert_eq!(&*main1, "main");
+  assert!(InternedSubstr::ptr_eq(&main0, &main1));
+  assert!(Arc::ptr_eq(main1.parent(), &parent0));
+  assert_eq!(main1.range(), 3..7);
+  assert_eq!(interner.len(), 1);
+  assert_eq!(interner.intern("main"), main0);
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn shares_parent() {
+  let mut interner = SubstrInterner::new();
+  let parent: Arc<str> = Arc::from("foo bar");
+  let foo = interner.intern_substr(&parent, 0..3);
+  let bar = interner.intern_substr(&parent, 4..7);
+  assert_eq!(Arc::strong_count(&parent), 5);
+  assert!(Arc::ptr_eq(foo.parent(), bar.parent()));
+  drop((foo, bar));
+  interner.clear();
+  assert_eq!(Arc::strong_count(&parent), 1);
+}
+
+#[test]
+fn lookup() {
+  let mut interner = SubstrInterner::new();
+  let parent: Arc<str> = Arc::from("foo bar");
+  interner.intern_substr(&parent, 4..7);
+  interner.intern("baz");
+  assert!(interner.contains("bar"));
+  assert!(interner.contains("baz"));
+  assert!(!interner.contains("foo"));
+  assert_eq!(interner.get("bar").as_deref(), Some("bar"));
+  assert_eq!(interner.get("foo"), None);
+  let mut strings: Vec<&str> = interner.iter().map(|string| &**string).collect();
+  strings.sort();
+  assert_eq!(strings, ["bar", "baz"]);
+}
+
+#[test]
+fn invalid_range() {
+  let parent: Arc<str> = Arc::from("☃ snowman");
+  assert!(panic::catch_unwind(|| SubstrInterner::new().intern_substr(&parent, 0..1)).is_err());
+  assert!(panic::catch_unwind(|| SubstrInterner::new().intern_substr(&parent, 4..20)).is_err());
+  #[allow(clippy::reversed_empty_ranges)]
+  let reversed = 5..4;
+  assert!(panic::catch_unwind(|| SubstrInterner::new().intern_substr(&parent, reversed)).is_err());
+  assert_eq!(&*SubstrInterner::new().intern_substr(&parent, 0..3), "☃");
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::mem;
+use std::num::NonZeroU8;
+use std::panic;
+use std::sync::Arc;
+use std::thread;
+
+use str_intern::symbol::{SymbolInterner, Reader, Resolver, Symbol, DefaultSymbol, KeysExhausted};
+
+#[test]
+fn round_trip() {
+  let mut interner = SymbolInterner::new();
+  assert!(interner.is_empty());
+  let foo = interner.intern("foo");
+  let bar = interner.intern(&String::from("bar"));
+  assert_ne!(foo, bar);
+  assert_eq!(interner.intern("foo"), foo);
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.resolve(foo), "foo");
+  assert_eq!(&interner[bar], "bar");
+  assert_eq!(interner.get("bar"), Some(bar));
+  assert_eq!(interner.get("baz"), None);
+  assert!(interner.contains("foo"));
+  assert!(!interner.contains("baz"));
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.iter().collect::<Vec<_>>(), [(foo, "foo"), (bar, "bar")]);
+  let strings = ["", "a", "foo", "bar", "ünïcödé", "foo bar"];
+  let symbols: Vec<_> = strings.iter().map(|string| interner.intern(string)).collect();
+  for (symbol, string) in symbols.into_iter().zip(strings) {
+    assert_eq!(interner.resolve(symbol), string);
+    assert_eq!(interner.get(string), Some(symbol));
+  }
+}
+
+#[test]
+fn stability() {
+  let mut interner = SymbolInterner::with_capacity(1);
+  let symbols: Vec<_> = (0..1000).map(|i| interner.intern(&i.to_string())).collect();
+  assert_eq!(symbols.iter().collect::<HashSet<_>>().len(), 1000);
+  for (i, &symbol) in symbols.iter().enumerate() {
+    assert_eq!(symbol.index(), i);
+  }
+  for i in 0..1000 {
+    interner.intern(&format!("other {i}"));
+  }
+  for (i, &symbol) in symbols.iter().enumerate() {
+    assert_eq!(interner.resolve(symbol), i.to_string());
+    assert_eq!(interner.intern(&i.to_string()), symbol);
+  }
+  let clone = interner.clone();
+  for &symbol in &symbols {
+    assert_eq!(clone.resolve(symbol), interner.resolve(symbol));
+  }
+  assert_eq!(interner.len(), 2000);
+}
+
+#[test]
+fn foreign_symbol() {
+  let mut big = SymbolInterner::new();
+  big.intern("foo");
+  let bar = big.intern("bar");
+  let small = SymbolInterner::<_, RandomState>::default();
+  assert_eq!(small.try_resolve(bar), None);
+  assert!(panic::catch_unwind(|| small.resolve(bar).len()).is_err());
+}
+
+#[test]
+fn size() {
+  const _: () = assert!(mem::size_of::<Option<DefaultSymbol>>() == 4);
+  assert_eq!(mem::size_of::<Symbol>(), 4);
+  assert_eq!(mem::size_of::<Symbol<u16>>(), 2);
+  assert_eq!(mem::size_of::<Option<Symbol<NonZeroU8>>>(), 1);
+  assert_eq!(mem::size_of::<Symbol<usize>>(), mem::size_of::<usize>());
+}
+
+#[test]
+fn keys_exhausted() {
+  let mut interner = SymbolInterner::<u8>::default();
+  for i in 0..256 {
+    assert_eq!(interner.intern(&i.to_string()).index(), i);
+  }
+  assert_eq!(interner.try_intern("256"), Err(KeysExhausted { len: 256 }));
+  assert_eq!(interner.try_intern("255").map(Symbol::key), Ok(255));
+  assert_eq!(interner.len(), 256);
+  assert!(panic::catch_unwind(move || interner.intern("256")).is_err());
+  let mut interner = SymbolInterner::<NonZeroU8>::default();
+  for i in 0..255 {
+    let symbol = interner.intern(&i.to_string());
+    assert_eq!(symbol.index(), i);
+    assert_eq!(interner.resolve(symbol), i.to_string());
+  }
+  assert_eq!(interner.try_intern("255"), Err(KeysExhausted { len: 255 }));
+  let mut interner = SymbolInterner::<usize>::default();
+  let foo = interner.intern("foo");
+  assert_eq!(foo.key(), 0);
+  assert_eq!(interner.iter().collect::<Vec<_>>(), [(foo, "foo")]);
+}
+
+#[test]
+fn reader() {
+  let mut interner = SymbolInterner::new();
+  let symbols: Vec<_> = ["foo", "bar", "", "ünïcödé"].iter().map(|string| (interner.intern(string), *string)).collect();
+  let debug = format!("{interner:?}");
+  let reader: Reader = interner.into_reader();
+  assert_eq!(format!("{reader:?}"), debug);
+  assert_eq!(reader.len(), 4);
+  for &(symbol, string) in &symbols {
+    assert_eq!(reader.resolve(symbol), string);
+    assert_eq!(&reader[symbol], string);
+    assert_eq!(reader.get(string), Some(symbol));
+    assert!(reader.contains(string));
+  }
+  assert_eq!(reader.get("baz"), None);
+  assert!(!reader.contains("baz"));
+  assert_eq!(reader.iter().collect::<Vec<_>>(), symbols);
+  let reader = Arc::new(reader);
+  thread::scope(|scope| {
+    for &(symbol, string) in &symbols {
+      let reader = &reader;
+      scope.spawn(move || assert_eq!(reader.get(string), Some(symbol)));
+    }
+  });
+  let resolver: Resolver = Arc::into_inner(reader).unwrap().into_resolver();
+  assert_eq!(format!("{resolver:?}"), debug);
+  assert_eq!(resolver.len(), 4);
+  for &(symbol, string) in &symbols {
+    assert_eq!(resolver.resolve(symbol), string);
+    assert_eq!(&resolver[symbol], string);
+  }
+  let mut other = SymbolInterner::new();
+  let out_of_range = ["a", "b", "c", "d", "e"].map(|string| other.intern(string))[4];
+  assert_eq!(resolver.try_resolve(out_of_range), None);
+  assert_eq!(resolver.iter().collect::<Vec<_>>(), symbols);
+  // Only the strings are left; the map from strings to symbols is gone.
+  assert_eq!(mem::size_of::<Resolver>(), mem::size_of::<Box<[Arc<str>]>>());
+  assert!(mem::size_of::<Resolver>() < mem::size_of::<Reader>());
+}
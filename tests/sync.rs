@@ -0,0 +1,1741 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Bound, RangeFull};
+use std::sync::{Arc, Barrier, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use str_intern::sync::{Interner, WeakInterner, OrderedInterner, OrdInterner, RwInterner, ShardedInterner, CachedInterner, ScopedInterner, SharedInterner, FrozenInterner, FixedState, SnapshotIter, InternedStr, TryInternError, ValidationError, GcReport, MemoryUsage, InternStats, LenStats, RemovalCause};
+
+#[test]
+fn len() {
+  let interner = Interner::new();
+  assert!(interner.is_empty());
+  interner.intern("foo");
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.len(), 2);
+  let mut locked = interner.lock();
+  assert_eq!(locked.len(), locked.iter().len());
+  locked.clear();
+  assert!(locked.is_empty());
+}
+
+#[test]
+fn lookup() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let locked = interner.lock();
+  assert!(locked.contains("foo"));
+  assert!(InternedStr::ptr_eq(&locked.get("foo").unwrap(), &foo));
+  assert!(!locked.contains("bar"));
+  assert_eq!(locked.get("bar"), None);
+  assert_eq!(locked.len(), 1);
+}
+
+#[test]
+fn remove() {
+  let interner = Interner::new();
+  let foo0 = interner.intern("foo");
+  assert!(InternedStr::ptr_eq(&foo0, &interner.remove("foo").unwrap()));
+  assert_eq!(interner.lock().remove("bar"), None);
+  let foo1 = interner.intern("foo");
+  assert!(!InternedStr::ptr_eq(&foo0, &foo1));
+}
+
+#[test]
+fn drain() {
+  let interner = Interner::new();
+  interner.intern("foo");
+  interner.intern("bar");
+  let mut locked = interner.lock();
+  let mut drain = locked.drain();
+  assert_eq!(drain.len(), 2);
+  drain.next();
+  drop(drain);
+  assert!(locked.is_empty());
+}
+
+#[test]
+fn extract_if() {
+  let interner: Interner = Interner::from_iter(["a0", "a1", "b0"].map(InternedStr::from));
+  let extracted: Vec<_> = interner.lock().extract_if(|string| string.starts_with('a')).collect();
+  assert_eq!(extracted.len(), 2);
+  assert_eq!(interner.len(), 1);
+  assert!(interner.contains("b0"));
+}
+
+#[test]
+fn capacity() {
+  let interner = Interner::with_capacity(100);
+  assert!(interner.capacity() >= 100);
+  interner.reserve(1000);
+  assert!(interner.lock().capacity() >= 1000);
+  interner.shrink_to_fit();
+  assert!(interner.capacity() < 1000);
+}
+
+#[test]
+fn try_reserve() {
+  let interner = Interner::with_capacity_and_hasher(10, RandomState::new());
+  assert!(interner.try_reserve(100).is_ok());
+  assert!(interner.capacity() >= 100);
+  assert!(interner.try_reserve(usize::MAX).is_err());
+}
+
+#[test]
+fn entry() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let mut locked = interner.lock();
+  let entry = locked.entry("foo").or_insert_with(|| -> String { panic!("foo should be occupied") });
+  assert!(InternedStr::ptr_eq(&entry, &foo));
+  locked.entry("bar").or_insert_with(|| String::from("bar"));
+  assert!(locked.contains("bar"));
+}
+
+#[test]
+fn entry_hashes_once_per_probe() {
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::BuildHasher;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  
+  #[derive(Clone, Default)]
+  struct CountingState(Arc<AtomicUsize>);
+  
+  impl BuildHasher for CountingState {
+    
+    type Hasher = DefaultHasher;
+    
+    fn build_hasher(&self) -> DefaultHasher {
+      self.0.fetch_add(1, Ordering::Relaxed);
+      DefaultHasher::new()
+    }
+    
+  }
+  
+  let state = CountingState::default();
+  let interner = Interner::with_capacity_and_hasher(8, state.clone());
+  interner.intern("foo");
+  let mut locked = interner.lock();
+  state.0.store(0, Ordering::Relaxed);
+  locked.entry("bar").or_insert();
+  // One hash to find that "bar" is vacant, and one to insert it, but none to look it up again.
+  assert_eq!(state.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn expect_interned() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  assert!(InternedStr::ptr_eq(&interner.expect_interned("foo"), &foo));
+  assert!(InternedStr::ptr_eq(&interner.lock()["foo"], &foo));
+}
+
+#[test]
+#[should_panic(expected = "\"bar\" has not been interned")]
+fn expect_interned_missing() {
+  Interner::new().expect_interned("bar");
+}
+
+#[test]
+fn expect_interned_does_not_poison() {
+  let interner = Interner::new();
+  let result = std::panic::catch_unwind(|| interner.expect_interned("bar"));
+  assert!(result.is_err());
+  interner.intern("bar");
+}
+
+#[test]
+fn intern_cow() {
+  let interner = Interner::new();
+  let foo0 = interner.intern_cow(Cow::Owned(String::from("foo")));
+  let foo1 = interner.lock().intern_cow(Cow::Borrowed("foo"));
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+}
+
+#[test]
+fn intern_from() {
+  let interner = Interner::new();
+  let foo = InternedStr::from("foo");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&foo)), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert!(InternedStr::ptr_eq(&interner.lock().intern_from(String::from("foo")), &foo));
+}
+
+#[test]
+fn intern_utf8() {
+  let interner = Interner::new();
+  let foo = interner.intern_utf8(b"foo").unwrap();
+  assert!(InternedStr::ptr_eq(&foo, &interner.lock().intern_utf8(b"foo").unwrap()));
+  assert_eq!(interner.intern_utf8(b"\xFFfoo").unwrap_err().valid_up_to(), 0);
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn intern_utf8_lossy() {
+  let interner = Interner::new();
+  let invalid0 = interner.intern_utf8_lossy(b"foo\xFFbar");
+  let invalid1 = interner.lock().intern_utf8_lossy(b"foo\xFFbar");
+  assert_eq!(&*invalid0, "foo\u{FFFD}bar");
+  assert!(InternedStr::ptr_eq(&invalid0, &invalid1));
+}
+
+#[test]
+fn intern_fmt() {
+  let interner = Interner::new();
+  let (module, name) = ("std", "mem");
+  let path = interner.intern_fmt(format_args!("{module}::{name}"));
+  assert!(InternedStr::ptr_eq(&interner.lock().intern_fmt(format_args!("{module}::{name}")), &path));
+  assert!(InternedStr::ptr_eq(&interner.intern("std::mem"), &path));
+}
+
+#[test]
+fn intern_display() {
+  struct Panicky;
+  impl Display for Panicky {
+    fn fmt(&self, _: &mut Formatter) -> fmt::Result {
+      panic!("Panicky always panics")
+    }
+  }
+  let interner = Interner::new();
+  let answer = interner.intern_display(&42);
+  assert!(InternedStr::ptr_eq(&answer, &interner.lock().intern_display("42")));
+  assert!(std::panic::catch_unwind(|| interner.intern_display(&Panicky)).is_err());
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn intern_join() {
+  let interner = Interner::new();
+  let path = interner.intern("a.b.c");
+  assert!(InternedStr::ptr_eq(&interner.intern_join(".", ["a", "b", "c"]), &path));
+  assert!(InternedStr::ptr_eq(&interner.lock().intern_join(".", ["a", "b", "c"]), &path));
+  assert_eq!(&*interner.intern_join(".", [""; 0]), "");
+}
+
+#[test]
+fn intern_split() {
+  let interner = Interner::new();
+  let line = ["red", "green", "blue"].repeat(1000).join(",");
+  let fields = interner.intern_split(&line, ',');
+  assert_eq!(fields.len(), 3000);
+  assert!(fields.chunks(3).all(|chunk| InternedStr::ptr_eq(&chunk[0], &fields[0])));
+  assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn intern_split_stats() {
+  let line = "red,green,red,,blue,green,red";
+  let mut local = str_intern::Interner::with_counting();
+  local.intern_split(line, ',').for_each(drop);
+  let locked = Interner::with_counting();
+  locked.lock().intern_split(line, ',').for_each(drop);
+  let interner = Interner::with_counting();
+  interner.intern_split(line, ',');
+  assert_eq!(local.stats(), InternStats { hits: 3, misses: 4, inserts: 4, bytes_saved_estimate: 11 });
+  assert_eq!(locked.stats(), local.stats());
+  assert_eq!(interner.stats(), local.stats());
+  for string in ["red", "green", "blue", ""] {
+    assert_eq!(locked.count_of(string), local.count_of(string));
+    assert_eq!(interner.count_of(string), local.count_of(string));
+  }
+}
+
+#[test]
+fn intern_lines() {
+  let interner = Interner::from_lines("foo\r\nbar\n\nfoo\n");
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.intern_lines("bar\r\nbaz\n".as_bytes()).unwrap(), 1);
+  assert!(interner.contains("baz"));
+}
+
+#[test]
+fn intern_lines_shrinking() {
+  let interner = Interner::new();
+  interner.set_gc_threshold(1);
+  assert_eq!(interner.intern_lines("foo\nbar\nbaz\n".as_bytes()).unwrap(), 3);
+  let interner = Interner::with_max_entries(2);
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.intern_lines("baz\nqux\n".as_bytes()).unwrap(), 2);
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("baz") && interner.contains("qux"));
+}
+
+#[test]
+fn intern_all() {
+  let interner = Interner::new();
+  let strings = interner.intern_all(vec![String::from("foo"), String::from("bar"), String::from("foo")]);
+  assert_eq!(strings, ["foo", "bar", "foo"].map(InternedStr::from));
+  assert!(strings.iter().all(|string| InternedStr::ptr_eq(string, &interner.intern(string))));
+  assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn intern_all_locks_once() {
+  let interner = Interner::new();
+  // If the interner were locked once per string, it would be unlocked while the iterator produces the next one.
+  let strings = interner.intern_all(["foo", "bar", "foo", "baz"].into_iter().inspect(|_| assert!(interner.try_lock().is_none())));
+  assert!(InternedStr::ptr_eq(&strings[0], &strings[2]));
+  assert_eq!(strings, ["foo", "bar", "foo", "baz"].map(InternedStr::from));
+  assert!(interner.try_lock().is_some());
+}
+
+#[test]
+fn intern_long_strings() {
+  let interner = Interner::new();
+  let strings: Vec<String> = (0..16).map(|i| format!("{i:04}").repeat(1024)).collect();
+  let interned: Vec<Vec<InternedStr>> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..8).map(|_| scope.spawn(|| strings.iter().map(|string| interner.intern(string)).collect())).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(interner.len(), strings.len());
+  for (i, string) in strings.iter().enumerate() {
+    assert_eq!(&*interned[0][i], string);
+    assert!(interned.iter().all(|strings| InternedStr::ptr_eq(&strings[i], &interned[0][i])));
+  }
+}
+
+#[test]
+fn extend() {
+  let mut interner = Interner::new();
+  interner.extend(["foo", "bar", "foo"]);
+  interner.lock().extend([String::from("bar"), String::from("baz")]);
+  assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn with_seeds() {
+  let (interner, seeds) = Interner::with_seeds(["if", "else", "while", "if"]);
+  assert_eq!(interner.len(), 3);
+  assert_eq!(seeds, ["if", "else", "while", "if"].map(InternedStr::from));
+  assert!(InternedStr::ptr_eq(&seeds[0], &seeds[3]));
+  assert!(InternedStr::ptr_eq(&interner.intern("if"), &seeds[0]));
+  assert!(InternedStr::ptr_eq(&interner.intern(String::from("while")), &seeds[2]));
+  assert!(interner.capacity() >= 3);
+  let (interner, seeds) = Interner::with_seeds(Vec::<String>::new());
+  assert!(interner.is_empty() && seeds.is_empty());
+}
+
+#[test]
+fn from() {
+  assert_eq!(Interner::from(["if", "else", "while", "if"]).len(), 3);
+  assert_eq!(Interner::from(&vec![String::from("if"), String::from("if")][..]).len(), 1);
+}
+
+#[test]
+fn get_or_intern_with() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let mut locked = interner.lock();
+  let hit = locked.get_or_intern_with("foo", || -> String { panic!("foo should already be interned") });
+  assert!(InternedStr::ptr_eq(&hit, &foo));
+  assert_eq!(&*locked.get_or_intern_with("bar", || "bar"), "bar");
+}
+
+#[test]
+fn intern_full() {
+  let interner = Interner::new();
+  assert!(interner.intern_full("foo").1);
+  assert!(!interner.intern_full("foo").1);
+  interner.remove("foo");
+  assert!(interner.lock().intern_full("foo").1);
+}
+
+#[test]
+fn canonicalize() {
+  let interner = Interner::new();
+  let foo0 = InternedStr::from("foo");
+  let foo1 = InternedStr::from("foo");
+  assert!(InternedStr::ptr_eq(&interner.canonicalize(InternedStr::clone(&foo0)), &foo0));
+  assert!(InternedStr::ptr_eq(&interner.lock().canonicalize(foo1), &foo0));
+}
+
+#[test]
+fn is_canonical() {
+  let interner = Interner::new();
+  let other = Interner::new();
+  let foo = interner.intern("foo");
+  let other_foo = other.intern("foo");
+  assert!(interner.is_canonical(&foo));
+  assert!(interner.contains_ptr(&foo));
+  assert!(!interner.is_canonical(&other_foo));
+  assert!(!interner.lock().contains_ptr(&other_foo));
+  assert!(!interner.is_canonical(&InternedStr::from("bar")));
+}
+
+#[test]
+fn dedupe_in_place() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let mut strings = ["foo", "bar", "foo", "bar", "baz"].map(InternedStr::from);
+  let bar = InternedStr::clone(&strings[1]);
+  assert_eq!(interner.dedupe_in_place(&mut strings), 3);
+  assert!(InternedStr::ptr_eq(&strings[0], &foo));
+  assert!(InternedStr::ptr_eq(&strings[2], &foo));
+  assert!(InternedStr::ptr_eq(&strings[1], &bar));
+  assert!(InternedStr::ptr_eq(&strings[3], &bar));
+  assert_eq!(interner.len(), 3);
+  assert_eq!(interner.lock().dedupe_in_place(&mut strings), 0);
+}
+
+#[test]
+fn new_ascii_case_insensitive() {
+  let interner = Interner::new_ascii_case_insensitive();
+  let content_type = interner.intern("Content-Type");
+  assert!(InternedStr::ptr_eq(&interner.intern("content-type"), &content_type));
+  assert!(InternedStr::ptr_eq(&interner.lock().intern("CONTENT-TYPE"), &content_type));
+  assert_eq!(&*content_type, "Content-Type");
+  assert!(interner.contains("cOnTeNt-TyPe"));
+  assert_eq!(interner.get("content-TYPE"), Some(InternedStr::clone(&content_type)));
+  let lower = interner.intern("straße");
+  let upper = interner.intern("STRASSE");
+  assert!(!InternedStr::ptr_eq(&lower, &upper));
+  assert!(!InternedStr::ptr_eq(&interner.intern("É"), &interner.intern("é")));
+  assert_eq!(interner.lock().iter().filter(|string| string.eq_ignore_ascii_case("content-type")).count(), 1);
+  assert_eq!(interner.len(), 5);
+  assert_eq!(interner.remove("CONTENT-type"), Some(content_type));
+  assert!(!interner.contains("Content-Type"));
+  assert_eq!(&*interner.intern("content-TYPE"), "content-TYPE");
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn new_nfc() {
+  let interner = Interner::new_nfc();
+  let decomposed = interner.intern("cafe\u{301}");
+  let composed = interner.intern("caf\u{e9}");
+  assert!(InternedStr::ptr_eq(&decomposed, &composed));
+  assert_eq!(&*decomposed, "caf\u{e9}");
+  assert!(interner.contains("cafe\u{301}"));
+  assert!(interner.contains("caf\u{e9}"));
+  let ascii = InternedStr::from("cafe");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&ascii)), &ascii));
+  assert!(interner.lock().iter().any(|string| &**string == "caf\u{e9}"));
+  assert!(!interner.lock().iter().any(|string| &**string == "cafe\u{301}"));
+  assert_eq!(interner.remove("cafe\u{301}"), Some(composed));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn with_normalizer() {
+  let interner = Interner::with_normalizer(RandomState::new(), |string| {
+    let trimmed = string.trim();
+    if trimmed.contains("  ") {
+      Cow::Owned(trimmed.split(' ').filter(|word| !word.is_empty()).collect::<Vec<_>>().join(" "))
+    } else {
+      Cow::Borrowed(trimmed)
+    }
+  });
+  let foo = interner.intern(" foo ");
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(&*foo, "foo");
+  let foo_bar = interner.intern("foo   bar");
+  assert!(InternedStr::ptr_eq(&interner.lock().intern(" foo bar"), &foo_bar));
+  assert_eq!(&*foo_bar, "foo bar");
+  assert!(interner.contains("foo  "));
+  assert_eq!(interner.get("  foo  bar "), Some(InternedStr::clone(&foo_bar)));
+  assert!(interner.lock().iter().all(|string| string.trim() == &**string));
+  assert_eq!(format!("{interner:?}").matches("foo").count(), 2);
+  assert_eq!(interner.remove(" foo"), Some(foo));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn with_validator() {
+  let interner = Interner::with_validator(|string| {
+    if string.len() > 8 {
+      Err(ValidationError::new("too long"))
+    } else if !string.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+      Err(ValidationError::new(format!("{string:?} is not an identifier")))
+    } else {
+      Ok(())
+    }
+  });
+  let foo = interner.try_intern("foo").unwrap();
+  assert!(InternedStr::ptr_eq(&interner.lock().try_intern("foo").unwrap(), &foo));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(interner.try_intern("foo_bar_baz"), Err(TryInternError::Invalid(ValidationError::new("too long"))));
+  assert_eq!(interner.lock().try_intern("foo bar").unwrap_err().to_string(), "\"foo bar\" is not an identifier");
+  assert!(!interner.contains("foo_bar_baz"));
+  assert!(!interner.contains("foo bar"));
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "too long")]
+fn with_validator_intern() {
+  let interner = Interner::with_validator(|_| Err(ValidationError::new("too long")));
+  let result = std::panic::catch_unwind(|| interner.intern("foo"));
+  assert!(result.is_err());
+  assert!(interner.try_intern("foo").is_err());
+  interner.intern("foo");
+}
+
+#[test]
+fn with_validator_not_poisoned() {
+  let mut interner = Interner::with_validator(|string| if string == "bad" { Err(ValidationError::new("bad")) } else { Ok(()) });
+  assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| interner.extend(["good", "bad", "ugly"]))).is_err());
+  assert!(interner.contains("good"));
+  assert!(!interner.contains("ugly"));
+  assert!(std::panic::catch_unwind(|| interner.intern_from(String::from("bad"))).is_err());
+  assert!(std::panic::catch_unwind(|| interner.intern_full("bad")).is_err());
+  assert!(std::panic::catch_unwind(|| interner.intern_all(["fine", "bad"])).is_err());
+  assert!(std::panic::catch_unwind(|| interner.intern_split("fine,bad", ',')).is_err());
+  assert!(std::panic::catch_unwind(|| interner.intern_lines("fine\nbad\n".as_bytes())).is_err());
+  assert!(std::panic::catch_unwind(|| interner.dedupe_in_place(&mut [InternedStr::from("bad")])).is_err());
+  // None of those poisoned it, so it can still be locked.
+  assert_eq!(interner.try_intern("bad"), Err(TryInternError::Invalid(ValidationError::new("bad"))));
+  assert_eq!(interner.lock().len(), 2);
+}
+
+#[test]
+fn with_max_len() {
+  let interner = Interner::with_max_len(3);
+  let foo = interner.try_intern("foo").unwrap();
+  assert!(InternedStr::ptr_eq(&interner.lock().try_intern("foo").unwrap(), &foo));
+  assert!(interner.try_intern("").is_ok());
+  assert_eq!(interner.try_intern("fooo"), Err(TryInternError::TooLong { len: 4, max: 3 }));
+  assert_eq!(interner.lock().try_intern("\u{e9}\u{e9}"), Err(TryInternError::TooLong { len: 4, max: 3 }));
+  assert!(!interner.contains("fooo"));
+  assert_eq!(interner.len(), 2);
+  assert_eq!(Interner::new().try_intern("a very long string indeed").as_deref(), Ok("a very long string indeed"));
+}
+
+#[test]
+fn gc() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("bazz");
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 7 });
+  assert_eq!(interner.len(), 1);
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  let bar = InternedStr::from("bar");
+  assert!(InternedStr::ptr_eq(&interner.intern_from(InternedStr::clone(&bar)), &bar));
+  assert_eq!(interner.lock().gc(), GcReport::default());
+  drop(bar);
+  assert_eq!(interner.gc(), GcReport { entries: 1, bytes: 3 });
+  let interner = Interner::new_ascii_case_insensitive();
+  let foo = interner.intern("Foo");
+  interner.intern("Bar");
+  assert_eq!(interner.gc(), GcReport { entries: 1, bytes: 3 });
+  assert!(InternedStr::ptr_eq(&interner.intern("FOO"), &foo));
+  assert!(!interner.contains("bar"));
+}
+
+#[test]
+fn set_gc_threshold() {
+  let interner = Interner::new();
+  assert_eq!(interner.gc_threshold(), 0);
+  interner.set_gc_threshold(3);
+  assert_eq!(interner.lock().gc_threshold(), 3);
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("foo");
+  assert_eq!(interner.len(), 2);
+  interner.intern("baz");
+  assert_eq!(interner.len(), 2);
+  assert!(!interner.contains("bar"));
+  assert!(interner.contains("baz"));
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  interner.lock().intern("qux");
+  interner.intern("quux");
+  assert_eq!(interner.len(), 4);
+  interner.intern("corge");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("foo"));
+  assert!(interner.contains("corge"));
+}
+
+#[test]
+fn with_max_entries() {
+  let interner = Interner::with_max_entries(3);
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("baz");
+  interner.intern("foo");
+  assert!(interner.get("bar").is_some());
+  interner.intern("qux");
+  assert_eq!(interner.len(), 3);
+  assert!(!interner.contains("baz"));
+  interner.intern("quux");
+  assert!(!interner.contains("foo"));
+  assert!(interner.contains("bar"));
+  let new_foo = interner.intern("foo");
+  assert_eq!(new_foo, foo);
+  assert!(!InternedStr::ptr_eq(&new_foo, &foo));
+  assert!(!interner.contains("qux"));
+  assert_eq!(interner.remove("bar").as_deref(), Some("bar"));
+  interner.intern("corge");
+  assert_eq!(interner.len(), 3);
+  assert!(interner.contains("quux"));
+  drop(foo);
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 9 });
+  interner.intern("grault");
+  interner.intern("garply");
+  interner.intern("waldo");
+  assert!(!interner.contains("foo"));
+  let interner = Interner::with_max_entries(1);
+  interner.intern("foo");
+  interner.intern("bar");
+  assert_eq!(interner.len(), 1);
+  assert!(interner.contains("bar"));
+}
+
+#[test]
+fn builder() {
+  let interner = Interner::builder().hasher(FixedState::with_seed(7)).ascii_case_insensitive().max_entries(2).build();
+  let foo = interner.intern("Foo");
+  assert!(InternedStr::ptr_eq(&interner.intern("FOO"), &foo));
+  interner.intern("bar");
+  interner.intern("foo");
+  interner.intern("baz");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("FOO"));
+  assert!(!interner.contains("BAR"));
+  let interner = Interner::builder().max_entries(3).max_bytes(6).build();
+  interner.intern("ab");
+  interner.intern("cd");
+  interner.intern("ef");
+  interner.intern("g");
+  assert_eq!(interner.len(), 3);
+  assert!(!interner.contains("ab"));
+  interner.intern("hijk");
+  assert_eq!(interner.len(), 2);
+  assert_eq!(interner.interned_bytes(), 5);
+  let interner = Interner::builder().validator(|string| {
+    if string.is_empty() {
+      Err(ValidationError::new("empty"))
+    } else {
+      Ok(())
+    }
+  }).counting().build();
+  interner.intern("foo");
+  interner.intern("foo");
+  assert!(interner.try_intern("").is_err());
+  assert_eq!(interner.count_of("foo"), Some(2));
+  assert_eq!(interner.count_of(""), None);
+}
+
+#[test]
+#[should_panic(expected = "at least one string")]
+fn builder_max_entries_zero() {
+  let _ = Interner::builder().max_entries(0);
+}
+
+#[test]
+fn interned_bytes() {
+  let interner = Interner::new();
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  interner.intern("barbaz");
+  interner.intern("foo");
+  assert_eq!(interner.interned_bytes(), 9);
+  interner.remove("foo");
+  interner.remove("qux");
+  assert_eq!(interner.interned_bytes(), 6);
+  interner.intern("abc");
+  interner.intern("abcd");
+  assert_eq!(interner.lock().extract_if(|string| string.starts_with('a')).count(), 2);
+  assert_eq!(interner.interned_bytes(), 6);
+  interner.intern("quux");
+  assert_eq!(interner.gc(), GcReport { entries: 2, bytes: 10 });
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  interner.clear();
+  assert_eq!(interner.interned_bytes(), 0);
+  interner.intern("foo");
+  assert_eq!(interner.lock().drain().count(), 1);
+  assert_eq!(interner.interned_bytes(), 0);
+  let foo = InternedStr::from("foo");
+  let set = HashSet::from([InternedStr::clone(&foo), InternedStr::from("quux")]);
+  assert_eq!(Interner::from_set(set).interned_bytes(), 7);
+}
+
+#[test]
+fn with_max_bytes() {
+  let interner = Interner::with_max_bytes(10);
+  interner.intern("foo");
+  interner.intern("bar");
+  interner.intern("baz");
+  interner.intern("foo");
+  assert_eq!(interner.interned_bytes(), 9);
+  interner.intern("quux");
+  assert_eq!(interner.interned_bytes(), 10);
+  assert!(!interner.contains("bar"));
+  interner.remove("foo");
+  assert_eq!(interner.interned_bytes(), 7);
+  interner.intern("abcdef");
+  assert_eq!(interner.interned_bytes(), 10);
+  assert!(!interner.contains("baz"));
+  interner.intern("a");
+  assert_eq!(interner.interned_bytes(), 7);
+  assert!(!interner.contains("quux"));
+  let long = interner.intern("abcdefghijk");
+  assert_eq!(&*long, "abcdefghijk");
+  assert!(!interner.contains("abcdefghijk"));
+  assert_eq!(interner.interned_bytes(), 7);
+  assert_eq!(interner.len(), 2);
+  // It was never saved, so it isn't reported as newly saved either.
+  assert!(!interner.intern_full("abcdefghijk").1);
+  assert_eq!(interner.len(), 2);
+  interner.clear();
+  assert_eq!(interner.interned_bytes(), 0);
+  assert!(interner.intern_full("abcdefghij").1);
+  assert_eq!(interner.interned_bytes(), 10);
+}
+
+#[test]
+fn interned_bytes_random() {
+  const WORDS: [&str; 8] = ["", "a", "foo", "Foo", "barbaz", "quux", "\u{e9}t\u{e9}", "abcdefghij"];
+  let interners = [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(3), Interner::with_max_bytes(12)];
+  for interner in interners {
+    // A fixed linear congruential generator, so that failures are reproducible.
+    let mut state = 12345u64;
+    let mut next = || {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) as usize
+    };
+    let mut held = Vec::new();
+    for _ in 0..1000 {
+      let word = WORDS[next() % WORDS.len()];
+      match next() % 16 {
+        0 => interner.clear(),
+        1 => {
+          interner.gc();
+        }
+        2 => {
+          interner.lock().extract_if(|string| string.len() > 3).for_each(drop);
+        }
+        3..=5 => {
+          interner.remove(word);
+        }
+        6..=8 => held.push(interner.intern(word)),
+        _ => {
+          interner.intern(word);
+        }
+      }
+      if held.len() > 4 {
+        held.remove(next() % held.len());
+      }
+      assert_eq!(interner.interned_bytes(), interner.lock().iter().map(|string| string.len()).sum::<usize>());
+    }
+  }
+}
+
+#[test]
+fn memory_usage() {
+  let interner = Interner::new();
+  assert_eq!(interner.memory_usage().strings, 0);
+  interner.intern("foo");
+  interner.intern("barbaz");
+  let usage = interner.memory_usage();
+  assert_eq!(usage.strings, 9);
+  assert_eq!(usage.headers, 2 * 2 * std::mem::size_of::<usize>());
+  assert!(usage.table >= 2 * std::mem::size_of::<InternedStr>());
+  assert_eq!(usage.total(), usage.strings + usage.headers + usage.table);
+  assert_eq!(MemoryUsage::default().total(), 0);
+  let interner = Interner::new_ascii_case_insensitive();
+  interner.intern("foo");
+  assert!(interner.memory_usage().table > Interner::new().memory_usage().table);
+}
+
+#[test]
+fn compact() {
+  for interner in [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(1000)] {
+    let held: Vec<_> = (0..1000).map(|i| interner.intern(i.to_string())).filter(|string| string.len() == 1).collect();
+    assert_eq!(interner.gc().entries, 990);
+    let capacity = interner.capacity();
+    assert!(capacity >= 1000);
+    let usage = interner.memory_usage();
+    let released = interner.lock().compact(100);
+    assert_eq!(released, capacity - interner.capacity());
+    assert!(interner.capacity() >= 100);
+    assert!(interner.capacity() < capacity);
+    assert!(interner.memory_usage().table < usage.table);
+    let capacity = interner.capacity();
+    let released = interner.compact(0);
+    assert_eq!(released, capacity - interner.capacity());
+    assert!(interner.capacity() < 100);
+    assert_eq!(interner.compact(0), 0);
+    assert_eq!(interner.len(), 10);
+    for string in &held {
+      assert!(InternedStr::ptr_eq(&interner.intern(&**string), string));
+    }
+    interner.intern("foo");
+    assert!(interner.contains("foo"));
+    assert_eq!(interner.len(), 11);
+  }
+}
+
+#[test]
+fn ref_counts() {
+  for interner in [Interner::new(), Interner::new_ascii_case_insensitive(), Interner::with_max_entries(10)] {
+    let foo = interner.intern("foo");
+    interner.intern("bar");
+    let counts = |interner: &Interner| {
+      let mut counts: Vec<_> = interner.lock().ref_counts().map(|(string, count)| (String::from(&**string), count)).collect();
+      counts.sort();
+      counts
+    };
+    assert_eq!(counts(&interner), [(String::from("bar"), 0), (String::from("foo"), 1)]);
+    let foos = vec![foo.clone(), foo.clone()];
+    assert_eq!(counts(&interner)[1].1, 3);
+    assert_eq!(interner.lock().leaked(2).map(|(string, _)| string).collect::<Vec<_>>(), [&foo]);
+    assert_eq!(interner.lock().leaked(4).count(), 0);
+    assert_eq!(format!("{:?}", interner.lock().leaked(1)), "{\"foo\": 3}");
+    drop(foos);
+    drop(foo);
+    assert_eq!(counts(&interner), [(String::from("bar"), 0), (String::from("foo"), 0)]);
+    assert_eq!(interner.lock().leaked(1).count(), 0);
+  }
+}
+
+#[test]
+fn len_stats() {
+  let interner = Interner::new();
+  assert_eq!(interner.len_stats(), LenStats::default());
+  assert_eq!(interner.len_stats().mean(), 0.0);
+  for string in ["", "a", "ab", "abc", "abcd", "abcdefg", "abcdefgh", "abcdefghijklmnopq", "ab"] {
+    interner.intern(string);
+  }
+  let stats = interner.len_stats();
+  assert_eq!(stats.count, 8);
+  assert_eq!(stats.min, 0);
+  assert_eq!(stats.max, 17);
+  assert_eq!(stats.total, 42);
+  assert_eq!(stats.mean(), 5.25);
+  assert_eq!(stats.histogram, [1, 1, 2, 2, 1, 1]);
+  assert_eq!(LenStats::bucket_range(0), 0..=0);
+  assert_eq!(LenStats::bucket_range(3), 4..=7);
+  assert_eq!(LenStats::bucket_range(64), 1 << 63..=usize::MAX);
+  assert_eq!(stats.to_string(), "\
+count: 8, total: 42, min: 0, max: 17, mean: 5.25
+                  length | count
+                       0 | 1
+                       1 | 1
+                   2..=3 | 2
+                   4..=7 | 2
+                  8..=15 | 1
+                 16..=31 | 1");
+}
+
+#[test]
+fn with_counting() {
+  let interner = Interner::with_counting();
+  assert_eq!(interner.count_of("foo"), None);
+  for string in ["foo", "bar", "foo", "baz", "bar", "qux", "foo", "quux"] {
+    interner.intern(string);
+  }
+  interner.intern_from(String::from("qux"));
+  assert!(interner.get("baz").is_some());
+  assert_eq!(interner.count_of("foo"), Some(3));
+  assert_eq!(interner.count_of("baz"), Some(1));
+  assert_eq!(interner.count_of("corge"), None);
+  let most_common: Vec<_> = interner.most_common(4).into_iter().map(|(string, count)| (String::from(&*string), count)).collect();
+  assert_eq!(most_common, [(String::from("foo"), 3), (String::from("bar"), 2), (String::from("qux"), 2), (String::from("baz"), 1)]);
+  assert_eq!(interner.lock().most_common(100).len(), 5);
+  assert!(interner.most_common(0).is_empty());
+  let foo = interner.intern("foo");
+  assert_eq!(interner.gc().entries, 4);
+  assert_eq!(interner.count_of("foo"), Some(4));
+  assert_eq!(interner.lock().count_of("bar"), None);
+  interner.remove("foo");
+  interner.intern("foo");
+  assert_eq!(interner.count_of("foo"), Some(1));
+  drop(foo);
+  assert_eq!(Interner::new().count_of("foo"), None);
+  assert!(Interner::new().most_common(1).is_empty());
+}
+
+#[test]
+fn counting_entry() {
+  let interner = Interner::with_counting();
+  interner.intern("a");
+  let mut locked = interner.lock();
+  locked.get_or_intern_with("a", || -> String { panic!("a should be occupied") });
+  locked.entry("a").or_insert();
+  locked.intern_split("a,a", ',').for_each(drop);
+  assert_eq!(locked.count_of("a"), Some(5));
+  let interner = Interner::with_counting();
+  interner.lock().intern_split("a,a", ',').for_each(drop);
+  assert_eq!(interner.count_of("a"), Some(2));
+  let interner = Interner::with_counting();
+  interner.intern_split("a,a", ',');
+  assert_eq!(interner.count_of("a"), Some(2));
+}
+
+#[test]
+fn stats() {
+  let interner = Interner::with_max_bytes(10);
+  assert_eq!(interner.stats(), InternStats::default());
+  for string in ["foo", "bar", "foo", "foo", "bazz", "bar", "too long!!!"] {
+    interner.intern(string);
+  }
+  interner.get("foo");
+  assert!(interner.contains("bar"));
+  assert_eq!(interner.stats(), InternStats { hits: 3, misses: 4, inserts: 3, bytes_saved_estimate: 9 });
+  interner.lock().reset_stats();
+  assert_eq!(interner.stats(), InternStats::default());
+  interner.intern_from(String::from("bazz"));
+  interner.intern("qux");
+  assert_eq!(interner.lock().stats(), InternStats { hits: 1, misses: 1, inserts: 1, bytes_saved_estimate: 4 });
+  let interner = Interner::with_validator(|string| if string.is_empty() { Err(ValidationError::new("empty")) } else { Ok(()) });
+  assert!(interner.try_intern("").is_err());
+  interner.intern("foo");
+  interner.intern("foo");
+  assert_eq!(interner.stats(), InternStats { hits: 1, misses: 2, inserts: 1, bytes_saved_estimate: 3 });
+}
+
+#[test]
+fn stats_entry() {
+  let interner = Interner::new();
+  interner.intern("a");
+  let mut locked = interner.lock();
+  locked.get_or_intern_with("a", || -> String { panic!("a should be occupied") });
+  locked.entry("a").or_insert();
+  locked.intern_split("a,a", ',').for_each(drop);
+  assert_eq!(locked.stats(), InternStats { hits: 4, misses: 1, inserts: 1, bytes_saved_estimate: 4 });
+}
+
+#[test]
+fn removal_hook() {
+  let (sender, receiver) = mpsc::channel();
+  let interner = Interner::with_max_entries(3);
+  interner.set_removal_hook(move |string, cause| sender.send((String::from(&**string), cause)).unwrap());
+  let take = || receiver.try_iter().collect::<Vec<_>>();
+  for string in ["foo", "bar", "baz", "qux"] {
+    interner.intern(string);
+  }
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Evicted)]);
+  interner.remove("bar");
+  assert_eq!(interner.remove("bar"), None);
+  assert_eq!(take(), [(String::from("bar"), RemovalCause::Explicit)]);
+  interner.retain(|string| &**string != "baz");
+  assert_eq!(take(), [(String::from("baz"), RemovalCause::Retain)]);
+  let kept = interner.intern("kept");
+  interner.intern("swept");
+  assert_eq!(interner.gc().entries, 2);
+  let mut swept = take();
+  swept.sort_by(|(a, _), (b, _)| a.cmp(b));
+  assert_eq!(swept, [(String::from("qux"), RemovalCause::Gc), (String::from("swept"), RemovalCause::Gc)]);
+  interner.intern("foo");
+  assert_eq!(interner.lock().extract_if(|string| &**string == "foo").count(), 1);
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Retain)]);
+  interner.intern("bar");
+  drop(interner.lock().drain());
+  let mut cleared = take();
+  cleared.sort_by(|(a, _), (b, _)| a.cmp(b));
+  assert_eq!(cleared, [(String::from("bar"), RemovalCause::Cleared), (String::from("kept"), RemovalCause::Cleared)]);
+  interner.intern("foo");
+  interner.clone().clear();
+  assert!(take().is_empty());
+  thread::scope(|scope| scope.spawn(|| interner.clear()).join().unwrap());
+  assert_eq!(take(), [(String::from("foo"), RemovalCause::Cleared)]);
+  drop(kept);
+}
+
+#[test]
+fn insert_hook() {
+  let (sender, receiver) = mpsc::channel();
+  let interner = Interner::with_max_len(10);
+  interner.set_insert_hook(move |string| sender.send(InternedStr::clone(string)).unwrap());
+  let foo = interner.intern("foo");
+  interner.intern("foo");
+  assert!(interner.try_intern("too long!!!").is_err());
+  interner.intern_all(["bar", "foo", "bar", "baz"]);
+  thread::scope(|scope| scope.spawn(|| interner.lock().extend(["qux", "baz"])).join().unwrap());
+  interner.intern_lines("foo\nquux\nquux\n".as_bytes()).unwrap();
+  interner.clone().intern("corge");
+  let inserted: Vec<_> = receiver.try_iter().collect();
+  assert_eq!(inserted.len(), 5);
+  assert!(InternedStr::ptr_eq(&inserted[0], &foo));
+  assert_eq!(inserted.iter().map(|string| &**string).collect::<Vec<_>>(), ["foo", "bar", "baz", "qux", "quux"]);
+}
+
+#[test]
+fn version() {
+  let interner = Interner::with_max_entries(2);
+  let mut version = interner.version();
+  let mut changed = |interner: &Interner| {
+    let changed = interner.version() > version;
+    version = interner.version();
+    changed
+  };
+  interner.clear();
+  assert!(!changed(&interner));
+  interner.intern("foo");
+  assert!(changed(&interner));
+  interner.intern("foo");
+  interner.get("foo");
+  assert!(!interner.contains("bar"));
+  assert!(!changed(&interner));
+  interner.intern_all(["bar", "baz"]);
+  assert!(changed(&interner));
+  assert!(interner.remove("bar").is_some());
+  assert!(changed(&interner));
+  assert!(interner.remove("bar").is_none());
+  interner.retain(|_| true);
+  assert!(!changed(&interner));
+  interner.retain(|string| &**string != "baz");
+  assert!(changed(&interner));
+  thread::scope(|scope| scope.spawn(|| interner.intern("qux")).join().unwrap());
+  assert!(changed(&interner));
+  assert_eq!(interner.gc().entries, 1);
+  assert!(changed(&interner));
+  assert_eq!(interner.gc().entries, 0);
+  assert!(!changed(&interner));
+  interner.intern("foo");
+  changed(&interner);
+  {
+    let mut locked = interner.lock();
+    locked.clear();
+    assert!(locked.version() > interner.version());
+  }
+  assert!(changed(&interner));
+  assert_eq!(interner.lock().version(), interner.version());
+  assert_eq!(interner.clone().version(), interner.version());
+}
+
+#[test]
+fn weak_interner() {
+  let interner = WeakInterner::new();
+  assert!(interner.is_empty());
+  let foo = interner.intern("foo");
+  assert_eq!(Arc::strong_count(&foo), 1);
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  let bar = interner.intern(String::from("bar"));
+  assert_eq!(interner.len(), 2);
+  assert!(InternedStr::ptr_eq(&interner.get("bar").unwrap(), &bar));
+  let weak = Arc::downgrade(&foo);
+  drop(foo);
+  assert!(weak.upgrade().is_none());
+  assert_eq!(interner.len(), 1);
+  assert!(!interner.contains("foo"));
+  assert!(interner.get("foo").is_none());
+  let foo = interner.intern("foo");
+  assert_eq!(Arc::strong_count(&foo), 1);
+  assert_eq!(interner.len(), 2);
+  drop((foo, bar));
+  assert!(interner.is_empty());
+  assert_eq!(interner.purge(), 2);
+  assert_eq!(interner.purge(), 0);
+}
+
+#[test]
+fn weak_interner_auto_purge() {
+  let interner = WeakInterner::new();
+  let foo = interner.intern("foo");
+  assert_eq!(interner.entries(), 1);
+  // Freed strings with unique hashes are still purged every so often, so the entries can't pile up.
+  for i in 0..10_000 {
+    interner.intern(i.to_string());
+    assert!(interner.entries() <= 128);
+  }
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(interner.len(), 1);
+  interner.purge();
+  assert_eq!(interner.entries(), 1);
+  interner.clear();
+  assert_eq!(interner.entries(), 0);
+}
+
+#[test]
+fn ordered_interner() {
+  let interner = OrderedInterner::new();
+  let words = ["the", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog", "quick", "the"];
+  for &word in &words {
+    let len = interner.len();
+    let hit = interner.contains(word);
+    interner.intern(word);
+    assert_eq!(interner.len(), if hit { len } else { len + 1 });
+  }
+  let order = ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog"];
+  assert_eq!(interner.to_vec().iter().map(|string| &**string).collect::<Vec<_>>(), order);
+  for (index, word) in order.into_iter().enumerate() {
+    assert_eq!(interner.index_of(word), Some(index));
+    assert_eq!(interner.get_index(index).as_deref(), Some(word));
+  }
+  assert_eq!(interner.get_index(order.len()), None);
+  assert_eq!(interner.index_of("cat"), None);
+  let fox = interner.get("fox").unwrap();
+  assert!(InternedStr::ptr_eq(&thread::scope(|scope| scope.spawn(|| interner.intern("fox")).join().unwrap()), &fox));
+  assert_eq!(interner.shift_remove("quick").as_deref(), Some("quick"));
+  assert_eq!(interner.shift_remove("quick"), None);
+  assert_eq!(interner.to_vec().iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "brown", "fox", "jumps", "over", "lazy", "dog"]);
+  assert_eq!(interner.index_of("dog"), Some(6));
+  assert_eq!(interner.swap_remove("brown").as_deref(), Some("brown"));
+  assert_eq!(interner.to_vec().iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "dog", "fox", "jumps", "over", "lazy"]);
+  assert_eq!(interner.index_of("dog"), Some(1));
+  let mut clone = interner.clone();
+  clone.extend(["lazy", "cat"]);
+  assert_eq!(clone.into_vec().iter().map(|string| &**string).collect::<Vec<_>>(), ["the", "dog", "fox", "jumps", "over", "lazy", "cat"]);
+  assert_eq!(interner.len(), 6);
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn ord_interner() {
+  let interner = OrdInterner::new();
+  assert_eq!(interner.first(), None);
+  let name = interner.intern("user:1000:name");
+  assert!(InternedStr::ptr_eq(&thread::scope(|scope| scope.spawn(|| interner.intern(String::from("user:1000:name"))).join().unwrap()), &name));
+  let mut interner = interner.lock();
+  for string in ["user:1001:name", "user:1000:name", "group:7:name", "user:1000:email", "user:1000:", "user:1001:", "user:1000:name"] {
+    interner.intern(string);
+  }
+  let range = |range: (Bound<&str>, Bound<&str>)| interner.range(range).map(|string| &**string).collect::<Vec<_>>();
+  assert_eq!(range((Bound::Included("user:1000:"), Bound::Excluded("user:1001:"))), ["user:1000:", "user:1000:email", "user:1000:name"]);
+  assert_eq!(range((Bound::Excluded("user:1000:"), Bound::Included("user:1001:"))), ["user:1000:email", "user:1000:name", "user:1001:"]);
+  assert_eq!(range((Bound::Unbounded, Bound::Excluded("user:"))), ["group:7:name"]);
+  assert_eq!(range((Bound::Included("user:1001:"), Bound::Unbounded)), ["user:1001:", "user:1001:name"]);
+  assert_eq!(range((Bound::Unbounded, Bound::Unbounded)).len(), 6);
+  assert!(range((Bound::Included("x"), Bound::Included("y"))).is_empty());
+  assert_eq!(interner.range("user:1000:".."user:1000:name").count(), 2);
+  assert_eq!(interner.range("user:1000:"..="user:1000:name").count(), 3);
+  assert_eq!(interner.range(.."user:1000:").count(), 1);
+  assert_eq!(interner.range("user:1000:email"..).rev().map(|string| &**string).collect::<Vec<_>>(), ["user:1001:name", "user:1001:", "user:1000:name", "user:1000:email"]);
+  assert_eq!(interner.range::<RangeFull>(..).count(), 6);
+  assert_eq!(interner.iter().map(|string| &**string).collect::<Vec<_>>(), range((Bound::Unbounded, Bound::Unbounded)));
+  assert_eq!(interner.first().map(|string| &**string), Some("group:7:name"));
+  assert_eq!(interner.last().map(|string| &**string), Some("user:1001:name"));
+  assert!(InternedStr::ptr_eq(&interner.get("user:1000:name").unwrap(), &name));
+  assert_eq!(interner.remove("group:7:name").as_deref(), Some("group:7:name"));
+  assert!(!interner.contains("group:7:name"));
+  assert_eq!(interner.len(), 5);
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn iter_sorted() {
+  let interner = Interner::new();
+  let strings = ["zebra", "Zebra", "apple", "Äpfel", "äpfel", "apfel", "", "日本", "z", "apple", "é", "e\u{301}"];
+  for string in strings {
+    interner.intern(string);
+  }
+  // By UTF-8 bytes, every uppercase ASCII letter comes before every lowercase one, and everything non-ASCII comes after both.
+  let sorted = ["", "Zebra", "apfel", "apple", "e\u{301}", "z", "zebra", "Äpfel", "äpfel", "é", "日本"];
+  let mut expected = strings.to_vec();
+  expected.sort();
+  expected.dedup();
+  assert_eq!(expected, sorted);
+  assert_eq!(interner.lock().iter_sorted().map(|string| &**string).collect::<Vec<_>>(), sorted);
+  assert_eq!(interner.lock().iter_sorted().len(), sorted.len());
+  assert_eq!(interner.lock().iter_sorted().next_back().map(|string| &**string), Some("日本"));
+  assert_eq!(interner.into_sorted_vec().iter().map(|string| &**string).collect::<Vec<_>>(), sorted);
+  assert_eq!(Interner::new().into_sorted_vec(), Vec::<InternedStr>::new());
+}
+
+#[test]
+fn iter_prefix() {
+  let strings = ["foo", "foo.bar", "foo.baz", "foo.bar.qux", "foobar", "fo", "bar.foo", "", "\u{D7FF}a", "\u{D7FF}", "\u{E000}", "\u{10FFFF}", "\u{10FFFF}\u{10FFFF}"];
+  let interner = Interner::new();
+  let ord_interner = OrdInterner::new();
+  for string in strings {
+    interner.intern(string);
+    ord_interner.intern(string);
+  }
+  let interner = interner.lock();
+  let ord_interner = ord_interner.lock();
+  for prefix in ["", "foo", "foo.", "foo.bar", "foo.bar.qux.", "f", "bar", "x", "\u{D7FF}", "\u{10FFFF}", "\u{10FFFF}\u{10FFFF}"] {
+    let mut expected: Vec<_> = strings.into_iter().filter(|string| string.starts_with(prefix)).collect();
+    expected.sort();
+    let mut actual: Vec<_> = interner.iter_prefix(prefix).map(|string| &**string).collect();
+    actual.sort();
+    assert_eq!(actual, expected, "prefix {prefix:?}");
+    assert_eq!(ord_interner.iter_prefix(prefix).map(|string| &**string).collect::<Vec<_>>(), expected, "prefix {prefix:?}");
+  }
+  assert_eq!(interner.iter_prefix("").count(), strings.len());
+  assert_eq!(interner.iter_prefix("foo.").count(), 3);
+}
+
+#[test]
+fn rw_interner() {
+  let interner = RwInterner::new();
+  let foo0 = interner.intern("foo");
+  let foo1 = interner.intern(String::from("foo"));
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  interner.intern("bar");
+  assert_eq!(interner.len(), 2);
+  assert!(interner.contains("bar"));
+  assert_eq!(interner.get("baz"), None);
+  let mut locked = interner.lock();
+  assert_eq!(locked.len(), locked.iter().len());
+  assert!(InternedStr::ptr_eq(&locked.intern("foo"), &foo0));
+  assert!(InternedStr::ptr_eq(&locked.remove("foo").unwrap(), &foo0));
+  drop(locked);
+  assert!(!interner.contains("foo"));
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn rw_interner_stress() {
+  let strings: Vec<String> = (0..200).map(|i| format!("string {i}")).collect();
+  let interner: RwInterner = strings[..100].iter().collect();
+  let expected: Vec<InternedStr> = strings[..100].iter().map(|string| interner.get(string).unwrap()).collect();
+  thread::scope(|scope| {
+    for _ in 0..16 {
+      scope.spawn(|| {
+        for _ in 0..100 {
+          for (string, expected) in strings.iter().zip(&expected) {
+            assert!(InternedStr::ptr_eq(&interner.intern(string), expected));
+          }
+        }
+      });
+    }
+    let writers: Vec<_> = (0..4).map(|_| scope.spawn(|| {
+      strings[100..].iter().map(|string| interner.intern(string)).collect::<Vec<_>>()
+    })).collect();
+    let written: Vec<Vec<InternedStr>> = writers.into_iter().map(|writer| writer.join().unwrap()).collect();
+    for strings in &written[1..] {
+      assert!(strings.iter().zip(&written[0]).all(|(string, expected)| InternedStr::ptr_eq(string, expected)));
+    }
+  });
+  assert_eq!(interner.len(), 200);
+  for string in &strings {
+    let saved = interner.get(string).unwrap();
+    assert!(InternedStr::ptr_eq(&interner.intern(string), &saved));
+  }
+}
+
+#[test]
+fn rw_interner_races() {
+  let interner = RwInterner::new();
+  let barrier = Barrier::new(8);
+  let interned: Vec<Vec<InternedStr>> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..8).map(|_| scope.spawn(|| {
+      (0..200).map(|i| {
+        // Every thread interns each new string at once, so some miss (and race for the write lock) while others hit.
+        if i % 20 == 0 {
+          barrier.wait();
+        }
+        interner.intern(format!("string {}", i / 2))
+      }).collect()
+    })).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(interner.len(), 100);
+  for (i, string) in interned[0].iter().enumerate() {
+    assert_eq!(**string, format!("string {}", i / 2));
+    assert!(InternedStr::ptr_eq(string, &interned[0][i - i % 2]));
+    assert!(interned.iter().all(|strings| InternedStr::ptr_eq(&strings[i], string)));
+  }
+}
+
+#[test]
+fn rw_interner_poison() {
+  let interner = RwInterner::new();
+  interner.intern("foo");
+  let _ = thread::scope(|scope| scope.spawn(|| {
+    let _locked = interner.lock();
+    panic!("poisoning the lock");
+  }).join());
+  assert!(std::panic::catch_unwind(|| interner.len()).is_err());
+}
+
+#[test]
+fn sharded_interner() {
+  assert!(ShardedInterner::new().shard_count().is_power_of_two());
+  assert_eq!(ShardedInterner::with_shards(0).shard_count(), 1);
+  assert_eq!(ShardedInterner::with_shards(5).shard_count(), 8);
+  let interner = ShardedInterner::with_shards(4);
+  let foo0 = interner.intern("foo");
+  let foo1 = interner.intern(String::from("foo"));
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  interner.intern("bar");
+  interner.intern("baz");
+  assert_eq!(interner.len(), 3);
+  assert!(interner.contains("bar"));
+  assert!(InternedStr::ptr_eq(&interner.get("foo").unwrap(), &foo0));
+  assert_eq!(interner.get("qux"), None);
+  let mut locked = interner.lock_all();
+  assert_eq!(locked.len(), 3);
+  assert_eq!(locked.iter().len(), 3);
+  let mut strings: Vec<&str> = locked.iter().map(|string| &**string).collect();
+  strings.sort();
+  assert_eq!(strings, ["bar", "baz", "foo"]);
+  assert!(InternedStr::ptr_eq(&locked.remove("foo").unwrap(), &foo0));
+  assert!(!locked.contains("foo"));
+  drop(locked);
+  interner.clear();
+  assert!(interner.is_empty());
+}
+
+#[test]
+fn sharded_interner_stress() {
+  let interner = ShardedInterner::with_shards(8);
+  let strings: Vec<String> = (0..1000).map(|i| format!("string {i}")).collect();
+  let results: Vec<Vec<InternedStr>> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..16).map(|offset| {
+      let (interner, strings) = (&interner, &strings);
+      scope.spawn(move || {
+        // Each thread interns an overlapping window of the strings, in a different order.
+        let mut saved: Vec<_> = (0..600).map(|i| (offset * 25 + i * 7) % 1000).map(|i| (i, interner.intern(&strings[i]))).collect();
+        saved.sort_by_key(|&(i, _)| i);
+        saved.into_iter().map(|(_, string)| string).collect()
+      })
+    }).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(interner.len(), interner.lock_all().iter().count());
+  for saved in results.iter().flatten() {
+    assert!(InternedStr::ptr_eq(saved, &interner.get(&**saved).unwrap()));
+  }
+}
+
+#[test]
+fn try_lock() {
+  let interner = Interner::new();
+  interner.intern("foo");
+  let (locked_tx, locked_rx) = mpsc::channel();
+  let (done_tx, done_rx) = mpsc::channel();
+  thread::scope(|scope| {
+    let interner = &interner;
+    scope.spawn(move || {
+      let _locked = interner.lock();
+      locked_tx.send(()).unwrap();
+      done_rx.recv().unwrap();
+    });
+    locked_rx.recv().unwrap();
+    let start = Instant::now();
+    assert!(interner.try_lock().is_none());
+    assert!(matches!(interner.try_lock_checked(), Ok(None)));
+    assert!(start.elapsed() < Duration::from_secs(1));
+    done_tx.send(()).unwrap();
+  });
+  let mut locked = interner.try_lock().unwrap();
+  assert!(locked.contains("foo"));
+  locked.intern("bar");
+  assert!(interner.try_lock().is_none());
+  drop(locked);
+  assert_eq!(interner.try_lock_checked().unwrap().unwrap().len(), 2);
+}
+
+#[test]
+#[cfg(not(feature = "parking_lot"))]
+fn try_lock_poisoned() {
+  let interner = Interner::new();
+  let _ = thread::scope(|scope| scope.spawn(|| {
+    let _locked = interner.lock();
+    panic!("poisoning the interner");
+  }).join());
+  assert!(interner.try_lock_checked().unwrap_err().into_inner().is_empty());
+  assert!(std::panic::catch_unwind(|| interner.try_lock().is_none()).is_err());
+}
+
+#[test]
+fn try_intern_nonblocking() {
+  let interner = Interner::new();
+  let foo0 = interner.try_intern_nonblocking("foo").unwrap();
+  let foo1 = interner.try_intern_nonblocking(String::from("foo")).unwrap();
+  assert!(InternedStr::ptr_eq(&foo0, &foo1));
+  let (locked_tx, locked_rx) = mpsc::channel();
+  let (done_tx, done_rx) = mpsc::channel();
+  thread::scope(|scope| {
+    let interner = &interner;
+    scope.spawn(move || {
+      let _locked = interner.lock();
+      locked_tx.send(()).unwrap();
+      done_rx.recv().unwrap();
+    });
+    locked_rx.recv().unwrap();
+    let start = Instant::now();
+    assert_eq!(interner.try_intern_nonblocking("bar"), None);
+    assert!(start.elapsed() < Duration::from_secs(1));
+    done_tx.send(()).unwrap();
+  });
+  assert!(!interner.contains("bar"));
+  assert!(InternedStr::ptr_eq(&interner.try_intern_nonblocking("foo").unwrap(), &foo0));
+}
+
+#[test]
+#[cfg(not(feature = "parking_lot"))]
+fn checked() {
+  fn poison(interner: &Interner) {
+    let _ = thread::scope(|scope| scope.spawn(|| {
+      let _locked = interner.lock();
+      panic!("poisoning the interner");
+    }).join());
+  }
+  
+  let interner = Interner::new();
+  let foo = interner.try_intern_checked("foo").unwrap();
+  assert!(interner.lock_checked().is_ok());
+  poison(&interner);
+  let error = interner.try_intern_checked("foo").unwrap_err();
+  assert_eq!(error.to_string(), "interner was poisoned");
+  assert!(InternedStr::ptr_eq(&error.into_inner(), &foo));
+  let bar = interner.try_intern_checked("bar").unwrap_err().into_inner();
+  let locked = interner.lock_checked().unwrap_err().into_inner();
+  assert!(InternedStr::ptr_eq(&locked.get("bar").unwrap(), &bar));
+  assert_eq!(locked.len(), 2);
+  drop(locked);
+  assert!(interner.try_clear().is_err());
+  assert!(interner.try_lock_checked().unwrap_err().get_ref().is_empty());
+  interner.try_intern_checked("baz").unwrap_err();
+  let set = interner.try_into_set().unwrap_err().into_inner();
+  assert_eq!(set, HashSet::from([InternedStr::from("baz")]));
+}
+
+#[test]
+#[cfg(not(feature = "parking_lot"))]
+fn try_into_poisoned() {
+  fn poisoned() -> Interner {
+    let interner = Interner::from(["foo", "bar"]);
+    let _ = thread::scope(|scope| scope.spawn(|| {
+      let mut locked = interner.lock();
+      locked.intern("baz");
+      panic!("poisoning the interner");
+    }).join());
+    interner
+  }
+  
+  let expected = HashSet::from(["foo", "bar", "baz"].map(InternedStr::from));
+  let error: str_intern::sync::PoisonedSet = poisoned().try_into_set().unwrap_err();
+  assert_eq!(error.into_inner(), expected);
+  let iter = poisoned().try_into_iter().unwrap_err().into_inner();
+  assert_eq!(iter.len(), 3);
+  assert_eq!(iter.collect::<HashSet<_>>(), expected);
+  assert!(std::panic::catch_unwind(|| poisoned().into_iter().count()).is_err());
+  assert_eq!(Interner::from(["foo"]).try_into_iter().unwrap().count(), 1);
+}
+
+#[test]
+#[cfg(not(feature = "parking_lot"))]
+fn clear_poison() {
+  fn poison(interner: &Interner) {
+    let _ = thread::scope(|scope| scope.spawn(|| {
+      let _locked = interner.lock();
+      panic!("poisoning the interner");
+    }).join());
+  }
+  
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  poison(&interner);
+  assert!(interner.lock_checked().is_err());
+  interner.clear_poison();
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  interner.intern("bar");
+  assert_eq!(interner.len(), 2);
+  poison(&interner);
+  let mut locked = interner.recover();
+  assert!(InternedStr::ptr_eq(&locked.intern("foo"), &foo));
+  drop(locked);
+  assert!(interner.lock_checked().is_ok());
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  // Recovering an interner that was never poisoned just locks it.
+  assert_eq!(interner.recover().len(), 2);
+}
+
+#[test]
+#[cfg(feature = "parking_lot")]
+fn parking_lot_never_poisoned() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let _ = thread::scope(|scope| scope.spawn(|| {
+    let _locked = interner.lock();
+    panic!("trying to poison the interner");
+  }).join());
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert!(interner.lock_checked().is_ok());
+  assert!(matches!(interner.try_lock_checked(), Ok(Some(_))));
+  assert!(interner.try_clear().is_ok());
+  assert!(interner.try_into_set().is_ok());
+}
+
+#[test]
+fn cached_interner() {
+  let interner = CachedInterner::with_cache_capacity(Arc::new(Interner::new()), 4);
+  let foo = interner.intern("foo");
+  assert_eq!(interner.local_cache_len(), 1);
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert!(InternedStr::ptr_eq(&interner.interner().intern("foo"), &foo));
+  let clone = interner.clone();
+  assert!(InternedStr::ptr_eq(&clone.intern("foo"), &foo));
+  assert_eq!(clone.local_cache_len(), 1);
+  // A separate CachedInterner for the same Interner has its own caches.
+  let other = CachedInterner::new(Arc::clone(interner.interner()));
+  assert_eq!(other.local_cache_len(), 0);
+  assert!(InternedStr::ptr_eq(&other.intern("foo"), &foo));
+  // The cache is cleared when it is full.
+  for string in ["bar", "baz", "qux"] {
+    interner.intern(string);
+  }
+  assert_eq!(interner.local_cache_len(), 4);
+  interner.intern("quux");
+  assert_eq!(interner.local_cache_len(), 1);
+  interner.clear_local_cache();
+  assert_eq!(interner.local_cache_len(), 0);
+  assert_eq!(interner.interner().len(), 5);
+}
+
+#[test]
+fn cached_interner_removal() {
+  let interner = CachedInterner::new(Arc::new(Interner::new()));
+  let foo = interner.intern("foo");
+  interner.intern("bar");
+  assert!(InternedStr::ptr_eq(&interner.interner().remove("foo").unwrap(), &foo));
+  // The cached "foo" is no longer canonical, so the whole cache is out of date.
+  assert_eq!(interner.local_cache_len(), 0);
+  let new_foo = interner.intern("foo");
+  assert!(!InternedStr::ptr_eq(&new_foo, &foo));
+  assert!(InternedStr::ptr_eq(&interner.interner().get("foo").unwrap(), &new_foo));
+  interner.interner().clear();
+  assert!(!InternedStr::ptr_eq(&interner.intern("foo"), &new_foo));
+}
+
+#[test]
+fn cached_interner_threads() {
+  let interner = CachedInterner::with_cache_capacity(Arc::new(Interner::new()), 16);
+  let interned: Vec<Vec<InternedStr>> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..8).map(|_| {
+      let interner = interner.clone();
+      scope.spawn(move || (0..500).map(|i| interner.intern(format!("string {}", i % 50))).collect())
+    }).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(interner.interner().len(), 50);
+  for (i, string) in interned[0].iter().enumerate() {
+    assert!(InternedStr::ptr_eq(string, &interner.interner().get(string).unwrap()));
+    assert!(interned.iter().all(|strings| Arc::ptr_eq(&strings[i], string)));
+  }
+}
+
+#[test]
+fn scoped_interner() {
+  let parent = Arc::new(Interner::from(["id", "name"]));
+  let id = parent.get("id").unwrap();
+  let mut scoped = ScopedInterner::new(Arc::clone(&parent));
+  assert!(InternedStr::ptr_eq(&scoped.intern("id"), &id));
+  assert!(!scoped.contains_local("id"));
+  let local = scoped.intern("local");
+  assert!(InternedStr::ptr_eq(&scoped.intern(String::from("local")), &local));
+  assert_eq!(scoped.local_len(), 1);
+  assert!(!parent.contains("local"));
+  // Once a string has been saved locally, it stays the same even if the parent saves it too.
+  let parents_local = parent.intern("local");
+  assert!(!InternedStr::ptr_eq(&parents_local, &local));
+  assert!(InternedStr::ptr_eq(&scoped.intern("local"), &local));
+  let mut snapshot = scoped.iter_snapshot();
+  snapshot.sort_unstable();
+  assert_eq!(snapshot, ["id", "local", "local", "name"].map(InternedStr::from));
+  assert_eq!(scoped.iter_local().count(), 1);
+  assert_eq!(scoped.into_local_set().len(), 1);
+  drop(snapshot);
+  assert_eq!(Arc::strong_count(&local), 1);
+  assert_eq!(parent.len(), 3);
+}
+
+#[test]
+fn leak() {
+  fn spawn_interning(interner: &'static Interner, i: usize) -> thread::JoinHandle<InternedStr> {
+    thread::spawn(move || interner.intern(format!("string {}", i % 4)))
+  }
+  
+  let interner = {
+    let interner = Interner::from(["string 0"]);
+    interner.leak()
+  };
+  let threads: Vec<_> = (0..16).map(|i| spawn_interning(interner, i)).collect();
+  let interned: Vec<InternedStr> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+  assert_eq!(interner.len(), 4);
+  for (i, string) in interned.iter().enumerate() {
+    assert!(InternedStr::ptr_eq(string, &interned[i % 4]));
+    assert!(InternedStr::ptr_eq(string, &interner.get(string).unwrap()));
+  }
+}
+
+#[test]
+fn shared() {
+  let interner: SharedInterner = Interner::shared();
+  let clone = SharedInterner::clone(&interner);
+  let foo = thread::spawn(move || clone.intern("foo")).join().unwrap();
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  assert_eq!(Arc::strong_count(&interner), 1);
+}
+
+#[test]
+fn static_interner() {
+  static INTERNER: Interner<FixedState> = Interner::with_hasher(FixedState::with_seed(42));
+  
+  let interned: Vec<Vec<InternedStr>> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..8).map(|_| scope.spawn(|| (0..100).map(|i| INTERNER.intern(format!("static {}", i % 10))).collect())).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(INTERNER.len(), 10);
+  for (i, string) in interned[0].iter().enumerate() {
+    assert!(interned.iter().all(|strings| InternedStr::ptr_eq(&strings[i], string)));
+  }
+}
+
+#[test]
+fn fixed_state() {
+  use std::hash::BuildHasher;
+  
+  assert_eq!(FixedState::new().hash_one("foo"), FixedState::default().hash_one("foo"));
+  assert_eq!(FixedState::with_seed(1).hash_one("foo"), FixedState::with_seed(1).hash_one("foo"));
+  assert_ne!(FixedState::with_seed(1).hash_one("foo"), FixedState::with_seed(2).hash_one("foo"));
+  assert_eq!(FixedState::with_seed(7).seed(), 7);
+}
+
+#[test]
+fn snapshot() {
+  let interner = Interner::from(["foo", "bar", "baz"]);
+  let mut snapshot = interner.snapshot();
+  snapshot.sort_unstable();
+  assert_eq!(snapshot, ["bar", "baz", "foo"].map(InternedStr::from));
+  assert!(snapshot.iter().all(|string| InternedStr::ptr_eq(string, &interner.get(string).unwrap())));
+  interner.intern("qux");
+  interner.remove("foo");
+  assert_eq!(snapshot.len(), 3);
+  assert!(snapshot.iter().any(|string| &**string == "foo"));
+  let sorted: BTreeSet<InternedStr> = interner.snapshot_into();
+  assert!(sorted.iter().eq(["bar", "baz", "qux"].map(InternedStr::from).iter()));
+  // The interner isn't locked once the snapshot has been taken.
+  assert!(interner.try_lock().is_some());
+}
+
+#[test]
+fn iter_snapshot() {
+  let interner = Interner::from(["foo", "bar", "baz"]);
+  let iter: SnapshotIter = (&interner).into_iter();
+  assert_eq!(iter.len(), 3);
+  let mut seen = HashSet::new();
+  thread::scope(|scope| {
+    let writer = scope.spawn(|| {
+      for i in 0..100 {
+        interner.intern(format!("string {i}"));
+      }
+    });
+    for string in &interner {
+      // Interning on this thread while iterating would deadlock if the interner were still locked.
+      interner.intern(format!("{string}!"));
+      seen.insert(string);
+    }
+    writer.join().unwrap();
+  });
+  assert!(["foo", "bar", "baz"].iter().all(|string| seen.contains(*string)));
+  assert!(seen.iter().all(|string| !string.ends_with('!')));
+  // Each string that was seen got a "!" version, besides the original 3 and the 100 from the other thread.
+  assert_eq!(interner.len(), 103 + seen.len());
+}
+
+#[test]
+fn visit() {
+  let interner = Interner::new();
+  let newly_saved: Vec<bool> = thread::scope(|scope| {
+    let threads: Vec<_> = (0..8).map(|_| scope.spawn(|| interner.visit(|locked| {
+      // Nothing else can intern "foo" between checking for it and interning it.
+      let newly_saved = locked.get("foo").is_none();
+      locked.intern("foo");
+      newly_saved
+    }))).collect();
+    threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+  });
+  assert_eq!(newly_saved.iter().filter(|&&newly_saved| newly_saved).count(), 1);
+  assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn for_each() {
+  let interner = Interner::from(["a", "bb", "ccc"]);
+  let mut total = 0;
+  let mut count = 0;
+  interner.for_each(|string| {
+    total += string.len();
+    count += 1;
+  });
+  assert_eq!((total, count), (6, 3));
+  assert_eq!(total, interner.interned_bytes());
+}
+
+#[test]
+fn eq_lock_order() {
+  let a = Arc::new(Interner::from(["foo", "bar"]));
+  let b = Arc::new(Interner::from(["foo", "bar"]));
+  let barrier = Arc::new(Barrier::new(2));
+  let (sender, receiver) = mpsc::channel();
+  for (a, b) in [(Arc::clone(&a), Arc::clone(&b)), (Arc::clone(&b), Arc::clone(&a))] {
+    let barrier = Arc::clone(&barrier);
+    let sender = sender.clone();
+    // These aren't scoped, so that a deadlock makes this test fail rather than hang.
+    thread::spawn(move || {
+      barrier.wait();
+      for _ in 0..10_000 {
+        assert!(*a == *b);
+      }
+      sender.send(()).unwrap();
+    });
+  }
+  for _ in 0..2 {
+    receiver.recv_timeout(Duration::from_secs(10)).expect("comparing interners in opposite orders deadlocked");
+  }
+}
+
+#[test]
+#[allow(clippy::eq_op)]
+fn eq_self() {
+  let (sender, receiver) = mpsc::channel();
+  // This isn't scoped, so that a deadlock makes this test fail rather than hang.
+  thread::spawn(move || {
+    let interner = Interner::from(["foo", "bar"]);
+    sender.send((interner == interner, interner != interner)).unwrap();
+  });
+  let (eq, ne) = receiver.recv_timeout(Duration::from_secs(10)).expect("comparing an interner with itself deadlocked");
+  assert!(eq);
+  assert!(!ne);
+}
+
+#[test]
+fn clone_from() {
+  let a = Interner::from(["foo", "bar"]);
+  let mut b = Interner::from(["baz"]);
+  let version = b.version();
+  b.clone_from(&a);
+  assert_eq!(b, a);
+  assert_ne!(b.version(), version);
+  assert!(InternedStr::ptr_eq(&b.get("foo").unwrap(), &a.get("foo").unwrap()));
+  // The version never goes backwards, even when cloning from an interner with a lower version.
+  let version = b.version();
+  b.clone_from(&Interner::new());
+  assert!(b.version() > version);
+  assert!(b.is_empty());
+}
+
+#[test]
+fn freeze() {
+  let interner = Interner::new();
+  let foo = interner.intern("foo");
+  let bar = interner.intern("bar");
+  let frozen = interner.freeze();
+  assert_eq!(frozen.len(), 2);
+  assert!(!frozen.is_empty());
+  assert!(InternedStr::ptr_eq(&frozen.get("foo").unwrap(), &foo));
+  assert!(InternedStr::ptr_eq(&frozen.get(String::from("bar")).unwrap(), &bar));
+  assert!(frozen.contains("foo"));
+  assert!(!frozen.contains("baz"));
+  assert_eq!(frozen.get("baz"), None);
+  assert_eq!(frozen.iter().cloned().collect::<HashSet<_>>(), HashSet::from([foo.clone(), bar.clone()]));
+  let frozen = Arc::new(frozen);
+  thread::scope(|scope| {
+    for _ in 0..4 {
+      let frozen = &frozen;
+      let foo = &foo;
+      scope.spawn(move || assert!(InternedStr::ptr_eq(&frozen.get("foo").unwrap(), foo)));
+    }
+  });
+  let interner = Arc::into_inner(frozen).unwrap().thaw();
+  assert!(InternedStr::ptr_eq(&interner.intern("foo"), &foo));
+  interner.intern("baz");
+  assert_eq!(interner.len(), 3);
+  let frozen: FrozenInterner = interner.freeze();
+  assert_eq!(frozen.into_iter().count(), 3);
+}
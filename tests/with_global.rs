@@ -0,0 +1,22 @@
+#![cfg(feature = "global")]
+
+use std::sync::Arc;
+
+use str_intern::sync::*;
+
+#[test]
+fn with_global_get_or_intern() {
+  let mut log = Vec::new();
+  let mut get_or_intern = |string: &str| with_global(|locked| {
+    locked.get(string).unwrap_or_else(|| {
+      log.push(String::from(string));
+      locked.intern(string)
+    })
+  });
+  let foo0 = get_or_intern("with_global foo");
+  let foo1 = get_or_intern("with_global foo");
+  let bar = get_or_intern("with_global bar");
+  assert!(Arc::ptr_eq(&foo0, &foo1));
+  assert!(Arc::ptr_eq(&bar, &intern("with_global bar")));
+  assert_eq!(log, ["with_global foo", "with_global bar"]);
+}
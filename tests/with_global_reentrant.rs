@@ -0,0 +1,14 @@
+#![cfg(all(feature = "global", debug_assertions))]
+
+// The panic poisons the GlobalInterner, so this needs its own test binary.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use str_intern::sync::*;
+
+#[test]
+fn with_global_reentrant() {
+  let result = panic::catch_unwind(AssertUnwindSafe(|| with_global(|_| with_global(|_| ()))));
+  let message = *result.unwrap_err().downcast::<&str>().unwrap();
+  assert!(message.contains("re-entrantly"));
+}